@@ -1,13 +1,14 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
 };
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
 use crate::msg::{DataFormat, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Store, NAMESPACE_KEY_INCREMENT, STORE};
+use crate::rdf::AbsoluteIri;
+use crate::state::{Store, NAMESPACE_COMPACTION, NAMESPACE_KEY_INCREMENT, NAMESPACE_REPAIR, STORE};
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
@@ -22,7 +23,45 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    STORE.save(deps.storage, &Store::new(info.sender, msg.limits.into()))?;
+    let mut store = Store::new(info.sender, msg.limits.into());
+    store.retain_empty_namespaces = msg.retain_empty_namespaces;
+    store.functional_properties = msg.functional_properties;
+    store.normalize_iris = msg.normalize_iris;
+    store.full_iri_keys = msg.full_iri_keys;
+    store.validate_language_tags = msg.validate_language_tags;
+    store.record_triple_metadata = msg.record_triple_metadata;
+    if let Some(base) = &msg.skolem_base {
+        AbsoluteIri::try_new(base)?;
+    }
+    store.skolem_base = msg.skolem_base;
+    store.lenient_rdf_xml = msg.lenient_rdf_xml;
+    store.byte_accounting = msg.byte_accounting;
+    store.subject_index = msg.subject_index;
+    store.invalid_utf8_replacement = msg.invalid_utf8_replacement;
+    store.label_index = msg.label_index;
+    store.strict_iri_chars = msg.strict_iri_chars;
+    store.dedup_imports = msg.dedup_imports;
+    store.canonical_booleans = msg.canonical_booleans;
+    store.class_hierarchy = msg.class_hierarchy;
+    store.skip_empty_literals = msg.skip_empty_literals;
+    store.reject_literal_types = msg.reject_literal_types;
+    store.require_absolute_iris = msg.require_absolute_iris;
+    for shape in msg.shapes {
+        if shape.min_count.unwrap_or(0) > shape.max_count.unwrap_or(u32::MAX) {
+            Err(StdError::generic_err(format!(
+                "Invalid shape for predicate {}: minimum count greater than maximum count",
+                shape.predicate
+            )))?;
+        }
+        if store.shapes.iter().any(|s| s.predicate == shape.predicate) {
+            Err(StdError::generic_err(format!(
+                "Duplicate shape for predicate {}",
+                shape.predicate
+            )))?;
+        }
+        store.shapes.push(shape.into());
+    }
+    STORE.save(deps.storage, &store)?;
     NAMESPACE_KEY_INCREMENT.save(deps.storage, &0u128)?;
 
     Ok(Response::default())
@@ -31,33 +70,91 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<'_>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::InsertData { format, data } => {
-            execute::insert(deps, info, format.unwrap_or_default(), data)
+        ExecuteMsg::InsertData {
+            format,
+            data,
+            idempotency_key,
+            resume_from,
+            segments,
+            default_graph,
+        } => execute::insert(
+            deps,
+            env,
+            info,
+            execute::data_segments(format, segments, &data),
+            data,
+            execute::InsertOptions {
+                idempotency_key,
+                resume_from,
+                default_graph,
+            },
+        ),
+        ExecuteMsg::InsertContainer {
+            prefixes,
+            container,
+            kind,
+            values,
+        } => execute::insert_container(deps, env, info, prefixes, container, kind, values),
+        ExecuteMsg::TouchData { format, data } => {
+            execute::touch(deps, env, info, format.unwrap_or_default(), data)
         }
+        ExecuteMsg::UpsertData {
+            format,
+            data,
+            force,
+        } => execute::upsert(
+            deps,
+            env,
+            info,
+            format.unwrap_or_default(),
+            data,
+            force.unwrap_or_default(),
+        ),
         ExecuteMsg::DeleteData {
             prefixes,
             delete,
             r#where,
         } => execute::delete(deps, info, prefixes, delete, r#where),
+        ExecuteMsg::DeleteByObject {
+            prefixes,
+            object,
+            limit,
+        } => execute::delete_by_object(deps, info, prefixes, object, limit),
+        ExecuteMsg::DeleteGraph {
+            prefixes,
+            graph,
+            limit,
+            after,
+        } => execute::delete_graph(deps, info, prefixes, graph, limit, after),
+        ExecuteMsg::CompactNamespaces { limit } => execute::compact_namespaces(deps, info, limit),
+        ExecuteMsg::RepairNamespaces { limit } => execute::repair_namespaces(deps, info, limit),
+        ExecuteMsg::IndexPredicates { limit } => execute::index_predicates(deps, info, limit),
+        ExecuteMsg::RegisterPrefixes { prefixes } => {
+            execute::register_prefixes(deps, info, prefixes)
+        }
     }
 }
 
 pub mod execute {
     use super::*;
     use crate::msg::{
-        DataFormat, Prefix, SelectItem, SimpleWhereCondition, TriplePattern, WhereClause,
-        WhereCondition,
+        ContainerKind, DataFormat, DataSegment, GraphName, Node, NodeOrLiteral, Prefix, SelectItem,
+        SimpleWhereCondition, TriplePattern, WhereClause, WhereCondition, IRI,
     };
     use crate::querier::{PlanBuilder, QueryEngine};
-    use crate::rdf::{Atom, PrefixMap, TripleReader};
-    use crate::storer::StoreEngine;
+    use crate::rdf::{self, AbsoluteIri, Atom, TripleReader};
+    use crate::state::{load_prefixes, merge_prefixes, PREFIXES};
+    use crate::storer::{self, StoreEngine};
+    use cosmwasm_std::Uint128;
+    use okp4_cursor::check_cursor_length;
+    use std::borrow::Cow;
     use std::collections::HashSet;
-    use std::io::BufReader;
+    use std::ops::Range;
 
     pub fn verify_owner(deps: &DepsMut<'_>, info: &MessageInfo) -> Result<(), ContractError> {
         if STORE.load(deps.storage)?.owner != info.sender {
@@ -67,24 +164,251 @@ pub mod execute {
         }
     }
 
+    /// Ensures no namespace compaction is in progress, the data being not writable meanwhile.
+    pub fn verify_not_compacting(deps: &DepsMut<'_>) -> Result<(), ContractError> {
+        if NAMESPACE_COMPACTION.may_load(deps.storage)?.is_some() {
+            Err(ContractError::NamespaceCompactionInProgress)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Ensures no namespace repair is in progress, the data being not writable meanwhile.
+    pub fn verify_not_repairing(deps: &DepsMut<'_>) -> Result<(), ContractError> {
+        if NAMESPACE_REPAIR.may_load(deps.storage)?.is_some() {
+            Err(ContractError::NamespaceRepairInProgress)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Builds the reader of the given data as configured by the store, i.e. parsing RDF/XML
+    /// leniently if so, and resolving the CURIEs of JSON triples with the registered prefixes.
+    pub fn triple_reader<'a>(
+        storage: &dyn Storage,
+        store: &Store,
+        format: &DataFormat,
+        data: &'a [u8],
+    ) -> StdResult<TripleReader<&'a [u8]>> {
+        configure_reader(
+            storage,
+            store,
+            TripleReader::new(format, data),
+            *format == DataFormat::JsonTriples,
+        )
+    }
+
+    /// Builds the reader of data made of segments in different formats, see
+    /// [TripleReader::multipart], as configured by the store like [triple_reader] does.
+    pub fn multipart_triple_reader<'a>(
+        storage: &dyn Storage,
+        store: &Store,
+        segments: &[(DataFormat, Range<usize>)],
+        data: &'a [u8],
+    ) -> StdResult<TripleReader<&'a [u8]>> {
+        configure_reader(
+            storage,
+            store,
+            TripleReader::multipart(data, segments)?,
+            segments
+                .iter()
+                .any(|(format, _)| *format == DataFormat::JsonTriples),
+        )
+    }
+
+    fn configure_reader<'a>(
+        storage: &dyn Storage,
+        store: &Store,
+        mut reader: TripleReader<&'a [u8]>,
+        with_json: bool,
+    ) -> StdResult<TripleReader<&'a [u8]>> {
+        if store.lenient_rdf_xml {
+            reader = reader.with_lenient_xml();
+        }
+        if with_json {
+            reader = reader.with_prefixes(load_prefixes(storage)?.into());
+        }
+        Ok(reader)
+    }
+
+    /// Returns the segments of the data to insert along with their format, the whole data being
+    /// a single segment in the given format when no segment is provided.
+    pub fn data_segments(
+        format: Option<DataFormat>,
+        segments: Option<Vec<DataSegment>>,
+        data: &[u8],
+    ) -> Vec<(DataFormat, Range<usize>)> {
+        match segments {
+            Some(segments) => segments
+                .into_iter()
+                .map(|segment| (segment.format, segment.start as usize..segment.end as usize))
+                .collect(),
+            None => vec![(format.unwrap_or_default(), 0..data.len())],
+        }
+    }
+
+    /// The options of an insertion, see [ExecuteMsg::InsertData].
+    #[derive(Default)]
+    pub struct InsertOptions {
+        pub idempotency_key: Option<String>,
+        pub resume_from: Option<Uint128>,
+        pub default_graph: Option<String>,
+    }
+
     pub fn insert(
         deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        mut segments: Vec<(DataFormat, Range<usize>)>,
+        data: Binary,
+        options: InsertOptions,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+        verify_not_repairing(&deps)?;
+        if let Some(graph) = &options.default_graph {
+            AbsoluteIri::try_new(graph)?;
+        }
+
+        let store = STORE.load(deps.storage)?;
+        let (data, replacement_count) = match &store.invalid_utf8_replacement {
+            Some(replacement) => {
+                rdf::replace_invalid_utf8_segments(data.as_slice(), &mut segments, replacement)?
+            }
+            None => (Cow::Borrowed(data.as_slice()), 0),
+        };
+        let mut reader = match segments.as_slice() {
+            [(format, range)] if *range == (0..data.len()) => {
+                triple_reader(deps.storage, &store, format, &data)?
+            }
+            _ => multipart_triple_reader(deps.storage, &store, &segments, &data)?,
+        };
+        if let Some(graph) = options.default_graph {
+            reader = reader.with_default_graph(graph);
+        }
+        let hints: Vec<_> = segments
+            .iter()
+            .filter(|(format, _)| *format == DataFormat::Turtle)
+            .filter_map(|(_, range)| data.get(range.clone()))
+            .flat_map(rdf::turtle_prefix_hints)
+            .collect();
+        let mut storer = StoreEngine::new(deps.storage)?.with_block_height(env.block.height);
+        if !hints.is_empty() {
+            storer = storer.with_namespace_hints(hints);
+        }
+        let progress = storer.store_all_once(
+            &mut reader,
+            options.idempotency_key.as_deref(),
+            options.resume_from.unwrap_or_default(),
+        )?;
+
+        let mut res = Response::new()
+            .add_attribute("action", "insert")
+            .add_attribute("triple_count", progress.count);
+        if replacement_count > 0 {
+            res = res.add_attribute("utf8_replacement_count", replacement_count.to_string());
+        }
+        if !progress.empty_literal_count.is_zero() {
+            res = res.add_attribute("empty_literal_count", progress.empty_literal_count);
+        }
+        if !reader.problems().is_empty() {
+            res = res
+                .add_attribute("skipped_count", reader.problems().len().to_string())
+                .add_attributes(
+                    reader
+                        .problems()
+                        .iter()
+                        .map(|problem| ("skipped", problem.as_str())),
+                );
+        }
+        Ok(match progress.resume_from {
+            Some(position) => res
+                .add_attribute("processed", progress.processed)
+                .add_attribute("resume_from", position),
+            None => res,
+        })
+    }
+
+    /// Inserts the triples of a container, serialized in N-Triples to go through [insert].
+    pub fn insert_container(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        prefixes: Vec<Prefix>,
+        container: Node,
+        kind: ContainerKind,
+        values: Vec<NodeOrLiteral>,
+    ) -> Result<Response, ContractError> {
+        let prefix_map = merge_prefixes(deps.storage, prefixes)?.into_inner();
+        let container: rdf::Subject = (container, &prefix_map).try_into()?;
+        let values = values
+            .into_iter()
+            .map(|value| match value {
+                NodeOrLiteral::Node(node) => (node, &prefix_map).try_into(),
+                NodeOrLiteral::Literal(literal) => (literal, &prefix_map).try_into(),
+            })
+            .collect::<StdResult<Vec<rdf::Value>>>()?;
+
+        let mut writer = rdf::TripleWriter::new(&DataFormat::NTriples, vec![]);
+        for atom in &rdf::container_atoms(container, kind, values) {
+            writer.write(&atom.into())?;
+        }
+        let data = Binary::from(writer.finish()?);
+
+        insert(
+            deps,
+            env,
+            info,
+            vec![(DataFormat::NTriples, 0..data.len())],
+            data,
+            InsertOptions::default(),
+        )
+    }
+
+    pub fn touch(
+        deps: DepsMut<'_>,
+        env: Env,
         info: MessageInfo,
         format: DataFormat,
         data: Binary,
     ) -> Result<Response, ContractError> {
         verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+        verify_not_repairing(&deps)?;
 
-        let buf = BufReader::new(data.as_slice());
-        let mut reader = TripleReader::new(&format, buf);
-        let mut storer = StoreEngine::new(deps.storage)?;
-        let count = storer.store_all(&mut reader)?;
+        let store = STORE.load(deps.storage)?;
+        let mut reader = triple_reader(deps.storage, &store, &format, data.as_slice())?;
+        let mut storer = StoreEngine::new(deps.storage)?.with_block_height(env.block.height);
+        let count = storer.touch_all(&mut reader)?;
 
         Ok(Response::new()
-            .add_attribute("action", "insert")
+            .add_attribute("action", "touch")
             .add_attribute("triple_count", count))
     }
 
+    pub fn upsert(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        format: DataFormat,
+        data: Binary,
+        force: bool,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+        verify_not_repairing(&deps)?;
+
+        let store = STORE.load(deps.storage)?;
+        let mut reader = triple_reader(deps.storage, &store, &format, data.as_slice())?;
+        let mut storer = StoreEngine::new(deps.storage)?.with_block_height(env.block.height);
+        let delta = storer.upsert_all(&mut reader, force)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "upsert")
+            .add_attribute("triple_count", delta.count())
+            .add_attribute("triple_count_delta", delta.to_string()))
+    }
+
     pub fn delete(
         deps: DepsMut<'_>,
         info: MessageInfo,
@@ -93,7 +417,157 @@ pub mod execute {
         r#where: WhereClause,
     ) -> Result<Response, ContractError> {
         verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+        verify_not_repairing(&deps)?;
+
+        let atoms = resolve_delete_atoms(deps.storage, prefixes, delete, &r#where)?;
+
+        let mut store = StoreEngine::new(deps.storage)?;
+        let delta = store.delete_all(&atoms)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "delete")
+            .add_attribute("triple_count", delta.count())
+            .add_attribute("triple_count_delta", delta.to_string()))
+    }
+
+    pub fn delete_by_object(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        prefixes: Vec<Prefix>,
+        object: NodeOrLiteral,
+        limit: u32,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+        verify_not_repairing(&deps)?;
+
+        let prefix_map = merge_prefixes(deps.storage, prefixes)?.into_inner();
+        let value: rdf::Value = match object {
+            NodeOrLiteral::Node(node) => (node, &prefix_map).try_into()?,
+            NodeOrLiteral::Literal(literal) => (literal, &prefix_map).try_into()?,
+        };
+
+        let limit = limit.min(STORE.load(deps.storage)?.limits.max_query_limit);
+        let mut store = StoreEngine::new(deps.storage)?;
+        let (delta, complete) = store.delete_by_object(&value, limit as usize)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "delete_by_object")
+            .add_attribute("triple_count", delta.count())
+            .add_attribute("triple_count_delta", delta.to_string())
+            .add_attribute("complete", complete.to_string()))
+    }
+
+    pub fn delete_graph(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        prefixes: Vec<Prefix>,
+        graph: GraphName,
+        limit: u32,
+        after: Option<Binary>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+        verify_not_repairing(&deps)?;
+
+        let prefix_map = merge_prefixes(deps.storage, prefixes)?.into_inner();
+        let graph = match graph {
+            GraphName::Default => None,
+            GraphName::Named(IRI::Full(iri)) => Some(iri),
+            GraphName::Named(IRI::Prefixed(curie)) => Some(rdf::expand_uri(&curie, &prefix_map)?),
+        };
+        if let Some(after) = &after {
+            check_cursor_length(after.as_slice())?;
+        }
+
+        let limit = limit.min(STORE.load(deps.storage)?.limits.max_query_limit);
+        let mut store = StoreEngine::new(deps.storage)?;
+        let (delta, cursor) =
+            store.delete_graph(graph.as_deref(), after.map(Into::into), limit as usize)?;
+
+        let res = Response::new()
+            .add_attribute("action", "delete_graph")
+            .add_attribute("triple_count", delta.count())
+            .add_attribute("triple_count_delta", delta.to_string())
+            .add_attribute("complete", cursor.is_none().to_string());
+        Ok(match cursor {
+            Some(cursor) => res.add_attribute("cursor", Binary::from(cursor).to_base64()),
+            None => res,
+        })
+    }
+
+    pub fn compact_namespaces(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        limit: u32,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_repairing(&deps)?;
+
+        let complete = storer::compact_namespaces(deps.storage, limit as usize)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "compact_namespaces")
+            .add_attribute("complete", complete.to_string()))
+    }
+
+    pub fn repair_namespaces(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        limit: u32,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+
+        let (complete, repaired) = storer::repair_namespaces(deps.storage, limit as usize)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "repair_namespaces")
+            .add_attribute("complete", complete.to_string())
+            .add_attribute("repaired_count", repaired.to_string()))
+    }
+
+    pub fn index_predicates(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        limit: u32,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        verify_not_compacting(&deps)?;
+
+        let complete = storer::index_predicates(deps.storage, limit as usize)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "index_predicates")
+            .add_attribute("complete", complete.to_string()))
+    }
+
+    pub fn register_prefixes(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        prefixes: Vec<Prefix>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let count = prefixes.len();
+        for prefix in prefixes {
+            AbsoluteIri::try_new(&prefix.namespace)?;
+            PREFIXES.save(deps.storage, prefix.prefix, &prefix.namespace)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "register_prefixes")
+            .add_attribute("prefix_count", count.to_string()))
+    }
 
+    /// Resolves the atoms to delete from the delete patterns and the where clause of a deletion.
+    pub fn resolve_delete_atoms(
+        storage: &dyn Storage,
+        prefixes: Vec<Prefix>,
+        delete: Vec<TriplePattern>,
+        r#where: &WhereClause,
+    ) -> StdResult<Vec<Atom>> {
         let patterns: Vec<TriplePattern> = if delete.is_empty() {
             r#where
                 .iter()
@@ -102,7 +576,7 @@ pub mod execute {
                         Ok(tp.clone())
                     }
                 })
-                .collect::<Result<_, ContractError>>()?
+                .collect::<StdResult<_>>()?
         } else {
             delete
         };
@@ -113,31 +587,38 @@ pub mod execute {
             .into_iter()
             .map(SelectItem::Variable)
             .collect();
-        let prefix_map = <PrefixMap>::from(prefixes).into_inner();
-        let plan = PlanBuilder::new(deps.storage, &prefix_map).build_plan(&r#where)?;
-
-        let response = QueryEngine::new(deps.storage).select(plan, variables)?;
-        let atoms: Vec<Atom> = if response.results.bindings.is_empty() {
-            vec![]
-        } else {
-            response
-                .results
-                .bindings
-                .iter()
-                .flat_map(|row| {
-                    patterns
-                        .iter()
-                        .map(|pattern| pattern.resolve(row, &prefix_map))
-                })
-                .collect::<Result<_, _>>()?
-        };
-
-        let mut store = StoreEngine::new(deps.storage)?;
-        let count = store.delete_all(&atoms)?;
-
-        Ok(Response::new()
-            .add_attribute("action", "delete")
-            .add_attribute("triple_count", count))
+        let store = STORE.load(storage)?;
+        let prefix_map = merge_prefixes(storage, prefixes)?.into_inner();
+        let plan = PlanBuilder::new(storage, &prefix_map)
+            .with_full_iri_keys(store.full_iri_keys)
+            .with_max_iri_byte_size(store.limits.max_iri_byte_size)
+            .with_canonical_booleans(store.canonical_booleans)
+            .with_normalize_iris(store.normalize_iris)
+            .build_plan(r#where)?;
+
+        let response = QueryEngine::new(storage)
+            .with_subject_index(store.subject_index)
+            .select(plan, variables)?;
+        let atoms: Vec<Atom> = response
+            .results
+            .bindings
+            .iter()
+            .flat_map(|row| {
+                patterns
+                    .iter()
+                    .map(|pattern| pattern.resolve(row, &prefix_map))
+            })
+            .collect::<StdResult<_>>()?;
+
+        for atom in &atoms {
+            for iri in atom.iris() {
+                rdf::check_iri_byte_size(iri, store.limits.max_iri_byte_size)?;
+            }
+            if store.validate_language_tags {
+                atom.value.check_language_tag()?;
+            }
+        }
+        Ok(atoms)
     }
 }
 
@@ -146,14 +627,145 @@ pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Store => to_binary(&query::store(deps)?),
         QueryMsg::Select { query } => to_binary(&query::select(deps, query)?),
-        QueryMsg::Describe { query, format } => {
-            to_binary(&query::describe(deps, query, format.unwrap_or_default())?)
-        }
-        QueryMsg::Construct { query, format } => to_binary(&query::construct(
+        QueryMsg::Describe {
+            query,
+            format,
+            style,
+            base,
+        } => to_binary(&query::describe(
+            deps,
+            query,
+            format.unwrap_or_default(),
+            style,
+            base,
+        )?),
+        QueryMsg::Construct {
+            query,
+            format,
+            style,
+            base,
+        } => to_binary(&query::construct(
             deps,
             query,
             format.unwrap_or(DataFormat::default()),
+            style,
+            base,
+        )?),
+        QueryMsg::ValidateDelete {
+            prefixes,
+            delete,
+            r#where,
+        } => to_binary(&query::validate_delete(deps, prefixes, delete, r#where)?),
+        QueryMsg::NamespaceRefs { namespace } => {
+            to_binary(&query::namespace_refs(deps, namespace)?)
+        }
+        QueryMsg::NamespaceTriples {
+            namespace,
+            format,
+            style,
+            after,
+            max_scan,
+        } => to_binary(&query::namespace_triples(
+            deps,
+            namespace,
+            format.unwrap_or_default(),
+            style,
+            after,
+            max_scan,
+        )?),
+        QueryMsg::DescribeSubject {
+            subject,
+            format,
+            style,
+            after,
+            max_scan,
+            follow_equivalents,
+        } => to_binary(&query::describe_subject(
+            deps,
+            subject,
+            format.unwrap_or_default(),
+            style,
+            after,
+            max_scan,
+            follow_equivalents.unwrap_or(false),
+        )?),
+        QueryMsg::DatatypeTriples {
+            datatype,
+            format,
+            style,
+            after,
+            max_scan,
+        } => to_binary(&query::datatype_triples(
+            deps,
+            datatype,
+            format.unwrap_or_default(),
+            style,
+            after,
+            max_scan,
+        )?),
+        QueryMsg::InsertedTriples {
+            from_height,
+            to_height,
+            format,
+            style,
+            after,
+            max_scan,
+        } => to_binary(&query::inserted_triples(
+            deps,
+            from_height,
+            to_height,
+            format.unwrap_or_default(),
+            style,
+            after,
+            max_scan,
+        )?),
+        QueryMsg::Label {
+            subject,
+            predicate,
+            language,
+        } => to_binary(&query::label(deps, subject, predicate, language)?),
+        QueryMsg::ListPredicates { after } => to_binary(&query::list_predicates(deps, after)?),
+        QueryMsg::Normalize { format, data } => {
+            to_binary(&query::normalize(deps, format.unwrap_or_default(), data)?)
+        }
+        QueryMsg::Diff {
+            format,
+            data,
+            with_new,
+        } => to_binary(&query::diff(
+            deps,
+            format.unwrap_or_default(),
+            data,
+            with_new.unwrap_or(false),
+        )?),
+        QueryMsg::EstimateImport { format, data } => to_binary(&query::estimate_import(
+            deps,
+            format.unwrap_or_default(),
+            data,
         )?),
+        QueryMsg::Explode { iri } => to_binary(&query::explode(deps, iri)?),
+        QueryMsg::Prefixes => to_binary(&query::prefixes(deps)?),
+        QueryMsg::ContentDigest { continuation } => {
+            to_binary(&query::content_digest(deps, continuation)?)
+        }
+        QueryMsg::Export {
+            format,
+            style,
+            base,
+            skolemize,
+            after,
+        } => to_binary(&query::export(
+            deps,
+            format.unwrap_or_default(),
+            style,
+            base,
+            skolemize.unwrap_or_default(),
+            after,
+        )?),
+        QueryMsg::StoredTriple { prefixes, triple } => {
+            to_binary(&query::stored_triple(deps, prefixes, triple)?)
+        }
+        QueryMsg::Stats => to_binary(&query::stats(deps)?),
     }
 }
 
@@ -162,45 +774,194 @@ pub mod query {
 
     use super::*;
     use crate::msg::{
-        ConstructQuery, DescribeQuery, DescribeResponse, Node, SelectItem, SelectQuery,
-        SelectResponse, SimpleWhereCondition, StoreResponse, TriplePattern, Value, VarOrNamedNode,
-        VarOrNode, VarOrNodeOrLiteral, WhereCondition,
+        ConstructQuery, ContentDigestResponse, DatatypeTriplesResponse, DeleteProblem,
+        DeleteProblemReason, DescribeQuery, DescribeResponse, DescribeSubjectResponse,
+        DiffResponse, EstimateImportResponse, ExplodeResponse, ExportResponse,
+        InsertedTriplesResponse, LabelResponse, ListPredicatesResponse, NamespaceRefsResponse,
+        NamespaceTriplesResponse, Node, NormalizeResponse, Prefix, PrefixesResponse, SelectItem,
+        SelectQuery, SelectResponse, SimpleWhereCondition, StatsResponse, StoreResponse,
+        StoredNamespace, StoredTriple, StoredTripleResponse, TriplePattern, TurtleStyle,
+        ValidateDeleteResponse, Value, VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause,
+        WhereCondition,
     };
-    use crate::querier::{PlanBuilder, QueryEngine};
-    use crate::rdf::{self, Atom, PrefixMap, TripleWriter};
+    use crate::querier::{ContentDigest, ContentDigestContinuation, PlanBuilder, QueryEngine};
+    use crate::rdf::{self, Atom, TripleWriter};
+    use crate::state::{self, namespaces};
+    use crate::state::{load_prefixes, merge_prefixes};
+    use crate::storer::{AtomProblem, OverlayStorage, StoreEngine};
+    use cosmwasm_std::{from_binary, Uint128, Uint64};
+    use okp4_cursor::check_cursor_length;
+    use std::any::type_name;
+    use std::borrow::Cow;
 
     pub fn store(deps: Deps<'_>) -> StdResult<StoreResponse> {
         STORE.load(deps.storage).map(Into::into)
     }
 
-    pub fn select(deps: Deps<'_>, query: SelectQuery) -> StdResult<SelectResponse> {
+    pub fn content_digest(
+        deps: Deps<'_>,
+        continuation: Option<Binary>,
+    ) -> StdResult<ContentDigestResponse> {
         let store = STORE.load(deps.storage)?;
+        let (mut digest, after) = match continuation {
+            Some(continuation) => {
+                let continuation: ContentDigestContinuation = from_binary(&continuation)?;
+                (continuation.digest, cursor_bytes(Some(continuation.after))?)
+            }
+            None => (ContentDigest::default(), None),
+        };
 
-        if query.select.len() > store.limits.max_query_variable_count as usize {
-            Err(StdError::generic_err(
-                "Maximum query variable count exceeded",
-            ))?;
-        }
+        let cursor = QueryEngine::new(deps.storage).content_digest(
+            &mut digest,
+            after,
+            store.limits.max_query_limit as usize,
+        )?;
+
+        Ok(match cursor {
+            Some(after) => ContentDigestResponse {
+                digest: None,
+                continuation: Some(to_binary(&ContentDigestContinuation {
+                    after: after.into(),
+                    digest,
+                })?),
+            },
+            None => ContentDigestResponse {
+                digest: Some(Binary::from(digest.finalize())),
+                continuation: None,
+            },
+        })
+    }
 
-        let count = query.limit.unwrap_or(store.limits.max_query_limit);
-        if count > store.limits.max_query_limit {
-            Err(StdError::generic_err("Maximum query limit exceeded"))?;
-        }
+    pub fn export(
+        deps: Deps<'_>,
+        format: DataFormat,
+        style: Option<TurtleStyle>,
+        base: Option<String>,
+        skolemize: bool,
+        after: Option<Binary>,
+    ) -> StdResult<ExportResponse> {
+        let store = STORE.load(deps.storage)?;
+        let skolem_base = match (skolemize, &store.skolem_base) {
+            (false, _) => None,
+            (true, Some(base)) => Some(base.as_str()),
+            (true, None) => Err(StdError::generic_err(
+                "Blank nodes can't be skolemized, no skolem base is configured",
+            ))?,
+        };
 
-        let prefix_map = PrefixMap::from(query.prefixes).into_inner();
-        let plan = PlanBuilder::new(deps.storage, &prefix_map)
-            .with_limit(count as usize)
-            .build_plan(&query.r#where)?;
+        let (keyed_atoms, cursor) = QueryEngine::new(deps.storage)
+            .all_triples(cursor_bytes(after)?, store.limits.max_query_limit as usize)?;
+        let (keys, atoms): (Vec<_>, Vec<_>) = keyed_atoms.into_iter().unzip();
+
+        let prefixes = load_prefixes(deps.storage)?;
+        let (data, written) = write_atoms_within(
+            &format,
+            style,
+            base.as_deref(),
+            skolem_base,
+            &atoms,
+            &prefixes,
+            store.limits.max_query_output_byte_size,
+        )?;
+        let truncated = written < atoms.len();
+        let cursor = if truncated {
+            keys.into_iter().nth(written - 1)
+        } else {
+            cursor
+        };
 
-        QueryEngine::new(deps.storage).select(plan, query.select)
+        Ok(ExportResponse {
+            format,
+            data: Binary::from(data),
+            cursor: cursor.map(Binary::from),
+            truncated,
+        })
     }
 
-    pub fn describe(
+    pub fn stored_triple(
         deps: Deps<'_>,
-        query: DescribeQuery,
-        format: DataFormat,
-    ) -> StdResult<DescribeResponse> {
-        fn get_value(
+        prefixes: Vec<Prefix>,
+        triple: TriplePattern,
+    ) -> StdResult<StoredTripleResponse> {
+        let prefix_map = merge_prefixes(deps.storage, prefixes)?.into_inner();
+        let atom = triple.resolve(&BTreeMap::new(), &prefix_map)?;
+
+        let Some(stored) = StoreEngine::stored_triple(deps.storage, &atom)? else {
+            return Ok(StoredTripleResponse { stored: None });
+        };
+        let mut keys = stored.namespace_keys();
+        keys.sort_unstable();
+        keys.dedup();
+        let namespaces = keys
+            .into_iter()
+            .map(|key| {
+                namespaces()
+                    .idx
+                    .key
+                    .item(deps.storage, key)?
+                    .map(|(_, namespace)| StoredNamespace {
+                        namespace: namespace.value,
+                        key: namespace.key.into(),
+                        counter: namespace.counter.into(),
+                    })
+                    .ok_or_else(|| StdError::not_found(type_name::<state::Namespace>()))
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(StoredTripleResponse {
+            stored: Some(StoredTriple {
+                object_hash: Binary::from(stored.object.as_hash().as_bytes().as_slice()),
+                predicate_key: Binary::from(stored.predicate.key()),
+                subject_key: Binary::from(stored.subject.key()),
+                namespaces,
+                repr: format!("{stored:?}"),
+            }),
+        })
+    }
+
+    pub fn stats(deps: Deps<'_>) -> StdResult<StatsResponse> {
+        STORE.load(deps.storage).map(|store| StatsResponse {
+            stat: store.stat.into(),
+            limits: store.limits.into(),
+        })
+    }
+
+    pub fn select(deps: Deps<'_>, query: SelectQuery) -> StdResult<SelectResponse> {
+        let store = STORE.load(deps.storage)?;
+
+        if query.select.len() > store.limits.max_query_variable_count as usize {
+            Err(StdError::generic_err(
+                "Maximum query variable count exceeded",
+            ))?;
+        }
+
+        let count = query.limit.unwrap_or(store.limits.max_query_limit);
+        if count > store.limits.max_query_limit {
+            Err(StdError::generic_err("Maximum query limit exceeded"))?;
+        }
+
+        let prefix_map = merge_prefixes(deps.storage, query.prefixes)?.into_inner();
+        let plan = PlanBuilder::new(deps.storage, &prefix_map)
+            .with_full_iri_keys(store.full_iri_keys)
+            .with_max_iri_byte_size(store.limits.max_iri_byte_size)
+            .with_canonical_booleans(store.canonical_booleans)
+            .with_normalize_iris(store.normalize_iris)
+            .with_limit(count as usize)
+            .build_plan(&query.r#where)?;
+
+        QueryEngine::new(deps.storage)
+            .with_subject_index(store.subject_index)
+            .select(plan, query.select)
+    }
+
+    pub fn describe(
+        deps: Deps<'_>,
+        query: DescribeQuery,
+        format: DataFormat,
+        style: Option<TurtleStyle>,
+        base: Option<String>,
+    ) -> StdResult<DescribeResponse> {
+        fn get_value(
             index: usize,
             vars: &[String],
             bindings: &BTreeMap<String, Value>,
@@ -253,12 +1014,18 @@ pub mod query {
                 ))],
             ),
         };
-        let prefix_map = <PrefixMap>::from(query.prefixes).into_inner();
+        let prefix_map = merge_prefixes(deps.storage, query.prefixes)?.into_inner();
         let plan = PlanBuilder::new(deps.storage, &prefix_map)
+            .with_full_iri_keys(store.full_iri_keys)
+            .with_max_iri_byte_size(store.limits.max_iri_byte_size)
+            .with_canonical_booleans(store.canonical_booleans)
+            .with_normalize_iris(store.normalize_iris)
             .with_limit(store.limits.max_query_limit as usize)
             .build_plan(&r#where)?;
 
-        let response = QueryEngine::new(deps.storage).select(plan, select)?;
+        let response = QueryEngine::new(deps.storage)
+            .with_subject_index(store.subject_index)
+            .select(plan, select)?;
 
         let mut vars = response.head.vars;
         let mut bindings = response.results.bindings;
@@ -270,7 +1037,13 @@ pub mod query {
         }
 
         let out: Vec<u8> = Vec::default();
-        let mut writer = TripleWriter::new(&format, out);
+        let mut writer = TripleWriter::with_base(&format, out, base).with_blank_node_relabeling();
+        if let Some(style) = style {
+            writer = writer.with_turtle_style(style);
+        }
+        if let Some(base) = store.skolem_base {
+            writer = writer.with_skolemization(base);
+        }
 
         for r in &bindings {
             let atom = &Atom {
@@ -280,16 +1053,9 @@ pub mod query {
             };
             let triple = atom.into();
 
-            writer.write(&triple).map_err(|e| {
-                StdError::serialize_err(
-                    "triple",
-                    format!("Error writing triple {}: {}", &triple, e),
-                )
-            })?;
+            writer.write(&triple)?;
         }
-        let out = writer
-            .finish()
-            .map_err(|e| StdError::serialize_err("triple", format!("Error writing triple: {e}")))?;
+        let out = writer.finish()?;
 
         Ok(DescribeResponse {
             format,
@@ -297,10 +1063,488 @@ pub mod query {
         })
     }
 
+    pub fn validate_delete(
+        deps: Deps<'_>,
+        prefixes: Vec<Prefix>,
+        delete: Vec<TriplePattern>,
+        r#where: WhereClause,
+    ) -> StdResult<ValidateDeleteResponse> {
+        let atoms = execute::resolve_delete_atoms(deps.storage, prefixes, delete, &r#where)?;
+
+        let problems = StoreEngine::validate_atoms(deps.storage, &atoms)?
+            .into_iter()
+            .map(|(atom, problem)| DeleteProblem {
+                triple: atom.to_string(),
+                reason: match problem {
+                    AtomProblem::Invalid(error) => DeleteProblemReason::Invalid { error },
+                    AtomProblem::MissingNamespace(namespace) => {
+                        DeleteProblemReason::MissingNamespace { namespace }
+                    }
+                    AtomProblem::NotFound => DeleteProblemReason::NotFound,
+                },
+            })
+            .collect();
+
+        Ok(ValidateDeleteResponse { problems })
+    }
+
+    /// Returns the number of triples a scan can examine, i.e. the maximum query limit or the
+    /// requested maximum scan if provided, which can't exceed it.
+    fn scan_limit(store: &Store, max_scan: Option<u32>) -> StdResult<usize> {
+        match max_scan {
+            Some(0) => Err(StdError::generic_err("The maximum scan must be positive")),
+            Some(max_scan) if max_scan > store.limits.max_query_limit => {
+                Err(StdError::generic_err("Maximum query limit exceeded"))
+            }
+            max_scan => Ok(max_scan.unwrap_or(store.limits.max_query_limit) as usize),
+        }
+    }
+
+    /// Returns the bytes of the cursor provided to continue a query from, rejecting the cursors
+    /// longer than [okp4_cursor::MAX_CURSOR_LENGTH].
+    fn cursor_bytes(after: Option<Binary>) -> StdResult<Option<Vec<u8>>> {
+        after
+            .map(|after| {
+                check_cursor_length(after.as_slice())?;
+                Ok(after.into())
+            })
+            .transpose()
+    }
+
+    pub fn namespace_refs(deps: Deps<'_>, namespace: String) -> StdResult<NamespaceRefsResponse> {
+        let namespace = namespaces().may_load(deps.storage, namespace)?;
+
+        Ok(NamespaceRefsResponse {
+            key: namespace.as_ref().map(|ns| ns.key.into()),
+            counter: namespace.map(|ns| ns.counter.into()),
+        })
+    }
+
+    pub fn namespace_triples(
+        deps: Deps<'_>,
+        namespace: String,
+        format: DataFormat,
+        style: Option<TurtleStyle>,
+        after: Option<Binary>,
+        max_scan: Option<u32>,
+    ) -> StdResult<NamespaceTriplesResponse> {
+        let store = STORE.load(deps.storage)?;
+        let ns_key = namespaces().load(deps.storage, namespace)?.key;
+
+        let (atoms, cursor) = QueryEngine::new(deps.storage).namespace_triples(
+            ns_key,
+            cursor_bytes(after)?,
+            scan_limit(&store, max_scan)?,
+        )?;
+
+        let out = write_atoms(&format, style, &atoms)?;
+
+        Ok(NamespaceTriplesResponse {
+            format,
+            data: Binary::from(out),
+            cursor: cursor.map(Binary::from),
+        })
+    }
+
+    pub fn datatype_triples(
+        deps: Deps<'_>,
+        datatype: String,
+        format: DataFormat,
+        style: Option<TurtleStyle>,
+        after: Option<Binary>,
+        max_scan: Option<u32>,
+    ) -> StdResult<DatatypeTriplesResponse> {
+        let store = STORE.load(deps.storage)?;
+
+        let (ns, value) = store.split_iri(&datatype)?;
+        let (atoms, cursor) = match namespaces().may_load(deps.storage, ns)? {
+            Some(namespace) => QueryEngine::new(deps.storage).datatype_triples(
+                &state::Node {
+                    namespace: namespace.key,
+                    value,
+                },
+                cursor_bytes(after)?,
+                scan_limit(&store, max_scan)?,
+            )?,
+            None => (vec![], None),
+        };
+
+        Ok(DatatypeTriplesResponse {
+            format: format.clone(),
+            data: Binary::from(write_atoms(&format, style, &atoms)?),
+            cursor: cursor.map(Binary::from),
+        })
+    }
+
+    pub fn inserted_triples(
+        deps: Deps<'_>,
+        from_height: Uint64,
+        to_height: Uint64,
+        format: DataFormat,
+        style: Option<TurtleStyle>,
+        after: Option<Binary>,
+        max_scan: Option<u32>,
+    ) -> StdResult<InsertedTriplesResponse> {
+        let store = STORE.load(deps.storage)?;
+        if !store.record_triple_metadata {
+            Err(StdError::generic_err(
+                "The store doesn't record the triple metadata",
+            ))?;
+        }
+
+        let (atoms, cursor) = QueryEngine::new(deps.storage).inserted_triples(
+            from_height.u64()..=to_height.u64(),
+            cursor_bytes(after)?,
+            scan_limit(&store, max_scan)?,
+        )?;
+
+        Ok(InsertedTriplesResponse {
+            format: format.clone(),
+            data: Binary::from(write_atoms(&format, style, &atoms)?),
+            cursor: cursor.map(Binary::from),
+        })
+    }
+
+    pub fn describe_subject(
+        deps: Deps<'_>,
+        subject: String,
+        format: DataFormat,
+        style: Option<TurtleStyle>,
+        after: Option<Binary>,
+        max_scan: Option<u32>,
+        follow_equivalents: bool,
+    ) -> StdResult<DescribeSubjectResponse> {
+        let store = STORE.load(deps.storage)?;
+        let limit = scan_limit(&store, max_scan)?;
+        let after = cursor_bytes(after)?;
+
+        let (ns, value) = store.split_iri(&subject)?;
+        let (atoms, cursor) = match namespaces().may_load(deps.storage, ns)? {
+            Some(namespace) => {
+                let subject = state::Subject::Named(state::Node {
+                    namespace: namespace.key,
+                    value,
+                });
+                if follow_equivalents {
+                    equivalent_subjects_triples(deps, &store, subject, after, limit)?
+                } else {
+                    QueryEngine::new(deps.storage).subject_triples(subject, after, limit)?
+                }
+            }
+            None => (vec![], None),
+        };
+
+        Ok(DescribeSubjectResponse {
+            format: format.clone(),
+            data: Binary::from(write_atoms(&format, style, &atoms)?),
+            cursor: cursor.map(Binary::from),
+        })
+    }
+
+    /// Returns at most `limit` triples of the subject followed by the ones of its equivalents, see
+    /// [QueryEngine::equivalent_subjects], the cursor being made of the position of the subject
+    /// to continue from, on 4 bytes, followed by the cursor within its triples.
+    fn equivalent_subjects_triples(
+        deps: Deps<'_>,
+        store: &Store,
+        subject: state::Subject,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<Atom>, Option<Vec<u8>>)> {
+        let mut properties = Vec::new();
+        for iri in [rdf::OWL_SAME_AS, rdf::OWL_EQUIVALENT_CLASS] {
+            let (ns, value) = store.split_iri(iri)?;
+            if let Some(namespace) = namespaces().may_load(deps.storage, ns)? {
+                properties.push(state::Node {
+                    namespace: namespace.key,
+                    value,
+                });
+            }
+        }
+
+        let engine = QueryEngine::new(deps.storage);
+        let mut subjects = vec![subject.clone()];
+        subjects.extend(engine.equivalent_subjects(
+            &subject,
+            &properties,
+            store.limits.max_query_limit as usize,
+        )?);
+
+        let (mut position, mut after) = match after {
+            Some(cursor) if cursor.len() >= 4 => {
+                let (position, after) = cursor.split_at(4);
+                (
+                    u32::from_be_bytes([position[0], position[1], position[2], position[3]])
+                        as usize,
+                    Some(after.to_vec()).filter(|after| !after.is_empty()),
+                )
+            }
+            Some(_) => Err(StdError::generic_err("Invalid cursor"))?,
+            None => (0, None),
+        };
+        let cursor = |position: usize, after: Option<Vec<u8>>| {
+            let mut cursor = (position as u32).to_be_bytes().to_vec();
+            cursor.extend(after.unwrap_or_default());
+            cursor
+        };
+
+        let mut atoms = Vec::new();
+        while let Some(subject) = subjects.get(position) {
+            let (found, next) =
+                engine.subject_triples(subject.clone(), after.take(), limit - atoms.len())?;
+            atoms.extend(found);
+            if next.is_some() {
+                return Ok((atoms, Some(cursor(position, next))));
+            }
+            position += 1;
+            if atoms.len() >= limit && position < subjects.len() {
+                return Ok((atoms, Some(cursor(position, None))));
+            }
+        }
+
+        Ok((atoms, None))
+    }
+
+    pub fn label(
+        deps: Deps<'_>,
+        subject: String,
+        predicate: String,
+        language: String,
+    ) -> StdResult<LabelResponse> {
+        let store = STORE.load(deps.storage)?;
+
+        let (s_ns, s_value) = store.split_iri(&subject)?;
+        let (p_ns, p_value) = store.split_iri(&predicate)?;
+        let values = match (
+            namespaces().may_load(deps.storage, s_ns)?,
+            namespaces().may_load(deps.storage, p_ns)?,
+        ) {
+            (Some(s_ns), Some(p_ns)) => QueryEngine::new(deps.storage)
+                .with_label_index(store.label_index)
+                .get_label(
+                    &state::Subject::Named(state::Node {
+                        namespace: s_ns.key,
+                        value: s_value,
+                    }),
+                    &state::Node {
+                        namespace: p_ns.key,
+                        value: p_value,
+                    },
+                    &language,
+                )?,
+            _ => vec![],
+        };
+
+        Ok(LabelResponse { values })
+    }
+
+    pub fn list_predicates(
+        deps: Deps<'_>,
+        after: Option<Binary>,
+    ) -> StdResult<ListPredicatesResponse> {
+        let store = STORE.load(deps.storage)?;
+
+        let (predicates, cursor) = QueryEngine::new(deps.storage)
+            .with_predicate_index(store.predicate_index)
+            .predicates(cursor_bytes(after)?, store.limits.max_query_limit as usize)?;
+
+        Ok(ListPredicatesResponse {
+            predicates,
+            cursor: cursor.map(Binary::from),
+        })
+    }
+
+    pub fn normalize(
+        deps: Deps<'_>,
+        format: DataFormat,
+        data: Binary,
+    ) -> StdResult<NormalizeResponse> {
+        let store = STORE.load(deps.storage)?;
+
+        let mut reader = execute::triple_reader(deps.storage, &store, &format, data.as_slice())?;
+        let atoms = StoreEngine::normalize_all(&mut reader, &store).map_err(|e| match e {
+            ContractError::Std(e) => e,
+            e => StdError::generic_err(e.to_string()),
+        })?;
+
+        Ok(NormalizeResponse {
+            data: Binary::from(write_atoms(&DataFormat::NTriples, None, &atoms)?),
+        })
+    }
+
+    pub fn diff(
+        deps: Deps<'_>,
+        format: DataFormat,
+        data: Binary,
+        with_new: bool,
+    ) -> StdResult<DiffResponse> {
+        let store = STORE.load(deps.storage)?;
+        let mut reader = execute::triple_reader(deps.storage, &store, &format, data.as_slice())?;
+        let diff =
+            StoreEngine::diff_all(deps.storage, &mut reader, with_new).map_err(|e| match e {
+                ContractError::Std(e) => e,
+                e => StdError::generic_err(e.to_string()),
+            })?;
+
+        Ok(DiffResponse {
+            new_count: diff.new_count,
+            existing_count: diff.existing_count,
+            new: diff
+                .new
+                .map(|atoms| write_atoms(&DataFormat::NTriples, None, &atoms).map(Binary::from))
+                .transpose()?,
+        })
+    }
+
+    pub fn estimate_import(
+        deps: Deps<'_>,
+        format: DataFormat,
+        data: Binary,
+    ) -> StdResult<EstimateImportResponse> {
+        let store = STORE.load(deps.storage)?;
+        let (data, _) = match &store.invalid_utf8_replacement {
+            Some(replacement) => rdf::replace_invalid_utf8(data.as_slice(), replacement),
+            None => (Cow::Borrowed(data.as_slice()), 0),
+        };
+        let mut reader = execute::triple_reader(deps.storage, &store, &format, &data)?;
+
+        // the insertion is run on top of the storage, its writes being discarded
+        let mut overlay = OverlayStorage::new(deps.storage);
+        let mut storer = StoreEngine::new(&mut overlay)?;
+        if format == DataFormat::Turtle {
+            storer = storer.with_namespace_hints(rdf::turtle_prefix_hints(&data));
+        }
+        let progress = storer
+            .store_all(&mut reader, Uint128::zero())
+            .map_err(|e| match e {
+                ContractError::Std(e) => e,
+                e => StdError::generic_err(e.to_string()),
+            })?;
+        let estimated = STORE.load(&overlay)?;
+
+        Ok(EstimateImportResponse {
+            triple_count: progress.count,
+            byte_size: estimated
+                .stat
+                .byte_size
+                .saturating_sub(store.stat.byte_size),
+            namespace_count: estimated
+                .stat
+                .namespace_count
+                .saturating_sub(store.stat.namespace_count),
+            resume_from: progress.resume_from,
+        })
+    }
+
+    pub fn explode(deps: Deps<'_>, iri: String) -> StdResult<ExplodeResponse> {
+        let store = STORE.load(deps.storage)?;
+
+        let (namespace, value) = StoreEngine::explode(&store, &iri).map_err(|e| match e {
+            ContractError::Std(e) => e,
+            e => StdError::generic_err(e.to_string()),
+        })?;
+
+        Ok(ExplodeResponse { namespace, value })
+    }
+
+    fn write_atoms(
+        format: &DataFormat,
+        style: Option<TurtleStyle>,
+        atoms: &[Atom],
+    ) -> StdResult<Vec<u8>> {
+        write_prefixed_atoms(format, style, None, None, atoms, &[])
+    }
+
+    /// Serializes the atoms, the IRIs being written in Turtle as prefixed names using the given
+    /// prefixes, only the ones actually used being declared, or relative to the given base.
+    fn write_prefixed_atoms(
+        format: &DataFormat,
+        style: Option<TurtleStyle>,
+        base: Option<&str>,
+        skolem_base: Option<&str>,
+        atoms: &[Atom],
+        prefixes: &[Prefix],
+    ) -> StdResult<Vec<u8>> {
+        let out: Vec<u8> = Vec::default();
+        let mut writer = TripleWriter::with_base(format, out, base.map(str::to_string));
+        if let Some(style) = style {
+            writer = writer.with_turtle_style(style);
+        }
+        if let Some(skolem_base) = skolem_base {
+            writer = writer.with_skolemization(skolem_base.to_string());
+        }
+        if !prefixes.is_empty() {
+            writer = writer.with_prefixes(
+                prefixes
+                    .iter()
+                    .map(|p| (p.prefix.clone(), p.namespace.clone())),
+            );
+        }
+        for atom in atoms {
+            let triple = atom.into();
+            writer.write(&triple)?;
+        }
+        Ok(writer.finish()?)
+    }
+
+    /// Serializes the longest run of the atoms fitting in the given number of bytes, returning
+    /// the data along with the number of atoms written, all of them if no maximum is given.
+    ///
+    /// Each atom must fit on its own: as the data is a complete document in the given format, it
+    /// can't be cut in the middle of a triple.
+    fn write_atoms_within(
+        format: &DataFormat,
+        style: Option<TurtleStyle>,
+        base: Option<&str>,
+        skolem_base: Option<&str>,
+        atoms: &[Atom],
+        prefixes: &[Prefix],
+        max: Option<Uint128>,
+    ) -> StdResult<(Vec<u8>, usize)> {
+        let write_atoms =
+            |atoms| write_prefixed_atoms(format, style, base, skolem_base, atoms, prefixes);
+        let data = write_atoms(atoms)?;
+        let Some(max) = max else {
+            return Ok((data, atoms.len()));
+        };
+        let fits = |data: &[u8]| Uint128::from(data.len() as u128) <= max;
+        if fits(&data) || atoms.is_empty() {
+            return Ok((data, atoms.len()));
+        }
+
+        // the size of the data grows with the number of atoms written, so the longest run fitting
+        // in is looked up by bisection, starting from the first atom fitting on its own
+        let first = write_atoms(&atoms[..1])?;
+        if !fits(&first) {
+            Err(StdError::generic_err(format!(
+                "The triple exceeds the maximum output byte size: {max}"
+            )))?;
+        }
+        let (mut written, mut data, mut upper) = (1, first, atoms.len());
+        while upper - written > 1 {
+            let middle = written + (upper - written) / 2;
+            let candidate = write_atoms(&atoms[..middle])?;
+            if fits(&candidate) {
+                (written, data) = (middle, candidate);
+            } else {
+                upper = middle;
+            }
+        }
+        Ok((data, written))
+    }
+
+    pub fn prefixes(deps: Deps<'_>) -> StdResult<PrefixesResponse> {
+        Ok(PrefixesResponse {
+            prefixes: load_prefixes(deps.storage)?,
+        })
+    }
+
     pub fn construct(
         _deps: Deps<'_>,
         _query: ConstructQuery,
         _format: DataFormat,
+        _style: Option<TurtleStyle>,
+        _base: Option<String>,
     ) -> StdResult<SelectResponse> {
         Err(StdError::generic_err("Not implemented"))
     }
@@ -309,28 +1553,36 @@ pub mod query {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::StoreError;
+    use crate::error::{RDFParseError, StoreError};
     use crate::msg::ExecuteMsg::{DeleteData, InsertData};
     use crate::msg::Node::NamedNode;
     use crate::msg::QueryMsg::Construct;
     use crate::msg::SimpleWhereCondition::TriplePattern;
     use crate::msg::IRI::{Full, Prefixed};
     use crate::msg::{
-        ConstructQuery, DescribeQuery, DescribeResponse, Head, Literal, Prefix, Results,
-        SelectItem, SelectQuery, SelectResponse, StoreLimitsInput, StoreLimitsInputBuilder,
-        StoreResponse, Value, VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereCondition,
+        ConstructQuery, ContentDigestResponse, DatatypeTriplesResponse, DeleteProblem,
+        DeleteProblemReason, DescribeQuery, DescribeResponse, DescribeSubjectResponse,
+        DiffResponse, EstimateImportResponse, ExplodeResponse, Head, InsertedTriplesResponse,
+        LabelResponse, ListPredicatesResponse, Literal, NamespaceRefsResponse,
+        NamespaceTriplesResponse, NodeOrLiteral, NormalizeResponse, Prefix, PrefixesResponse,
+        Results, SelectItem, SelectQuery, SelectResponse, StatsResponse, StoreLimitsInput,
+        StoreLimitsInputBuilder, StoreResponse, TurtleStyle, ValidateDeleteResponse, Value,
+        VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereCondition,
     };
+    use crate::querier::{ContentDigest, ContentDigestContinuation};
     use crate::state::{
         namespaces, triples, Namespace, Node, Object, StoreLimits, StoreStat, Subject, Triple,
+        TripleMetadata, TriplePK, PREDICATE_INDEXING,
     };
     use crate::{msg, state};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{from_binary, Addr, Attribute, Order, Uint128};
-    use std::collections::BTreeMap;
+    use cosmwasm_std::{from_binary, Addr, Attribute, Order, OwnedDeps, Uint128};
+    use std::any::type_name;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::env;
     use std::fs::File;
     use std::io::Read;
     use std::path::Path;
-    use std::{env, u128};
 
     #[test]
     fn proper_initialization() {
@@ -341,11 +1593,40 @@ mod tests {
                 max_triple_count: Uint128::from(1u128),
                 max_byte_size: Uint128::from(2u128),
                 max_triple_byte_size: Uint128::from(3u128),
+                max_literal_byte_size: Some(Uint128::from(3u128)),
                 max_query_limit: 4,
                 max_query_variable_count: 5,
                 max_insert_data_byte_size: Uint128::from(6u128),
                 max_insert_data_triple_count: Uint128::from(7u128),
+                allowed_schemes: Some(vec!["https".to_string()]),
+                max_triples_per_subject: Some(Uint128::from(8u128)),
+                max_triples_per_call: Some(Uint128::from(9u128)),
+                max_iri_byte_size: Some(Uint128::from(10u128)),
+                max_collection_triple_count: Some(Uint128::from(11u128)),
+                max_blank_nodes_per_import: Some(Uint128::from(12u128)),
+                max_query_output_byte_size: Some(Uint128::from(13u128)),
+                max_insert_data_byte_size_per_subject: Some(Uint128::from(14u128)),
             },
+            shapes: vec![],
+            retain_empty_namespaces: false,
+            functional_properties: vec![],
+            normalize_iris: false,
+            full_iri_keys: false,
+            validate_language_tags: false,
+            record_triple_metadata: false,
+            skolem_base: None,
+            lenient_rdf_xml: false,
+            byte_accounting: msg::ByteAccounting::Raw,
+            subject_index: false,
+            invalid_utf8_replacement: None,
+            label_index: false,
+            strict_iri_chars: false,
+            dedup_imports: false,
+            canonical_booleans: false,
+            class_hierarchy: BTreeMap::new(),
+            skip_empty_literals: false,
+            reject_literal_types: false,
+            require_absolute_iris: false,
         };
 
         let info = mock_info("owner", &[]);
@@ -360,10 +1641,19 @@ mod tests {
                 max_triple_count: Uint128::from(1u128),
                 max_byte_size: Uint128::from(2u128),
                 max_triple_byte_size: Uint128::from(3u128),
+                max_literal_byte_size: Some(Uint128::from(3u128)),
                 max_query_limit: 4,
                 max_query_variable_count: 5,
                 max_insert_data_byte_size: Uint128::from(6u128),
                 max_insert_data_triple_count: Uint128::from(7u128),
+                allowed_schemes: Some(vec!["https".to_string()]),
+                max_triples_per_subject: Some(Uint128::from(8u128)),
+                max_triples_per_call: Some(Uint128::from(9u128)),
+                max_iri_byte_size: Some(Uint128::from(10u128)),
+                max_collection_triple_count: Some(Uint128::from(11u128)),
+                max_blank_nodes_per_import: Some(Uint128::from(12u128)),
+                max_query_output_byte_size: Some(Uint128::from(13u128)),
+                max_insert_data_byte_size_per_subject: Some(Uint128::from(14u128)),
             }
         );
         assert_eq!(
@@ -378,28 +1668,99 @@ mod tests {
         assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 0u128);
     }
 
+    #[test]
+    fn proper_insert_quads() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NQuads),
+                data: read_test_data("sample.nq"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "40")
+            ]
+        );
+
+        // the triples are the ones of the other formats, in the graph the quads name, accounted
+        // for its IRI and namespace
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            StoreStat {
+                triple_count: 40u128.into(),
+                namespace_count: 18u128.into(),
+                byte_size: (7103u128 + 40 * "https://ontology.okp4.space/cognigraph".len() as u128)
+                    .into(),
+            },
+        );
+        let graph = namespaces()
+            .load(&deps.storage, "https://ontology.okp4.space/".to_string())
+            .unwrap();
+        assert_eq!(graph.counter, 40u128);
+        assert!(triples()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .all(|res| res.unwrap().1.graph
+                == Some(Node {
+                    namespace: graph.key,
+                    value: "cognigraph".to_string(),
+                })));
+    }
+
     #[test]
     fn proper_insert() {
         let cases = vec![
             InsertData {
                 format: Some(DataFormat::RDFXml),
                 data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
             InsertData {
                 format: Some(DataFormat::Turtle),
                 data: read_test_data("sample.ttl"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
             InsertData {
                 format: Some(DataFormat::NTriples),
                 data: read_test_data("sample.nt"),
-            },
-            InsertData {
-                format: Some(DataFormat::NQuads),
-                data: read_test_data("sample.nq"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
             InsertData {
                 format: None,
                 data: read_test_data("sample.ttl"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         ];
 
@@ -492,6 +1853,8 @@ mod tests {
                         namespace: 0u128,
                         value: "97ff7e16-c08d-47be-8475-211016c82e33".to_string()
                     }),
+                    metadata: None,
+                    graph: None,
                 }
             )
         }
@@ -515,6 +1878,10 @@ mod tests {
             InsertData {
                 format: Some(DataFormat::RDFXml),
                 data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         );
         assert!(res.is_err());
@@ -529,7 +1896,10 @@ mod tests {
                     .max_triple_count(30u128)
                     .build()
                     .unwrap(),
-                Some(ContractError::from(StoreError::TripleCount(30u128.into()))),
+                Some(ContractError::from(StoreError::TripleCount(
+                    31u128.into(),
+                    30u128.into(),
+                ))),
             ),
             (
                 StoreLimitsInputBuilder::default()
@@ -543,7 +1913,10 @@ mod tests {
                     .max_byte_size(50u128)
                     .build()
                     .unwrap(),
-                Some(ContractError::from(StoreError::ByteSize(50u128.into()))),
+                Some(ContractError::from(StoreError::ByteSize(
+                    176u128.into(),
+                    50u128.into(),
+                ))),
             ),
             (
                 StoreLimitsInputBuilder::default()
@@ -558,6 +1931,7 @@ mod tests {
                     .build()
                     .unwrap(),
                 Some(ContractError::from(StoreError::InsertDataByteSize(
+                    551u128.into(),
                     500u128.into(),
                 ))),
             ),
@@ -585,12 +1959,30 @@ mod tests {
                     .unwrap(),
                 None,
             ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_literal_byte_size(20u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::LiteralByteSize(
+                    24u128.into(),
+                    20u128.into(),
+                ))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_literal_byte_size(400u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
             (
                 StoreLimitsInputBuilder::default()
                     .max_insert_data_triple_count(30u128)
                     .build()
                     .unwrap(),
                 Some(ContractError::from(StoreError::InsertDataTripleCount(
+                    31u128.into(),
                     30u128.into(),
                 ))),
             ),
@@ -601,21 +1993,53 @@ mod tests {
                     .unwrap(),
                 None,
             ),
-        ];
-
-        let exec_msg = InsertData {
-            format: Some(DataFormat::RDFXml),
-            data: read_test_data("sample.rdf.xml"),
-        };
-        for case in cases {
-            let mut deps = mock_dependencies();
-
+            (
+                StoreLimitsInputBuilder::default()
+                    .allowed_schemes(vec![
+                        "https".to_string(),
+                        "did".to_string(),
+                        "urn".to_string(),
+                    ])
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::IriSchemeNotAllowed(
+                    "http://www.w3.org/1999/02/22-rdf-syntax-ns#type".to_string(),
+                ))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .allowed_schemes(vec![
+                        "https".to_string(),
+                        "HTTP".to_string(),
+                        "did".to_string(),
+                        "urn".to_string(),
+                    ])
+                    .build()
+                    .unwrap(),
+                None,
+            ),
+        ];
+
+        let exec_msg = InsertData {
+            format: Some(DataFormat::RDFXml),
+            data: read_test_data("sample.rdf.xml"),
+            idempotency_key: None,
+            resume_from: None,
+            segments: None,
+            default_graph: None,
+        };
+        for case in cases {
+            let mut deps = mock_dependencies();
+
             let info = mock_info("owner", &[]);
             instantiate(
                 deps.as_mut(),
                 mock_env(),
                 info.clone(),
-                InstantiateMsg { limits: case.0 },
+                InstantiateMsg {
+                    limits: case.0,
+                    ..Default::default()
+                },
             )
             .unwrap();
 
@@ -631,182 +2055,49 @@ mod tests {
     }
 
     #[test]
-    fn proper_delete() {
-        let id = "https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
+    fn insert_literal_size_limits() {
+        let data = "<http://a.org/s> <http://a.org/p> \"a literal of 28 bytes long..\" .";
         let cases = vec![
             (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/dataverse/dataspace/metadata/unknown"
-                                .to_string(),
-                        ))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/dataverse/dataspace/metadata/unknown"
-                                .to_string(),
-                        ))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }))],
-                },
-                0,
-                0,
-                Uint128::from(7103u128),
-            ),
-            (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }))],
-                },
-                1,
-                0,
-                Uint128::from(6921u128),
-            ),
-            (
-                DeleteData {
-                    prefixes: vec![
-                        Prefix {
-                            prefix: "core".to_string(),
-                            namespace: "https://ontology.okp4.space/core/".to_string(),
-                        },
-                        Prefix {
-                            prefix: "thesaurus".to_string(),
-                            namespace: "https://ontology.okp4.space/thesaurus/topic/".to_string(),
-                        },
-                    ],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Prefixed(
-                            "core:hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Prefixed(
-                            "thesaurus:Test".to_string(),
-                        ))),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Prefixed(
-                            "core:hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Prefixed(
-                            "thesaurus:Test".to_string(),
-                        ))),
-                    }))],
-                },
-                1,
-                0,
-                Uint128::from(6921u128),
-            ),
-            (
-                DeleteData {
-                    prefixes: vec![
-                        Prefix {
-                            prefix: "core".to_string(),
-                            namespace: "https://ontology.okp4.space/core/".to_string(),
-                        },
-                        Prefix {
-                            prefix: "thesaurus".to_string(),
-                            namespace: "https://ontology.okp4.space/thesaurus/topic/".to_string(),
-                        },
-                    ],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Prefixed(
-                            "core:hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Prefixed(
-                            "core:hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }))],
-                },
-                1,
-                0,
-                Uint128::from(6921u128),
+                StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(27u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::LiteralByteSize(
+                    28u128.into(),
+                    27u128.into(),
+                ))),
             ),
             (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Variable("p".to_string()),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Variable("p".to_string()),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }))],
-                },
-                11,
-                2,
-                Uint128::from(5272u128),
+                StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(30u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::TripleByteSize(
+                    56u128.into(),
+                    30u128.into(),
+                ))),
             ),
             (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                        predicate: VarOrNode::Variable("p".to_string()),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }))],
-                },
-                11,
-                2,
-                Uint128::from(5272u128),
+                StoreLimitsInputBuilder::default()
+                    .max_literal_byte_size(27u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::LiteralByteSize(
+                    28u128.into(),
+                    27u128.into(),
+                ))),
             ),
             (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Variable("s".to_string()),
-                        predicate: VarOrNode::Variable("p".to_string()),
-                        object: VarOrNodeOrLiteral::Variable("0".to_string()),
-                    }))],
-                },
-                40,
-                17,
-                Uint128::from(0u128),
+                StoreLimitsInputBuilder::default()
+                    .max_literal_byte_size(28u128)
+                    .build()
+                    .unwrap(),
+                None,
             ),
         ];
 
-        for case in cases {
+        for (limits, expected) in cases {
             let mut deps = mock_dependencies();
 
             let info = mock_info("owner", &[]);
@@ -814,386 +2105,5594 @@ mod tests {
                 deps.as_mut(),
                 mock_env(),
                 info.clone(),
-                InstantiateMsg::default(),
+                InstantiateMsg {
+                    limits,
+                    ..Default::default()
+                },
             )
             .unwrap();
 
-            execute(
+            let res = execute(
                 deps.as_mut(),
                 mock_env(),
-                info.clone(),
+                info,
                 InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
                 },
-            )
-            .unwrap();
-
-            let res = execute(deps.as_mut(), mock_env(), info, case.0);
-
-            assert!(res.is_ok());
-            assert_eq!(
-                res.unwrap().attributes,
-                vec![
-                    Attribute::new("action", "delete"),
-                    Attribute::new("triple_count", case.1.to_string())
-                ]
             );
 
-            assert_eq!(
-                STORE.load(&deps.storage).unwrap().stat,
-                StoreStat {
-                    triple_count: (40u128 - u128::try_from(case.1).unwrap()).into(),
-                    namespace_count: (17u128 - u128::try_from(case.2).unwrap()).into(),
-                    byte_size: case.3,
-                },
-            );
-            assert_eq!(
-                triples()
-                    .range_raw(&deps.storage, None, None, Order::Ascending)
-                    .count(),
-                40 - case.1
-            );
-            assert_eq!(
-                namespaces()
-                    .range_raw(&deps.storage, None, None, Order::Ascending)
-                    .count(),
-                17 - case.2
-            );
+            assert_eq!(res.err(), expected);
         }
     }
 
     #[test]
-    fn invalid_delete() {
-        struct TC {
-            command: ExecuteMsg,
-            expected: ContractError,
-        }
-        let cases = vec![
-            TC {
-                command: DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Prefixed("foo:bar".to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Prefixed("foo:bar".to_string()))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }))],
-                },
-                expected: StdError::generic_err("Prefix not found: foo").into(),
+    fn insert_max_triples_per_subject() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_triples_per_subject(3u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
             },
-            TC {
-                command: DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                        predicate: VarOrNode::Variable("z".to_string()),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                        predicate: VarOrNode::Variable("p".to_string()),
-                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                    }))],
+        )
+        .unwrap();
+
+        let cases = vec![
+            ("<http://a.org/s> <http://a.org/p> \"1\" , \"2\" .", None),
+            ("<http://a.org/s> <http://a.org/p> \"1\" .", None),
+            (
+                "<http://a.org/s> <http://a.org/p> \"3\" , \"4\" .",
+                Some(ContractError::from(StoreError::TriplesPerSubject(
+                    4u128.into(),
+                    3u128.into(),
+                ))),
+            ),
+            ("<http://a.org/s> <http://a.org/p> \"3\" .", None),
+            (
+                "<http://a.org/s> <http://a.org/p2> \"1\" .",
+                Some(ContractError::from(StoreError::TriplesPerSubject(
+                    4u128.into(),
+                    3u128.into(),
+                ))),
+            ),
+            (
+                "<http://a.org/other> <http://a.org/p> \"1\" , \"2\" , \"3\" .",
+                None,
+            ),
+            (
+                "_:b <http://a.org/p> \"1\" , \"2\" , \"3\" , \"4\" .",
+                Some(ContractError::from(StoreError::TriplesPerSubject(
+                    4u128.into(),
+                    3u128.into(),
+                ))),
+            ),
+        ];
+
+        for (data, expected) in cases {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
                 },
-                expected: StdError::generic_err("Selected variable not found in query").into(),
+            );
+
+            assert_eq!(res.err(), expected);
+        }
+    }
+
+    #[test]
+    fn insert_shapes() {
+        let shapes = vec![
+            msg::PredicateShape {
+                predicate: "http://a.org/name".to_string(),
+                min_count: Some(1),
+                max_count: Some(2),
+                datatype: Some("http://www.w3.org/2001/XMLSchema#string".to_string()),
             },
-            TC {
-                command: DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TriplePattern {
-                        subject: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasTopic".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                        ))),
-                    }],
-                    r#where: vec![],
-                },
-                expected: StdError::generic_err("Empty basic graph pattern").into(),
+            msg::PredicateShape {
+                predicate: "http://a.org/age".to_string(),
+                min_count: None,
+                max_count: Some(1),
+                datatype: Some("http://www.w3.org/2001/XMLSchema#integer".to_string()),
             },
         ];
+        let violation = |subject: &str, predicate: &str, reason: &str| {
+            Some(ContractError::from(StoreError::ShapeViolation(
+                subject.to_string(),
+                predicate.to_string(),
+                reason.to_string(),
+            )))
+        };
 
-        for case in cases {
-            let mut deps = mock_dependencies();
+        let cases = vec![
+            (
+                vec!["<http://a.org/s> <http://a.org/name> \"a\" , \"b\" ."],
+                None,
+            ),
+            (
+                vec!["<http://a.org/s> <http://a.org/name> \"a\" ; <http://a.org/age> 42 ."],
+                None,
+            ),
+            (
+                vec!["<http://a.org/s> <http://a.org/other> \"a\" ."],
+                violation("<http://a.org/s>", "http://a.org/name", "fewer than 1 values"),
+            ),
+            (
+                vec![
+                    "<http://a.org/s> <http://a.org/name> \"a\" .",
+                    "<http://a.org/s> <http://a.org/other> \"a\" .",
+                ],
+                None,
+            ),
+            (
+                vec!["<http://a.org/s> <http://a.org/name> \"a\" , \"b\" , \"c\" ."],
+                violation("<http://a.org/s>", "http://a.org/name", "more than 2 values"),
+            ),
+            (
+                vec![
+                    "<http://a.org/s> <http://a.org/name> \"a\" , \"b\" .",
+                    "<http://a.org/s> <http://a.org/name> \"c\" .",
+                ],
+                violation("<http://a.org/s>", "http://a.org/name", "more than 2 values"),
+            ),
+            (
+                vec![
+                    "<http://a.org/s> <http://a.org/name> \"a\" , \"b\" .",
+                    "<http://a.org/s> <http://a.org/name> \"b\" .",
+                ],
+                None,
+            ),
+            (
+                vec!["<http://a.org/s> <http://a.org/name> \"a\"@en ."],
+                violation(
+                    "<http://a.org/s>",
+                    "http://a.org/name",
+                    "expected a literal of datatype <http://www.w3.org/2001/XMLSchema#string>, got \"a\"@en",
+                ),
+            ),
+            (
+                vec!["<http://a.org/s> <http://a.org/name> \"a\" ; <http://a.org/age> \"42\" ."],
+                violation(
+                    "<http://a.org/s>",
+                    "http://a.org/age",
+                    "expected a literal of datatype <http://www.w3.org/2001/XMLSchema#integer>, got \"42\"",
+                ),
+            ),
+            (
+                vec!["<http://a.org/s> <http://a.org/name> <http://a.org/o> ."],
+                violation(
+                    "<http://a.org/s>",
+                    "http://a.org/name",
+                    "expected a literal of datatype <http://www.w3.org/2001/XMLSchema#string>, got <http://a.org/o>",
+                ),
+            ),
+        ];
 
+        for (data, expected) in cases {
+            let mut deps = mock_dependencies();
             let info = mock_info("owner", &[]);
             instantiate(
                 deps.as_mut(),
                 mock_env(),
                 info.clone(),
-                InstantiateMsg::default(),
+                InstantiateMsg {
+                    shapes: shapes.clone(),
+                    ..Default::default()
+                },
             )
             .unwrap();
 
-            execute(
+            let mut res = Ok(Response::default());
+            for chunk in data {
+                res = execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    InsertData {
+                        format: Some(DataFormat::Turtle),
+                        data: Binary::from(chunk.as_bytes()),
+                        idempotency_key: None,
+                        resume_from: None,
+                        segments: None,
+                        default_graph: None,
+                    },
+                );
+            }
+
+            assert_eq!(res.err(), expected);
+        }
+    }
+
+    #[test]
+    fn insert_functional_properties() {
+        let conflict = |value: &str| {
+            Some(ContractError::Store(
+                StoreError::FunctionalPropertyConflict(
+                    "<http://a.org/s>".to_string(),
+                    "http://a.org/name".to_string(),
+                    value.to_string(),
+                ),
+            ))
+        };
+        let cases = vec![
+            (
+                vec![
+                    r#"<http://a.org/s> <http://a.org/name> "Alice" ; <http://a.org/nick> "A" , "Al" ."#,
+                    r#"<http://a.org/s> <http://a.org/name> "Alice" . <http://a.org/o> <http://a.org/name> "Bob" ."#,
+                ],
+                None,
+            ),
+            (
+                vec![r#"<http://a.org/s> <http://a.org/name> "Alice" , "Alice" , "Bob" ."#],
+                conflict("\"Bob\""),
+            ),
+            (
+                vec![
+                    r#"<http://a.org/s> <http://a.org/name> "Alice" ."#,
+                    r#"<http://a.org/s> <http://a.org/name> <http://a.org/alice> ."#,
+                ],
+                conflict("<http://a.org/alice>"),
+            ),
+        ];
+
+        for (inserts, expected) in cases {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
                 deps.as_mut(),
                 mock_env(),
                 info.clone(),
-                InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
+                InstantiateMsg {
+                    functional_properties: vec!["http://a.org/name".to_string()],
+                    ..Default::default()
                 },
             )
             .unwrap();
 
-            let res = execute(deps.as_mut(), mock_env(), info, case.command);
+            let res = inserts
+                .into_iter()
+                .map(|data| {
+                    execute(
+                        deps.as_mut(),
+                        mock_env(),
+                        info.clone(),
+                        InsertData {
+                            format: Some(DataFormat::Turtle),
+                            data: Binary::from(data.as_bytes()),
+                            idempotency_key: None,
+                            resume_from: None,
+                            segments: None,
+                            default_graph: None,
+                        },
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>();
 
-            assert!(res.is_err());
-            assert_eq!(res.unwrap_err(), case.expected);
+            assert_eq!(res.err(), expected);
         }
     }
 
     #[test]
-    fn proper_store() {
-        let mut deps = mock_dependencies();
-        STORE
-            .save(
-                deps.as_mut().storage,
-                &Store {
-                    owner: Addr::unchecked("owner"),
-                    limits: StoreLimits {
-                        max_triple_count: 1u128.into(),
-                        max_byte_size: 2u128.into(),
-                        max_triple_byte_size: 3u128.into(),
-                        max_query_limit: 4u32,
-                        max_query_variable_count: 5u32,
-                        max_insert_data_byte_size: 6u128.into(),
-                        max_insert_data_triple_count: 7u128.into(),
-                    },
-                    stat: StoreStat {
-                        triple_count: 1u128.into(),
-                        namespace_count: 2u128.into(),
-                        byte_size: 3u128.into(),
-                    },
+    fn insert_lenient_rdf_xml() {
+        let data = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:a="http://a.org/">
+  <rdf:Description rdf:about="http://a.org/s">
+    <a:p>&unknown;</a:p>
+    <a:q>1</a:q>
+  </rdf:Description>
+</rdf:RDF>"#;
+
+        for lenient_rdf_xml in [false, true] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    lenient_rdf_xml,
+                    ..Default::default()
                 },
             )
             .unwrap();
 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Store);
-        assert!(res.is_ok());
-        assert_eq!(
-            from_binary::<StoreResponse>(&res.unwrap()).unwrap(),
-            StoreResponse {
-                owner: "owner".to_string(),
-                limits: msg::StoreLimits {
-                    max_triple_count: 1u128.into(),
-                    max_byte_size: 2u128.into(),
-                    max_triple_byte_size: 3u128.into(),
-                    max_query_limit: 4u32,
-                    max_query_variable_count: 5u32,
-                    max_insert_data_byte_size: 6u128.into(),
-                    max_insert_data_triple_count: 7u128.into(),
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
                 },
-                stat: msg::StoreStat {
-                    triple_count: 1u128.into(),
-                    namespace_count: 2u128.into(),
-                    byte_size: 3u128.into(),
-                }
+            );
+            if !lenient_rdf_xml {
+                assert!(matches!(
+                    res,
+                    Err(ContractError::ParseRDF(RDFParseError::Xml(_)))
+                ));
+                continue;
             }
-        );
-    }
-
-    fn read_test_data(file: &str) -> Binary {
-        let mut bytes: Vec<u8> = Vec::new();
-
-        File::open(
-            Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
-                .join("testdata")
-                .join(file),
-        )
-        .unwrap()
-        .read_to_end(&mut bytes)
-        .unwrap();
-
-        Binary::from(bytes)
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", "1"),
+                    Attribute::new("skipped_count", "1"),
+                    Attribute::new(
+                        "skipped",
+                        "Error while escaping character at range 1..8: Unrecognized escape symbol: \"unknown\""
+                    ),
+                ]
+            );
+            assert_eq!(
+                triples()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                1
+            );
+        }
     }
 
     #[test]
-    fn proper_select() {
-        let cases = vec![
-            (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
+    fn insert_invalid_utf8_replacement() {
+        let mut data = b"<http://a.org/s> <http://a.org/p> \"caf".to_vec();
+        data.extend_from_slice(b"\xe9 \xff\xfe\" .");
+
+        for (replacement, expected) in [
+            (None, None),
+            (Some("\u{FFFD}"), Some("caf\u{FFFD} \u{FFFD}\u{FFFD}")),
+            (Some("?"), Some("caf? ??")),
+        ] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    invalid_utf8_replacement: replacement.map(ToString::to_string),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.clone()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            );
+            let Some(expected) = expected else {
+                assert!(matches!(res, Err(ContractError::ParseRDF(_))));
+                continue;
+            };
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", "1"),
+                    Attribute::new("utf8_replacement_count", "3"),
+                ]
+            );
+
+            let (_, triple) = triples()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .next()
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                triple.object,
+                Object::Literal(state::Literal::Simple {
+                    value: expected.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn insert_segments() {
+        let mut header = b"@prefix a: <http://a.org/> .\na:s a:p \"caf".to_vec();
+        header.extend_from_slice(b"\xe9\" , \"2\" .\n");
+        let body = b"<http://b.org/s> <http://b.org/p> \"3\" .\n";
+        let data = [header.as_slice(), body].concat();
+        let segments = vec![
+            msg::DataSegment {
+                format: DataFormat::Turtle,
+                start: 0,
+                end: header.len() as u32,
+            },
+            msg::DataSegment {
+                format: DataFormat::NTriples,
+                start: header.len() as u32,
+                end: data.len() as u32,
+            },
+        ];
+
+        for (replacement, segments, expected) in [
+            (Some("?"), Some(segments.clone()), Ok("3")),
+            (None, Some(segments.clone()), Err(())),
+            (Some("?"), None, Err(())),
+            (
+                Some("?"),
+                Some(vec![segments[1].clone(), segments[0].clone()]),
+                Err(()),
+            ),
+        ] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    invalid_utf8_replacement: replacement.map(ToString::to_string),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.clone()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments,
+                    default_graph: None,
+                },
+            );
+            let Ok(expected) = expected else {
+                assert!(res.is_err());
+                continue;
+            };
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", expected),
+                    Attribute::new("utf8_replacement_count", "1"),
+                ]
+            );
+            assert_eq!(
+                triples()
+                    .range(&deps.storage, None, None, Order::Ascending)
+                    .filter_map(Result::ok)
+                    .filter(|(_, triple)| triple.object
+                        == Object::Literal(state::Literal::Simple {
+                            value: "caf?".to_string()
+                        }))
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn insert_normalize_iris() {
+        let data = "<HTTP://Example.com:80/s%2f> <http://example.com/p> <http://example.com/o> .
+            <http://example.com/s%2F> <http://EXAMPLE.com/p> <http://example.com:80/o> .";
+        for (normalize_iris, expected) in [(true, 1), (false, 2)] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    normalize_iris,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                triples()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn insert_dedup_imports() {
+        let data = "<http://example.com/s> <http://example.com/p> \"o\" .
+            <http://example.com/s> <http://example.com/p> <http://example.com/o> .
+            <http://example.com/s> <http://example.com/p> \"o\" .
+            <http://example.com/s> <http://example.com/p> \"o\" .";
+        for (dedup_imports, expected_count, expected_size) in
+            [(true, "2", 101u128), (false, "4", 183u128)]
+        {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    dedup_imports,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                res.attributes,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", expected_count),
+                ]
+            );
+            assert_eq!(
+                triples()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                2
+            );
+            let stat = STORE.load(&deps.storage).unwrap().stat;
+            assert_eq!(
+                stat.triple_count,
+                Uint128::from(expected_count.parse::<u128>().unwrap())
+            );
+            assert_eq!(stat.byte_size, Uint128::from(expected_size));
+        }
+    }
+
+    #[test]
+    fn insert_dedup_imports_skolemized() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                dedup_imports: true,
+                skolem_base: Some("https://a.org/".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> _:x .
+                    <http://a.org/s> <http://a.org/p> <https://a.org/.well-known/genid/x> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "1"),
+            ]
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::one()
+        );
+    }
+
+    #[test]
+    fn canonical_booleans() {
+        let data = r#"@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            <http://a.org/s> <http://a.org/p> "1"^^xsd:boolean, "true"^^xsd:boolean .
+            <http://a.org/s> <http://a.org/q> "0"^^xsd:boolean ."#;
+        let boolean = |value: &str| {
+            Object::Literal(state::Literal::Typed {
+                value: value.to_string(),
+                datatype: state::Node {
+                    namespace: 1,
+                    value: "boolean".to_string(),
+                },
+            })
+        };
+        for (canonical_booleans, expected_inserted, expected_remaining) in [
+            (true, vec!["true", "false"], vec!["false"]),
+            (false, vec!["1", "true", "0"], vec!["true", "0"]),
+        ] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    canonical_booleans,
+                    dedup_imports: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+            let objects = |deps: Deps<'_>| {
+                let mut objects = triples()
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|res| res.map(|(_, triple)| triple.object))
+                    .collect::<StdResult<Vec<Object>>>()
+                    .unwrap();
+                objects.sort_by_key(|object| format!("{object:?}"));
+                objects
+            };
+            let mut expected = expected_inserted
+                .into_iter()
+                .map(boolean)
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|object| format!("{object:?}"));
+            assert_eq!(objects(deps.as_ref()), expected);
+            assert_eq!(
+                STORE.load(&deps.storage).unwrap().stat.triple_count,
+                Uint128::from(expected.len() as u128)
+            );
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("http://a.org/s".to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+                        object: VarOrNodeOrLiteral::Literal(Literal::TypedValue {
+                            value: "1".to_string(),
+                            datatype: Full("http://www.w3.org/2001/XMLSchema#boolean".to_string()),
+                        }),
+                    }))],
+                },
+            )
+            .unwrap();
+            let mut expected = expected_remaining
+                .into_iter()
+                .map(boolean)
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|object| format!("{object:?}"));
+            assert_eq!(objects(deps.as_ref()), expected);
+        }
+    }
+
+    #[test]
+    fn insert_byte_accounting() {
+        let curie = "@prefix ex: <http://example.com/> . ex:s ex:p ex:o .";
+        let full = "<http://example.com/s> <http://example.com/p> <http://example.com/o> .";
+        let unnormalized =
+            "<HTTP://Example.com:80/s> <http://EXAMPLE.com/p> <http://example.com:80/o> .";
+        let cases = vec![
+            (msg::ByteAccounting::Raw, false, unnormalized, None),
+            (msg::ByteAccounting::Raw, true, curie, Some(60u128)),
+            (msg::ByteAccounting::Raw, true, full, Some(60u128)),
+            (msg::ByteAccounting::Raw, true, unnormalized, Some(60u128)),
+            (msg::ByteAccounting::Expanded, true, curie, Some(60u128)),
+            (msg::ByteAccounting::Expanded, true, full, Some(60u128)),
+            (
+                msg::ByteAccounting::Expanded,
+                true,
+                unnormalized,
+                Some(60u128),
+            ),
+        ];
+
+        for (byte_accounting, normalize_iris, data, expected) in cases {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits: StoreLimitsInputBuilder::default()
+                        .max_insert_data_byte_size(60u128)
+                        .build()
+                        .unwrap(),
+                    normalize_iris,
+                    byte_accounting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            );
+            match expected {
+                Some(byte_size) => {
+                    assert!(res.is_ok());
+                    assert_eq!(
+                        STORE.load(&deps.storage).unwrap().stat.byte_size,
+                        Uint128::from(byte_size)
+                    );
+                }
+                None => assert_eq!(
+                    res.err(),
+                    Some(ContractError::from(StoreError::InsertDataByteSize(
+                        Uint128::from(66u128),
+                        Uint128::from(60u128)
+                    )))
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn delete_validate_language_tags() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                validate_language_tags: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(r#"<http://a.org/s> <http://a.org/p> "a"@en ."#.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+                    object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                        value: "a".to_string(),
+                        language: "en_US".to_string(),
+                    }),
+                }],
+                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                }))],
+            },
+        );
+
+        assert_eq!(
+            res.err(),
+            Some(ContractError::Std(StdError::generic_err(
+                "Invalid language tag 'en_US': The given language subtag is invalid"
+            )))
+        );
+    }
+
+    #[test]
+    fn instantiate_invalid_shapes() {
+        let shape =
+            |predicate: &str, min_count: Option<u32>, max_count: Option<u32>| msg::PredicateShape {
+                predicate: predicate.to_string(),
+                min_count,
+                max_count,
+                datatype: None,
+            };
+        let cases = vec![
+            (
+                vec![shape("http://a.org/p", Some(2), Some(1))],
+                Some(ContractError::Std(StdError::generic_err(
+                    "Invalid shape for predicate http://a.org/p: minimum count greater than maximum count",
+                ))),
+            ),
+            (
+                vec![
+                    shape("http://a.org/p", None, Some(1)),
+                    shape("http://a.org/p", Some(1), None),
+                ],
+                Some(ContractError::Std(StdError::generic_err(
+                    "Duplicate shape for predicate http://a.org/p",
+                ))),
+            ),
+            (
+                vec![
+                    shape("http://a.org/p", Some(1), Some(1)),
+                    shape("http://a.org/q", Some(3), None),
+                ],
+                None,
+            ),
+        ];
+
+        for (shapes, expected) in cases {
+            let mut deps = mock_dependencies();
+            let res = instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                InstantiateMsg {
+                    shapes,
+                    ..Default::default()
+                },
+            );
+
+            assert_eq!(res.err(), expected);
+        }
+    }
+
+    #[test]
+    fn insert_idempotency_key() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str, key: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: Some(key.to_string()),
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap()
+            .attributes
+        };
+        let attributes = |count: u128| {
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", count.to_string()),
+            ]
+        };
+
+        // first apply
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> \"1\" , \"2\" .",
+                "k1"
+            ),
+            attributes(2)
+        );
+        // duplicate apply, with blank nodes which would otherwise be inserted again
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "_:b <http://a.org/p> \"1\" . <http://a.org/s> <http://a.org/p> \"3\" .",
+                "k1"
+            ),
+            attributes(2)
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::from(2u128)
+        );
+        // distinct key
+        assert_eq!(
+            insert(deps.as_mut(), "_:b <http://a.org/p> \"1\" .", "k2"),
+            attributes(1)
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::from(3u128)
+        );
+
+        // keys falling out of the retention window are pruned
+        for i in 0..state::IDEMPOTENCY_KEY_RETENTION {
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> \"1\" .",
+                &format!("n{i}"),
+            );
+        }
+        assert!(!state::idempotency_keys().has(&deps.storage, "k1".to_string()));
+        assert!(!state::idempotency_keys().has(&deps.storage, "k2".to_string()));
+        assert!(state::idempotency_keys().has(&deps.storage, "n0".to_string()));
+        assert_eq!(
+            state::idempotency_keys()
+                .keys_raw(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            state::IDEMPOTENCY_KEY_RETENTION as usize
+        );
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> \"4\" .",
+                "k1"
+            ),
+            attributes(1)
+        );
+    }
+
+    #[test]
+    fn touch_data() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                record_triple_metadata: true,
+                skolem_base: None,
+                lenient_rdf_xml: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let env_at = |height: u64| {
+            let mut env = mock_env();
+            env.block.height = height;
+            env
+        };
+        let metadata = |deps: &OwnedDeps<_, _, _>| {
+            triples()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .map(|res| res.unwrap().1.metadata)
+                .collect::<Vec<_>>()
+        };
+        let touch = |deps: DepsMut<'_>, height: u64, data: &str| {
+            execute(
+                deps,
+                env_at(height),
+                info.clone(),
+                ExecuteMsg::TouchData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                },
+            )
+        };
+
+        execute(
+            deps.as_mut(),
+            env_at(10),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from("<http://a.org/s> <http://a.org/p> \"1\" .".as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            metadata(&deps),
+            vec![Some(TripleMetadata {
+                inserted_at: 10,
+                last_seen_at: 10,
+            })]
+        );
+        let stat = STORE.load(&deps.storage).unwrap().stat;
+
+        for _ in 0..2 {
+            assert_eq!(
+                touch(
+                    deps.as_mut(),
+                    15,
+                    "<http://a.org/s> <http://a.org/p> \"1\" , \"2\" . <http://b.org/s> <http://a.org/p> \"1\" ."
+                )
+                .unwrap()
+                .attributes,
+                vec![
+                    Attribute::new("action", "touch"),
+                    Attribute::new("triple_count", "1"),
+                ]
+            );
+            assert_eq!(
+                metadata(&deps),
+                vec![Some(TripleMetadata {
+                    inserted_at: 10,
+                    last_seen_at: 15,
+                })]
+            );
+            assert_eq!(STORE.load(&deps.storage).unwrap().stat, stat);
+        }
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            touch(
+                deps.as_mut(),
+                15,
+                "<http://a.org/s> <http://a.org/p> \"1\" ."
+            )
+            .err(),
+            Some(ContractError::Std(StdError::generic_err(
+                "The store doesn't record the triple metadata"
+            )))
+        );
+    }
+
+    #[test]
+    fn touch_data_skolemized() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                record_triple_metadata: true,
+                skolem_base: Some("https://a.org/".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut env = mock_env();
+        env.block.height = 10;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from("<http://a.org/s> <http://a.org/p> _:x .".as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        env.block.height = 15;
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::TouchData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> <https://a.org/.well-known/genid/x> ."
+                        .as_bytes(),
+                ),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "touch"),
+                Attribute::new("triple_count", "1"),
+            ]
+        );
+        assert_eq!(
+            triples()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .map(|res| res.unwrap().1.metadata)
+                .collect::<Vec<_>>(),
+            vec![Some(TripleMetadata {
+                inserted_at: 10,
+                last_seen_at: 15,
+            })]
+        );
+    }
+
+    #[test]
+    fn insert_resume_from() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_triples_per_call(2u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let data = "<http://a.org/s> <http://a.org/p> \"1\" , \"2\" , \"3\" . <http://a.org/s> <http://a.org/q> \"4\" .";
+        let insert = |deps: DepsMut<'_>, resume_from: Option<u128>| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: resume_from.map(Uint128::from),
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap()
+            .attributes
+        };
+
+        assert_eq!(
+            insert(deps.as_mut(), None),
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "2"),
+                Attribute::new("processed", "2"),
+                Attribute::new("resume_from", "2"),
+            ]
+        );
+        assert_eq!(
+            insert(deps.as_mut(), Some(2)),
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "2"),
+            ]
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::from(4u128)
+        );
+        assert_eq!(
+            insert(deps.as_mut(), Some(4)),
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "0"),
+            ]
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::from(4u128)
+        );
+    }
+
+    #[test]
+    fn proper_upsert() {
+        let instantiate_msg = InstantiateMsg {
+            functional_properties: vec!["http://a.org/name".to_string()],
+            ..Default::default()
+        };
+        let info = mock_info("owner", &[]);
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            instantiate_msg.clone(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    r#"<http://a.org/s1> <http://a.org/name> "Old" ; <http://a.org/tag> "x" , "y" ."#
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let cases = vec![
+            // replacing an existing value
+            (
+                r#"<http://a.org/s1> <http://a.org/name> "New" ."#,
+                false,
+                Ok("0"),
+            ),
+            // inserting a fresh value
+            (
+                r#"<http://a.org/s2> <http://a.org/name> "Fresh" ."#,
+                false,
+                Ok("1"),
+            ),
+            // upserting the current value
+            (
+                r#"<http://a.org/s1> <http://a.org/name> "New" ."#,
+                false,
+                Ok("0"),
+            ),
+            (
+                r#"<http://a.org/s1> <http://a.org/tag> "z" ."#,
+                false,
+                Err(StoreError::NonFunctionalUpsert(
+                    "http://a.org/tag".to_string(),
+                )),
+            ),
+            (
+                r#"<http://a.org/s1> <http://a.org/tag> "z" ."#,
+                true,
+                Ok("-1"),
+            ),
+            (
+                r#"<http://a.org/s2> <http://a.org/ref> <http://b.org/o> ."#,
+                true,
+                Ok("1"),
+            ),
+            // replacing a value in a namespace referenced by no other triple
+            (
+                r#"<http://a.org/s2> <http://a.org/ref> "lit" ."#,
+                true,
+                Ok("0"),
+            ),
+            // the last value wins
+            (
+                r#"<http://a.org/s3> <http://a.org/name> "A" , "B" ."#,
+                false,
+                Ok("1"),
+            ),
+        ];
+        for (data, force, expected) in cases {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::UpsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    force: Some(force),
+                },
+            );
+            match expected {
+                Ok(delta) => assert_eq!(
+                    res.unwrap().attributes[2],
+                    Attribute::new("triple_count_delta", delta),
+                    "{data}"
+                ),
+                Err(e) => assert_eq!(res.err(), Some(ContractError::Store(e))),
+            }
+        }
+
+        let mut expected_deps = mock_dependencies();
+        instantiate(
+            expected_deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        execute(
+            expected_deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    r#"<http://a.org/s1> <http://a.org/name> "New" ; <http://a.org/tag> "z" .
+                    <http://a.org/s2> <http://a.org/name> "Fresh" ; <http://a.org/ref> "lit" .
+                    <http://a.org/s3> <http://a.org/name> "B" ."#
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let dump = |storage: &dyn Storage| {
+            triples()
+                .range(storage, None, None, Order::Ascending)
+                .map(|res| res.unwrap().1)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(dump(&deps.storage), dump(&expected_deps.storage));
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            STORE.load(&expected_deps.storage).unwrap().stat
+        );
+    }
+
+    #[test]
+    fn proper_delete() {
+        let id = "https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
+        let cases = vec![
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/dataverse/dataspace/metadata/unknown"
+                                .to_string(),
+                        ))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/dataverse/dataspace/metadata/unknown"
+                                .to_string(),
+                        ))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }))],
+                },
+                0,
+                0,
+                Uint128::from(7103u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }))],
+                },
+                1,
+                0,
+                Uint128::from(6921u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![
+                        Prefix {
+                            prefix: "core".to_string(),
+                            namespace: "https://ontology.okp4.space/core/".to_string(),
+                        },
+                        Prefix {
+                            prefix: "thesaurus".to_string(),
+                            namespace: "https://ontology.okp4.space/thesaurus/topic/".to_string(),
+                        },
+                    ],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Prefixed(
+                            "core:hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Prefixed(
+                            "thesaurus:Test".to_string(),
+                        ))),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Prefixed(
+                            "core:hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Prefixed(
+                            "thesaurus:Test".to_string(),
+                        ))),
+                    }))],
+                },
+                1,
+                0,
+                Uint128::from(6921u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![
+                        Prefix {
+                            prefix: "core".to_string(),
+                            namespace: "https://ontology.okp4.space/core/".to_string(),
+                        },
+                        Prefix {
+                            prefix: "thesaurus".to_string(),
+                            namespace: "https://ontology.okp4.space/thesaurus/topic/".to_string(),
+                        },
+                    ],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Prefixed(
+                            "core:hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Prefixed(
+                            "core:hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
+                },
+                1,
+                0,
+                Uint128::from(6921u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
+                },
+                11,
+                2,
+                Uint128::from(5272u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
+                },
+                11,
+                2,
+                Uint128::from(5272u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Variable("s".to_string()),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("0".to_string()),
+                    }))],
+                },
+                40,
+                17,
+                Uint128::from(0u128),
+            ),
+        ];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            let res = execute(deps.as_mut(), mock_env(), info, case.0);
+
+            assert!(res.is_ok());
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "delete"),
+                    Attribute::new("triple_count", case.1.to_string()),
+                    Attribute::new(
+                        "triple_count_delta",
+                        if case.1 == 0 {
+                            "0".to_string()
+                        } else {
+                            format!("-{}", case.1)
+                        }
+                    )
+                ]
+            );
+
+            assert_eq!(
+                STORE.load(&deps.storage).unwrap().stat,
+                StoreStat {
+                    triple_count: (40u128 - u128::try_from(case.1).unwrap()).into(),
+                    namespace_count: (17u128 - u128::try_from(case.2).unwrap()).into(),
+                    byte_size: case.3,
+                },
+            );
+            assert_eq!(
+                triples()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                40 - case.1
+            );
+            assert_eq!(
+                namespaces()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                17 - case.2
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_delete() {
+        struct TC {
+            command: ExecuteMsg,
+            expected: ContractError,
+        }
+        let cases = vec![
+            TC {
+                command: DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Prefixed("foo:bar".to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Prefixed("foo:bar".to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }))],
+                },
+                expected: StdError::generic_err("Unknown prefix 'foo' in 'foo:bar'").into(),
+            },
+            TC {
+                command: DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                        predicate: VarOrNode::Variable("z".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
+                },
+                expected: StdError::generic_err("Selected variable not found in query").into(),
+            },
+            TC {
+                command: DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasTopic".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                        ))),
+                    }],
+                    r#where: vec![],
+                },
+                expected: StdError::generic_err("Empty basic graph pattern").into(),
+            },
+        ];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            let res = execute(deps.as_mut(), mock_env(), info, case.command);
+
+            assert!(res.is_err());
+            assert_eq!(res.unwrap_err(), case.expected);
+        }
+    }
+
+    #[test]
+    fn proper_store() {
+        let mut deps = mock_dependencies();
+        STORE
+            .save(
+                deps.as_mut().storage,
+                &Store {
+                    owner: Addr::unchecked("owner"),
+                    limits: StoreLimits {
+                        max_triple_count: 1u128.into(),
+                        max_byte_size: 2u128.into(),
+                        max_triple_byte_size: 3u128.into(),
+                        max_literal_byte_size: None,
+                        max_query_limit: 4u32,
+                        max_query_variable_count: 5u32,
+                        max_insert_data_byte_size: 6u128.into(),
+                        max_insert_data_triple_count: 7u128.into(),
+                        allowed_schemes: None,
+                        max_triples_per_subject: None,
+                        max_triples_per_call: None,
+                        max_iri_byte_size: None,
+                        max_collection_triple_count: None,
+                        max_blank_nodes_per_import: None,
+                        max_query_output_byte_size: None,
+                        max_insert_data_byte_size_per_subject: None,
+                    },
+                    stat: StoreStat {
+                        triple_count: 1u128.into(),
+                        namespace_count: 2u128.into(),
+                        byte_size: 3u128.into(),
+                    },
+                    shapes: vec![],
+                    retain_empty_namespaces: false,
+                    functional_properties: vec![],
+                    normalize_iris: false,
+                    full_iri_keys: false,
+                    validate_language_tags: false,
+                    record_triple_metadata: false,
+                    skolem_base: None,
+                    lenient_rdf_xml: false,
+                    byte_accounting: msg::ByteAccounting::Raw,
+                    subject_index: false,
+                    invalid_utf8_replacement: None,
+                    label_index: false,
+                    strict_iri_chars: false,
+                    dedup_imports: false,
+                    canonical_booleans: false,
+                    class_hierarchy: BTreeMap::new(),
+                    skip_empty_literals: false,
+                    reject_literal_types: false,
+                    require_absolute_iris: false,
+                    predicate_index: true,
+                },
+            )
+            .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Store);
+        assert!(res.is_ok());
+        assert_eq!(
+            from_binary::<StoreResponse>(&res.unwrap()).unwrap(),
+            StoreResponse {
+                owner: "owner".to_string(),
+                limits: msg::StoreLimits {
+                    max_triple_count: 1u128.into(),
+                    max_byte_size: 2u128.into(),
+                    max_triple_byte_size: 3u128.into(),
+                    max_literal_byte_size: None,
+                    max_query_limit: 4u32,
+                    max_query_variable_count: 5u32,
+                    max_insert_data_byte_size: 6u128.into(),
+                    max_insert_data_triple_count: 7u128.into(),
+                    allowed_schemes: None,
+                    max_triples_per_subject: None,
+                    max_triples_per_call: None,
+                    max_iri_byte_size: None,
+                    max_collection_triple_count: None,
+                    max_blank_nodes_per_import: None,
+                    max_query_output_byte_size: None,
+                    max_insert_data_byte_size_per_subject: None,
+                },
+                stat: msg::StoreStat {
+                    triple_count: 1u128.into(),
+                    namespace_count: 2u128.into(),
+                    byte_size: 3u128.into(),
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let data = r#"<http://a.org/s> <http://a.org/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer> .
+<http://a.org/s> <http://a.org/p> "un"@fr .
+<http://a.org/s> <http://b.org/q> _:n1 .
+_:n1 <http://b.org/q> "a \"quoted\"\nvalue" .
+_:n1 <http://a.org/p> <http://c.org/o#frag> .
+"#;
+        let new_store = || {
+            let mut deps = mock_dependencies();
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                InstantiateMsg {
+                    limits: StoreLimitsInputBuilder::default()
+                        .max_query_limit(2u32)
+                        .build()
+                        .unwrap(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            deps
+        };
+        let insert = |deps: DepsMut<'_>, format: DataFormat, data: Binary| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                InsertData {
+                    format: Some(format),
+                    data,
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+        };
+        let export = |deps: Deps<'_>, format: DataFormat| {
+            let mut chunks = vec![];
+            let mut after = None;
+            loop {
+                let res = query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Export {
+                        format: Some(format.clone()),
+                        style: None,
+                        base: None,
+                        skolemize: None,
+                        after,
+                    },
+                )
+                .unwrap();
+                let res: msg::ExportResponse = from_binary(&res).unwrap();
+                assert_eq!(res.format, format);
+                chunks.push(res.data);
+                match res.cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break chunks,
+                }
+            }
+        };
+        let sorted_lines = |chunks: Vec<Binary>| {
+            let mut lines: Vec<String> = chunks
+                .iter()
+                .flat_map(|chunk| {
+                    String::from_utf8(chunk.to_vec())
+                        .unwrap()
+                        .lines()
+                        .map(str::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            lines.sort();
+            lines
+        };
+
+        let mut source = new_store();
+        insert(
+            source.as_mut(),
+            DataFormat::NTriples,
+            Binary::from(data.as_bytes()),
+        );
+
+        for format in [DataFormat::Turtle, DataFormat::RDFXml] {
+            let chunks = export(source.as_ref(), format.clone());
+            assert_eq!(chunks.len(), 3);
+
+            let mut target = new_store();
+            for chunk in chunks {
+                insert(target.as_mut(), format.clone(), chunk);
+            }
+
+            let mut expected: Vec<String> = data.lines().map(str::to_string).collect();
+            expected.sort();
+            assert_eq!(
+                sorted_lines(export(target.as_ref(), DataFormat::NTriples)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn export_turtle_style() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> \"x\" .\n<http://a.org/s> <http://a.org/q> \"y\" ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let cases = vec![
+            (
+                DataFormat::Turtle,
+                Some(TurtleStyle::Flat),
+                "<http://a.org/s> <http://a.org/q> \"y\" .\n<http://a.org/s> <http://a.org/p> \"x\" .\n",
+            ),
+            (
+                DataFormat::Turtle,
+                Some(TurtleStyle::Grouped),
+                "<http://a.org/s> <http://a.org/q> \"y\" ;\n\t<http://a.org/p> \"x\" .\n",
+            ),
+            (
+                DataFormat::NTriples,
+                Some(TurtleStyle::Grouped),
+                "<http://a.org/s> <http://a.org/q> \"y\" .\n<http://a.org/s> <http://a.org/p> \"x\" .\n",
+            ),
+        ];
+        for (format, style, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Export {
+                    format: Some(format),
+                    style,
+                    base: None,
+                    skolemize: None,
+                    after: None,
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                String::from_utf8(from_binary::<msg::ExportResponse>(&res).unwrap().data.0)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn relative_to_base() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> <http://a.org/o> .\n<http://a.org/s> <http://a.org/q> <http://b.org/o> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let cases = vec![
+            (
+                DataFormat::Turtle,
+                Some("http://a.org/"),
+                "@base <http://a.org/> .\n<s> <p> <o> .\n<s> <q> <http://b.org/o> .\n",
+            ),
+            (
+                DataFormat::NTriples,
+                Some("http://a.org/"),
+                "<http://a.org/s> <http://a.org/p> <http://a.org/o> .\n<http://a.org/s> <http://a.org/q> <http://b.org/o> .\n",
+            ),
+            (
+                DataFormat::Turtle,
+                None,
+                "<http://a.org/s> <http://a.org/p> <http://a.org/o> .\n<http://a.org/s> <http://a.org/q> <http://b.org/o> .\n",
+            ),
+        ];
+        for (format, base, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Export {
+                    format: Some(format.clone()),
+                    style: Some(TurtleStyle::Flat),
+                    base: base.map(str::to_string),
+                    skolemize: None,
+                    after: None,
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                String::from_utf8(from_binary::<msg::ExportResponse>(&res).unwrap().data.0)
+                    .unwrap(),
+                expected
+            );
+
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("http://a.org/s".to_string())),
+                        r#where: vec![],
+                    },
+                    format: Some(format),
+                    style: Some(TurtleStyle::Flat),
+                    base: base.map(str::to_string),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                String::from_utf8(from_binary::<DescribeResponse>(&res).unwrap().data.0).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn export_output_byte_size() {
+        let data = r#"<http://a.org/s> <http://a.org/p> "1" .
+<http://a.org/s> <http://a.org/p> "2" .
+<http://a.org/s> <http://a.org/p> "3" .
+<http://a.org/s> <http://a.org/p> "4" .
+<http://a.org/s> <http://a.org/p> "5" .
+"#;
+        let line_size = data.lines().next().unwrap().len() + 1;
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(3u32)
+                    .max_query_output_byte_size(2 * line_size as u128 + 1)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let mut chunks = vec![];
+        let mut lines = vec![];
+        let mut after = None;
+        loop {
+            let res: msg::ExportResponse = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Export {
+                        format: Some(DataFormat::NTriples),
+                        style: None,
+                        base: None,
+                        skolemize: None,
+                        after,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            let data = String::from_utf8(res.data.to_vec()).unwrap();
+            assert!(data.len() <= 2 * line_size + 1);
+            chunks.push((data.lines().count(), res.truncated));
+            lines.extend(data.lines().map(str::to_string));
+            match res.cursor {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(chunks, vec![(2, true), (2, true), (1, false)]);
+        lines.sort();
+        assert_eq!(lines, data.lines().collect::<Vec<_>>());
+
+        STORE
+            .update(&mut deps.storage, |mut store| -> StdResult<_> {
+                store.limits.max_query_output_byte_size =
+                    Some(Uint128::from(line_size as u128 - 2));
+                Ok(store)
+            })
+            .unwrap();
+        assert_eq!(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Export {
+                    format: Some(DataFormat::NTriples),
+                    style: None,
+                    base: None,
+                    skolemize: None,
+                    after: None,
+                },
+            ),
+            Err(StdError::generic_err(format!(
+                "The triple exceeds the maximum output byte size: {}",
+                line_size - 2
+            )))
+        );
+    }
+
+    #[test]
+    fn proper_stored_triple() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "<http://a.org/s> <http://b.org/p> \"1\"^^<http://a.org/int> .".as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let stored_triple = |object: VarOrNodeOrLiteral| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StoredTriple {
+                    prefixes: vec![Prefix {
+                        prefix: "a".to_string(),
+                        namespace: "http://a.org/".to_string(),
+                    }],
+                    triple: msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Prefixed("a:s".to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full("http://b.org/p".to_string()))),
+                        object,
+                    },
+                },
+            )
+            .unwrap();
+            from_binary::<msg::StoredTripleResponse>(&res)
+                .unwrap()
+                .stored
+        };
+
+        let triple = Triple {
+            subject: Subject::Named(Node {
+                namespace: 0,
+                value: "s".to_string(),
+            }),
+            predicate: Node {
+                namespace: 1,
+                value: "p".to_string(),
+            },
+            object: Object::Literal(state::Literal::Typed {
+                value: "1".to_string(),
+                datatype: Node {
+                    namespace: 0,
+                    value: "int".to_string(),
+                },
+            }),
+            metadata: None,
+            graph: None,
+        };
+        assert_eq!(
+            stored_triple(VarOrNodeOrLiteral::Literal(Literal::TypedValue {
+                value: "1".to_string(),
+                datatype: Prefixed("a:int".to_string()),
+            })),
+            Some(msg::StoredTriple {
+                object_hash: Binary::from(triple.object.as_hash().as_bytes().as_slice()),
+                predicate_key: Binary::from(triple.predicate.key()),
+                subject_key: Binary::from(triple.subject.key()),
+                namespaces: vec![
+                    msg::StoredNamespace {
+                        namespace: "http://a.org/".to_string(),
+                        key: Uint128::zero(),
+                        counter: Uint128::from(2u128),
+                    },
+                    msg::StoredNamespace {
+                        namespace: "http://b.org/".to_string(),
+                        key: Uint128::one(),
+                        counter: Uint128::one(),
+                    },
+                ],
+                repr: format!("{triple:?}"),
+            })
+        );
+
+        for object in [
+            VarOrNodeOrLiteral::Literal(Literal::Simple("1".to_string())),
+            VarOrNodeOrLiteral::Node(NamedNode(Full("http://unknown.org/o".to_string()))),
+        ] {
+            assert_eq!(stored_triple(object), None);
+        }
+
+        assert_eq!(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StoredTriple {
+                    prefixes: vec![],
+                    triple: msg::TriplePattern {
+                        subject: VarOrNode::Variable("s".to_string()),
+                        predicate: VarOrNode::Node(NamedNode(Full("http://b.org/p".to_string()))),
+                        object: VarOrNodeOrLiteral::Literal(Literal::Simple("1".to_string())),
+                    },
+                },
+            )
+            .err(),
+            Some(StdError::generic_err("Unbound subject variable: \"s\""))
+        );
+    }
+
+    #[test]
+    fn proper_stats() {
+        let mut deps = mock_dependencies();
+        let limits = StoreLimitsInputBuilder::default()
+            .max_triple_count(100u128)
+            .build()
+            .unwrap();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: limits.clone(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: read_test_data("sample.ttl"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats).unwrap();
+        assert_eq!(
+            from_binary::<StatsResponse>(&res).unwrap(),
+            StatsResponse {
+                stat: msg::StoreStat {
+                    triple_count: 40u128.into(),
+                    namespace_count: 17u128.into(),
+                    byte_size: 7103u128.into(),
+                },
+                limits: state::StoreLimits::from(limits).into(),
+            }
+        );
+    }
+
+    #[test]
+    fn content_digest() {
+        let digest = |data: &str, max_query_limit: u32| {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits: StoreLimitsInputBuilder::default()
+                        .max_query_limit(max_query_limit)
+                        .build()
+                        .unwrap(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            let mut calls = 0;
+            let mut continuation = None;
+            loop {
+                calls += 1;
+                let res = from_binary::<ContentDigestResponse>(
+                    &query(
+                        deps.as_ref(),
+                        mock_env(),
+                        QueryMsg::ContentDigest { continuation },
+                    )
+                    .unwrap(),
+                )
+                .unwrap();
+                match res {
+                    ContentDigestResponse {
+                        digest: Some(digest),
+                        continuation: None,
+                    } => return (digest, calls),
+                    ContentDigestResponse {
+                        digest: None,
+                        continuation: Some(next),
+                    } => continuation = Some(next),
+                    res => panic!("unexpected response: {res:?}"),
+                }
+            }
+        };
+
+        let data = "<http://a.org/s> <http://a.org/p> \"1\" .\n\
+                    <http://b.org/s> <http://a.org/p> <http://b.org/o> .\n\
+                    <http://a.org/s> <http://b.org/p> _:x .\n\
+                    _:x <http://a.org/p> \"2\"@en .\n";
+        let reordered = "_:y <http://a.org/p> \"2\"@en .\n\
+                         <http://a.org/s> <http://b.org/p> _:y .\n\
+                         <http://b.org/s> <http://a.org/p> <http://b.org/o> .\n\
+                         <http://a.org/s> <http://a.org/p> \"1\" .\n";
+        let changed = "<http://a.org/s> <http://a.org/p> \"1\" .\n\
+                       <http://b.org/s> <http://a.org/p> <http://b.org/o> .\n\
+                       <http://a.org/s> <http://b.org/p> _:x .\n\
+                       _:x <http://a.org/p> \"2\"@fr .\n";
+
+        let (expected, calls) = digest(data, 30);
+        assert_eq!(calls, 1);
+        assert_eq!(expected.len(), 32);
+        assert_eq!(digest(data, 3), (expected.clone(), 2));
+        assert_eq!(digest(reordered, 30).0, expected);
+        assert_eq!(digest(reordered, 1), (expected.clone(), 4));
+        assert_ne!(digest(changed, 30).0, expected);
+        assert_ne!(digest("", 30).0, expected);
+    }
+
+    fn read_test_data(file: &str) -> Binary {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        File::open(
+            Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+                .join("testdata")
+                .join(file),
+        )
+        .unwrap()
+        .read_to_end(&mut bytes)
+        .unwrap();
+
+        Binary::from(bytes)
+    }
+
+    #[test]
+    fn proper_select() {
+        let cases = vec![
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
+                        SelectItem::Variable("b".to_string()),
+                    ],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(
+                        msg::TriplePattern {
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNode::Node(NamedNode(Full(
+                                "https://ontology.okp4.space/core/hasDescription".to_string(),
+                            ))),
+                            object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                        },
+                    ))],
+                    limit: None,
+                },
+                SelectResponse {
+                    head: Head {
+                        vars: vec!["a".to_string(), "b".to_string()],
+                    },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "A test Data Space.".to_string(),
+                                        lang: Some("en".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "Un Dataset de test.".to_string(),
+                                        lang: Some("fr".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "A test Dataset.".to_string(),
+                                        lang: Some("en".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "Un Data Space de test.".to_string(),
+                                        lang: Some("fr".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ])
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() }],
+                    select: vec![
+                     SelectItem::Variable("a".to_string()),
+                    ],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(
+                     msg::TriplePattern {
+                         subject: VarOrNode::Variable("a".to_string()),
+                         predicate: VarOrNode::Node(NamedNode(Prefixed(
+                             "core:hasDescription".to_string(),
+                         ))),
+                         object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
+                     },
+                    ))],
+                    limit: None,
+                },
+                SelectResponse {
+                 head: Head {
+                     vars: vec!["a".to_string()],
+                 },
+                 results: Results {
+                     bindings: vec![
+                         BTreeMap::from([
+                             (
+                                 "a".to_string(),
+                                 Value::URI {
+                                     value: Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
+                                 }
+                             ),
+                         ])
+                     ],
+                 },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
+                    ],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(
+                        msg::TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string()))),
+                            predicate: VarOrNode::Variable("a".to_string()),
+                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
+                        },
+                    ))],
+                    limit: None,
+                },
+                SelectResponse {
+                    head: Head {
+                        vars: vec!["a".to_string()],
+                    },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.okp4.space/core/hasDescription".to_string())
+                                    }
+                                ),
+                            ])
+                        ],
+                    },
+                },
+            )
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            assert!(res.is_ok());
+
+            let result = from_binary::<SelectResponse>(&res.unwrap()).unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn select_with_registered_prefixes() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> \"1\" . <http://b.org/s> <http://b.org/p> \"2\" ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let prefix = |prefix: &str, namespace: &str| Prefix {
+            prefix: prefix.to_string(),
+            namespace: namespace.to_string(),
+        };
+        let register = |deps: DepsMut<'_>, sender: &str, prefixes: Vec<Prefix>| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::RegisterPrefixes { prefixes },
+            )
+        };
+        assert_eq!(
+            register(
+                deps.as_mut(),
+                "not-owner",
+                vec![prefix("a", "http://a.org/")]
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+        assert_eq!(
+            register(deps.as_mut(), "owner", vec![prefix("a", "a.org/")]).err(),
+            Some(ContractError::Std(StdError::generic_err(
+                "Not an absolute IRI: a.org/"
+            )))
+        );
+        assert_eq!(
+            register(deps.as_mut(), "owner", vec![prefix("a", "http://a.org/")])
+                .unwrap()
+                .attributes,
+            vec![
+                Attribute::new("action", "register_prefixes"),
+                Attribute::new("prefix_count", "1"),
+            ]
+        );
+        assert_eq!(
+            from_binary::<PrefixesResponse>(
+                &query(deps.as_ref(), mock_env(), QueryMsg::Prefixes).unwrap()
+            )
+            .unwrap(),
+            PrefixesResponse {
+                prefixes: vec![prefix("a", "http://a.org/")],
+            }
+        );
+
+        let cases = vec![
+            // registered prefix only
+            (vec![], "a:p", Ok("http://a.org/s")),
+            // provided prefix only
+            (
+                vec![prefix("b", "http://b.org/")],
+                "b:p",
+                Ok("http://b.org/s"),
+            ),
+            // provided prefix overriding the registered one
+            (
+                vec![prefix("a", "http://b.org/")],
+                "a:p",
+                Ok("http://b.org/s"),
+            ),
+            (
+                vec![],
+                "b:p",
+                Err(StdError::generic_err("Unknown prefix 'b' in 'b:p'")),
+            ),
+        ];
+        for (prefixes, predicate, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes,
+                        select: vec![SelectItem::Variable("s".to_string())],
+                        r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNode::Node(NamedNode(Prefixed(predicate.to_string()))),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }))],
+                        limit: None,
+                    },
+                },
+            );
+
+            assert_eq!(
+                res.map(|res| from_binary::<SelectResponse>(&res)
+                    .unwrap()
+                    .results
+                    .bindings),
+                expected.map(|subject| vec![BTreeMap::from([(
+                    "s".to_string(),
+                    Value::URI {
+                        value: Full(subject.to_string())
+                    }
+                )])])
+            );
+        }
+    }
+
+    #[test]
+    fn max_collection_triple_count() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_collection_triple_count(6u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+        };
+
+        let oversized = format!(
+            "<http://a.org/s> <http://a.org/p> ( {} ) .",
+            (0..100)
+                .map(|i| format!("\"{i}\""))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        assert_eq!(
+            insert(deps.as_mut(), &oversized).err(),
+            Some(ContractError::from(StoreError::CollectionTripleCount(
+                Uint128::from(7u128),
+                Uint128::from(6u128)
+            )))
+        );
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> ( 1 2 3 4 ) ."
+            )
+            .err(),
+            Some(ContractError::from(StoreError::CollectionTripleCount(
+                Uint128::from(7u128),
+                Uint128::from(6u128)
+            )))
+        );
+
+        // nested collections are accounted on their own
+        let res = insert(
+            deps.as_mut(),
+            "<http://a.org/s> <http://a.org/p> ( 1 2 3 ), ( ( 1 2 3 ) ( 4 5 6 ) 7 ) .",
+        )
+        .unwrap();
+        assert_eq!(res.attributes[1], Attribute::new("triple_count", "26"));
+    }
+
+    #[test]
+    fn max_blank_nodes_per_import() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_blank_nodes_per_import(3u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+        };
+
+        // a blank node is accounted once whatever the number of triples it appears in
+        insert(
+            deps.as_mut(),
+            "_:a <http://a.org/p> _:b .
+            _:b <http://a.org/p> _:c .
+            _:c <http://a.org/p> _:a .
+            <http://a.org/s> <http://a.org/p> _:c .",
+        )
+        .unwrap();
+        // the limit applies per insertion
+        insert(deps.as_mut(), "_:d <http://a.org/p> _:e .").unwrap();
+
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "_:a <http://a.org/p> _:b .
+                _:c <http://a.org/p> _:d ."
+            )
+            .err(),
+            Some(ContractError::from(StoreError::BlankNodesPerImport(
+                Uint128::from(4u128),
+                Uint128::from(3u128)
+            )))
+        );
+    }
+
+    #[test]
+    fn max_insert_data_byte_size_per_subject() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_insert_data_byte_size_per_subject(100u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+        };
+
+        // each triple of the subjects below accounts for 38 bytes
+        insert(
+            deps.as_mut(),
+            "<http://a.org/s> <http://a.org/p> \"0123456789\" .
+            <http://a.org/s> <http://a.org/p> \"abcdefghij\" .
+            <http://a.org/t> <http://a.org/p> \"0123456789\" .
+            <http://a.org/t> <http://a.org/p> \"abcdefghij\" .",
+        )
+        .unwrap();
+        // the limit applies per insertion
+        insert(
+            deps.as_mut(),
+            "<http://a.org/s> <http://a.org/p> \"ABCDEFGHIJ\" .",
+        )
+        .unwrap();
+
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> \"klmnopqrst\" .
+                <http://a.org/t> <http://a.org/p> \"klmnopqrst\" .
+                <http://a.org/s> <http://a.org/p> \"uvwxyzabcd\" .
+                <http://a.org/s> <http://a.org/p> \"KLMNOPQRST\" ."
+            )
+            .err(),
+            Some(ContractError::from(
+                StoreError::InsertDataByteSizePerSubject(
+                    "<http://a.org/s>".to_string(),
+                    Uint128::from(114u128),
+                    Uint128::from(100u128)
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn max_iri_byte_size() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_iri_byte_size(20u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+        };
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> \"1\"^^<http://a.org/datatype> ."
+            )
+            .err(),
+            Some(ContractError::from(StoreError::IriByteSize(
+                Uint128::from(21u128),
+                Uint128::from(20u128)
+            )))
+        );
+        insert(deps.as_mut(), "<http://a.org/s> <http://a.org/p> \"1\" .").unwrap();
+
+        // a short CURIE standing for a huge IRI
+        let prefixes = vec![Prefix {
+            prefix: "x".to_string(),
+            namespace: format!("http://a.org/{}/", "x".repeat(100)),
+        }];
+        let pattern = msg::TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNode::Node(NamedNode(Prefixed("x:p".to_string()))),
+            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+        };
+        let expected = || StdError::generic_err("Maximum IRI byte size exceeded: 115 / 20");
+
+        assert_eq!(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: prefixes.clone(),
+                        select: vec![SelectItem::Variable("s".to_string())],
+                        r#where: vec![WhereCondition::Simple(TriplePattern(pattern.clone()))],
+                        limit: None,
+                    },
+                },
+            )
+            .err(),
+            Some(expected())
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                DeleteData {
+                    prefixes,
+                    delete: vec![pattern],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Variable("s".to_string()),
+                        predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
+                },
+            )
+            .err(),
+            Some(ContractError::Std(expected()))
+        );
+    }
+
+    #[test]
+    fn select_with_full_iri_keys() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                full_iri_keys: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> <http://b.org/o> .\n".as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            namespaces()
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec!["".to_string()]
+        );
+
+        let pattern = msg::TriplePattern {
+            subject: VarOrNode::Node(NamedNode(Full("http://a.org/s".to_string()))),
+            predicate: VarOrNode::Node(NamedNode(Prefixed("a:p".to_string()))),
+            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+        };
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Select {
+                query: SelectQuery {
+                    prefixes: vec![Prefix {
+                        prefix: "a".to_string(),
+                        namespace: "http://a.org/".to_string(),
+                    }],
+                    select: vec![SelectItem::Variable("o".to_string())],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(pattern.clone()))],
+                    limit: None,
+                },
+            },
+        );
+        assert_eq!(
+            from_binary::<SelectResponse>(&res.unwrap())
+                .unwrap()
+                .results
+                .bindings,
+            vec![BTreeMap::from([(
+                "o".to_string(),
+                Value::URI {
+                    value: Full("http://b.org/o".to_string())
+                }
+            )])]
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DeleteData {
+                prefixes: vec![Prefix {
+                    prefix: "a".to_string(),
+                    namespace: "http://a.org/".to_string(),
+                }],
+                delete: vec![],
+                r#where: vec![WhereCondition::Simple(TriplePattern(pattern))],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn invalid_select() {
+        let cases = vec![
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
                         SelectItem::Variable("b".to_string()),
                     ],
+                    r#where: vec![],
+                    limit: None,
+                },
+                Err(StdError::generic_err(
+                    "Maximum query variable count exceeded",
+                )),
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![],
+                    r#where: vec![],
+                    limit: Some(8000),
+                },
+                Err(StdError::generic_err("Maximum query limit exceeded")),
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix {
+                        prefix: "core".to_string(),
+                        namespace: "https://ontology.okp4.space/core/".to_string(),
+                    }],
+                    select: vec![SelectItem::Variable("a".to_string())],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Variable("a".to_string()),
+                        predicate: VarOrNode::Node(NamedNode(Prefixed(
+                            "invalid:hasDescription".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                            value: "A test Dataset.".to_string(),
+                            language: "en".to_string(),
+                        }),
+                    }))],
+                    limit: None,
+                },
+                Err(StdError::generic_err(
+                    "Unknown prefix 'invalid' in 'invalid:hasDescription'",
+                )),
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![SelectItem::Variable("u".to_string())],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Variable("a".to_string()),
+                        predicate: VarOrNode::Node(NamedNode(Full(
+                            "https://ontology.okp4.space/core/hasDescription".to_string(),
+                        ))),
+                        object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                            value: "A test Dataset.".to_string(),
+                            language: "en".to_string(),
+                        }),
+                    }))],
+                    limit: None,
+                },
+                Err(StdError::generic_err(
+                    "Selected variable not found in query",
+                )),
+            ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_variable_count: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            assert_eq!(res, expected);
+        }
+    }
+
+    #[test]
+    fn formats_describe() {
+        let cases = vec![
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![],
+                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                    r#where: vec![],
+                },
+                format: Some(DataFormat::Turtle),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::Turtle,
+                data: Binary::from(
+                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
+\t<https://ontology.okp4.space/core/hasTag> \"Test\" , \"OKP4\" ;
+\t<https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;
+\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;
+\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
+\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;
+\t<https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .
+\
+                ".to_string().as_bytes().to_vec()),
+            }
+        ),
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![],
+                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                    r#where: vec![],
+                },
+                format: Some(DataFormat::RDFXml),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::RDFXml,
+                data: Binary::from(
+                   "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+<rdf:Description rdf:about=\"https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473\">\
+<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"https://ontology.okp4.space/metadata/dataspace/GeneralMetadata\"/>\
+<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"http://www.w3.org/2002/07/owl#NamedIndividual\"/>\
+<hasTag xmlns=\"https://ontology.okp4.space/core/\">Test</hasTag><hasTag xmlns=\"https://ontology.okp4.space/core/\">OKP4</hasTag>\
+<hasTitle xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"fr\">Data Space de test</hasTitle>\
+<hasTitle xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"en\">Test Data Space</hasTitle>\
+<hasTopic xmlns=\"https://ontology.okp4.space/core/\" rdf:resource=\"https://ontology.okp4.space/thesaurus/topic/Test\"/>\
+<describes xmlns=\"https://ontology.okp4.space/core/\" rdf:resource=\"https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33\"/>\
+<hasPublisher xmlns=\"https://ontology.okp4.space/core/\">OKP4</hasPublisher>\
+<hasDescription xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"en\">A test Data Space.</hasDescription>\
+<hasDescription xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"fr\">Un Data Space de test.</hasDescription></rdf:Description>\
+</rdf:RDF>\
+\
+                ".to_string().as_bytes().to_vec()),
+            }
+        ),
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![],
+                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                    r#where: vec![WhereCondition::Simple(TriplePattern(
+                        msg::TriplePattern {
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNode::Node(NamedNode(Full(
+                                "https://ontology.okp4.space/core/hasDescription".to_string(),
+                            ))),
+                            object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                        },
+                    ))],
+                },
+                format: Some(DataFormat::NTriples),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::NTriples,
+                data: Binary::from(
+                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"Test\" .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"OKP4\" .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Test Data Space\"@en .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasPublisher> \"OKP4\" .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"Un Data Space de test.\"@fr .
+\
+                ".to_string().as_bytes().to_vec()),
+            }
+        ),
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![],
+                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                    r#where: vec![],
+                },
+                format: Some(DataFormat::NQuads),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::NQuads,
+                data: Binary::from(
+                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"Test\" .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"OKP4\" .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Test Data Space\"@en .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasPublisher> \"OKP4\" .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en .
+<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"Un Data Space de test.\"@fr .
+\
+                ".to_string().as_bytes().to_vec()),
+            }
+        ),
+    ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn prefixes_describe() {
+        let cases = vec![
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![
+                        Prefix {
+                            prefix: "metadata".to_string(),
+                            namespace: "https://ontology.okp4.space/dataverse/dataspace/metadata/".to_string(),
+                        },
+                    ],
+                    resource: VarOrNamedNode::NamedNode(Prefixed("metadata:dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                    r#where: vec![],
+                },
+                format: Some(DataFormat::Turtle),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::Turtle,
+                data: Binary::from(
+                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
+\t<https://ontology.okp4.space/core/hasTag> \"Test\" , \"OKP4\" ;
+\t<https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;
+\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;
+\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
+\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;
+\t<https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .
+\
+                ".to_string().as_bytes().to_vec()),
+            }
+        ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn variable_describe() {
+        let cases = vec![
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() }],
+                    resource: VarOrNamedNode::Variable("a".to_string()),
                     r#where: vec![WhereCondition::Simple(TriplePattern(
                         msg::TriplePattern {
                             subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNode::Node(NamedNode(Full(
-                                "https://ontology.okp4.space/core/hasDescription".to_string(),
+                            predicate: VarOrNode::Node(NamedNode(Prefixed(
+                                "core:hasDescription".to_string(),
                             ))),
-                            object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
+                        },
+                       ))],
+                },
+                format: Some(DataFormat::Turtle),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::Turtle,
+                data: Binary::from(
+                   "<https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.okp4.space/core/hasTag> \"test\" ;\n\t<https://ontology.okp4.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr ;\n\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;\n\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.okp4.space/core/hasFormat> <https://ontology.okp4.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.okp4.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.okp4.space/core/hasLicense> <https://ontology.okp4.space/thesaurus/license/LO-FR-1_0> ;\n\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en .\n".to_string().as_bytes().to_vec()),
+            }
+        ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn variable_mutiple_resources_describe() {
+        let cases = vec![
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() }],
+                    resource: VarOrNamedNode::Variable("a".to_string()),
+                    r#where: vec![WhereCondition::Simple(TriplePattern(
+                        msg::TriplePattern {
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNode::Node(NamedNode(Prefixed(
+                                "core:hasPublisher".to_string(),
+                            ))),
+                            object: VarOrNodeOrLiteral::Literal(Literal::Simple("OKP4".to_string())),
+                        },
+                       ))],
+                },
+                format: Some(DataFormat::Turtle),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::Turtle,
+                data: Binary::from(
+                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.okp4.space/core/hasTag> \"Test\" , \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;\n\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;\n\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;\n\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .\n<https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.okp4.space/core/hasTag> \"test\" ;\n\t<https://ontology.okp4.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr ;\n\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;\n\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.okp4.space/core/hasFormat> <https://ontology.okp4.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.okp4.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.okp4.space/core/hasLicense> <https://ontology.okp4.space/thesaurus/license/LO-FR-1_0> ;\n\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en .\n".to_string().as_bytes().to_vec()),
+            }
+        ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn blanknode_describe() {
+        let cases = vec![
+        (
+            QueryMsg::Describe {
+                query: DescribeQuery {
+                    prefixes: vec![
+                        Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() },
+                        Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.okp4.space/dataverse/dataset/metadata/".to_string()}
+                    ],
+                    resource: VarOrNamedNode::Variable("x".to_string()),
+                    r#where: vec![WhereCondition::Simple(TriplePattern(
+                        msg::TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
+                            predicate: VarOrNode::Node(NamedNode(Prefixed(
+                                "core:hasTemporalCoverage".to_string(),
+                            ))),
+                            object: VarOrNodeOrLiteral::Variable("x".to_string()),
+                        },
+                       )),
+                       ],
+                },
+                format: Some(DataFormat::Turtle),
+                style: None,
+                base: None,
+            },
+            DescribeResponse {
+                format: DataFormat::Turtle,
+                data: Binary::from(
+                    "_:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> , <https://ontology.okp4.space/core/Period> ;\n\t<https://ontology.okp4.space/core/hasStartDate> \"2022-01-01T00:00:00+00:00\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n".to_string().as_bytes().to_vec()),
+            }
+        ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: read_test_data("blank-nodes.ttl"),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn describe_with_skolemization() {
+        let info = mock_info("owner", &[]);
+        let new_store = |data: &str| {
+            let mut deps = mock_dependencies();
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    skolem_base: Some("https://a.org/".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+            deps
+        };
+        let describe = |deps: &OwnedDeps<_, _, _>| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("http://a.org/s".to_string())),
+                        r#where: vec![],
+                    },
+                    format: Some(DataFormat::NTriples),
+                    style: None,
+                    base: None,
+                },
+            )
+            .unwrap();
+            String::from_utf8(from_binary::<DescribeResponse>(&res).unwrap().data.0).unwrap()
+        };
+
+        let exported = describe(&new_store(
+            "<http://a.org/s> <http://a.org/p> _:x .\n<http://a.org/s> <http://a.org/q> _:y .\n",
+        ));
+        assert_eq!(
+            exported,
+            "<http://a.org/s> <http://a.org/p> <https://a.org/.well-known/genid/x> .\n\
+             <http://a.org/s> <http://a.org/q> <https://a.org/.well-known/genid/y> .\n"
+        );
+
+        let deps = new_store(&exported);
+        let mut objects = triples()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .map(|res| res.unwrap().1.object)
+            .collect::<Vec<_>>();
+        objects.sort_by_key(|object| format!("{object:?}"));
+        assert_eq!(
+            objects,
+            vec![
+                Object::Blank("x".to_string()),
+                Object::Blank("y".to_string())
+            ]
+        );
+        assert_eq!(describe(&deps), exported);
+    }
+
+    #[test]
+    fn export_with_skolemization() {
+        let info = mock_info("owner", &[]);
+        let new_store = |skolem_base: Option<&str>, data: &str| {
+            let mut deps = mock_dependencies();
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    skolem_base: skolem_base.map(str::to_string),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+            deps
+        };
+        let export = |deps: &OwnedDeps<_, _, _>, skolemize: Option<bool>| {
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Export {
+                    format: Some(DataFormat::NTriples),
+                    style: None,
+                    base: None,
+                    skolemize,
+                    after: None,
+                },
+            )
+            .map(|res| {
+                String::from_utf8(from_binary::<msg::ExportResponse>(&res).unwrap().data.0).unwrap()
+            })
+        };
+
+        let data = "<http://a.org/s> <http://a.org/p> _:x .\n";
+        let deps = new_store(Some("https://a.org/"), data);
+        assert_eq!(export(&deps, None), Ok(data.to_string()));
+        assert_eq!(export(&deps, Some(false)), Ok(data.to_string()));
+
+        let exported = export(&deps, Some(true)).unwrap();
+        assert_eq!(
+            exported,
+            "<http://a.org/s> <http://a.org/p> <https://a.org/.well-known/genid/x> .\n"
+        );
+        assert_eq!(
+            export(&new_store(Some("https://a.org/"), &exported), None),
+            Ok(data.to_string())
+        );
+
+        assert_eq!(
+            export(&new_store(None, data), Some(true)),
+            Err(StdError::generic_err(
+                "Blank nodes can't be skolemized, no skolem base is configured"
+            ))
+        );
+    }
+
+    #[test]
+    fn proper_construct() {
+        let id = "https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
+        let cases = vec![(
+            ConstructQuery {
+                prefixes: vec![],
+                construct: vec![msg::TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                    predicate: VarOrNode::Variable("p".to_string()),
+                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                }],
+                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                    predicate: VarOrNode::Node(NamedNode(Full(
+                        "https://ontology.okp4.space/core/hasTopic".to_string(),
+                    ))),
+                    object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                        "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
+                    ))),
+                }))],
+            },
+            0,
+        )];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                Construct {
+                    query: case.0,
+                    format: Some(DataFormat::default()),
+                    style: None,
+                    base: None,
+                },
+            );
+
+            assert!(res.is_err());
+            assert_eq!(res.err().unwrap(), StdError::generic_err("Not implemented"));
+        }
+    }
+
+    #[test]
+    fn proper_namespace_triples() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix b: <http://b.org/> .
+@prefix c: <http://c.org/> .
+
+a:s1 a:p "x" .
+a:s2 b:p b:o .
+b:s3 b:p "1"^^a:int .
+_:n c:p "y" .
+c:s4 c:p c:o .
+"#;
+
+        let cases = vec![
+            (
+                "http://a.org/",
+                vec![
+                    "<http://a.org/s1> <http://a.org/p> \"x\" .",
+                    "<http://a.org/s2> <http://b.org/p> <http://b.org/o> .",
+                    "<http://b.org/s3> <http://b.org/p> \"1\"^^<http://a.org/int> .",
+                ],
+            ),
+            (
+                "http://b.org/",
+                vec![
+                    "<http://a.org/s2> <http://b.org/p> <http://b.org/o> .",
+                    "<http://b.org/s3> <http://b.org/p> \"1\"^^<http://a.org/int> .",
+                ],
+            ),
+            (
+                "http://c.org/",
+                vec![
+                    "<http://c.org/s4> <http://c.org/p> <http://c.org/o> .",
+                    "_:n <http://c.org/p> \"y\" .",
+                ],
+            ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (namespace, expected) in cases {
+            for (max_scan, expected_calls) in [(None, 3), (Some(2), 3), (Some(1), 5)] {
+                let mut lines = vec![];
+                let mut after = None;
+                let mut calls = 0;
+                loop {
+                    let res = query(
+                        deps.as_ref(),
+                        mock_env(),
+                        QueryMsg::NamespaceTriples {
+                            namespace: namespace.to_string(),
+                            format: Some(DataFormat::NTriples),
+                            style: None,
+                            after,
+                            max_scan,
                         },
-                    ))],
-                    limit: None,
+                    )
+                    .unwrap();
+                    let result = from_binary::<NamespaceTriplesResponse>(&res).unwrap();
+                    calls += 1;
+
+                    lines.extend(
+                        String::from_utf8(result.data.to_vec())
+                            .unwrap()
+                            .lines()
+                            .map(ToString::to_string),
+                    );
+                    match result.cursor {
+                        Some(cursor) => after = Some(cursor),
+                        None => break,
+                    }
+                }
+                lines.sort();
+
+                assert_eq!(calls, expected_calls);
+                assert_eq!(lines, expected);
+            }
+        }
+
+        for (max_scan, expected) in [
+            (0, "The maximum scan must be positive"),
+            (3, "Maximum query limit exceeded"),
+        ] {
+            assert_eq!(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::NamespaceTriples {
+                        namespace: "http://a.org/".to_string(),
+                        format: None,
+                        style: None,
+                        after: None,
+                        max_scan: Some(max_scan),
+                    },
+                ),
+                Err(StdError::generic_err(expected))
+            );
+        }
+
+        assert_eq!(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::NamespaceTriples {
+                    namespace: "http://d.org/".to_string(),
+                    format: None,
+                    style: None,
+                    after: None,
+                    max_scan: None,
                 },
-                SelectResponse {
-                    head: Head {
-                        vars: vec!["a".to_string(), "b".to_string()],
+            )
+            .err()
+            .unwrap(),
+            StdError::not_found(type_name::<Namespace>())
+        );
+    }
+
+    #[test]
+    fn proper_datatype_triples() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+a:s1 a:p "2023-01-01T00:00:00Z"^^xsd:dateTime .
+a:s1 a:q "x" .
+a:s2 a:p "2023-06-01T00:00:00Z"^^xsd:dateTime .
+a:s2 a:q "1"^^xsd:integer .
+a:s3 a:p xsd:dateTime .
+a:s3 a:q "y"@en .
+a:s4 a:p "2023-12-01T00:00:00Z"^^xsd:dateTime .
+a:s4 a:q "z"^^xsd:string .
+"#;
+
+        let cases = vec![
+            (
+                "http://www.w3.org/2001/XMLSchema#dateTime",
+                vec![
+                    "<http://a.org/s1> <http://a.org/p> \"2023-01-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .",
+                    "<http://a.org/s2> <http://a.org/p> \"2023-06-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .",
+                    "<http://a.org/s4> <http://a.org/p> \"2023-12-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .",
+                ],
+                4,
+            ),
+            (
+                "http://www.w3.org/2001/XMLSchema#integer",
+                vec![
+                    "<http://a.org/s2> <http://a.org/q> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .",
+                ],
+                4,
+            ),
+            ("http://www.w3.org/2001/XMLSchema#string", vec![], 4),
+            ("http://www.w3.org/2001/XMLSchema#boolean", vec![], 4),
+            ("http://b.org/type", vec![], 1),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (datatype, expected, expected_calls) in cases {
+            let mut lines = vec![];
+            let mut after = None;
+            let mut calls = 0;
+            loop {
+                let res = query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::DatatypeTriples {
+                        datatype: datatype.to_string(),
+                        format: Some(DataFormat::NTriples),
+                        style: None,
+                        after,
+                        max_scan: None,
                     },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "A test Data Space.".to_string(),
-                                        lang: Some("en".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "Un Dataset de test.".to_string(),
-                                        lang: Some("fr".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "A test Dataset.".to_string(),
-                                        lang: Some("en".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "Un Data Space de test.".to_string(),
-                                        lang: Some("fr".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ])
+                )
+                .unwrap();
+                let result = from_binary::<DatatypeTriplesResponse>(&res).unwrap();
+                calls += 1;
+
+                lines.extend(
+                    String::from_utf8(result.data.to_vec())
+                        .unwrap()
+                        .lines()
+                        .map(ToString::to_string),
+                );
+                match result.cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+            lines.sort();
+
+            assert_eq!(calls, expected_calls);
+            assert_eq!(lines, expected);
+        }
+    }
+
+    #[test]
+    fn proper_describe_subject() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix b: <http://b.org/> .
+
+a:s a:p1 "1" ;
+    a:p2 "2" , "3" ;
+    b:p3 b:o ;
+    b:p4 "4"@en ;
+    a:p5 _:n ;
+    a:p6 "6"^^b:int .
+a:other a:p1 "1" .
+b:s a:p1 "1" .
+"#;
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let cases = vec![
+            (
+                "http://a.org/s",
+                4,
+                vec![
+                    "<http://a.org/s> <http://a.org/p1> \"1\" .",
+                    "<http://a.org/s> <http://a.org/p2> \"3\" .",
+                    "<http://a.org/s> <http://a.org/p2> \"2\" .",
+                    "<http://a.org/s> <http://a.org/p5> _:n .",
+                    "<http://a.org/s> <http://a.org/p6> \"6\"^^<http://b.org/int> .",
+                    "<http://a.org/s> <http://b.org/p3> <http://b.org/o> .",
+                    "<http://a.org/s> <http://b.org/p4> \"4\"@en .",
+                ],
+            ),
+            (
+                "http://b.org/s",
+                1,
+                vec!["<http://b.org/s> <http://a.org/p1> \"1\" ."],
+            ),
+            ("http://a.org/unknown", 1, vec![]),
+            ("http://c.org/s", 1, vec![]),
+        ];
+
+        for (subject, expected_calls, expected) in cases {
+            let mut lines = vec![];
+            let mut after = None;
+            let mut calls = 0;
+            loop {
+                let res = query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::DescribeSubject {
+                        subject: subject.to_string(),
+                        format: Some(DataFormat::NTriples),
+                        style: None,
+                        after,
+                        max_scan: None,
+                        follow_equivalents: None,
+                    },
+                )
+                .unwrap();
+                let result = from_binary::<DescribeSubjectResponse>(&res).unwrap();
+                calls += 1;
+
+                lines.extend(
+                    String::from_utf8(result.data.to_vec())
+                        .unwrap()
+                        .lines()
+                        .map(ToString::to_string),
+                );
+                match result.cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+
+            assert_eq!(calls, expected_calls);
+            assert_eq!(lines, expected);
+        }
+    }
+
+    #[test]
+    fn describe_subject_with_equivalents() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix b: <http://b.org/> .
+@prefix c: <http://c.org/> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+
+a:s a:p "1" ;
+    owl:sameAs b:s .
+b:s a:p "2" ;
+    owl:sameAs c:u .
+c:t owl:equivalentClass a:s ;
+    a:q "3" .
+c:u a:p "4" .
+"#;
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let cases = vec![
+            (
+                None,
+                1,
+                vec![
+                    "<http://a.org/s> <http://a.org/p> \"1\" .",
+                    "<http://a.org/s> <http://www.w3.org/2002/07/owl#sameAs> <http://b.org/s> .",
+                ],
+            ),
+            (
+                Some(true),
+                3,
+                vec![
+                    "<http://a.org/s> <http://a.org/p> \"1\" .",
+                    "<http://a.org/s> <http://www.w3.org/2002/07/owl#sameAs> <http://b.org/s> .",
+                    "<http://b.org/s> <http://a.org/p> \"2\" .",
+                    "<http://b.org/s> <http://www.w3.org/2002/07/owl#sameAs> <http://c.org/u> .",
+                    "<http://c.org/t> <http://a.org/q> \"3\" .",
+                    "<http://c.org/t> <http://www.w3.org/2002/07/owl#equivalentClass> <http://a.org/s> .",
+                ],
+            ),
+        ];
+
+        for (follow_equivalents, expected_calls, expected) in cases {
+            let mut lines = vec![];
+            let mut after = None;
+            let mut calls = 0;
+            loop {
+                let res = query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::DescribeSubject {
+                        subject: "http://a.org/s".to_string(),
+                        format: Some(DataFormat::NTriples),
+                        style: None,
+                        after,
+                        max_scan: None,
+                        follow_equivalents,
+                    },
+                )
+                .unwrap();
+                let result = from_binary::<DescribeSubjectResponse>(&res).unwrap();
+                calls += 1;
+
+                lines.extend(
+                    String::from_utf8(result.data.to_vec())
+                        .unwrap()
+                        .lines()
+                        .map(ToString::to_string),
+                );
+                match result.cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+            lines.sort();
+
+            assert_eq!(calls, expected_calls);
+            assert_eq!(lines, expected);
+        }
+    }
+
+    #[test]
+    fn proper_label() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+a:cat rdfs:label "Cat"@en , "Puss"@EN , "Kitty"@en-US , "Chat"@fr , "cat" ;
+    rdfs:comment "A feline"@en ;
+    a:sound "Meow"@en .
+a:dog rdfs:label "Dog"@en .
+"#;
+        let label = |deps: Deps<'_>, subject: &str, predicate: &str, language: &str| {
+            from_binary::<LabelResponse>(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Label {
+                        subject: subject.to_string(),
+                        predicate: predicate.to_string(),
+                        language: language.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .values
+        };
+        let rdfs_label = "http://www.w3.org/2000/01/rdf-schema#label";
+
+        for label_index in [false, true] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    label_index,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                state::labels()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                if label_index { 6 } else { 0 }
+            );
+
+            let cases = vec![
+                ("http://a.org/cat", rdfs_label, "en", vec!["Cat", "Puss"]),
+                ("http://a.org/cat", rdfs_label, "EN-us", vec!["Kitty"]),
+                ("http://a.org/cat", rdfs_label, "fr", vec!["Chat"]),
+                ("http://a.org/cat", rdfs_label, "de", vec![]),
+                ("http://a.org/cat", "http://a.org/sound", "en", vec!["Meow"]),
+                ("http://a.org/dog", rdfs_label, "en", vec!["Dog"]),
+                ("http://a.org/cow", rdfs_label, "en", vec![]),
+                ("http://b.org/cat", rdfs_label, "en", vec![]),
+            ];
+            for (subject, predicate, language, expected) in cases {
+                assert_eq!(
+                    label(deps.as_ref(), subject, predicate, language),
+                    expected,
+                    "{subject} {predicate} {language}"
+                );
+            }
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("http://a.org/cat".to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(rdfs_label.to_string()))),
+                        object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                            value: "Cat".to_string(),
+                            language: "en".to_string(),
+                        }),
+                    }))],
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                label(deps.as_ref(), "http://a.org/cat", rdfs_label, "en"),
+                vec!["Puss"]
+            );
+        }
+    }
+
+    #[test]
+    fn proper_list_predicates() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix b: <http://b.org/> .
+
+a:s a:p1 "1" , "2" ;
+    a:p2 "2" ;
+    b:p3 b:o .
+a:other a:p1 "1" ;
+    b:p3 "3" .
+b:s a:p4 a:s .
+"#;
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let list_predicates = |deps: Deps<'_>| {
+            let mut predicates = vec![];
+            let mut after = None;
+            let mut calls = 0;
+            loop {
+                let res = query(deps, mock_env(), QueryMsg::ListPredicates { after }).unwrap();
+                let result = from_binary::<ListPredicatesResponse>(&res).unwrap();
+                calls += 1;
+
+                predicates.extend(result.predicates);
+                match result.cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+            (calls, predicates)
+        };
+
+        assert_eq!(list_predicates(deps.as_ref()), (1, vec![]));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            list_predicates(deps.as_ref()),
+            (
+                2,
+                vec![
+                    "http://a.org/p1".to_string(),
+                    "http://a.org/p2".to_string(),
+                    "http://a.org/p4".to_string(),
+                    "http://b.org/p3".to_string(),
+                ]
+            )
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p2".to_string()))),
+                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                }))],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            list_predicates(deps.as_ref()),
+            (
+                2,
+                vec![
+                    "http://a.org/p1".to_string(),
+                    "http://a.org/p4".to_string(),
+                    "http://b.org/p3".to_string(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn proper_compact_namespaces() {
+        let data = r#"@prefix a: <http://a.org/> .
+@prefix b: <http://b.org/> .
+@prefix c: <http://c.org/> .
+@prefix d: <http://d.org/> .
+@prefix e: <http://e.org/> .
+
+a:s b:p "1" .
+c:s d:p c:o ;
+    d:q "2"^^a:int .
+d:s d:p "3" .
+e:s e:p "4" .
+"#;
+
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        for (s, p, o) in [
+            ("http://a.org/s", "http://b.org/p", "1"),
+            ("http://e.org/s", "http://e.org/p", "4"),
+        ] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(s.to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(p.to_string()))),
+                        object: VarOrNodeOrLiteral::Literal(Literal::Simple(o.to_string())),
+                    }))],
+                },
+            )
+            .unwrap();
+        }
+
+        let all_triples = |deps: Deps<'_>| {
+            let res = query(
+                deps,
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: vec![],
+                        select: vec![
+                            SelectItem::Variable("s".to_string()),
+                            SelectItem::Variable("p".to_string()),
+                            SelectItem::Variable("o".to_string()),
                         ],
+                        r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }))],
+                        limit: None,
                     },
                 },
-            ),
-            (
-                SelectQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() }],
-                    select: vec![
-                     SelectItem::Variable("a".to_string()),
-                    ],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(
-                     msg::TriplePattern {
-                         subject: VarOrNode::Variable("a".to_string()),
-                         predicate: VarOrNode::Node(NamedNode(Prefixed(
-                             "core:hasDescription".to_string(),
-                         ))),
-                         object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
-                     },
-                    ))],
-                    limit: None,
+            )
+            .unwrap();
+            let mut bindings: Vec<String> = from_binary::<SelectResponse>(&res)
+                .unwrap()
+                .results
+                .bindings
+                .iter()
+                .map(|binding| format!("{binding:?}"))
+                .collect();
+            bindings.sort();
+            bindings
+        };
+        let namespace_keys = |deps: Deps<'_>| {
+            namespaces()
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(ns, namespace)| (ns, namespace.key)))
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap()
+        };
+        let compact = |deps: DepsMut<'_>, limit: u32| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::CompactNamespaces { limit },
+            )
+        };
+
+        let expected_triples = all_triples(deps.as_ref());
+        assert_eq!(expected_triples.len(), 3);
+        assert_eq!(
+            namespace_keys(deps.as_ref()),
+            vec![
+                ("http://a.org/".to_string(), 0),
+                ("http://c.org/".to_string(), 2),
+                ("http://d.org/".to_string(), 3),
+            ]
+        );
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("not-owner", &[]),
+                ExecuteMsg::CompactNamespaces { limit: 1 },
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+
+        let res = compact(deps.as_mut(), 2).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "compact_namespaces"),
+                Attribute::new("complete", "false"),
+            ]
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from("<http://a.org/s> <http://a.org/p> \"1\" .".as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .err(),
+            Some(ContractError::NamespaceCompactionInProgress)
+        );
+
+        let mut calls = 1;
+        while compact(deps.as_mut(), 2).unwrap().attributes
+            != vec![
+                Attribute::new("action", "compact_namespaces"),
+                Attribute::new("complete", "true"),
+            ]
+        {
+            calls += 1;
+        }
+
+        assert_eq!(calls, 9);
+        assert_eq!(
+            namespace_keys(deps.as_ref()),
+            vec![
+                ("http://a.org/".to_string(), 0),
+                ("http://c.org/".to_string(), 1),
+                ("http://d.org/".to_string(), 2),
+            ]
+        );
+        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 3);
+        assert_eq!(all_triples(deps.as_ref()), expected_triples);
+
+        // compacting dense keys is a no-op
+        let res = compact(deps.as_mut(), 10).unwrap();
+        assert_eq!(res.attributes[1], Attribute::new("complete", "true"));
+        assert_eq!(all_triples(deps.as_ref()), expected_triples);
+    }
+
+    #[test]
+    fn proper_repair_namespaces() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "<http://a.org/s> <http://b.org/p> <http://a.org/o> .
+                    <http://a.org/s> <http://b.org/q> \"1\" ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        // seed an orphaned namespace
+        namespaces()
+            .save(
+                &mut deps.storage,
+                "http://orphan.org/".to_string(),
+                &Namespace {
+                    value: "http://orphan.org/".to_string(),
+                    key: 42,
+                    counter: 3,
+                },
+            )
+            .unwrap();
+        let mut store = STORE.load(&deps.storage).unwrap();
+        store.stat.namespace_count += Uint128::one();
+        STORE.save(&mut deps.storage, &store).unwrap();
+
+        let repair = |deps: DepsMut<'_>, limit: u32| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::RepairNamespaces { limit },
+            )
+        };
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("not-owner", &[]),
+                ExecuteMsg::RepairNamespaces { limit: 1 },
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+
+        let res = repair(deps.as_mut(), 1).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "repair_namespaces"),
+                Attribute::new("complete", "false"),
+                Attribute::new("repaired_count", "0"),
+            ]
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from("<http://a.org/s> <http://a.org/p> \"1\" .".as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .err(),
+            Some(ContractError::NamespaceRepairInProgress)
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::CompactNamespaces { limit: 1 },
+            )
+            .err(),
+            Some(ContractError::NamespaceRepairInProgress)
+        );
+
+        let mut repaired = 0;
+        loop {
+            let res = repair(deps.as_mut(), 1).unwrap();
+            repaired += res.attributes[2].value.parse::<u32>().unwrap();
+            if res.attributes[1] == Attribute::new("complete", "true") {
+                break;
+            }
+        }
+
+        assert_eq!(repaired, 1);
+        assert_eq!(
+            namespaces()
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec!["http://a.org/".to_string(), "http://b.org/".to_string()]
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.namespace_count,
+            Uint128::from(2u128)
+        );
+        assert!(NAMESPACE_REPAIR.may_load(&deps.storage).unwrap().is_none());
+
+        // repairing sound namespaces is a no-op
+        let res = repair(deps.as_mut(), 10).unwrap();
+        assert_eq!(res.attributes[1], Attribute::new("complete", "true"));
+        assert_eq!(res.attributes[2], Attribute::new("repaired_count", "0"));
+    }
+
+    #[test]
+    fn index_predicates() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p1> \"1\" , \"2\" ;
+                        <http://a.org/p2> <http://a.org/o> ;
+                        <http://b.org/p3> \"3\" .
+                    <http://a.org/o> <http://a.org/p1> \"1\" ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        // simulate the triples stored before the predicate index was maintained
+        let stored = triples()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .map(|res| res.unwrap().1)
+            .collect::<Vec<_>>();
+        let legacy_triples = cw_storage_plus::Map::<TriplePK<'_>, Triple>::new("TRIPLE");
+        for triple in &stored {
+            let object_hash = triple.object.as_hash();
+            let pk = (
+                object_hash.as_bytes().as_slice(),
+                triple.predicate.key(),
+                triple.subject.key(),
+            );
+            triples().remove(&mut deps.storage, pk.clone()).unwrap();
+            legacy_triples.save(&mut deps.storage, pk, triple).unwrap();
+        }
+        let mut store = STORE.load(&deps.storage).unwrap();
+        store.predicate_index = false;
+        STORE.save(&mut deps.storage, &store).unwrap();
+
+        let indexed_count = |deps: Deps<'_>| {
+            triples()
+                .idx
+                .predicate
+                .prefix_range_raw(deps.storage, None, None, Order::Ascending)
+                .count()
+        };
+        let list_predicates = |deps: Deps<'_>| {
+            let mut predicates = vec![];
+            let mut after = None;
+            loop {
+                let res = query(deps, mock_env(), QueryMsg::ListPredicates { after }).unwrap();
+                let result = from_binary::<ListPredicatesResponse>(&res).unwrap();
+                predicates.extend(result.predicates);
+                match result.cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+            predicates
+        };
+        let expected_predicates = vec![
+            "http://a.org/p1".to_string(),
+            "http://a.org/p2".to_string(),
+            "http://b.org/p3".to_string(),
+        ];
+
+        // the predicates are listed by scanning the triples while not indexed
+        assert_eq!(indexed_count(deps.as_ref()), 0);
+        assert_eq!(list_predicates(deps.as_ref()), expected_predicates);
+
+        let index = |deps: DepsMut<'_>, limit: u32| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::IndexPredicates { limit },
+            )
+        };
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("not-owner", &[]),
+                ExecuteMsg::IndexPredicates { limit: 1 },
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+
+        let res = index(deps.as_mut(), 3).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "index_predicates"),
+                Attribute::new("complete", "false"),
+            ]
+        );
+        assert_eq!(indexed_count(deps.as_ref()), 3);
+        assert!(!STORE.load(&deps.storage).unwrap().predicate_index);
+        assert_eq!(list_predicates(deps.as_ref()), expected_predicates);
+
+        let res = index(deps.as_mut(), 3).unwrap();
+        assert_eq!(res.attributes[1], Attribute::new("complete", "true"));
+        assert_eq!(indexed_count(deps.as_ref()), stored.len());
+        assert!(STORE.load(&deps.storage).unwrap().predicate_index);
+        assert!(PREDICATE_INDEXING
+            .may_load(&deps.storage)
+            .unwrap()
+            .is_none());
+
+        // the predicates are now listed from the index
+        assert_eq!(list_predicates(deps.as_ref()), expected_predicates);
+        let res = index(deps.as_mut(), 1).unwrap();
+        assert_eq!(res.attributes[1], Attribute::new("complete", "true"));
+    }
+
+    #[test]
+    fn subject_index_consistency() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                subject_index: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let assert_consistent = |deps: Deps<'_>| {
+            let mut expected = triples()
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(_, triple)| (triple.subject_key(), triple)))
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            expected.sort_by(|a, b| a.0.cmp(&b.0));
+            assert_eq!(
+                state::subject_triples()
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()
+                    .unwrap(),
+                expected
+            );
+        };
+        let describe_s = |deps: Deps<'_>| {
+            let res = query(
+                deps,
+                mock_env(),
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("http://a.org/s".to_string())),
+                        r#where: vec![],
+                    },
+                    format: Some(DataFormat::NTriples),
+                    style: None,
+                    base: None,
                 },
-                SelectResponse {
-                 head: Head {
-                     vars: vec!["a".to_string()],
-                 },
-                 results: Results {
-                     bindings: vec![
-                         BTreeMap::from([
-                             (
-                                 "a".to_string(),
-                                 Value::URI {
-                                     value: Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
-                                 }
-                             ),
-                         ])
-                     ],
-                 },
+            )
+            .unwrap();
+            from_binary::<DescribeResponse>(&res).unwrap().data
+        };
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "<http://a.org/s> <http://b.org/p> <http://c.org/o>, \"1\" ;
+                        <http://b.org/q> \"2\" .
+                    <http://d.org/s> <http://b.org/p> <http://a.org/s> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        assert_consistent(deps.as_ref());
+        assert_eq!(
+            describe_s(deps.as_ref()),
+            Binary::from(
+                "<http://a.org/s> <http://b.org/p> \"1\" .
+<http://a.org/s> <http://b.org/p> <http://c.org/o> .
+<http://a.org/s> <http://b.org/q> \"2\" .
+"
+                .as_bytes()
+            )
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full("http://a.org/s".to_string()))),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://b.org/p".to_string()))),
+                    object: VarOrNodeOrLiteral::Node(NamedNode(Full("http://c.org/o".to_string()))),
+                }))],
+            },
+        )
+        .unwrap();
+        assert_consistent(deps.as_ref());
+
+        // the key of the namespace of the last subject being remapped, so is its subject key
+        while execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::CompactNamespaces { limit: 10 },
+        )
+        .unwrap()
+        .attributes[1]
+            != Attribute::new("complete", "true")
+        {}
+        assert_consistent(deps.as_ref());
+        assert_eq!(
+            describe_s(deps.as_ref()),
+            Binary::from(
+                "<http://a.org/s> <http://b.org/p> \"1\" .
+<http://a.org/s> <http://b.org/q> \"2\" .
+"
+                .as_bytes()
+            )
+        );
+    }
+
+    #[test]
+    fn delete_retain_empty_namespaces() {
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+        };
+        let delete_b = |deps: DepsMut<'_>| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("http://b.org/s".to_string()))),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
                 },
+            )
+            .unwrap();
+        };
+        let namespace_state = |deps: Deps<'_>| {
+            (
+                namespaces()
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|res| res.map(|(ns, namespace)| (ns, namespace.key, namespace.counter)))
+                    .collect::<StdResult<Vec<_>>>()
+                    .unwrap(),
+                STORE.load(deps.storage).unwrap().stat.namespace_count,
+            )
+        };
+
+        let cases = vec![
+            (
+                false,
+                vec![("http://a.org/".to_string(), 0, 2)],
+                vec![
+                    ("http://a.org/".to_string(), 0, 3),
+                    ("http://b.org/".to_string(), 2, 1),
+                ],
             ),
             (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
-                    ],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(
-                        msg::TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full("https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string()))),
-                            predicate: VarOrNode::Variable("a".to_string()),
-                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
-                        },
-                    ))],
-                    limit: None,
+                true,
+                vec![
+                    ("http://a.org/".to_string(), 0, 2),
+                    ("http://b.org/".to_string(), 1, 0),
+                ],
+                vec![
+                    ("http://a.org/".to_string(), 0, 3),
+                    ("http://b.org/".to_string(), 1, 1),
+                ],
+            ),
+        ];
+
+        for (retain_empty_namespaces, after_delete, after_reinsert) in cases {
+            let mut deps = mock_dependencies();
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                InstantiateMsg {
+                    retain_empty_namespaces,
+                    ..Default::default()
                 },
-                SelectResponse {
-                    head: Head {
-                        vars: vec!["a".to_string()],
+            )
+            .unwrap();
+
+            insert(
+                deps.as_mut(),
+                "<http://a.org/s> <http://a.org/p> \"1\" .\n<http://b.org/s> <http://a.org/p> \"2\" .",
+            );
+            delete_b(deps.as_mut());
+            let expected_count = Uint128::from(after_delete.len() as u128);
+            assert_eq!(
+                namespace_state(deps.as_ref()),
+                (after_delete, expected_count)
+            );
+
+            insert(deps.as_mut(), "<http://b.org/s> <http://a.org/p> \"2\" .");
+            assert_eq!(
+                namespace_state(deps.as_ref()),
+                (after_reinsert, Uint128::from(2u128))
+            );
+        }
+    }
+
+    #[test]
+    fn xsd_string_literal_is_simple_literal() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> \"x\"^^<http://www.w3.org/2001/XMLSchema#string> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let stored = triples()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .map(|res| res.map(|(_, triple)| triple.object))
+            .collect::<StdResult<Vec<Object>>>()
+            .unwrap();
+        assert_eq!(
+            stored,
+            vec![Object::Literal(state::Literal::Simple {
+                value: "x".to_string()
+            })]
+        );
+        let stat = STORE.load(&deps.storage).unwrap().stat;
+        assert_eq!(stat.namespace_count, Uint128::one());
+        assert_eq!(stat.byte_size, Uint128::new(29));
+
+        for literal in [
+            Literal::Simple("x".to_string()),
+            Literal::TypedValue {
+                value: "x".to_string(),
+                datatype: Full("http://www.w3.org/2001/XMLSchema#string".to_string()),
+            },
+        ] {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: vec![],
+                        select: vec![SelectItem::Variable("s".to_string())],
+                        r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNode::Node(NamedNode(Full(
+                                "http://a.org/p".to_string(),
+                            ))),
+                            object: VarOrNodeOrLiteral::Literal(literal),
+                        }))],
+                        limit: None,
                     },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.okp4.space/core/hasDescription".to_string())
-                                    }
-                                ),
-                            ])
-                        ],
+                },
+            )
+            .unwrap();
+            let result = from_binary::<SelectResponse>(&res).unwrap();
+            assert_eq!(result.results.bindings.len(), 1);
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full("http://a.org/s".to_string()))),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+                    object: VarOrNodeOrLiteral::Literal(Literal::Simple("x".to_string())),
+                }))],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            StoreStat::default()
+        );
+    }
+
+    #[test]
+    fn delete_unnormalized_iris() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                normalize_iris: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<HTTP://Example.com:80/s> <http://example.com/p> <http://example.com/o> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.byte_size,
+            Uint128::from(60u128)
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::DescribeSubject {
+                subject: "http://EXAMPLE.com:80/s".to_string(),
+                format: Some(DataFormat::NTriples),
+                style: None,
+                after: None,
+                max_scan: None,
+                follow_equivalents: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(from_binary::<DescribeSubjectResponse>(&res).unwrap().data.0)
+                .unwrap(),
+            "<http://example.com/s> <http://example.com/p> <http://example.com/o> .\n"
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full("http://EXAMPLE.com/s".to_string()))),
+                    predicate: VarOrNode::Node(NamedNode(Full(
+                        "HTTP://example.com:80/p".to_string(),
+                    ))),
+                    object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                        "http://example.com:80/o".to_string(),
+                    ))),
+                }))],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            StoreStat::default()
+        );
+    }
+
+    #[test]
+    fn proper_validate_delete() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from("<http://a.org/s> <http://a.org/p> \"x\" .".as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let r#where = vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+            object: VarOrNodeOrLiteral::Literal(Literal::Simple("x".to_string())),
+        }))];
+        let cases = vec![
+            (vec![], vec![]),
+            (
+                vec![msg::TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+                    object: VarOrNodeOrLiteral::Literal(Literal::Simple("y".to_string())),
+                }],
+                vec![DeleteProblem {
+                    triple: "<http://a.org/s> <http://a.org/p> 'y'".to_string(),
+                    reason: DeleteProblemReason::NotFound,
+                }],
+            ),
+            (
+                vec![msg::TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNode::Node(NamedNode(Full("http://b.org/p".to_string()))),
+                    object: VarOrNodeOrLiteral::Literal(Literal::Simple("x".to_string())),
+                }],
+                vec![DeleteProblem {
+                    triple: "<http://a.org/s> <http://b.org/p> 'x'".to_string(),
+                    reason: DeleteProblemReason::MissingNamespace {
+                        namespace: "http://b.org/".to_string(),
                     },
+                }],
+            ),
+        ];
+
+        for (delete, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ValidateDelete {
+                    prefixes: vec![],
+                    delete,
+                    r#where: r#where.clone(),
                 },
             )
-        ];
+            .unwrap();
+
+            assert_eq!(
+                from_binary::<ValidateDeleteResponse>(&res).unwrap(),
+                ValidateDeleteResponse { problems: expected }
+            );
+        }
+    }
 
+    #[test]
+    fn validate_delete_canonical_booleans() {
         let mut deps = mock_dependencies();
 
         let info = mock_info("owner", &[]);
@@ -1201,7 +7700,10 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            InstantiateMsg::default(),
+            InstantiateMsg {
+                canonical_booleans: true,
+                ..Default::default()
+            },
         )
         .unwrap();
 
@@ -1210,244 +7712,466 @@ mod tests {
             mock_env(),
             info,
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
-            assert!(res.is_ok());
+        let r#where = vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+        }))];
+        let boolean = |value: &str| msg::TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNode::Node(NamedNode(Full("http://a.org/p".to_string()))),
+            object: VarOrNodeOrLiteral::Literal(Literal::TypedValue {
+                value: value.to_string(),
+                datatype: Full("http://www.w3.org/2001/XMLSchema#boolean".to_string()),
+            }),
+        };
+        let cases = vec![
+            (boolean("1"), vec![]),
+            (boolean("true"), vec![]),
+            (
+                boolean("0"),
+                vec![DeleteProblem {
+                    triple: "<http://a.org/s> <http://a.org/p> '0^^http://www.w3.org/2001/XMLSchema#boolean'"
+                        .to_string(),
+                    reason: DeleteProblemReason::NotFound,
+                }],
+            ),
+        ];
 
-            let result = from_binary::<SelectResponse>(&res.unwrap()).unwrap();
-            assert_eq!(result, expected);
+        for (pattern, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ValidateDelete {
+                    prefixes: vec![],
+                    delete: vec![pattern],
+                    r#where: r#where.clone(),
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                from_binary::<ValidateDeleteResponse>(&res).unwrap(),
+                ValidateDeleteResponse { problems: expected }
+            );
         }
     }
 
     #[test]
-    fn invalid_select() {
+    fn proper_normalize() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .allowed_schemes(vec!["http".to_string()])
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let normalize = |deps: Deps<'_>, data: &str| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::Normalize {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                },
+            )
+            .and_then(|res| from_binary::<NormalizeResponse>(&res))
+            .map(|res| String::from_utf8(res.data.0).unwrap())
+        };
+
+        assert_eq!(
+            normalize(
+                deps.as_ref(),
+                r#"@prefix a: <http://a.org/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+a:s a:p "x"^^xsd:string , "1"^^xsd:integer , "y"@en ;
+    a:q a:o .
+"#
+            ),
+            Ok(r#"<http://a.org/s> <http://a.org/p> "x" .
+<http://a.org/s> <http://a.org/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer> .
+<http://a.org/s> <http://a.org/p> "y"@en .
+<http://a.org/s> <http://a.org/q> <http://a.org/o> .
+"#
+            .to_string())
+        );
+        assert_eq!(
+            normalize(deps.as_ref(), "<http://a.org/s> <http://a.org/p> <urn:a> ."),
+            Err(StdError::generic_err(
+                StoreError::IriSchemeNotAllowed("urn:a".to_string()).to_string()
+            ))
+        );
+
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(store.stat.triple_count, Uint128::zero());
+        assert_eq!(store.stat.namespace_count, Uint128::zero());
+    }
+
+    #[test]
+    fn proper_diff() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    r#"<http://a.org/s> <http://a.org/p> "x" .
+<http://a.org/s> <http://a.org/q> <http://a.org/o> .
+"#
+                    .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        let store_before = STORE.load(&deps.storage).unwrap();
+
+        let data = r#"<http://a.org/s> <http://a.org/p> "x"^^<http://www.w3.org/2001/XMLSchema#string> .
+<http://a.org/s> <http://a.org/p> "y" .
+<http://a.org/s> <http://a.org/q> <http://a.org/o> .
+<http://b.org/s> <http://a.org/p> "y" .
+<http://a.org/s> <http://a.org/p> "y" .
+"#;
         let cases = vec![
             (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
-                        SelectItem::Variable("b".to_string()),
-                    ],
-                    r#where: vec![],
-                    limit: None,
+                None,
+                DiffResponse {
+                    new_count: Uint128::new(2),
+                    existing_count: Uint128::new(2),
+                    new: None,
                 },
-                Err(StdError::generic_err(
-                    "Maximum query variable count exceeded",
-                )),
             ),
             (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![],
-                    r#where: vec![],
-                    limit: Some(8000),
+                Some(true),
+                DiffResponse {
+                    new_count: Uint128::new(2),
+                    existing_count: Uint128::new(2),
+                    new: Some(Binary::from(
+                        r#"<http://a.org/s> <http://a.org/p> "y" .
+<http://b.org/s> <http://a.org/p> "y" .
+"#
+                        .as_bytes(),
+                    )),
                 },
-                Err(StdError::generic_err("Maximum query limit exceeded")),
             ),
-            (
-                SelectQuery {
+        ];
+
+        for (with_new, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Diff {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    with_new,
+                },
+            )
+            .and_then(|res| from_binary::<DiffResponse>(&res));
+
+            assert_eq!(res, Ok(expected));
+        }
+
+        assert_eq!(STORE.load(&deps.storage).unwrap(), store_before);
+        assert_eq!(
+            namespaces()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn proper_explode() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let explode = |deps: Deps<'_>, iri: &str| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::Explode {
+                    iri: iri.to_string(),
+                },
+            )
+            .and_then(|res| from_binary::<ExplodeResponse>(&res))
+            .map(|res| (res.namespace, res.value))
+        };
+        let ok = |namespace: &str, value: &str| Ok((namespace.to_string(), value.to_string()));
+
+        assert_eq!(
+            explode(deps.as_ref(), "http://www.w3.org/2001/XMLSchema#dateTime"),
+            ok("http://www.w3.org/2001/XMLSchema#", "dateTime")
+        );
+        assert_eq!(
+            explode(deps.as_ref(), "https://ontology.okp4.space/core/Governance"),
+            ok("https://ontology.okp4.space/core/", "Governance")
+        );
+        assert_eq!(
+            explode(deps.as_ref(), "did:key:0x04d1f1b8f8a7a28f9a5a254c326a963a"),
+            ok("did:key:", "0x04d1f1b8f8a7a28f9a5a254c326a963a")
+        );
+        assert_eq!(
+            explode(deps.as_ref(), "wow:this/is#weird"),
+            ok("wow:this/is#", "weird")
+        );
+        assert_eq!(
+            explode(deps.as_ref(), "this#is:weird/too"),
+            ok("this#is:weird/", "too")
+        );
+        assert_eq!(
+            explode(deps.as_ref(), "this_doesn't_work"),
+            Err(StdError::generic_err("Couldn't extract IRI namespace"))
+        );
+
+        let mut store = STORE.load(&deps.storage).unwrap();
+        store.require_absolute_iris = true;
+        STORE.save(deps.as_mut().storage, &store).unwrap();
+        assert_eq!(
+            explode(deps.as_ref(), "wow:this/is#weird"),
+            ok("wow:this/is#", "weird")
+        );
+        assert_eq!(
+            explode(deps.as_ref(), "this#is:weird/too"),
+            Err(StdError::generic_err(
+                "Not an absolute IRI: this#is:weird/too"
+            ))
+        );
+
+        store.require_absolute_iris = false;
+        store.full_iri_keys = true;
+        STORE.save(deps.as_mut().storage, &store).unwrap();
+        assert_eq!(
+            explode(deps.as_ref(), "http://a.org/s"),
+            ok("", "http://a.org/s")
+        );
+        assert_eq!(
+            namespaces()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn proper_delete_by_object() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    r#"<http://a.org/s1> <http://a.org/p> <http://d.org/deprecated> .
+<http://a.org/s2> <http://a.org/p> <http://d.org/deprecated> .
+<http://b.org/s3> <http://a.org/q> <http://d.org/deprecated> .
+<http://a.org/s1> <http://a.org/p> <http://a.org/kept> .
+<http://a.org/s1> <http://a.org/q> "deprecated" .
+"#
+                    .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+
+        let delete_by_object = |deps: DepsMut<'_>, sender: &str, object: NodeOrLiteral| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::DeleteByObject {
                     prefixes: vec![Prefix {
-                        prefix: "core".to_string(),
-                        namespace: "https://ontology.okp4.space/core/".to_string(),
+                        prefix: "d".to_string(),
+                        namespace: "http://d.org/".to_string(),
                     }],
-                    select: vec![SelectItem::Variable("a".to_string())],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Variable("a".to_string()),
-                        predicate: VarOrNode::Node(NamedNode(Prefixed(
-                            "invalid:hasDescription".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
-                            value: "A test Dataset.".to_string(),
-                            language: "en".to_string(),
-                        }),
-                    }))],
-                    limit: None,
-                },
-                Err(StdError::generic_err("Prefix not found: invalid")),
-            ),
-            (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![SelectItem::Variable("u".to_string())],
-                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                        subject: VarOrNode::Variable("a".to_string()),
-                        predicate: VarOrNode::Node(NamedNode(Full(
-                            "https://ontology.okp4.space/core/hasDescription".to_string(),
-                        ))),
-                        object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
-                            value: "A test Dataset.".to_string(),
-                            language: "en".to_string(),
-                        }),
-                    }))],
-                    limit: None,
+                    object,
+                    limit: 2,
                 },
-                Err(StdError::generic_err(
-                    "Selected variable not found in query",
-                )),
+            )
+            .map(|res| res.attributes)
+        };
+        let deprecated = || NodeOrLiteral::Node(NamedNode(Prefixed("d:deprecated".to_string())));
+        let attributes = |count: u128, complete: bool| {
+            vec![
+                Attribute::new("action", "delete_by_object"),
+                Attribute::new("triple_count", count.to_string()),
+                Attribute::new(
+                    "triple_count_delta",
+                    if count > 0 {
+                        format!("-{count}")
+                    } else {
+                        "0".to_string()
+                    },
+                ),
+                Attribute::new("complete", complete.to_string()),
+            ]
+        };
+
+        assert_eq!(
+            delete_by_object(deps.as_mut(), "not-owner", deprecated()),
+            Err(ContractError::Unauthorized)
+        );
+        assert_eq!(
+            delete_by_object(deps.as_mut(), "owner", deprecated()),
+            Ok(attributes(2, false))
+        );
+        assert_eq!(
+            delete_by_object(deps.as_mut(), "owner", deprecated()),
+            Ok(attributes(1, true))
+        );
+        assert_eq!(
+            delete_by_object(deps.as_mut(), "owner", deprecated()),
+            Ok(attributes(0, true))
+        );
+        assert_eq!(
+            delete_by_object(
+                deps.as_mut(),
+                "owner",
+                NodeOrLiteral::Node(NamedNode(Full("http://c.org/unknown".to_string())))
             ),
-        ];
+            Ok(attributes(0, true))
+        );
 
-        let mut deps = mock_dependencies();
+        assert_eq!(
+            triples()
+                .range_raw(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            2
+        );
 
-        let info = mock_info("owner", &[]);
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(store.stat.triple_count, Uint128::new(2));
+        assert_eq!(store.stat.namespace_count, Uint128::new(1));
+        assert_eq!(
+            namespaces()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .map(|res| res.unwrap().0)
+                .collect::<Vec<_>>(),
+            vec!["http://a.org/".to_string()]
+        );
+    }
+
+    #[test]
+    fn delete_by_object_limit() {
+        let mut deps = mock_dependencies();
         instantiate(
             deps.as_mut(),
             mock_env(),
-            info.clone(),
+            mock_info("owner", &[]),
             InstantiateMsg {
-                limits: StoreLimitsInput {
-                    max_query_variable_count: 1,
-                    ..Default::default()
-                },
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
             },
         )
         .unwrap();
-
         execute(
             deps.as_mut(),
             mock_env(),
-            info,
+            mock_info("owner", &[]),
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    r#"<http://a.org/s1> <http://a.org/p> <http://d.org/o> .
+<http://a.org/s2> <http://a.org/p> <http://d.org/o> .
+<http://a.org/s3> <http://a.org/p> <http://d.org/o> .
+"#
+                    .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
-            assert_eq!(res, expected);
-        }
-    }
-
-    #[test]
-    fn formats_describe() {
-        let cases = vec![
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![],
-                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                    r#where: vec![],
-                },
-                format: Some(DataFormat::Turtle),
-            },
-            DescribeResponse {
-                format: DataFormat::Turtle,
-                data: Binary::from(
-                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
-\t<https://ontology.okp4.space/core/hasTag> \"Test\" , \"OKP4\" ;
-\t<https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;
-\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;
-\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
-\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;
-\t<https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-            }
-        ),
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![],
-                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                    r#where: vec![],
-                },
-                format: Some(DataFormat::RDFXml),
-            },
-            DescribeResponse {
-                format: DataFormat::RDFXml,
-                data: Binary::from(
-                   "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
-<rdf:Description rdf:about=\"https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473\">\
-<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"https://ontology.okp4.space/metadata/dataspace/GeneralMetadata\"/>\
-<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"http://www.w3.org/2002/07/owl#NamedIndividual\"/>\
-<hasTag xmlns=\"https://ontology.okp4.space/core/\">Test</hasTag><hasTag xmlns=\"https://ontology.okp4.space/core/\">OKP4</hasTag>\
-<hasTitle xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"fr\">Data Space de test</hasTitle>\
-<hasTitle xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"en\">Test Data Space</hasTitle>\
-<hasTopic xmlns=\"https://ontology.okp4.space/core/\" rdf:resource=\"https://ontology.okp4.space/thesaurus/topic/Test\"/>\
-<describes xmlns=\"https://ontology.okp4.space/core/\" rdf:resource=\"https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33\"/>\
-<hasPublisher xmlns=\"https://ontology.okp4.space/core/\">OKP4</hasPublisher>\
-<hasDescription xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"en\">A test Data Space.</hasDescription>\
-<hasDescription xmlns=\"https://ontology.okp4.space/core/\" xml:lang=\"fr\">Un Data Space de test.</hasDescription></rdf:Description>\
-</rdf:RDF>\
-\
-                ".to_string().as_bytes().to_vec()),
-            }
-        ),
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![],
-                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                    r#where: vec![WhereCondition::Simple(TriplePattern(
-                        msg::TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNode::Node(NamedNode(Full(
-                                "https://ontology.okp4.space/core/hasDescription".to_string(),
-                            ))),
-                            object: VarOrNodeOrLiteral::Variable("b".to_string()),
-                        },
-                    ))],
-                },
-                format: Some(DataFormat::NTriples),
-            },
-            DescribeResponse {
-                format: DataFormat::NTriples,
-                data: Binary::from(
-                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"Test\" .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"OKP4\" .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Test Data Space\"@en .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasPublisher> \"OKP4\" .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-            }
-        ),
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
+        let delete_by_object = |deps: DepsMut<'_>| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::DeleteByObject {
                     prefixes: vec![],
-                    resource: VarOrNamedNode::NamedNode(Full("https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                    r#where: vec![],
+                    object: NodeOrLiteral::Node(NamedNode(Full("http://d.org/o".to_string()))),
+                    limit: u32::MAX,
                 },
-                format: Some(DataFormat::NQuads),
-            },
-            DescribeResponse {
-                format: DataFormat::NQuads,
-                data: Binary::from(
-                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"Test\" .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTag> \"OKP4\" .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTitle> \"Test Data Space\"@en .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasPublisher> \"OKP4\" .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en .
-<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.okp4.space/core/hasDescription> \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-            }
-        ),
-    ];
+            )
+            .map(|res| {
+                res.attributes
+                    .into_iter()
+                    .filter(|attr| attr.key == "triple_count" || attr.key == "complete")
+                    .map(|attr| attr.value)
+                    .collect::<Vec<_>>()
+            })
+        };
 
-        let mut deps = mock_dependencies();
+        assert_eq!(
+            delete_by_object(deps.as_mut()),
+            Ok(vec!["2".to_string(), "false".to_string()])
+        );
+        assert_eq!(
+            delete_by_object(deps.as_mut()),
+            Ok(vec!["1".to_string(), "true".to_string()])
+        );
+    }
 
+    #[test]
+    fn insert_default_graph() {
+        let mut deps = mock_dependencies();
         let info = mock_info("owner", &[]);
         instantiate(
             deps.as_mut(),
@@ -1457,67 +8181,151 @@ mod tests {
         )
         .unwrap();
 
-        execute(
+        let insert = |deps: DepsMut<'_>, format, data: &str, default_graph: Option<&str>| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("owner", &[]),
+                InsertData {
+                    format: Some(format),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: default_graph.map(str::to_string),
+                },
+            )
+        };
+        let graphs = |deps: Deps<'_>| {
+            triples()
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|res| {
+                    let triple = res.unwrap().1;
+                    let Object::Literal(state::Literal::Simple { value }) = triple.object else {
+                        panic!("unexpected object: {:?}", triple.object);
+                    };
+                    let graph = triple.graph.map(|node| {
+                        node.as_iri(&mut |key| {
+                            Ok(namespaces()
+                                .idx
+                                .key
+                                .item(deps.storage, key)?
+                                .unwrap()
+                                .1
+                                .value)
+                        })
+                        .unwrap()
+                    });
+                    (value, graph)
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                DataFormat::NQuads,
+                "<http://a.org/s> <http://a.org/p> \"0\" .",
+                Some("default"),
+            )
+            .err(),
+            Some(ContractError::Std(StdError::generic_err(
+                "Not an absolute IRI: default"
+            )))
+        );
+
+        insert(
             deps.as_mut(),
-            mock_env(),
-            info,
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
-            },
+            DataFormat::NQuads,
+            "<http://a.org/s> <http://a.org/p> \"1\" .
+<http://a.org/s> <http://a.org/p> \"2\" <http://a.org/g> .
+<http://a.org/s> <http://a.org/p> \"3\" _:g .",
+            Some("http://g.org/default"),
+        )
+        .unwrap();
+        insert(
+            deps.as_mut(),
+            DataFormat::NTriples,
+            "<http://a.org/s> <http://a.org/p> \"4\" .",
+            Some("http://g.org/default"),
+        )
+        .unwrap();
+        insert(
+            deps.as_mut(),
+            DataFormat::NTriples,
+            "<http://a.org/s> <http://a.org/p> \"5\" .",
+            None,
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
+        assert_eq!(
+            graphs(deps.as_ref()),
+            BTreeMap::from([
+                ("1".to_string(), Some("http://g.org/default".to_string())),
+                ("2".to_string(), Some("http://a.org/g".to_string())),
+                ("3".to_string(), None),
+                ("4".to_string(), Some("http://g.org/default".to_string())),
+                ("5".to_string(), None),
+            ])
+        );
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(store.stat.namespace_count, Uint128::new(2));
+        let byte_size = store.stat.byte_size;
 
-            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+        // storing the triples in another graph moves them, releasing the graph they leave
+        insert(
+            deps.as_mut(),
+            DataFormat::NQuads,
+            "<http://a.org/s> <http://a.org/p> \"1\" .
+<http://a.org/s> <http://a.org/p> \"4\" <http://a.org/g> .",
+            None,
+        )
+        .unwrap();
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
-        }
-    }
+        assert_eq!(
+            graphs(deps.as_ref())
+                .into_iter()
+                .filter(|(value, _)| value == "1" || value == "4")
+                .collect::<Vec<_>>(),
+            vec![
+                ("1".to_string(), None),
+                ("4".to_string(), Some("http://a.org/g".to_string())),
+            ]
+        );
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(store.stat.namespace_count, Uint128::new(1));
+        assert!(namespaces()
+            .may_load(&deps.storage, "http://g.org/".to_string())
+            .unwrap()
+            .is_none());
+        // the triples of 29 bytes are accounted as stored again, in <http://a.org/g> for one of
+        // them, less the IRI of the graph they leave
+        assert_eq!(
+            store.stat.byte_size,
+            byte_size + Uint128::new(2 * 29 + 14 - 2 * 20)
+        );
 
-    #[test]
-    fn prefixes_describe() {
-        let cases = vec![
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![
-                        Prefix {
-                            prefix: "metadata".to_string(),
-                            namespace: "https://ontology.okp4.space/dataverse/dataspace/metadata/".to_string(),
-                        },
-                    ],
-                    resource: VarOrNamedNode::NamedNode(Prefixed("metadata:dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                    r#where: vec![],
-                },
-                format: Some(DataFormat::Turtle),
-            },
-            DescribeResponse {
-                format: DataFormat::Turtle,
-                data: Binary::from(
-                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
-\t<https://ontology.okp4.space/core/hasTag> \"Test\" , \"OKP4\" ;
-\t<https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;
-\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;
-\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
-\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;
-\t<https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-            }
-        ),
-        ];
+        // deleting a triple releases the graph it's stored in
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::DeleteByObject {
+                prefixes: vec![],
+                object: NodeOrLiteral::Literal(Literal::Simple("4".to_string())),
+                limit: 10,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.byte_size,
+            store.stat.byte_size - Uint128::new(29 + 14)
+        );
+    }
 
+    #[test]
+    fn delete_graph() {
         let mut deps = mock_dependencies();
-
         let info = mock_info("owner", &[]);
         instantiate(
             deps.as_mut(),
@@ -1526,292 +8334,990 @@ mod tests {
             InstantiateMsg::default(),
         )
         .unwrap();
-
         execute(
             deps.as_mut(),
             mock_env(),
             info,
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::NQuads),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> \"1\" <http://g.org/g1> .
+<http://a.org/s> <http://a.org/p> \"2\" <http://g.org/g1> .
+<http://a.org/s> <http://a.org/q> <http://c.org/o> <http://g.org/g1> .
+<http://a.org/s> <http://a.org/p> \"3\" <http://a.org/g2> .
+<http://a.org/s> <http://a.org/p> \"4\" .
+<http://a.org/s> <http://a.org/p> \"5\" ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
+        let objects = |deps: Deps<'_>| {
+            triples()
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|res| {
+                    let triple = res.unwrap().1;
+                    match triple.object {
+                        Object::Literal(state::Literal::Simple { value }) => value,
+                        Object::Named(node) => node.value,
+                        object => panic!("unexpected object: {object:?}"),
+                    }
+                })
+                .collect::<BTreeSet<_>>()
+        };
+        let drop = |deps: &mut OwnedDeps<_, _, _>, graph: msg::GraphName, limit: u32| {
+            let mut calls = 0;
+            let mut after = None;
+            loop {
+                let res = execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("owner", &[]),
+                    ExecuteMsg::DeleteGraph {
+                        prefixes: vec![Prefix {
+                            prefix: "g".to_string(),
+                            namespace: "http://g.org/".to_string(),
+                        }],
+                        graph: graph.clone(),
+                        limit,
+                        after,
+                    },
+                )
+                .unwrap();
+                calls += 1;
+                assert_eq!(res.attributes[0], Attribute::new("action", "delete_graph"));
+                match res.attributes.iter().find(|attr| attr.key == "cursor") {
+                    Some(cursor) => {
+                        assert_eq!(res.attributes[3], Attribute::new("complete", "false"));
+                        after = Some(Binary::from_base64(&cursor.value).unwrap());
+                    }
+                    None => {
+                        assert_eq!(res.attributes[3], Attribute::new("complete", "true"));
+                        return calls;
+                    }
+                }
+            }
+        };
 
-            assert!(res.is_ok());
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("not-owner", &[]),
+                ExecuteMsg::DeleteGraph {
+                    prefixes: vec![],
+                    graph: msg::GraphName::Default,
+                    limit: 1,
+                    after: None,
+                },
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
 
-            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+        // a graph in an unknown namespace is empty
+        assert_eq!(
+            drop(
+                &mut deps,
+                msg::GraphName::Named(Full("http://unknown.org/g".to_string())),
+                1
+            ),
+            1
+        );
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
-        }
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(
+            drop(
+                &mut deps,
+                msg::GraphName::Named(Prefixed("g:g1".to_string())),
+                2
+            ),
+            3
+        );
+        assert_eq!(
+            objects(deps.as_ref()),
+            BTreeSet::from(["3", "4", "5"].map(str::to_string))
+        );
+        // the namespaces of the graph and of its object are released along with the triples
+        let stat = STORE.load(&deps.storage).unwrap().stat;
+        assert_eq!(stat.triple_count, store.stat.triple_count - Uint128::new(3));
+        assert_eq!(
+            stat.namespace_count,
+            store.stat.namespace_count - Uint128::new(2)
+        );
+        assert_eq!(
+            stat.byte_size,
+            store.stat.byte_size - Uint128::new(2 * (29 + 15) + 14 + 14 + 14 + 15)
+        );
+
+        assert_eq!(drop(&mut deps, msg::GraphName::Default, 10), 1);
+        assert_eq!(objects(deps.as_ref()), BTreeSet::from(["3".to_string()]));
+        let stat = STORE.load(&deps.storage).unwrap().stat;
+        assert_eq!(stat.triple_count, Uint128::one());
+        assert_eq!(stat.byte_size, Uint128::new(29 + 15));
     }
 
     #[test]
-    fn variable_describe() {
-        let cases = vec![
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() }],
-                    resource: VarOrNamedNode::Variable("a".to_string()),
-                    r#where: vec![WhereCondition::Simple(TriplePattern(
-                        msg::TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNode::Node(NamedNode(Prefixed(
-                                "core:hasDescription".to_string(),
-                            ))),
-                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
-                        },
-                       ))],
+    fn proper_insert_container() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let insert_container = |deps: DepsMut<'_>, sender: &str| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::InsertContainer {
+                    prefixes: vec![Prefix {
+                        prefix: "a".to_string(),
+                        namespace: "http://a.org/".to_string(),
+                    }],
+                    container: NamedNode(Prefixed("a:c".to_string())),
+                    kind: msg::ContainerKind::Seq,
+                    values: vec![
+                        NodeOrLiteral::Node(NamedNode(Prefixed("a:first".to_string()))),
+                        NodeOrLiteral::Literal(Literal::Simple("second".to_string())),
+                    ],
                 },
-                format: Some(DataFormat::Turtle),
+            )
+            .map(|res| res.attributes)
+        };
+
+        assert_eq!(
+            insert_container(deps.as_mut(), "not-owner"),
+            Err(ContractError::Unauthorized)
+        );
+        assert_eq!(
+            insert_container(deps.as_mut(), "owner"),
+            Ok(vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "3"),
+            ])
+        );
+
+        let stored = |predicate: &str, object: VarOrNodeOrLiteral| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StoredTriple {
+                    prefixes: vec![],
+                    triple: msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("http://a.org/c".to_string()))),
+                        predicate: VarOrNode::Node(NamedNode(Full(format!(
+                            "http://www.w3.org/1999/02/22-rdf-syntax-ns#{predicate}"
+                        )))),
+                        object,
+                    },
+                },
+            )
+            .unwrap();
+            from_binary::<msg::StoredTripleResponse>(&res)
+                .unwrap()
+                .stored
+                .is_some()
+        };
+        assert!(stored(
+            "type",
+            VarOrNodeOrLiteral::Node(NamedNode(Full(
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#Seq".to_string()
+            )))
+        ));
+        assert!(stored(
+            "_1",
+            VarOrNodeOrLiteral::Node(NamedNode(Full("http://a.org/first".to_string())))
+        ));
+        assert!(stored(
+            "_2",
+            VarOrNodeOrLiteral::Literal(Literal::Simple("second".to_string()))
+        ));
+        assert!(!stored(
+            "_1",
+            VarOrNodeOrLiteral::Literal(Literal::Simple("second".to_string()))
+        ));
+    }
+
+    #[test]
+    fn proper_inserted_triples() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                record_triple_metadata: true,
+                ..Default::default()
             },
-            DescribeResponse {
-                format: DataFormat::Turtle,
-                data: Binary::from(
-                   "<https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.okp4.space/core/hasTag> \"test\" ;\n\t<https://ontology.okp4.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr ;\n\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;\n\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.okp4.space/core/hasFormat> <https://ontology.okp4.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.okp4.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.okp4.space/core/hasLicense> <https://ontology.okp4.space/thesaurus/license/LO-FR-1_0> ;\n\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en .\n".to_string().as_bytes().to_vec()),
-            }
-        ),
+        )
+        .unwrap();
+
+        for (height, data) in [
+            (10, "<http://a.org/s> <http://a.org/p> \"1\" ."),
+            (20, "<http://a.org/s> <http://a.org/p> \"2\" ."),
+        ] {
+            let mut env = mock_env();
+            env.block.height = height;
+            execute(
+                deps.as_mut(),
+                env,
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let inserted = |deps: Deps<'_>, from: u64, to: u64| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::InsertedTriples {
+                    from_height: from.into(),
+                    to_height: to.into(),
+                    format: Some(DataFormat::NTriples),
+                    style: None,
+                    after: None,
+                    max_scan: None,
+                },
+            )
+            .map(|res| {
+                let res = from_binary::<InsertedTriplesResponse>(&res).unwrap();
+                assert_eq!(res.cursor, None);
+                let data = String::from_utf8(res.data.to_vec()).unwrap();
+                let mut lines = data.lines().map(str::to_string).collect::<Vec<_>>();
+                lines.sort();
+                lines
+            })
+        };
+
+        let first = "<http://a.org/s> <http://a.org/p> \"1\" .".to_string();
+        let second = "<http://a.org/s> <http://a.org/p> \"2\" .".to_string();
+        let cases = vec![
+            (0, 100, vec![first.clone(), second.clone()]),
+            (10, 20, vec![first.clone(), second.clone()]),
+            (10, 19, vec![first]),
+            (11, 20, vec![second.clone()]),
+            (15, 25, vec![second]),
+            (21, 30, vec![]),
+            (20, 10, vec![]),
         ];
+        for (from, to, expected) in cases {
+            assert_eq!(
+                inserted(deps.as_ref(), from, to),
+                Ok(expected),
+                "{from}..={to}"
+            );
+        }
 
         let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg::default()).unwrap();
+        assert_eq!(
+            inserted(deps.as_ref(), 0, 100),
+            Err(StdError::generic_err(
+                "The store doesn't record the triple metadata"
+            ))
+        );
+    }
 
+    #[test]
+    fn collapse_redundant_types() {
+        let mut deps = mock_dependencies();
         let info = mock_info("owner", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            InstantiateMsg::default(),
+            InstantiateMsg {
+                class_hierarchy: BTreeMap::from([
+                    (
+                        "http://a.org/Dog".to_string(),
+                        vec!["http://a.org/Mammal".to_string()],
+                    ),
+                    (
+                        "http://a.org/Mammal".to_string(),
+                        vec!["http://a.org/Animal".to_string()],
+                    ),
+                    (
+                        "http://a.org/Animal".to_string(),
+                        vec!["http://www.w3.org/2002/07/owl#Thing".to_string()],
+                    ),
+                    (
+                        "http://a.org/Person".to_string(),
+                        vec!["http://a.org/Human".to_string()],
+                    ),
+                    (
+                        "http://a.org/Human".to_string(),
+                        vec!["http://a.org/Person".to_string()],
+                    ),
+                ]),
+                ..Default::default()
+            },
         )
         .unwrap();
 
-        execute(
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap()
+        };
+        let stored = |deps: Deps<'_>| {
+            let res = query(
+                deps,
+                mock_env(),
+                QueryMsg::Export {
+                    format: Some(DataFormat::NTriples),
+                    style: None,
+                    base: None,
+                    skolemize: None,
+                    after: None,
+                },
+            )
+            .unwrap();
+            let data = from_binary::<msg::ExportResponse>(&res).unwrap().data;
+            let mut lines = String::from_utf8(data.to_vec())
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            lines.sort();
+            lines
+        };
+        let typed = |subject: &str, class: &str| {
+            format!("<http://a.org/{subject}> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <{class}> .")
+        };
+
+        insert(
+            deps.as_mut(),
+            "@prefix a: <http://a.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            a:rex a owl:Thing , a:Dog , a:Animal ; a:name \"Rex\" .
+            a:tom a a:Mammal .
+            a:felix a a:Cat , owl:Thing .
+            a:bob a a:Person , a:Human .",
+        );
+        let mut expected = vec![
+            typed("bob", "http://a.org/Human"),
+            typed("bob", "http://a.org/Person"),
+            typed("felix", "http://a.org/Cat"),
+            typed("felix", "http://www.w3.org/2002/07/owl#Thing"),
+            typed("rex", "http://a.org/Dog"),
+            "<http://a.org/rex> <http://a.org/name> \"Rex\" .".to_string(),
+            typed("tom", "http://a.org/Mammal"),
+        ];
+        expected.sort();
+        assert_eq!(stored(deps.as_ref()), expected);
+
+        // a more specific type inserted later collapses the stored assertions it implies
+        insert(deps.as_mut(), "<http://a.org/tom> a <http://a.org/Dog> .");
+        expected.retain(|line| *line != typed("tom", "http://a.org/Mammal"));
+        expected.push(typed("tom", "http://a.org/Dog"));
+        expected.sort();
+        assert_eq!(stored(deps.as_ref()), expected);
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::from(expected.len() as u128)
+        );
+    }
+
+    #[test]
+    fn estimate_import() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(
             deps.as_mut(),
             mock_env(),
-            info,
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_insert_data_triple_count: 3u128.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+        };
+        let estimate = |deps: Deps<'_>, data: &str| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::EstimateImport {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                },
+            )
+            .map(|res| from_binary::<EstimateImportResponse>(&res).unwrap())
+        };
 
-            assert!(res.is_ok());
+        insert(deps.as_mut(), "<http://a.org/s> <http://a.org/p> \"1\" .").unwrap();
 
-            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+        let data = "<http://a.org/s> <http://a.org/p> \"1\" , \"2\" .
+            <http://b.org/s> <http://c.org/p> <http://a.org/o> .";
+        let before = STORE.load(&deps.storage).unwrap();
+        let estimated = estimate(deps.as_ref(), data).unwrap();
+        assert_eq!(STORE.load(&deps.storage).unwrap(), before);
+        assert_eq!(
+            estimated,
+            EstimateImportResponse {
+                triple_count: Uint128::from(3u128),
+                byte_size: estimated.byte_size,
+                namespace_count: Uint128::from(2u128),
+                resume_from: None,
+            }
+        );
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
-        }
+        let res = insert(deps.as_mut(), data).unwrap();
+        let after = STORE.load(&deps.storage).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", estimated.triple_count),
+            ]
+        );
+        assert_eq!(
+            after.stat.triple_count - before.stat.triple_count,
+            estimated.triple_count
+        );
+        assert_eq!(
+            after.stat.byte_size - before.stat.byte_size,
+            estimated.byte_size
+        );
+        assert_eq!(
+            after.stat.namespace_count - before.stat.namespace_count,
+            estimated.namespace_count
+        );
+
+        // the limits apply as they would to the insertion
+        let data = "<http://a.org/s> <http://a.org/p> \"3\" , \"4\" , \"5\" , \"6\" .";
+        assert_eq!(
+            estimate(deps.as_ref(), data),
+            Err(StdError::generic_err(
+                insert(deps.as_mut(), data).unwrap_err().to_string()
+            ))
+        );
     }
 
     #[test]
-    fn variable_mutiple_resources_describe() {
-        let cases = vec![
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() }],
-                    resource: VarOrNamedNode::Variable("a".to_string()),
-                    r#where: vec![WhereCondition::Simple(TriplePattern(
-                        msg::TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNode::Node(NamedNode(Prefixed(
-                                "core:hasPublisher".to_string(),
-                            ))),
-                            object: VarOrNodeOrLiteral::Literal(Literal::Simple("OKP4".to_string())),
-                        },
-                       ))],
+    fn skip_empty_literals() {
+        let data = "<http://a.org/s> <http://a.org/p> \"\" , \"x\" , \"\"@en .
+            <http://a.org/s> <http://a.org/q> \"\"^^<http://a.org/datatype> , <http://a.org/o> .";
+
+        for (skip_empty_literals, expected) in [
+            (
+                false,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", "5"),
+                ],
+            ),
+            (
+                true,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", "3"),
+                    Attribute::new("empty_literal_count", "2"),
+                ],
+            ),
+        ] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    skip_empty_literals,
+                    ..Default::default()
                 },
-                format: Some(DataFormat::Turtle),
-            },
-            DescribeResponse {
-                format: DataFormat::Turtle,
-                data: Binary::from(
-                   "<https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.okp4.space/core/hasTag> \"Test\" , \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;\n\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;\n\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;\n\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .\n<https://ontology.okp4.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.okp4.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.okp4.space/core/hasTag> \"test\" ;\n\t<https://ontology.okp4.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr ;\n\t<https://ontology.okp4.space/core/hasTopic> <https://ontology.okp4.space/thesaurus/topic/Test> ;\n\t<https://ontology.okp4.space/core/describes> <https://ontology.okp4.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.okp4.space/core/hasFormat> <https://ontology.okp4.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.okp4.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.okp4.space/core/hasLicense> <https://ontology.okp4.space/thesaurus/license/LO-FR-1_0> ;\n\t<https://ontology.okp4.space/core/hasPublisher> \"OKP4\" ;\n\t<https://ontology.okp4.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en .\n".to_string().as_bytes().to_vec()),
-            }
-        ),
-        ];
+            )
+            .unwrap();
 
-        let mut deps = mock_dependencies();
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .unwrap();
+            assert_eq!(res.attributes, expected, "{skip_empty_literals}");
+            assert_eq!(
+                triples()
+                    .range(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                if skip_empty_literals { 3 } else { 5 }
+            );
+        }
+    }
 
+    #[test]
+    fn namespace_refs() {
+        let mut deps = mock_dependencies();
         let info = mock_info("owner", &[]);
-        instantiate(
-            deps.as_mut(),
-            mock_env(),
-            info.clone(),
-            InstantiateMsg::default(),
-        )
-        .unwrap();
+        instantiate(deps.as_mut(), mock_env(), info.clone(), Default::default()).unwrap();
+
+        let refs = |deps: Deps<'_>, namespace: &str| {
+            from_binary::<NamespaceRefsResponse>(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::NamespaceRefs {
+                        namespace: namespace.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .counter
+            .map(|counter| counter.u128())
+        };
 
         execute(
             deps.as_mut(),
             mock_env(),
-            info,
+            info.clone(),
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> <http://b.org/o> .
+<http://a.org/s2> <http://a.org/p> \"x\" .
+"
+                    .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         )
         .unwrap();
+        assert_eq!(refs(deps.as_ref(), "http://a.org/"), Some(4));
+        assert_eq!(refs(deps.as_ref(), "http://b.org/"), Some(1));
+        assert_eq!(refs(deps.as_ref(), "http://c.org/"), None);
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
+        let delete = |deps: DepsMut<'_>, subject: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full(subject.to_string()))),
+                        predicate: VarOrNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }))],
+                },
+            )
+            .unwrap();
+        };
 
-            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+        delete(deps.as_mut(), "http://a.org/s");
+        assert_eq!(refs(deps.as_ref(), "http://a.org/"), Some(2));
+        assert_eq!(refs(deps.as_ref(), "http://b.org/"), None);
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
-        }
+        delete(deps.as_mut(), "http://a.org/s2");
+        assert_eq!(refs(deps.as_ref(), "http://a.org/"), None);
     }
 
     #[test]
-    fn blanknode_describe() {
+    fn reject_literal_types() {
         let cases = vec![
-        (
-            QueryMsg::Describe {
-                query: DescribeQuery {
-                    prefixes: vec![
-                        Prefix { prefix: "core".to_string(), namespace: "https://ontology.okp4.space/core/".to_string() },
-                        Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.okp4.space/dataverse/dataset/metadata/".to_string()}
-                    ],
-                    resource: VarOrNamedNode::Variable("x".to_string()),
-                    r#where: vec![WhereCondition::Simple(TriplePattern(
-                        msg::TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
-                            predicate: VarOrNode::Node(NamedNode(Prefixed(
-                                "core:hasTemporalCoverage".to_string(),
-                            ))),
-                            object: VarOrNodeOrLiteral::Variable("x".to_string()),
-                        },
-                       )),
-                       ],
-                },
-                format: Some(DataFormat::Turtle),
-            },
-            DescribeResponse {
-                format: DataFormat::Turtle,
-                data: Binary::from(
-                    "<riog00000001> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> , <https://ontology.okp4.space/core/Period> ;\n\t<https://ontology.okp4.space/core/hasStartDate> \"2022-01-01T00:00:00+00:00\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n".to_string().as_bytes().to_vec()),
-            }
-        ),
+            (
+                "<http://a.org/s> a <http://a.org/Class> , _:c .",
+                true,
+                Ok(2),
+            ),
+            ("<http://a.org/s> <http://a.org/p> \"Class\" .", true, Ok(1)),
+            (
+                "<http://a.org/s> a <http://a.org/Class> .
+                <http://a.org/s2> a \"Class\" .",
+                false,
+                Ok(2),
+            ),
+            (
+                "<http://a.org/s> a <http://a.org/Class> .
+                <http://a.org/s2> a \"Class\" .",
+                true,
+                Err(ContractError::from(StoreError::LiteralType(
+                    "<http://a.org/s2>".to_string(),
+                    "\"Class\"".to_string(),
+                ))),
+            ),
+            (
+                "_:b a \"Class\"@en .",
+                true,
+                Err(ContractError::from(StoreError::LiteralType(
+                    "_:b".to_string(),
+                    "\"Class\"@en".to_string(),
+                ))),
+            ),
         ];
 
-        let mut deps = mock_dependencies();
+        for (data, reject_literal_types, expected) in cases {
+            let mut deps = mock_dependencies();
+            let info = mock_info("owner", &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    reject_literal_types,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
+                },
+            )
+            .map(|_| {
+                triples()
+                    .range(&deps.storage, None, None, Order::Ascending)
+                    .count()
+            });
+            assert_eq!(res, expected, "{data}");
+        }
+    }
 
+    #[test]
+    fn cursor_length() {
+        let mut deps = mock_dependencies();
         let info = mock_info("owner", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            InstantiateMsg::default(),
+            InstantiateMsg {
+                record_triple_metadata: true,
+                ..Default::default()
+            },
         )
         .unwrap();
-
         execute(
             deps.as_mut(),
             mock_env(),
             info,
             InsertData {
-                format: Some(DataFormat::Turtle),
-                data: read_test_data("blank-nodes.ttl"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<http://a.org/s> <http://a.org/p> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> ."
+                        .as_bytes(),
+                ),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
-
-            let result = from_binary::<DescribeResponse>(&res.unwrap()).unwrap();
+        let too_long = || Some(Binary::from(vec![0; okp4_cursor::MAX_CURSOR_LENGTH + 1]));
+        let cases = vec![
+            QueryMsg::NamespaceTriples {
+                namespace: "http://a.org/".to_string(),
+                format: None,
+                style: None,
+                after: too_long(),
+                max_scan: None,
+            },
+            QueryMsg::DatatypeTriples {
+                datatype: "http://www.w3.org/2001/XMLSchema#integer".to_string(),
+                format: None,
+                style: None,
+                after: too_long(),
+                max_scan: None,
+            },
+            QueryMsg::InsertedTriples {
+                from_height: 0u64.into(),
+                to_height: u64::MAX.into(),
+                format: None,
+                style: None,
+                after: too_long(),
+                max_scan: None,
+            },
+            QueryMsg::DescribeSubject {
+                subject: "http://a.org/s".to_string(),
+                format: None,
+                style: None,
+                after: too_long(),
+                max_scan: None,
+                follow_equivalents: None,
+            },
+            QueryMsg::DescribeSubject {
+                subject: "http://a.org/s".to_string(),
+                format: None,
+                style: None,
+                after: too_long(),
+                max_scan: None,
+                follow_equivalents: Some(true),
+            },
+            QueryMsg::ListPredicates { after: too_long() },
+            QueryMsg::Export {
+                format: None,
+                style: None,
+                base: None,
+                skolemize: None,
+                after: too_long(),
+            },
+            QueryMsg::ContentDigest {
+                continuation: Some(
+                    to_binary(&ContentDigestContinuation {
+                        after: too_long().unwrap(),
+                        digest: ContentDigest::default(),
+                    })
+                    .unwrap(),
+                ),
+            },
+        ];
 
-            assert_eq!(result.format, expected.format);
+        for msg in cases {
             assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
+                query(deps.as_ref(), mock_env(), msg.clone()),
+                Err(StdError::parse_err(
+                    "Cursor",
+                    format!(
+                        "cursor too long: {} bytes, maximum is {}",
+                        okp4_cursor::MAX_CURSOR_LENGTH + 1,
+                        okp4_cursor::MAX_CURSOR_LENGTH
+                    )
+                )),
+                "{msg:?}"
             );
         }
     }
 
     #[test]
-    fn proper_construct() {
-        let id = "https://ontology.okp4.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
-        let cases = vec![(
-            ConstructQuery {
-                prefixes: vec![],
-                construct: vec![msg::TriplePattern {
-                    subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                    predicate: VarOrNode::Variable("p".to_string()),
-                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                }],
-                r#where: vec![WhereCondition::Simple(TriplePattern(msg::TriplePattern {
-                    subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                    predicate: VarOrNode::Node(NamedNode(Full(
-                        "https://ontology.okp4.space/core/hasTopic".to_string(),
-                    ))),
-                    object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                        "https://ontology.okp4.space/thesaurus/topic/Test".to_string(),
-                    ))),
-                }))],
-            },
-            0,
-        )];
-
-        for case in cases {
+    fn require_absolute_iris() {
+        let data = r#"[
+            {"subject": {"type": "uri", "value": {"full": "a.org/s"}},
+             "predicate": {"type": "uri", "value": {"full": "http://a.org/p"}},
+             "object": {"type": "literal", "value": "x"}}
+        ]"#;
+
+        for (require_absolute_iris, expected) in [
+            (false, Ok(1)),
+            (
+                true,
+                Err(ContractError::Std(StdError::generic_err(
+                    "Not an absolute IRI: a.org/s",
+                ))),
+            ),
+        ] {
             let mut deps = mock_dependencies();
-
             let info = mock_info("owner", &[]);
             instantiate(
                 deps.as_mut(),
                 mock_env(),
                 info.clone(),
                 InstantiateMsg {
-                    limits: StoreLimitsInput::default(),
+                    require_absolute_iris,
+                    ..Default::default()
                 },
             )
             .unwrap();
 
-            execute(
+            let res = execute(
                 deps.as_mut(),
                 mock_env(),
-                info.clone(),
+                info,
                 InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
+                    format: Some(DataFormat::JsonTriples),
+                    data: Binary::from(data.as_bytes()),
+                    idempotency_key: None,
+                    resume_from: None,
+                    segments: None,
+                    default_graph: None,
                 },
             )
-            .unwrap();
+            .map(|_| {
+                triples()
+                    .range(&deps.storage, None, None, Order::Ascending)
+                    .count()
+            });
+            assert_eq!(res, expected, "{require_absolute_iris}");
+        }
+    }
 
-            let res = query(
-                deps.as_ref(),
-                mock_env(),
-                Construct {
-                    query: case.0,
-                    format: Some(DataFormat::default()),
-                },
-            );
+    #[test]
+    fn insert_json_triples() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), Default::default()).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::RegisterPrefixes {
+                prefixes: vec![Prefix {
+                    prefix: "a".to_string(),
+                    namespace: "http://a.org/".to_string(),
+                }],
+            },
+        )
+        .unwrap();
 
-            assert!(res.is_err());
-            assert_eq!(res.err().unwrap(), StdError::generic_err("Not implemented"));
-        }
+        let data = r#"[
+            {"subject": {"type": "uri", "value": {"prefixed": "a:s"}},
+             "predicate": {"type": "uri", "value": {"prefixed": "a:p"}},
+             "object": {"type": "uri", "value": {"full": "http://b.org/o"}}},
+            {"subject": {"type": "uri", "value": {"prefixed": "a:s"}},
+             "predicate": {"type": "uri", "value": {"full": "http://b.org/q"}},
+             "object": {"type": "literal", "value": "x"}}
+        ]"#;
+        // the registered prefixes are available to the queries reading data as well
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Normalize {
+                format: Some(DataFormat::JsonTriples),
+                data: Binary::from(data.as_bytes()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(from_binary::<msg::NormalizeResponse>(&res).unwrap().data.0)
+                .unwrap()
+                .lines()
+                .count(),
+            2
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::JsonTriples),
+                data: Binary::from(data.as_bytes()),
+                idempotency_key: None,
+                resume_from: None,
+                segments: None,
+                default_graph: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "2"),
+            ]
+        );
+
+        let export = |deps: Deps<'_>, format: DataFormat| {
+            from_binary::<msg::ExportResponse>(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Export {
+                        format: Some(format),
+                        style: None,
+                        base: None,
+                        skolemize: None,
+                        after: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .data
+        };
+
+        let data = String::from_utf8(export(deps.as_ref(), DataFormat::NTriples).to_vec()).unwrap();
+        let mut lines = data.lines().collect::<Vec<_>>();
+        lines.sort_unstable();
+        assert_eq!(
+            lines,
+            vec![
+                "<http://a.org/s> <http://a.org/p> <http://b.org/o> .",
+                "<http://a.org/s> <http://b.org/q> \"x\" .",
+            ]
+        );
+
+        let mut document: msg::JsonTriples =
+            from_binary(&export(deps.as_ref(), DataFormat::JsonTriples)).unwrap();
+        assert_eq!(
+            document.prefixes,
+            BTreeMap::from([("a".to_string(), "http://a.org/".to_string())])
+        );
+        document
+            .triples
+            .sort_by_key(|triple| format!("{:?}", triple.predicate));
+        assert_eq!(
+            document.triples.last(),
+            Some(&msg::Triple {
+                subject: Value::URI {
+                    value: Prefixed("a:s".to_string())
+                },
+                predicate: Value::URI {
+                    value: Prefixed("a:p".to_string())
+                },
+                object: Value::URI {
+                    value: Full("http://b.org/o".to_string())
+                },
+            })
+        );
     }
 }
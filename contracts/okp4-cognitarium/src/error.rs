@@ -1,6 +1,7 @@
 use cosmwasm_std::{StdError, Uint128};
 use rio_turtle::TurtleError;
 use rio_xml::RdfXmlError;
+use std::io;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -19,6 +20,12 @@ pub enum ContractError {
 
     #[error("Only the owner can perform this operation.")]
     Unauthorized,
+
+    #[error("A namespace compaction is in progress, it must be completed first.")]
+    NamespaceCompactionInProgress,
+
+    #[error("A namespace repair is in progress, it must be completed first.")]
+    NamespaceRepairInProgress,
 }
 
 impl From<RdfXmlError> for ContractError {
@@ -33,22 +40,60 @@ impl From<TurtleError> for ContractError {
     }
 }
 
+/// The errors of the store, the ones of its limits giving the value reached along with the
+/// maximum, so that a client can e.g. retry with smaller chunks.
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum StoreError {
-    #[error("Maximum triples number exceeded: {0}")]
-    TripleCount(Uint128),
+    #[error("Maximum triples number exceeded: {0} / {1}")]
+    TripleCount(Uint128, Uint128),
 
-    #[error("Maximum byte size exceeded: {0}")]
-    ByteSize(Uint128),
+    #[error("Maximum byte size exceeded: {0} / {1}")]
+    ByteSize(Uint128, Uint128),
 
     #[error("Maximum triple byte size exceeded: {0} / {1}")]
     TripleByteSize(Uint128, Uint128),
 
-    #[error("Maximum insert byte size exceeded: {0}")]
-    InsertDataByteSize(Uint128),
+    #[error("Maximum IRI byte size exceeded: {0} / {1}")]
+    IriByteSize(Uint128, Uint128),
+
+    #[error("Maximum collection triple count exceeded: {0} / {1}")]
+    CollectionTripleCount(Uint128, Uint128),
+
+    #[error("Maximum blank nodes per import exceeded: {0} / {1}")]
+    BlankNodesPerImport(Uint128, Uint128),
+
+    #[error("Maximum literal byte size exceeded: {0} / {1}")]
+    LiteralByteSize(Uint128, Uint128),
+
+    #[error("Maximum insert byte size exceeded: {0} / {1}")]
+    InsertDataByteSize(Uint128, Uint128),
+
+    #[error("Maximum insert triple count exceeded: {0} / {1}")]
+    InsertDataTripleCount(Uint128, Uint128),
+
+    #[error("Maximum insert byte size per subject exceeded for {0}: {1} / {2}")]
+    InsertDataByteSizePerSubject(String, Uint128, Uint128),
+
+    #[error("Maximum triples number per subject exceeded: {0} / {1}")]
+    TriplesPerSubject(Uint128, Uint128),
+
+    #[error("Shape violation on subject {0} and predicate <{1}>: {2}")]
+    ShapeViolation(String, String, String),
+
+    #[error("Conflicting value {2} for the functional property <{1}> of subject {0}")]
+    FunctionalPropertyConflict(String, String, String),
+
+    #[error("Invalid rdf:type of subject {0}, expecting a class IRI: {1}")]
+    LiteralType(String, String),
 
-    #[error("Maximum insert triple count exceeded: {0}")]
-    InsertDataTripleCount(Uint128),
+    #[error("Invalid predicate, expecting a named node: {0}")]
+    InvalidPredicate(String),
+
+    #[error("IRI scheme not allowed: {0}")]
+    IriSchemeNotAllowed(String),
+
+    #[error("Cannot upsert the values of the non functional property <{0}> unless forced")]
+    NonFunctionalUpsert(String),
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -60,6 +105,30 @@ pub enum RDFParseError {
     Turtle(String),
 }
 
+#[derive(Error, Debug)]
+pub enum WriteError {
+    #[error("Error creating the formatter: {0}")]
+    Formatter(io::Error),
+
+    #[error("Error writing triple {0}: {1}")]
+    Format(String, io::Error),
+
+    #[error("Error writing triple: {0}")]
+    Io(io::Error),
+}
+
+impl From<WriteError> for ContractError {
+    fn from(value: WriteError) -> Self {
+        ContractError::FormatRDF(value.to_string())
+    }
+}
+
+impl From<WriteError> for StdError {
+    fn from(value: WriteError) -> Self {
+        StdError::serialize_err("triple", value.to_string())
+    }
+}
+
 impl From<RdfXmlError> for RDFParseError {
     fn from(value: RdfXmlError) -> Self {
         RDFParseError::Xml(value.to_string())
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Binary, Uint128, Uint64};
 use derive_builder::Builder;
 use std::collections::BTreeMap;
 
@@ -10,6 +10,148 @@ pub struct InstantiateMsg {
     /// Limitations regarding store usage.
     #[serde(default)]
     pub limits: StoreLimitsInput,
+    /// The shapes the inserted triples must conform to, at most one per predicate.
+    #[serde(default)]
+    pub shapes: Vec<PredicateShape>,
+    /// Tells if the namespaces no longer referenced by any triple are kept instead of being
+    /// removed, preserving their key for when they are used again.
+    #[serde(default)]
+    pub retain_empty_namespaces: bool,
+    /// The IRIs of the functional properties, i.e. the predicates a subject can only have one
+    /// value for, inserting a second distinct value being rejected.
+    #[serde(default)]
+    pub functional_properties: Vec<String>,
+    /// Tells if the IRIs of the inserted triples are normalized as per
+    /// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-6.2.2) before being stored, i.e.
+    /// with their scheme and host lowercased, their default port removed and their percent-encoded
+    /// octets uppercased, so that IRIs only differing by these denote the same resource.
+    #[serde(default)]
+    pub normalize_iris: bool,
+    /// Tells if the nodes are keyed by their full IRI instead of being split into a namespace and
+    /// a local value, sparing the namespace resolution when inserting data at the cost of the
+    /// compression of the namespaces shared by the IRIs.
+    #[serde(default)]
+    pub full_iri_keys: bool,
+    /// Tells if the language tags of the literals of the triples to delete are checked to be
+    /// well-formed as per [BCP 47](https://www.rfc-editor.org/rfc/rfc5646#section-2.2.9), as the
+    /// ones of the inserted data always are.
+    #[serde(default)]
+    pub validate_language_tags: bool,
+    /// Tells if metadata are recorded alongside the triples, i.e. the heights of the blocks they
+    /// have been inserted and last asserted at, the latter being refreshed by
+    /// [ExecuteMsg::TouchData].
+    #[serde(default)]
+    pub record_triple_metadata: bool,
+    /// The base IRI of the skolem IRIs, i.e. `{base}/.well-known/genid/{label}`, standing for the
+    /// blank nodes of the triples described, and of the ones exported if requested, see
+    /// [QueryMsg::Export], for the consumers not handling blank nodes. The skolem IRIs under this
+    /// base being inserted are turned back into the blank nodes they stand for.
+    /// Default to [None] if not set, which keeps the blank nodes as is.
+    #[serde(default)]
+    pub skolem_base: Option<String>,
+    /// Tells if the RDF/XML properties whose text content holds entity references that can't be
+    /// resolved, e.g. undefined entities, are skipped and reported instead of failing the whole
+    /// insertion. The other XML issues, including the same references in tags, remain fatal.
+    #[serde(default)]
+    pub lenient_rdf_xml: bool,
+    /// How the byte size of the inserted triples is accounted against the store limits.
+    /// Default to [ByteAccounting::Raw] if not set.
+    #[serde(default)]
+    pub byte_accounting: ByteAccounting,
+    /// Tells if a copy of the triples sorted by subject, predicate and object is maintained, so
+    /// that the queries with a bound subject read the triples of the subject in a single range
+    /// scan instead of resolving each of them through the subject index.
+    ///
+    /// This roughly doubles the storage used by the triples and the gas spent writing them, in
+    /// exchange for cheaper subject bound queries, e.g. [QueryMsg::Describe].
+    #[serde(default)]
+    pub subject_index: bool,
+    /// The placeholder the invalid UTF-8 sequences of the inserted data are replaced with instead
+    /// of failing the insertion, e.g. `"\u{FFFD}"` for the Unicode replacement character, the
+    /// number of replacements being reported by the `utf8_replacement_count` attribute.
+    ///
+    /// Such sequences are expected in literal values: being not allowed in IRIs, the ones
+    /// occurring elsewhere are still likely to fail the parsing once replaced.
+    /// Default to [None] if not set, which rejects the data holding invalid UTF-8 sequences.
+    #[serde(default)]
+    pub invalid_utf8_replacement: Option<String>,
+    /// Tells if the values of the language-tagged literals are indexed by subject, predicate and
+    /// language, so that the labels of a subject in a given language are read at once, see
+    /// [QueryMsg::Label], instead of scanning all the objects of the subject and predicate.
+    #[serde(default)]
+    pub label_index: bool,
+    /// Tells if the IRIs of the triples inserted, touched or deleted are checked to only contain
+    /// the characters permitted in IRIs as per
+    /// [RFC 3987](https://www.rfc-editor.org/rfc/rfc3987#section-2.2) before being split into
+    /// their namespace and local value, rejecting for instance spaces and control characters.
+    ///
+    /// The parsers already reject such IRIs in the data, this mainly covers the IRIs provided
+    /// in the messages, e.g. the triple patterns to delete.
+    #[serde(default)]
+    pub strict_iri_chars: bool,
+    /// Tells if the triples repeated within the data of a single insertion are only stored and
+    /// counted once, instead of being accounted for each occurrence in the store statistics and
+    /// limits.
+    ///
+    /// The repetitions are tracked in memory up to a bounded number of distinct triples per
+    /// insertion, the triples read past it being accounted for as without the option.
+    #[serde(default)]
+    pub dedup_imports: bool,
+    /// Tells if the `xsd:boolean` typed literals are stored in their canonical lexical form, i.e.
+    /// `1` as `true` and `0` as `false`, for the two forms of a value to be the same object.
+    ///
+    /// The literals of the triples inserted and deleted, and of the triple patterns of the
+    /// queries, are canonicalized alike.
+    #[serde(default)]
+    pub canonical_booleans: bool,
+    /// The direct superclasses of the classes, by class IRI, the `rdf:type` assertions of the
+    /// subjects typed by an insertion which are implied by a more specific type of the subject as
+    /// per this hierarchy being deleted once the data stored, e.g. `owl:Thing` for any class
+    /// declared under it.
+    ///
+    /// Only the configured hierarchy is considered, not the `rdfs:subClassOf` triples of the
+    /// store. Default to an empty hierarchy if not set, which keeps all the type assertions.
+    #[serde(default)]
+    pub class_hierarchy: BTreeMap<String, Vec<String>>,
+    /// Tells if the inserted triples whose object is an empty simple or typed literal, e.g. `""`,
+    /// often an artifact of the data export, are skipped instead of being stored, the number of
+    /// skipped triples being reported by the `empty_literal_count` attribute.
+    ///
+    /// The deletions are unaffected.
+    #[serde(default)]
+    pub skip_empty_literals: bool,
+    /// Tells if the inserted triples with the `rdf:type` predicate are required to have a class
+    /// IRI as object, a literal such as `x a "SomeClass"` being a frequent modeling mistake, the
+    /// insertion failing on the first one with its subject.
+    #[serde(default)]
+    pub reject_literal_types: bool,
+    /// Tells if the IRIs of the triples inserted, touched or deleted are required to be absolute,
+    /// i.e. to start with a scheme, rejecting the relative IRIs left unresolved by data lacking a
+    /// base, meaningless once stored.
+    #[serde(default)]
+    pub require_absolute_iris: bool,
+}
+
+/// # ByteAccounting
+/// Represents how the byte size of a triple is accounted against the byte size limits of the
+/// store, e.g. [StoreLimitsInput::max_insert_data_byte_size].
+///
+/// In both modes, the prefixed names and relative IRIs of the data are accounted once expanded,
+/// as resolved by the parsers, and the IRIs are accounted normalized if the store normalizes
+/// them, so that the triples deleted release what they have been charged whatever the form their
+/// IRIs are given in.
+#[cw_serde]
+#[derive(Copy, Default, Eq)]
+pub enum ByteAccounting {
+    /// # Raw
+    /// The IRIs are accounted as read from the data.
+    #[serde(rename = "raw")]
+    #[default]
+    Raw,
+    /// # Expanded
+    /// The IRIs are accounted as stored.
+    #[serde(rename = "expanded")]
+    Expanded,
 }
 
 /// Execute messages
@@ -29,6 +171,84 @@ pub enum ExecuteMsg {
         /// The data must be serialized in the format specified by the `format` field. And the data
         /// are subject to the limitations defined by the `limits` specified at contract instantiation.
         data: Binary,
+        /// An optional key identifying this insertion, allowing to safely retry it: if an insertion
+        /// with the same key has already been performed among the most recent ones, it acts as
+        /// no-op and reports the outcome of the original insertion.
+        idempotency_key: Option<String>,
+        /// The number of triples of the data already processed by previous calls, to resume a
+        /// partial insertion from, the insertion of the data being partial when it exceeds the
+        /// maximum number of triples processed per call.
+        resume_from: Option<Uint128>,
+        /// The segments the data is made of when serialized in different formats, e.g. a Turtle
+        /// header followed by an N-Triples body, the `format` field being then ignored.
+        /// The segments must be ordered and must not overlap, the bytes out of them being ignored.
+        #[serde(default)]
+        segments: Option<Vec<DataSegment>>,
+        /// The IRI of the named graph the triples read without a graph name are stored in, i.e.
+        /// all the triples of the formats not supporting graphs and the N-Quads lacking one, the
+        /// default graph being used if not provided. The N-Quads named by a blank node are stored
+        /// in the default graph.
+        #[serde(default)]
+        default_graph: Option<String>,
+    },
+
+    /// # InsertContainer
+    /// Insert an [RDF container](https://www.w3.org/TR/rdf11-schema/#ch_containervocab) holding
+    /// the given values in order, i.e. its typing as a `rdf:Seq`, `rdf:Bag` or `rdf:Alt` followed
+    /// by one `rdf:_1`, `rdf:_2`... membership triple per value, as [ExecuteMsg::InsertData] does
+    /// with the data of these triples.
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    InsertContainer {
+        /// The prefixes used in the container and its values.
+        prefixes: Vec<Prefix>,
+        /// The container, i.e. the subject of the triples.
+        container: Node,
+        /// The kind of the container.
+        kind: ContainerKind,
+        /// The values held by the container, in order.
+        values: Vec<NodeOrLiteral>,
+    },
+
+    /// # TouchData
+    /// Re-asserts the provided triples existing in the store, refreshing the height of the block
+    /// they have last been asserted at without altering the store otherwise. For non-existing
+    /// triples it acts as no-op.
+    ///
+    /// The store must record the triple metadata, see [InstantiateMsg::record_triple_metadata].
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    TouchData {
+        /// The data format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The triples to touch.
+        data: Binary,
+    },
+
+    /// # UpsertData
+    /// Insert the data as RDF triples in the store, each triple replacing the values its subject
+    /// has for its predicate, i.e. the other triples with the same subject and predicate being
+    /// deleted. When several triples of the data share the same subject and predicate, the last
+    /// one wins. For already existing triples being the only value of their predicate, it acts as
+    /// no-op.
+    ///
+    /// Unless forced, the predicates of the triples must be declared as functional properties,
+    /// see [InstantiateMsg::functional_properties].
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    UpsertData {
+        /// The data format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The triples to upsert.
+        data: Binary,
+        /// Tells if the predicates not declared as functional properties can be upserted.
+        /// Default to `false` if not provided.
+        force: Option<bool>,
     },
 
     /// # DeleteData
@@ -74,6 +294,110 @@ pub enum ExecuteMsg {
         /// considered for deletion.
         r#where: WhereClause,
     },
+
+    /// # DeleteByObject
+    /// Deletes the triples having the given object, e.g. to retract every statement pointing to
+    /// a deprecated entity, without having to enumerate them first.
+    ///
+    /// The deletion is performed in chunks, each call deleting at most `limit` triples; it has to
+    /// be repeated until the `complete` attribute of the response is `true`.
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    DeleteByObject {
+        /// The prefixes used in the object.
+        prefixes: Vec<Prefix>,
+        /// The object of the triples to delete.
+        object: NodeOrLiteral,
+        /// The maximum number of triples deleted by this call, capped by the `max_query_limit`
+        /// of the store.
+        limit: u32,
+    },
+
+    /// # DeleteGraph
+    /// Deletes the triples of a graph, i.e. drops it, the triples of the other graphs being left
+    /// untouched.
+    ///
+    /// The deletion is performed in chunks, each call scanning at most `limit` triples of the
+    /// store from the given cursor and deleting the ones of the graph; it has to be repeated with
+    /// the `cursor` attribute of the response until its `complete` attribute is `true`. The
+    /// triples stored in the graph meanwhile before the cursor are only deleted by a new pass.
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    DeleteGraph {
+        /// The prefixes used in the graph name.
+        prefixes: Vec<Prefix>,
+        /// The graph to delete.
+        graph: GraphName,
+        /// The maximum number of triples scanned by this call, capped by the `max_query_limit`
+        /// of the store.
+        limit: u32,
+        /// The cursor to continue from a previous call, as given by its `cursor` attribute.
+        #[serde(default)]
+        after: Option<Binary>,
+    },
+
+    /// # CompactNamespaces
+    /// Remaps the keys of the namespaces to a dense range, reclaiming the keys of the removed
+    /// namespaces, and rewrites the triples accordingly.
+    ///
+    /// The compaction is performed in chunks, each call processing at most `limit` triples and
+    /// namespaces and resuming the previous one; it has to be repeated until the `complete`
+    /// attribute of the response is `true`. Meanwhile, the data can't be inserted nor deleted.
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    CompactNamespaces {
+        /// The maximum number of triples and namespaces processed by this call.
+        limit: u32,
+    },
+
+    /// # RepairNamespaces
+    /// Removes the orphaned namespaces, i.e. the ones still accounted as referenced while no
+    /// triple references them, and fixes the namespace count of the store accordingly. The store
+    /// retaining the empty namespaces, their reference counter is reset instead.
+    ///
+    /// The repair is performed in chunks, each call processing at most `limit` triples and
+    /// namespaces and resuming the previous one; it has to be repeated until the `complete`
+    /// attribute of the response is `true`. Meanwhile, the data can't be inserted nor deleted, nor
+    /// the namespaces compacted.
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    RepairNamespaces {
+        /// The maximum number of triples and namespaces processed by this call.
+        limit: u32,
+    },
+
+    /// # IndexPredicates
+    /// Builds the index of the predicates of the triples stored before it was maintained, the
+    /// predicates being listed by scanning all the triples until it is complete.
+    ///
+    /// The indexing is performed in chunks, each call processing at most `limit` triples and
+    /// resuming the previous one; it has to be repeated until the `complete` attribute of the
+    /// response is `true`. It can't be performed while the namespaces are being compacted.
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    IndexPredicates {
+        /// The maximum number of triples processed by this call.
+        limit: u32,
+    },
+
+    /// # RegisterPrefixes
+    /// Registers prefixes in the store, replacing the namespace of the already registered ones.
+    ///
+    /// The registered prefixes are available to all the queries and deletions, along with the
+    /// prefixes they provide which take precedence over the registered ones, as well as to the
+    /// CURIEs of the inserted [DataFormat::JsonTriples].
+    ///
+    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
+    /// this action.
+    RegisterPrefixes {
+        /// The prefixes to register, their namespace having to be an absolute IRI.
+        prefixes: Vec<Prefix>,
+    },
 }
 
 /// # SelectQuery
@@ -108,6 +432,14 @@ pub enum QueryMsg {
         /// The format in which the triples are serialized.
         /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
         format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The base IRI declared in Turtle (`@base`) and RDF/XML (`xml:base`), the IRIs under it
+        /// being written relative to it. Ignored by the other formats.
+        #[serde(default)]
+        base: Option<String>,
     },
 
     /// # Construct
@@ -121,7 +453,344 @@ pub enum QueryMsg {
         /// The format in which the triples are serialized.
         /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
         format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The base IRI declared in Turtle (`@base`) and RDF/XML (`xml:base`), the IRIs under it
+        /// being written relative to it. Ignored by the other formats.
+        #[serde(default)]
+        base: Option<String>,
+    },
+
+    /// # ValidateDelete
+    ///
+    /// Checks, without performing it, that the deletion defined by the provided query would apply
+    /// on existing triples, i.e. that all the triples to delete can be resolved and exist in the
+    /// store. Returns the problems found, if any.
+    ///
+    /// This allows to avoid wasting gas on a delete that would fail or have no effect.
+    #[returns(ValidateDeleteResponse)]
+    ValidateDelete {
+        /// The prefixes used in the operation.
+        prefixes: Vec<Prefix>,
+        /// Specifies the specific triple patterns to delete.
+        /// If nothing is provided, the patterns from the `where` clause are used for deletion.
+        delete: Vec<TriplePattern>,
+        /// Defines the patterns that data (RDF triples) should match in order for it to be
+        /// considered for deletion.
+        r#where: WhereClause,
+    },
+
+    /// # NamespaceTriples
+    ///
+    /// Returns the triples having their subject, predicate or object in the provided namespace as a
+    /// set of RDF triples serialized in the provided format.
+    ///
+    /// This is a scan over all the triples of the store, bounded to the maximum query limit of
+    /// triples scanned per call, or to the provided maximum scan if lower; the returned cursor
+    /// allows to continue the scan.
+    #[returns(NamespaceTriplesResponse)]
+    NamespaceTriples {
+        /// The namespace IRI.
+        namespace: String,
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The cursor to continue a previous scan from.
+        after: Option<Binary>,
+        /// The maximum number of triples scanned by this call, which can't exceed the maximum
+        /// query limit.
+        #[serde(default)]
+        max_scan: Option<u32>,
+    },
+
+    /// # NamespaceRefs
+    ///
+    /// Returns the number of references to the provided namespace, i.e. of the IRIs of the stored
+    /// triples it is the namespace of, as accounted by the store to know when the namespace can
+    /// be removed.
+    ///
+    /// This exposes the namespaces bookkeeping of the store for diagnostics purposes.
+    #[returns(NamespaceRefsResponse)]
+    NamespaceRefs {
+        /// The namespace IRI.
+        namespace: String,
+    },
+
+    /// # DescribeSubject
+    ///
+    /// Returns all the triples having the provided subject as a set of RDF triples serialized in
+    /// the provided format.
+    ///
+    /// The triples are paginated, at most the maximum query limit of triples, or the provided
+    /// maximum scan if lower, being returned per call; the returned cursor allows to get the next
+    /// ones.
+    ///
+    /// If requested, the triples of the subjects asserted equivalent to the provided one through
+    /// `owl:sameAs` or `owl:equivalentClass`, in either direction, are returned as well, after
+    /// the ones of the provided subject. Only one hop is followed, and at most the maximum query
+    /// limit of equivalents are considered.
+    #[returns(DescribeSubjectResponse)]
+    DescribeSubject {
+        /// The subject IRI.
+        subject: String,
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The cursor to continue from a previous page.
+        after: Option<Binary>,
+        /// The maximum number of triples scanned by this call, which can't exceed the maximum
+        /// query limit.
+        #[serde(default)]
+        max_scan: Option<u32>,
+        /// Whether to also return the triples of the subjects asserted equivalent to the provided
+        /// one, defaults to `false`.
+        #[serde(default)]
+        follow_equivalents: Option<bool>,
+    },
+
+    /// # DatatypeTriples
+    ///
+    /// Returns the triples whose object is a literal typed with the provided datatype as a set of
+    /// RDF triples serialized in the provided format.
+    ///
+    /// As `xsd:string` typed literals are stored as simple literals, no triple is returned for
+    /// this datatype.
+    ///
+    /// This is a scan over all the triples of the store, bounded to the maximum query limit of
+    /// triples scanned per call, or to the provided maximum scan if lower; the returned cursor
+    /// allows to continue the scan.
+    #[returns(DatatypeTriplesResponse)]
+    DatatypeTriples {
+        /// The datatype IRI.
+        datatype: String,
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The cursor to continue a previous scan from.
+        after: Option<Binary>,
+        /// The maximum number of triples scanned by this call, which can't exceed the maximum
+        /// query limit.
+        #[serde(default)]
+        max_scan: Option<u32>,
+    },
+
+    /// # InsertedTriples
+    ///
+    /// Returns the triples inserted at a block height within the provided range, bounds included,
+    /// as a set of RDF triples serialized in the provided format, e.g. for auditing purposes.
+    ///
+    /// The store must record the triple metadata, see [InstantiateMsg::record_triple_metadata];
+    /// the triples stored before, whose insertion height is unknown, are never returned.
+    ///
+    /// This is a scan over all the triples of the store, bounded to the maximum query limit of
+    /// triples scanned per call, or to the provided maximum scan if lower; the returned cursor
+    /// allows to continue the scan.
+    #[returns(InsertedTriplesResponse)]
+    InsertedTriples {
+        /// The lowest insertion height of the triples to return.
+        from_height: Uint64,
+        /// The highest insertion height of the triples to return.
+        to_height: Uint64,
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The cursor to continue a previous scan from.
+        after: Option<Binary>,
+        /// The maximum number of triples scanned by this call, which can't exceed the maximum
+        /// query limit.
+        #[serde(default)]
+        max_scan: Option<u32>,
+    },
+
+    /// # Label
+    ///
+    /// Returns the values of the literals tagged with the provided language, compared
+    /// case-insensitively, the provided subject has for the provided predicate, e.g. its
+    /// `rdfs:label` in a given language.
+    ///
+    /// The lookup reads the label index if the store maintains it, see
+    /// [InstantiateMsg::label_index], and scans the objects of the subject and predicate otherwise.
+    #[returns(LabelResponse)]
+    Label {
+        /// The subject IRI.
+        subject: String,
+        /// The predicate IRI.
+        predicate: String,
+        /// The language tag.
+        language: String,
+    },
+
+    /// # ListPredicates
+    ///
+    /// Returns the distinct predicates used by the triples of the store.
+    ///
+    /// The predicates are paginated, at most the maximum query limit of predicates being returned
+    /// per call; the returned cursor allows to get the next ones.
+    #[returns(ListPredicatesResponse)]
+    ListPredicates {
+        /// The cursor to continue from a previous page.
+        after: Option<Binary>,
+    },
+
+    /// # Normalize
+    ///
+    /// Returns the provided triples as they would be stored, serialized in
+    /// [N-Triples](https://www.w3.org/TR/n-triples/) format, without storing them.
+    ///
+    /// This allows to preview the normalizations applied by the store when inserting data, for
+    /// example `xsd:string` typed literals being stored as simple literals.
+    #[returns(NormalizeResponse)]
+    Normalize {
+        /// The data format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The data to normalize.
+        data: Binary,
+    },
+
+    /// # Diff
+    ///
+    /// Compares the provided triples to the ones of the store, telling how many of them would be
+    /// added by an insertion and how many are already stored, without storing anything.
+    ///
+    /// The triples are compared as they would be stored (see [QueryMsg::Normalize]), the
+    /// duplicates in the provided data being counted once.
+    #[returns(DiffResponse)]
+    Diff {
+        /// The data format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The data to compare to the store.
+        data: Binary,
+        /// Whether to return the triples not in the store, defaults to `false`.
+        with_new: Option<bool>,
+    },
+
+    /// # EstimateImport
+    ///
+    /// Returns what an insertion of the provided triples would add to the store, e.g. for a
+    /// client to estimate its cost before submitting it, without storing anything.
+    ///
+    /// The insertion is run as [ExecuteMsg::InsertData] would, with the same checks, limits and
+    /// accounting, the query failing if the insertion would; only the triples an insertion would
+    /// process in a single call are accounted for.
+    #[returns(EstimateImportResponse)]
+    EstimateImport {
+        /// The data format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The data to estimate the insertion of.
+        data: Binary,
+    },
+
+    /// # Explode
+    ///
+    /// Returns how the provided IRI would be split by the store into a namespace and a local
+    /// value when stored, without storing anything, e.g. to predict the namespaces an insertion
+    /// would allocate.
+    ///
+    /// The IRI is subject to the same checks and normalizations as the IRIs of the inserted
+    /// triples, the query failing if it would be rejected.
+    #[returns(ExplodeResponse)]
+    Explode {
+        /// The full IRI to split.
+        iri: String,
+    },
+
+    /// # Prefixes
+    ///
+    /// Returns the prefixes registered in the store.
+    #[returns(PrefixesResponse)]
+    Prefixes,
+
+    /// # ContentDigest
+    ///
+    /// Returns a digest of all the triples of the store, two stores holding the same triples
+    /// having the same digest whatever the order they have been inserted in.
+    ///
+    /// The triples are folded by chunks of at most the maximum query limit of triples per call;
+    /// the returned continuation allows to fold the next ones, the digest being returned once all
+    /// the triples have been folded.
+    #[returns(ContentDigestResponse)]
+    ContentDigest {
+        /// The continuation of a previous call.
+        continuation: Option<Binary>,
+    },
+
+    /// # Export
+    ///
+    /// Returns all the triples of the store as a set of RDF triples serialized in the provided
+    /// format, e.g. to migrate the store to another format.
+    ///
+    /// The triples are exported by chunks of at most the maximum query limit of triples per call,
+    /// each chunk being a complete document in the provided format; the returned cursor allows to
+    /// export the next ones. The blank nodes keep their identifiers in the store, so that the
+    /// chunks are consistent with each other.
+    ///
+    /// In Turtle, the IRIs are written as prefixed names using the prefixes registered in the
+    /// store, each chunk declaring only the prefixes its triples actually use. In JSON triples,
+    /// they're written as CURIEs in a [JsonTriples] document carrying the registered prefixes.
+    #[returns(ExportResponse)]
+    Export {
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The layout of the triples serialized in Turtle, the other formats being left unchanged.
+        /// If not provided, only the subjects and predicates of consecutive triples are factorized.
+        #[serde(default)]
+        style: Option<TurtleStyle>,
+        /// The base IRI declared in Turtle (`@base`) and RDF/XML (`xml:base`), the IRIs under it
+        /// being written relative to it. Ignored by the other formats.
+        #[serde(default)]
+        base: Option<String>,
+        /// Tells if the blank nodes are written as the skolem IRIs minted from their identifiers
+        /// under the skolem base of the store, see [InstantiateMsg::skolem_base], for the consumers
+        /// not handling blank nodes, the store having to be configured with one.
+        /// Default to `false` if not provided.
+        #[serde(default)]
+        skolemize: Option<bool>,
+        /// The cursor to continue from a previous chunk.
+        after: Option<Binary>,
     },
+
+    /// # StoredTriple
+    ///
+    /// Returns how the provided triple is stored, i.e. the keys of its nodes and the hash of its
+    /// object, if present in the store.
+    ///
+    /// This exposes the storage encoding of the triples for diagnostics purposes, it isn't meant
+    /// to be relied on.
+    #[returns(StoredTripleResponse)]
+    StoredTriple {
+        /// The prefixes used in the triple.
+        prefixes: Vec<Prefix>,
+        /// The triple, which can't have variables.
+        triple: TriplePattern,
+    },
+
+    /// # Stats
+    ///
+    /// Returns the current usage counters of the store along with the limits they are bound to.
+    #[returns(StatsResponse)]
+    Stats,
 }
 
 /// # DataFormat
@@ -136,6 +805,9 @@ pub enum DataFormat {
     RDFXml,
     /// # Turtle
     /// Output in [Turtle](https://www.w3.org/TR/turtle/) format.
+    ///
+    /// Both the `@prefix`/`@base` directives and their SPARQL-style `PREFIX`/`BASE` forms are
+    /// accepted in the data read.
     #[serde(rename = "turtle")]
     #[default]
     Turtle,
@@ -147,6 +819,62 @@ pub enum DataFormat {
     /// Output in [N-Quads](https://www.w3.org/TR/n-quads/) format.
     #[serde(rename = "n_quads")]
     NQuads,
+    /// # JSON Triples
+    /// Output as a JSON array of [Triple], with full IRIs, or as a [JsonTriples] document carrying
+    /// the prefixes of its CURIEs when written with prefixes, e.g. by [QueryMsg::Export].
+    ///
+    /// The data read can be either of them, the prefixes registered in the store being available
+    /// as well when read by the store.
+    #[serde(rename = "json_triples")]
+    JsonTriples,
+}
+
+/// # DataSegment
+/// A segment of data serialized in its own format, given by its byte range in the data.
+#[cw_serde]
+pub struct DataSegment {
+    /// The format in which the triples of the segment are serialized.
+    pub format: DataFormat,
+    /// The position in the data of the first byte of the segment.
+    pub start: u32,
+    /// The position in the data of the byte following the last one of the segment.
+    pub end: u32,
+}
+
+/// # ContainerKind
+/// Represents the kind of an [RDF container](https://www.w3.org/TR/rdf11-schema/#ch_containervocab),
+/// telling how its members are meant to be interpreted.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum ContainerKind {
+    /// # Seq
+    /// An ordered container, i.e. a `rdf:Seq`.
+    #[serde(rename = "seq")]
+    Seq,
+    /// # Bag
+    /// An unordered container, i.e. a `rdf:Bag`.
+    #[serde(rename = "bag")]
+    Bag,
+    /// # Alt
+    /// A container of alternatives, i.e. a `rdf:Alt`.
+    #[serde(rename = "alt")]
+    Alt,
+}
+
+/// # TurtleStyle
+/// Represents the layout of the triples serialized in [Turtle](https://www.w3.org/TR/turtle/).
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum TurtleStyle {
+    /// # Flat
+    /// Each triple is written on its own line, without any factorization.
+    #[serde(rename = "flat")]
+    Flat,
+    /// # Grouped
+    /// The triples are grouped by subject, each subject being written once with the list of its
+    /// predicates and objects, in the order the subjects and predicates first appear.
+    #[serde(rename = "grouped")]
+    Grouped,
 }
 
 /// # StoreLimitsInput
@@ -172,6 +900,11 @@ pub struct StoreLimitsInput {
     /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
     #[serde(default = "StoreLimitsInput::default_max_triple_byte_size")]
     pub max_triple_byte_size: Uint128,
+    /// The maximum number of bytes the store can contains for a single literal, counted as the size
+    /// of its value including the size of its data type or language tag if any.
+    /// Default to the maximum triple byte size if not set.
+    #[serde(default)]
+    pub max_literal_byte_size: Option<Uint128>,
     /// The maximum limit of a query, i.e. the maximum number of triples returned by a select query.
     /// Default to 30 if not set.
     #[serde(default = "StoreLimitsInput::default_max_query_limit")]
@@ -188,6 +921,46 @@ pub struct StoreLimitsInput {
     /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
     #[serde(default = "StoreLimitsInput::default_max_insert_data_triple_count")]
     pub max_insert_data_triple_count: Uint128,
+    /// The IRI schemes (e.g. `https`, `did`) the subjects, predicates and objects of the triples
+    /// stored must use, compared case-insensitively.
+    /// Default to [None] if not set, which allows any scheme.
+    #[serde(default)]
+    pub allowed_schemes: Option<Vec<String>>,
+    /// The maximum number of triples a single subject can have in the store.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_triples_per_subject: Option<Uint128>,
+    /// The maximum number of triples an insert data query processes in a single call, a partial
+    /// insertion then being reported along with the position to resume it from.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_triples_per_call: Option<Uint128>,
+    /// The maximum number of bytes of an IRI, the ones given as CURIEs being checked once expanded.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_iri_byte_size: Option<Uint128>,
+    /// The maximum number of triples an [RDF collection](https://www.w3.org/TR/rdf11-mt/#rdf-collections)
+    /// of the inserted data can expand into, i.e. the `rdf:first` and `rdf:rest` triples chaining
+    /// its blank nodes, a nested collection being accounted on its own.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_collection_triple_count: Option<Uint128>,
+    /// The maximum number of distinct blank nodes the data of a single insertion can hold, each of
+    /// them being stored under its own key.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_blank_nodes_per_import: Option<Uint128>,
+    /// The maximum number of bytes of the data returned by a query exporting triples, the output
+    /// being truncated to the triples fitting in it, a cursor allowing to get the remaining ones.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_query_output_byte_size: Option<Uint128>,
+    /// The maximum number of bytes the data of a single insertion can hold for a single subject,
+    /// counted as the sum of the size of the triples of the subject, so that a subject can't
+    /// consume the whole insertion budget.
+    /// Default to [None] if not set, which can be considered as no limit.
+    #[serde(default)]
+    pub max_insert_data_byte_size_per_subject: Option<Uint128>,
 }
 
 impl StoreLimitsInput {
@@ -220,14 +993,40 @@ impl Default for StoreLimitsInput {
             max_triple_count: Self::default_max_triple_count(),
             max_byte_size: Self::default_max_byte_size(),
             max_triple_byte_size: Self::default_max_triple_byte_size(),
+            max_literal_byte_size: None,
             max_query_limit: Self::default_max_query_limit(),
             max_query_variable_count: Self::default_max_query_variable_count(),
             max_insert_data_byte_size: Self::default_max_insert_data_byte_size(),
             max_insert_data_triple_count: Self::default_max_insert_data_triple_count(),
+            allowed_schemes: None,
+            max_triples_per_subject: None,
+            max_triples_per_call: None,
+            max_iri_byte_size: None,
+            max_collection_triple_count: None,
+            max_blank_nodes_per_import: None,
+            max_query_output_byte_size: None,
+            max_insert_data_byte_size_per_subject: None,
         }
     }
 }
 
+/// # PredicateShape
+/// Contains the constraints the triples having a given predicate must satisfy, as a bounded subset
+/// of [SHACL](https://www.w3.org/TR/shacl/) property shapes.
+#[cw_serde]
+pub struct PredicateShape {
+    /// The IRI of the predicate the shape applies to.
+    pub predicate: String,
+    /// The minimum number of triples with this predicate each subject of inserted triples must
+    /// have.
+    pub min_count: Option<u32>,
+    /// The maximum number of triples with this predicate a subject can have.
+    pub max_count: Option<u32>,
+    /// The IRI of the datatype the objects must have, the objects being literals.
+    /// Simple literals are of type `xsd:string`, language tagged ones of type `rdf:langString`.
+    pub datatype: Option<String>,
+}
+
 /// # StoreResponse
 ///
 /// Contains information related to triple store.
@@ -263,6 +1062,11 @@ pub struct StoreLimits {
     /// storing very large triples, especially literals.
     pub max_triple_byte_size: Uint128,
 
+    /// The maximum number of bytes the store can contains for a single literal, counted as the size
+    /// of its value including the size of its data type or language tag if any. The maximum
+    /// triple byte size applies if not set.
+    pub max_literal_byte_size: Option<Uint128>,
+
     /// The maximum limit of a query, i.e. the maximum number of triples returned by a select query.
     pub max_query_limit: u32,
 
@@ -274,6 +1078,38 @@ pub struct StoreLimits {
 
     /// The maximum number of triples an insert data query can contains (after parsing).
     pub max_insert_data_triple_count: Uint128,
+
+    /// The IRI schemes the subjects, predicates and objects of the triples stored must use, any
+    /// scheme being allowed if not set.
+    pub allowed_schemes: Option<Vec<String>>,
+
+    /// The maximum number of triples a single subject can have in the store, no limit being
+    /// applied if not set.
+    pub max_triples_per_subject: Option<Uint128>,
+
+    /// The maximum number of triples an insert data query processes in a single call, no limit
+    /// being applied if not set.
+    pub max_triples_per_call: Option<Uint128>,
+
+    /// The maximum number of bytes of an IRI, once expanded if given as a CURIE, no limit being
+    /// applied if not set.
+    pub max_iri_byte_size: Option<Uint128>,
+
+    /// The maximum number of triples an RDF collection of the inserted data can expand into, no
+    /// limit being applied if not set.
+    pub max_collection_triple_count: Option<Uint128>,
+
+    /// The maximum number of distinct blank nodes the data of a single insertion can hold, no
+    /// limit being applied if not set.
+    pub max_blank_nodes_per_import: Option<Uint128>,
+
+    /// The maximum number of bytes of the data returned by a query exporting triples, no limit
+    /// being applied if not set.
+    pub max_query_output_byte_size: Option<Uint128>,
+
+    /// The maximum number of bytes the data of a single insertion can hold for a single subject,
+    /// no limit being applied if not set.
+    pub max_insert_data_byte_size_per_subject: Option<Uint128>,
 }
 
 /// # StoreStat
@@ -335,6 +1171,248 @@ pub struct ConstructResponse {
     pub data: Binary,
 }
 
+/// # ValidateDeleteResponse
+/// Represents the response of a [QueryMsg::ValidateDelete] query.
+#[cw_serde]
+pub struct ValidateDeleteResponse {
+    /// The problems found on the triples to delete, empty if the deletion is valid.
+    pub problems: Vec<DeleteProblem>,
+}
+
+/// # DeleteProblem
+/// Represents a problem preventing a triple from being deleted.
+#[cw_serde]
+pub struct DeleteProblem {
+    /// The triple in error.
+    pub triple: String,
+    /// The reason of the problem.
+    pub reason: DeleteProblemReason,
+}
+
+/// # DeleteProblemReason
+/// Represents the reason of a [DeleteProblem].
+#[cw_serde]
+pub enum DeleteProblemReason {
+    /// # Invalid
+    /// The triple cannot be resolved.
+    Invalid {
+        /// The resolution error.
+        error: String,
+    },
+    /// # MissingNamespace
+    /// A namespace referenced by the triple doesn't exist in the store.
+    MissingNamespace {
+        /// The missing namespace.
+        namespace: String,
+    },
+    /// # NotFound
+    /// The triple doesn't exist in the store.
+    NotFound,
+}
+
+/// # NamespaceTriplesResponse
+/// Represents the response of a [QueryMsg::NamespaceTriples] query.
+#[cw_serde]
+pub struct NamespaceTriplesResponse {
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The data serialized in the specified format.
+    pub data: Binary,
+    /// The cursor to continue the scan from, if there are remaining triples to scan.
+    pub cursor: Option<Binary>,
+}
+
+/// # NamespaceRefsResponse
+/// Represents the response of a [QueryMsg::NamespaceRefs] query.
+#[cw_serde]
+pub struct NamespaceRefsResponse {
+    /// The key of the namespace, or none if the namespace doesn't exist in the store.
+    pub key: Option<Uint128>,
+    /// The number of references to the namespace, or none if the namespace doesn't exist in the
+    /// store.
+    pub counter: Option<Uint128>,
+}
+
+/// # DescribeSubjectResponse
+/// Represents the response of a [QueryMsg::DescribeSubject] query.
+#[cw_serde]
+pub struct DescribeSubjectResponse {
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The data serialized in the specified format.
+    pub data: Binary,
+    /// The cursor to get the next page from, if there are remaining triples.
+    pub cursor: Option<Binary>,
+}
+
+/// # DatatypeTriplesResponse
+/// Represents the response of a [QueryMsg::DatatypeTriples] query.
+#[cw_serde]
+pub struct DatatypeTriplesResponse {
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The data serialized in the specified format.
+    pub data: Binary,
+    /// The cursor to continue the scan from, if there are remaining triples to scan.
+    pub cursor: Option<Binary>,
+}
+
+/// # InsertedTriplesResponse
+/// Represents the response of a [QueryMsg::InsertedTriples] query.
+#[cw_serde]
+pub struct InsertedTriplesResponse {
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The data serialized in the specified format.
+    pub data: Binary,
+    /// The cursor to continue the scan from, if there are remaining triples to scan.
+    pub cursor: Option<Binary>,
+}
+
+/// # LabelResponse
+/// Represents the response of a [QueryMsg::Label] query.
+#[cw_serde]
+pub struct LabelResponse {
+    /// The values of the labels, in lexical order.
+    pub values: Vec<String>,
+}
+
+/// # ListPredicatesResponse
+/// Represents the response of a [QueryMsg::ListPredicates] query.
+#[cw_serde]
+pub struct ListPredicatesResponse {
+    /// The IRIs of the predicates.
+    pub predicates: Vec<String>,
+    /// The cursor to get the next page from, if there are remaining predicates.
+    pub cursor: Option<Binary>,
+}
+
+/// # NormalizeResponse
+/// Represents the response of a [QueryMsg::Normalize] query.
+#[cw_serde]
+pub struct NormalizeResponse {
+    /// The normalized triples serialized in [N-Triples](https://www.w3.org/TR/n-triples/) format.
+    pub data: Binary,
+}
+
+/// # DiffResponse
+/// Represents the response of a [QueryMsg::Diff] query.
+#[cw_serde]
+pub struct DiffResponse {
+    /// The number of distinct provided triples not in the store.
+    pub new_count: Uint128,
+    /// The number of distinct provided triples already in the store.
+    pub existing_count: Uint128,
+    /// The provided triples not in the store, serialized in
+    /// [N-Triples](https://www.w3.org/TR/n-triples/) format, if requested.
+    pub new: Option<Binary>,
+}
+
+/// # EstimateImportResponse
+/// Represents the response of a [QueryMsg::EstimateImport] query.
+#[cw_serde]
+pub struct EstimateImportResponse {
+    /// The number of triples the insertion would add to the store.
+    pub triple_count: Uint128,
+    /// The byte size the insertion would add to the store, as accounted against its limits.
+    pub byte_size: Uint128,
+    /// The number of namespaces the insertion would add to the store.
+    pub namespace_count: Uint128,
+    /// The position of the first triple an insertion would leave to a next call, if the data
+    /// exceeds the maximum number of triples processed per call.
+    pub resume_from: Option<Uint128>,
+}
+
+/// # ExplodeResponse
+/// Represents the response of a [QueryMsg::Explode] query.
+#[cw_serde]
+pub struct ExplodeResponse {
+    /// The namespace of the IRI, empty if the store keys the nodes by full IRI.
+    pub namespace: String,
+    /// The local value of the IRI in its namespace.
+    pub value: String,
+}
+
+/// # PrefixesResponse
+/// Represents the response of a [QueryMsg::Prefixes] query.
+#[cw_serde]
+pub struct PrefixesResponse {
+    /// The registered prefixes, ordered by prefix.
+    pub prefixes: Vec<Prefix>,
+}
+
+/// # ContentDigestResponse
+/// Represents the response of a [QueryMsg::ContentDigest] query.
+#[cw_serde]
+pub struct ContentDigestResponse {
+    /// The digest of the triples, once all of them have been folded.
+    pub digest: Option<Binary>,
+    /// The continuation to fold the remaining triples from, if any.
+    pub continuation: Option<Binary>,
+}
+
+/// # ExportResponse
+/// Represents the response of a [QueryMsg::Export] query.
+#[cw_serde]
+pub struct ExportResponse {
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The data serialized in the specified format.
+    pub data: Binary,
+    /// The cursor to get the next chunk from, if there are remaining triples.
+    pub cursor: Option<Binary>,
+    /// Tells if the chunk has been truncated to fit in the maximum output byte size, holding less
+    /// triples than the maximum query limit while there are remaining ones.
+    pub truncated: bool,
+}
+
+/// # StoredTripleResponse
+/// Represents the response of a [QueryMsg::StoredTriple] query.
+#[cw_serde]
+pub struct StoredTripleResponse {
+    /// The stored triple, if present in the store.
+    pub stored: Option<StoredTriple>,
+}
+
+/// # StoredTriple
+/// Represents the storage encoding of a triple.
+#[cw_serde]
+pub struct StoredTriple {
+    /// The hash of the object of the triple.
+    pub object_hash: Binary,
+    /// The key of the predicate of the triple.
+    pub predicate_key: Binary,
+    /// The key of the subject of the triple.
+    pub subject_key: Binary,
+    /// The namespaces the nodes of the triple are in, along with their keys.
+    pub namespaces: Vec<StoredNamespace>,
+    /// The debug representation of the stored triple.
+    pub repr: String,
+}
+
+/// # StoredNamespace
+/// Represents a namespace along with the key its nodes are stored with.
+#[cw_serde]
+pub struct StoredNamespace {
+    /// The namespace IRI.
+    pub namespace: String,
+    /// The key of the namespace.
+    pub key: Uint128,
+    /// The number of nodes referencing the namespace.
+    pub counter: Uint128,
+}
+
+/// # StatsResponse
+/// Represents the response of a [QueryMsg::Stats] query.
+#[cw_serde]
+pub struct StatsResponse {
+    /// The store current usage.
+    pub stat: StoreStat,
+
+    /// The store limits.
+    pub limits: StoreLimits,
+}
+
 /// # Head
 /// Represents the head of a [SelectResponse].
 #[cw_serde]
@@ -381,6 +1459,30 @@ pub enum Value {
     },
 }
 
+/// # Triple
+/// Represents a triple given by the values of its subject, predicate and object, e.g. as an
+/// element of a JSON array of triples.
+#[cw_serde]
+pub struct Triple {
+    /// The subject of the triple.
+    pub subject: Value,
+    /// The predicate of the triple.
+    pub predicate: Value,
+    /// The object of the triple.
+    pub object: Value,
+}
+
+/// # JsonTriples
+/// Represents a self-describing JSON serialization of triples, carrying the prefixes their CURIEs
+/// are compacted with.
+#[cw_serde]
+pub struct JsonTriples {
+    /// The namespaces of the prefixes, keyed by prefix.
+    pub prefixes: BTreeMap<String, String>,
+    /// The triples.
+    pub triples: Vec<Triple>,
+}
+
 /// # SelectQuery
 /// Represents a SELECT query over the triple store, allowing to select variables to return
 /// and to filter the results.
@@ -546,6 +1648,31 @@ pub enum VarOrNodeOrLiteral {
     Literal(Literal),
 }
 
+/// # GraphName
+/// Represents the name of a graph.
+#[cw_serde]
+pub enum GraphName {
+    /// # Default
+    /// The default graph, holding the triples stored without a graph name.
+    Default,
+    /// # Named
+    /// A named graph, identified by its IRI.
+    Named(IRI),
+}
+
+/// # NodeOrLiteral
+/// Represents either a node or a literal.
+#[cw_serde]
+pub enum NodeOrLiteral {
+    /// # Node
+    /// A node, i.e. an IRI or a blank node.
+    Node(Node),
+    /// # Literal
+    /// An RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal), i.e. a simple literal,
+    /// a language-tagged string or a typed value.
+    Literal(Literal),
+}
+
 /// # Literal
 /// An RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal).
 #[cw_serde]
@@ -589,7 +1716,8 @@ mod tests {
     use crate::msg::Node::{BlankNode, NamedNode};
     use crate::msg::IRI::{Full, Prefixed};
     use crate::msg::{
-        InstantiateMsg, StoreLimitsInput, TriplePattern, VarOrNode, VarOrNodeOrLiteral,
+        InstantiateMsg, QueryMsg, StoreLimitsInput, TriplePattern, TurtleStyle, VarOrNode,
+        VarOrNodeOrLiteral,
     };
     use cosmwasm_std::Uint128;
     use schemars::_serde_json;
@@ -626,6 +1754,29 @@ mod tests {
         assert_eq!(msg.limits.max_insert_data_triple_count, Uint128::MAX);
     }
 
+    #[test]
+    fn construct_deserialization() {
+        let layout = |json: &str| match _serde_json::from_str(json).unwrap() {
+            QueryMsg::Construct { style, base, .. } => (style, base),
+            _ => unreachable!(),
+        };
+        let query = r#""query": {"prefixes": [], "construct": [], "where": []}"#;
+
+        assert_eq!(
+            layout(&format!(r#"{{"construct": {{{query}}}}}"#)),
+            (None, None)
+        );
+        assert_eq!(
+            layout(&format!(
+                r#"{{"construct": {{{query}, "style": "grouped", "base": "http://a.org/"}}}}"#
+            )),
+            (
+                Some(TurtleStyle::Grouped),
+                Some("http://a.org/".to_string())
+            )
+        );
+    }
+
     #[test]
     fn variables_from_triple_pattern() {
         let (s, p, o) = ("s".to_string(), "p".to_string(), "o".to_string());
@@ -0,0 +1,49 @@
+use crate::msg::DataFormat;
+use crate::rdf::{Atom, TripleWriter};
+use cosmwasm_std::{Binary, StdResult, Uint128, Uint256};
+use serde::{Deserialize, Serialize};
+
+/// The running state of the digest of the triples of a store, folding the triples in any order so
+/// that two stores holding the same triples have the same digest, whatever the order the triples
+/// have been inserted in and the keys they are stored under.
+///
+/// Each triple is serialized in [N-Triples](https://www.w3.org/TR/n-triples/), its blank nodes
+/// being relabeled, and hashed with blake3, the hashes being summed modulo 2^256. The blank nodes
+/// being relabeled triple by triple, the digest doesn't capture how they relate the triples.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentDigest {
+    sum: Uint256,
+    count: Uint128,
+}
+
+impl ContentDigest {
+    pub fn fold(&mut self, atom: &Atom) -> StdResult<()> {
+        let mut writer =
+            TripleWriter::new(&DataFormat::NTriples, Vec::new()).with_blank_node_relabeling();
+        let triple = atom.into();
+        writer.write(&triple)?;
+        let line = writer.finish()?;
+
+        self.sum = self
+            .sum
+            .wrapping_add(Uint256::from_be_bytes(*blake3::hash(&line).as_bytes()));
+        self.count += Uint128::one();
+        Ok(())
+    }
+
+    /// Returns the digest of the triples folded so far.
+    pub fn finalize(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.sum.to_be_bytes());
+        hasher.update(&self.count.u128().to_be_bytes());
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// The state a content digest is continued from across calls, made of the digest of the triples
+/// folded so far and the raw key of the last one.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContentDigestContinuation {
+    pub after: Binary,
+    pub digest: ContentDigest,
+}
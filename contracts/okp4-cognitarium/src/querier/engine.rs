@@ -1,19 +1,96 @@
 use crate::msg::{Head, Results, SelectItem, SelectResponse, Value};
 use crate::querier::plan::{PatternValue, QueryNode, QueryPlan};
 use crate::querier::variable::{ResolvedVariable, ResolvedVariables};
-use crate::state::{namespaces, triples, Object, Predicate, Subject, Triple};
+use crate::querier::{ContentDigest, TripleResolver};
+use crate::rdf;
+use crate::state::{
+    labels, subject_triples, triples, Literal, Node, Object, Predicate, Subject, Triple,
+};
 use cosmwasm_std::{Order, StdError, StdResult, Storage};
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use cw_storage_plus::{Bound, PrefixBound};
+use std::collections::{BTreeMap, VecDeque};
 use std::iter;
+use std::ops::RangeInclusive;
 use std::rc::Rc;
 
 pub struct QueryEngine<'a> {
     storage: &'a dyn Storage,
+    subject_index: bool,
+    label_index: bool,
+    predicate_index: bool,
 }
 
 impl<'a> QueryEngine<'a> {
     pub fn new(storage: &'a dyn Storage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            subject_index: false,
+            label_index: false,
+            predicate_index: true,
+        }
+    }
+
+    /// Tells if the store maintains the copy of the triples sorted by subject, read instead of the
+    /// subject and predicate index when the subject of a triple pattern is bound.
+    pub fn with_subject_index(mut self, subject_index: bool) -> Self {
+        self.subject_index = subject_index;
+        self
+    }
+
+    /// Tells if the store maintains the index of the language-tagged literals, read instead of
+    /// scanning the objects of a subject and predicate when looking up their labels.
+    pub fn with_label_index(mut self, label_index: bool) -> Self {
+        self.label_index = label_index;
+        self
+    }
+
+    /// Tells if all the triples of the store are in the predicate index, the predicates being
+    /// listed by scanning all the triples otherwise.
+    pub fn with_predicate_index(mut self, predicate_index: bool) -> Self {
+        self.predicate_index = predicate_index;
+        self
+    }
+
+    /// Returns the values of the literals tagged with the given language, compared
+    /// case-insensitively, the given subject has for the given predicate, in lexical order.
+    pub fn get_label(
+        &self,
+        subject: &Subject,
+        predicate: &Predicate,
+        language: &str,
+    ) -> StdResult<Vec<String>> {
+        let mut values = if self.label_index {
+            labels()
+                .may_load(
+                    self.storage,
+                    (
+                        subject.key(),
+                        predicate.key(),
+                        language.to_ascii_lowercase(),
+                    ),
+                )?
+                .unwrap_or_default()
+        } else {
+            triples()
+                .idx
+                .subject_and_predicate
+                .prefix((subject.key(), predicate.key()))
+                .range(self.storage, None, None, Order::Ascending)
+                .filter_map(|res| match res {
+                    Ok((_, triple)) => match triple.object {
+                        Object::Literal(Literal::I18NString {
+                            value,
+                            language: tag,
+                        }) if tag.eq_ignore_ascii_case(language) => Some(Ok(value)),
+                        _ => None,
+                    },
+                    Err(e) => Some(Err(e)),
+                })
+                .collect::<StdResult<Vec<_>>>()?
+        };
+        values.sort();
+
+        Ok(values)
     }
 
     pub fn select(
@@ -47,10 +124,355 @@ impl<'a> QueryEngine<'a> {
         })
     }
 
-    pub fn eval_plan(&'a self, plan: QueryPlan) -> ResolvedVariablesIterator<'_> {
-        return self.eval_node(plan.entrypoint)(ResolvedVariables::with_capacity(
-            plan.variables.len(),
-        ));
+    /// Scans the triples referencing the given namespace key in their subject, predicate or object,
+    /// starting after the given raw triple key, and returns them as atoms.
+    ///
+    /// As this is a scan over all the triples, at most `limit` triples are scanned per call. When
+    /// there are remaining triples to scan, the raw key of the last scanned one is returned to
+    /// continue the scan from, even if no matching triple has been found.
+    pub fn namespace_triples(
+        &self,
+        ns_key: u128,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<rdf::Atom>, Option<Vec<u8>>)> {
+        let mut iter = triples()
+            .range_raw(
+                self.storage,
+                after.map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut atoms = Vec::new();
+        let mut last_key = None;
+        for _ in 0..limit {
+            let (key, triple) = match iter.next() {
+                Some(res) => res?,
+                None => break,
+            };
+            if triple.references_namespace(ns_key) {
+                atoms.push(resolver.resolve(&triple)?);
+            }
+            last_key = Some(key);
+        }
+
+        Ok((atoms, iter.peek().and(last_key)))
+    }
+
+    /// Scans the triples whose object is a literal typed with the given datatype, starting after
+    /// the given raw triple key, and returns them as atoms.
+    ///
+    /// As this is a scan over all the triples, at most `limit` triples are scanned per call. When
+    /// there are remaining triples to scan, the raw key of the last scanned one is returned to
+    /// continue the scan from, even if no matching triple has been found.
+    pub fn datatype_triples(
+        &self,
+        datatype: &Node,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<rdf::Atom>, Option<Vec<u8>>)> {
+        let mut iter = triples()
+            .range_raw(
+                self.storage,
+                after.map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut atoms = Vec::new();
+        let mut last_key = None;
+        for _ in 0..limit {
+            let (key, triple) = match iter.next() {
+                Some(res) => res?,
+                None => break,
+            };
+            if matches!(&triple.object, Object::Literal(Literal::Typed { datatype: d, .. }) if d == datatype)
+            {
+                atoms.push(resolver.resolve(&triple)?);
+            }
+            last_key = Some(key);
+        }
+
+        Ok((atoms, iter.peek().and(last_key)))
+    }
+
+    /// Scans the triples inserted at a block height within the given inclusive range, starting
+    /// after the given raw triple key, and returns them as atoms, the triples without metadata
+    /// being skipped.
+    ///
+    /// As this is a scan over all the triples, at most `limit` triples are scanned per call. When
+    /// there are remaining triples to scan, the raw key of the last scanned one is returned to
+    /// continue the scan from, even if no matching triple has been found.
+    pub fn inserted_triples(
+        &self,
+        heights: RangeInclusive<u64>,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<rdf::Atom>, Option<Vec<u8>>)> {
+        let mut iter = triples()
+            .range_raw(
+                self.storage,
+                after.map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut atoms = Vec::new();
+        let mut last_key = None;
+        for _ in 0..limit {
+            let (key, triple) = match iter.next() {
+                Some(res) => res?,
+                None => break,
+            };
+            if triple
+                .metadata
+                .map_or(false, |metadata| heights.contains(&metadata.inserted_at))
+            {
+                atoms.push(resolver.resolve(&triple)?);
+            }
+            last_key = Some(key);
+        }
+
+        Ok((atoms, iter.peek().and(last_key)))
+    }
+
+    /// Returns at most `limit` triples of the store, starting after the given raw triple key, as
+    /// atoms along with their raw key. When there are remaining triples, the raw key of the last
+    /// returned one is returned to continue from.
+    #[allow(clippy::type_complexity)]
+    pub fn all_triples(
+        &self,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<(Vec<u8>, rdf::Atom)>, Option<Vec<u8>>)> {
+        let mut iter = triples()
+            .range_raw(
+                self.storage,
+                after.map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut atoms = Vec::new();
+        let mut last_key = None;
+        for _ in 0..limit {
+            let (key, triple) = match iter.next() {
+                Some(res) => res?,
+                None => break,
+            };
+            let atom = resolver.resolve(&triple)?;
+            atoms.push((key.clone(), atom));
+            last_key = Some(key);
+        }
+
+        Ok((atoms, iter.peek().and(last_key)))
+    }
+
+    /// Folds at most `limit` triples of the store into the given digest, starting after the given
+    /// raw triple key. When there are remaining triples, the raw key of the last folded one is
+    /// returned to continue from.
+    pub fn content_digest(
+        &self,
+        digest: &mut ContentDigest,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<Option<Vec<u8>>> {
+        let mut iter = triples()
+            .range_raw(
+                self.storage,
+                after.map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut last_key = None;
+        for _ in 0..limit {
+            let (key, triple) = match iter.next() {
+                Some(res) => res?,
+                None => break,
+            };
+            digest.fold(&resolver.resolve(&triple)?)?;
+            last_key = Some(key);
+        }
+
+        Ok(iter.peek().and(last_key))
+    }
+
+    /// Returns at most `limit` triples having the given subject, starting after the given raw
+    /// subject index key, as atoms.
+    ///
+    /// The scan relies on the subject and predicate index, so only the triples of the subject are
+    /// read. When there are remaining triples, the raw index key of the last returned one is
+    /// returned to continue from.
+    pub fn subject_triples(
+        &self,
+        subject: Subject,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<rdf::Atom>, Option<Vec<u8>>)> {
+        let mut iter = triples()
+            .idx
+            .subject_and_predicate
+            .sub_prefix(subject.key())
+            .range_raw(
+                self.storage,
+                after.map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut atoms = Vec::new();
+        let mut last_key = None;
+        for _ in 0..limit {
+            let (pk, triple) = match iter.next() {
+                Some(res) => res?,
+                None => break,
+            };
+            atoms.push(resolver.resolve(&triple)?);
+            last_key = Some(subject_index_key(&triple.predicate, &pk));
+        }
+
+        Ok((atoms, iter.peek().and(last_key)))
+    }
+
+    /// Returns the subjects asserted equivalent to the given one through the given symmetric
+    /// properties, e.g. `owl:sameAs`, i.e. its objects for these properties along with the
+    /// subjects having it as object for them.
+    ///
+    /// Only one hop is followed, the equivalents of the equivalents being not looked up. At most
+    /// `limit` equivalents are returned, ordered by key, the subject itself being excluded.
+    pub fn equivalent_subjects(
+        &self,
+        subject: &Subject,
+        properties: &[Node],
+        limit: usize,
+    ) -> StdResult<Vec<Subject>> {
+        let as_object = match subject {
+            Subject::Named(node) => Object::Named(node.clone()),
+            Subject::Blank(node) => Object::Blank(node.clone()),
+        };
+
+        let mut equivalents = BTreeMap::new();
+        for property in properties {
+            for res in triples()
+                .idx
+                .subject_and_predicate
+                .prefix((subject.key(), property.key()))
+                .range(self.storage, None, None, Order::Ascending)
+            {
+                let equivalent = match res?.1.object {
+                    Object::Named(node) => Subject::Named(node),
+                    Object::Blank(node) => Subject::Blank(node),
+                    Object::Literal(_) => continue,
+                };
+                equivalents.insert(equivalent.key(), equivalent);
+            }
+            for res in triples()
+                .prefix((as_object.as_hash().as_bytes(), property.key()))
+                .range(self.storage, None, None, Order::Ascending)
+            {
+                let equivalent = res?.1.subject;
+                equivalents.insert(equivalent.key(), equivalent);
+            }
+        }
+        equivalents.remove(&subject.key());
+
+        Ok(equivalents.into_values().take(limit).collect())
+    }
+
+    /// Returns the distinct predicates in use, ordered by their key, starting after the given
+    /// predicate key. Thanks to the predicate index, it costs a single storage seek per predicate
+    /// whatever the number of triples using it.
+    pub fn predicates(
+        &self,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<String>, Option<Vec<u8>>)> {
+        if !self.predicate_index {
+            return self.scan_predicates(after, limit);
+        }
+
+        let next_predicate = |after: Option<Vec<u8>>| -> StdResult<Option<Predicate>> {
+            triples()
+                .idx
+                .predicate
+                .prefix_range_raw(
+                    self.storage,
+                    after.map(PrefixBound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()
+                .map(|maybe_triple| maybe_triple.map(|(_, triple)| triple.predicate))
+        };
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut predicates = Vec::new();
+        let mut last_key = after;
+        for _ in 0..limit {
+            let predicate = match next_predicate(last_key.clone())? {
+                Some(predicate) => predicate,
+                None => return Ok((predicates, None)),
+            };
+            predicates.push(resolver.resolve_node(&predicate)?);
+            last_key = Some(predicate.key());
+        }
+
+        let cursor = match next_predicate(last_key.clone())? {
+            Some(_) => last_key,
+            None => None,
+        };
+        Ok((predicates, cursor))
+    }
+
+    /// Lists the predicates as [Self::predicates] does, scanning all the triples of the store for
+    /// the ones not indexed yet, which is as expensive as the store is large.
+    fn scan_predicates(
+        &self,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(Vec<String>, Option<Vec<u8>>)> {
+        let mut found = BTreeMap::new();
+        for res in triples().range(self.storage, None, None, Order::Ascending) {
+            let (_, triple) = res?;
+            let key = triple.predicate.key();
+            if after.as_ref().map_or(true, |after| key > *after) {
+                found.insert(key, triple.predicate);
+                // Keeps one predicate more than the limit to tell if there are next ones.
+                if found.len() > limit + 1 {
+                    found.pop_last();
+                }
+            }
+        }
+
+        let mut resolver = TripleResolver::new(self.storage);
+        let mut predicates = Vec::new();
+        let mut last_key = None;
+        for (key, predicate) in found.iter().take(limit) {
+            predicates.push(resolver.resolve_node(predicate)?);
+            last_key = Some(key.clone());
+        }
+
+        let cursor = if found.len() > limit { last_key } else { None };
+        Ok((predicates, cursor))
+    }
+
+    pub fn eval_plan(&'a self, plan: QueryPlan) -> ResolvedVariablesIterator<'a> {
+        self.eval_node(plan.entrypoint)(ResolvedVariables::with_capacity(plan.variables.len()))
     }
 
     fn eval_node(
@@ -65,6 +487,7 @@ impl<'a> QueryEngine<'a> {
             } => Rc::new(move |vars| {
                 Box::new(TriplePatternIterator::new(
                     self.storage,
+                    self.subject_index,
                     vars,
                     subject.clone(),
                     predicate.clone(),
@@ -217,6 +640,7 @@ type TriplePatternBindings = (Option<usize>, Option<usize>, Option<usize>);
 impl<'a> TriplePatternIterator<'a> {
     fn new(
         storage: &'a dyn Storage,
+        subject_index: bool,
         input: ResolvedVariables,
         subject: PatternValue<Subject>,
         predicate: PatternValue<Predicate>,
@@ -228,7 +652,7 @@ impl<'a> TriplePatternIterator<'a> {
             return Self {
                 input,
                 output_bindings,
-                triple_iter: Self::make_state_iter(storage, filters),
+                triple_iter: Self::make_state_iter(storage, subject_index, filters),
             };
         }
 
@@ -241,9 +665,32 @@ impl<'a> TriplePatternIterator<'a> {
 
     fn make_state_iter(
         storage: &'a dyn Storage,
+        subject_index: bool,
         filters: TriplePatternFilters,
     ) -> Box<dyn Iterator<Item = StdResult<Triple>> + 'a> {
         match filters {
+            (Some(s), Some(p), None) if subject_index => Box::new(
+                subject_triples()
+                    .prefix((s.key(), p.key()))
+                    .range(storage, None, None, Order::Ascending)
+                    .map(|res| res.map(|(_, t)| t)),
+            ),
+            (Some(s), None, Some(o)) if subject_index => Box::new(
+                subject_triples()
+                    .sub_prefix(s.key())
+                    .range(storage, None, None, Order::Ascending)
+                    .filter(move |res| match res {
+                        Ok((_, triple)) => triple.object == o,
+                        Err(_) => true,
+                    })
+                    .map(|res| res.map(|(_, t)| t)),
+            ),
+            (Some(s), None, None) if subject_index => Box::new(
+                subject_triples()
+                    .sub_prefix(s.key())
+                    .range(storage, None, None, Order::Ascending)
+                    .map(|res| res.map(|(_, t)| t)),
+            ),
             (Some(s), Some(p), Some(o)) => {
                 let res = triples().load(storage, (o.as_hash().as_bytes(), p.key(), s.key()));
                 match res {
@@ -367,10 +814,9 @@ impl<'a> Iterator for TriplePatternIterator<'a> {
 }
 
 struct SolutionsIterator<'a> {
-    storage: &'a dyn Storage,
     iter: ResolvedVariablesIterator<'a>,
     bindings: BTreeMap<String, usize>,
-    ns_cache: HashMap<u128, String>,
+    resolver: TripleResolver<'a>,
 }
 
 impl<'a> SolutionsIterator<'a> {
@@ -380,28 +826,48 @@ impl<'a> SolutionsIterator<'a> {
         bindings: BTreeMap<String, usize>,
     ) -> Self {
         Self {
-            storage,
             iter,
             bindings,
-            ns_cache: HashMap::new(),
+            resolver: TripleResolver::new(storage),
         }
     }
+}
 
-    fn resolve_ns(&mut self, ns_key: u128) -> StdResult<String> {
-        if let Some(ns) = self.ns_cache.get(&ns_key) {
-            return Ok(ns.clone());
-        }
+/// Builds the raw key of a triple in the subject and predicate index, without the subject part,
+/// i.e. the length prefixed predicate key followed by the triple primary key.
+fn subject_index_key(predicate: &Predicate, pk: &[u8]) -> Vec<u8> {
+    let predicate = predicate.key();
+    let mut key = Vec::with_capacity(2 + predicate.len() + pk.len());
+    key.extend((predicate.len() as u16).to_be_bytes());
+    key.extend(predicate);
+    key.extend(pk);
 
-        let ns = namespaces().idx.key.item(self.storage, ns_key).and_then(
-            |maybe_ns| match maybe_ns {
-                Some(ns) => Ok(ns.1.value),
-                None => Err(StdError::not_found("Namespace")),
-            },
-        )?;
+    key
+}
 
-        self.ns_cache.insert(ns_key, ns.clone());
-        Ok(ns)
-    }
+/// Converts a stored triple into an atom, resolving the namespace keys with the given function.
+pub fn triple_to_atom<F>(triple: &Triple, ns_fn: &mut F) -> StdResult<rdf::Atom>
+where
+    F: FnMut(u128) -> StdResult<String>,
+{
+    Ok(rdf::Atom {
+        subject: match &triple.subject {
+            Subject::Named(node) => rdf::Subject::NamedNode(node.as_iri(ns_fn)?),
+            Subject::Blank(node) => rdf::Subject::BlankNode(node.clone()),
+        },
+        property: rdf::Property(triple.predicate.as_iri(ns_fn)?),
+        value: match &triple.object {
+            Object::Named(node) => rdf::Value::NamedNode(node.as_iri(ns_fn)?),
+            Object::Blank(node) => rdf::Value::BlankNode(node.clone()),
+            Object::Literal(Literal::Simple { value }) => rdf::Value::LiteralSimple(value.clone()),
+            Object::Literal(Literal::I18NString { value, language }) => {
+                rdf::Value::LiteralLang(value.clone(), language.clone())
+            }
+            Object::Literal(Literal::Typed { value, datatype }) => {
+                rdf::Value::LiteralDatatype(value.clone(), datatype.as_iri(ns_fn)?)
+            }
+        },
+    })
 }
 
 impl<'a> Iterator for SolutionsIterator<'a> {
@@ -427,7 +893,10 @@ impl<'a> Iterator for SolutionsIterator<'a> {
                     })
                     .map(|res| {
                         res.and_then(|(name, var)| -> StdResult<(String, Value)> {
-                            Ok((name, var.as_value(&mut |ns_key| self.resolve_ns(ns_key))?))
+                            Ok((
+                                name,
+                                var.as_value(&mut |ns_key| self.resolver.resolve_ns(ns_key))?,
+                            ))
                         })
                     })
                     .collect::<StdResult<BTreeMap<String, Value>>>()
@@ -439,7 +908,7 @@ impl<'a> Iterator for SolutionsIterator<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::msg::{DataFormat, StoreLimitsInput, IRI};
+    use crate::msg::{ByteAccounting, DataFormat, StoreLimitsInput, IRI};
     use crate::rdf::TripleReader;
     use crate::state;
     use crate::state::{Literal, Store, StoreStat, NAMESPACE_KEY_INCREMENT, STORE};
@@ -474,6 +943,27 @@ mod test {
                     owner: Addr::unchecked("owner"),
                     limits: StoreLimitsInput::default().into(),
                     stat: StoreStat::default(),
+                    shapes: vec![],
+                    retain_empty_namespaces: false,
+                    functional_properties: vec![],
+                    normalize_iris: false,
+                    full_iri_keys: false,
+                    validate_language_tags: false,
+                    record_triple_metadata: false,
+                    skolem_base: None,
+                    lenient_rdf_xml: false,
+                    byte_accounting: ByteAccounting::Raw,
+                    subject_index: false,
+                    invalid_utf8_replacement: None,
+                    label_index: false,
+                    strict_iri_chars: false,
+                    dedup_imports: false,
+                    canonical_booleans: false,
+                    class_hierarchy: BTreeMap::new(),
+                    skip_empty_literals: false,
+                    reject_literal_types: false,
+                    require_absolute_iris: false,
+                    predicate_index: true,
                 },
             )
             .unwrap();
@@ -482,7 +972,10 @@ mod test {
         let buf = BufReader::new(data.as_slice());
         let mut reader = TripleReader::new(&DataFormat::RDFXml, buf);
         let mut storer = StoreEngine::new(storage).unwrap();
-        let count = storer.store_all(&mut reader).unwrap();
+        let count = storer
+            .store_all(&mut reader, Uint128::zero())
+            .unwrap()
+            .count;
 
         assert_eq!(count, Uint128::new(40u128));
     }
@@ -861,7 +1354,7 @@ mod test {
                 .iter()
                 .map(|v| {
                     let mut vars = ResolvedVariables::with_capacity(2);
-                    if let Some(val) = v.get(0) {
+                    if let Some(val) = v.first() {
                         vars.set(0, ResolvedVariable::Subject(Subject::Blank(val.clone())));
                     }
                     if let Some(val) = v.get(1) {
@@ -1072,7 +1565,7 @@ mod test {
 
         for case in cases {
             assert_eq!(
-                TriplePatternIterator::make_state_iter(&deps.storage, case.filters).count(),
+                TriplePatternIterator::make_state_iter(&deps.storage, false, case.filters).count(),
                 case.expects
             );
         }
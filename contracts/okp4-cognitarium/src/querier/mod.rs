@@ -1,8 +1,11 @@
+mod digest;
 mod engine;
 mod plan;
 mod plan_builder;
+mod resolver;
 mod variable;
 
+pub use digest::*;
 pub use engine::*;
-pub use plan::*;
 pub use plan_builder::*;
+pub use resolver::*;
@@ -6,7 +6,7 @@ use crate::querier::plan::{PatternValue, QueryNode, QueryPlan};
 use crate::rdf::expand_uri;
 use crate::state::{namespaces, Object, Predicate, Subject};
 use crate::{rdf, state};
-use cosmwasm_std::{StdError, StdResult, Storage};
+use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
 use std::collections::HashMap;
 
 pub struct PlanBuilder<'a> {
@@ -15,6 +15,10 @@ pub struct PlanBuilder<'a> {
     variables: Vec<String>,
     limit: Option<usize>,
     skip: Option<usize>,
+    full_iri_keys: bool,
+    max_iri_byte_size: Option<Uint128>,
+    canonical_booleans: bool,
+    normalize_iris: bool,
 }
 
 impl<'a> PlanBuilder<'a> {
@@ -25,9 +29,37 @@ impl<'a> PlanBuilder<'a> {
             variables: Vec::new(),
             skip: None,
             limit: None,
+            full_iri_keys: false,
+            max_iri_byte_size: None,
+            canonical_booleans: false,
+            normalize_iris: false,
         }
     }
 
+    /// Keys the nodes by full IRI, as done by the stores configured so.
+    pub fn with_full_iri_keys(mut self, full_iri_keys: bool) -> Self {
+        self.full_iri_keys = full_iri_keys;
+        self
+    }
+
+    /// Bounds the byte size of the IRIs once expanded, as done by the store limits.
+    pub fn with_max_iri_byte_size(mut self, max_iri_byte_size: Option<Uint128>) -> Self {
+        self.max_iri_byte_size = max_iri_byte_size;
+        self
+    }
+
+    /// Canonicalizes the `xsd:boolean` literals, as done by the stores configured so.
+    pub fn with_canonical_booleans(mut self, canonical_booleans: bool) -> Self {
+        self.canonical_booleans = canonical_booleans;
+        self
+    }
+
+    /// Normalizes the IRIs once expanded, as done by the stores configured so.
+    pub fn with_normalize_iris(mut self, normalize_iris: bool) -> Self {
+        self.normalize_iris = normalize_iris;
+        self
+    }
+
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
         self
@@ -147,28 +179,52 @@ impl<'a> PlanBuilder<'a> {
                 Literal::LanguageTaggedString { value, language } => {
                     state::Literal::I18NString { value, language }
                 }
-                Literal::TypedValue { value, datatype } => state::Literal::Typed {
-                    value,
-                    datatype: self.build_named_node(datatype)?,
+                Literal::TypedValue { value, datatype } => match self.expand_iri(datatype)? {
+                    iri if iri == rdf::XSD_STRING => state::Literal::Simple { value },
+                    iri if iri == rdf::XSD_BOOLEAN && self.canonical_booleans => {
+                        state::Literal::Typed {
+                            value: rdf::canonical_boolean(&value).to_string(),
+                            datatype: self.build_named_node(IRI::Full(iri))?,
+                        }
+                    }
+                    iri => state::Literal::Typed {
+                        value,
+                        datatype: self.build_named_node(IRI::Full(iri))?,
+                    },
                 },
             })),
         })
     }
 
-    fn build_named_node(&mut self, value: IRI) -> StdResult<state::Node> {
-        match value {
-            IRI::Prefixed(prefixed) => expand_uri(&prefixed, self.prefixes),
-            IRI::Full(full) => Ok(full),
+    fn expand_iri(&self, value: IRI) -> StdResult<String> {
+        let iri = match value {
+            IRI::Prefixed(prefixed) => expand_uri(&prefixed, self.prefixes)?,
+            IRI::Full(full) => full,
+        };
+        rdf::check_iri_byte_size(&iri, self.max_iri_byte_size)?;
+        if self.normalize_iris {
+            return Ok(rdf::normalize_iri(&iri).into_owned());
         }
-        .and_then(|iri| rdf::explode_iri(&iri))
-        .and_then(|(ns_key, v)| {
-            namespaces()
-                .load(self.storage, ns_key)
-                .map(|ns| state::Node {
-                    namespace: ns.key,
-                    value: v,
-                })
-        })
+        Ok(iri)
+    }
+
+    fn build_named_node(&mut self, value: IRI) -> StdResult<state::Node> {
+        self.expand_iri(value)
+            .and_then(|iri| {
+                if self.full_iri_keys {
+                    Ok((String::new(), iri))
+                } else {
+                    rdf::explode_iri(&iri)
+                }
+            })
+            .and_then(|(ns_key, v)| {
+                namespaces()
+                    .load(self.storage, ns_key)
+                    .map(|ns| state::Node {
+                        namespace: ns.key,
+                        value: v,
+                    })
+            })
     }
 
     fn resolve_variable(&mut self, v: String) -> usize {
@@ -293,7 +349,9 @@ mod test {
             ),
             (
                 IRI::Prefixed("okp5:resource".to_string()),
-                Err(StdError::generic_err("Prefix not found: okp5")),
+                Err(StdError::generic_err(
+                    "Unknown prefix 'okp5' in 'okp5:resource'",
+                )),
             ),
         ];
 
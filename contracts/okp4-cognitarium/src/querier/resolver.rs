@@ -0,0 +1,114 @@
+use crate::querier::triple_to_atom;
+use crate::rdf;
+use crate::state::{namespaces, Node, Triple};
+use cosmwasm_std::{StdError, StdResult, Storage};
+use std::collections::HashMap;
+
+/// Resolves the stored triples back into atoms, i.e. with their full IRIs, caching the values of
+/// the namespaces looked up so that each one is read once from the namespace table.
+///
+/// The resolved atoms own their IRIs and convert into [rio_api::model::Triple] borrowing them,
+/// e.g. to be written by a [rdf::TripleWriter].
+pub struct TripleResolver<'a> {
+    storage: &'a dyn Storage,
+    ns_cache: HashMap<u128, String>,
+}
+
+impl<'a> TripleResolver<'a> {
+    pub fn new(storage: &'a dyn Storage) -> Self {
+        Self {
+            storage,
+            ns_cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the value of the namespace with the given key.
+    pub fn resolve_ns(&mut self, ns_key: u128) -> StdResult<String> {
+        if let Some(ns) = self.ns_cache.get(&ns_key) {
+            return Ok(ns.clone());
+        }
+
+        let ns = namespaces().idx.key.item(self.storage, ns_key).and_then(
+            |maybe_ns| match maybe_ns {
+                Some(ns) => Ok(ns.1.value),
+                None => Err(StdError::not_found("Namespace")),
+            },
+        )?;
+
+        self.ns_cache.insert(ns_key, ns.clone());
+        Ok(ns)
+    }
+
+    /// Returns the full IRI of the given node.
+    pub fn resolve_node(&mut self, node: &Node) -> StdResult<String> {
+        node.as_iri(&mut |key| self.resolve_ns(key))
+    }
+
+    /// Returns the given triple as an atom.
+    pub fn resolve(&mut self, triple: &Triple) -> StdResult<rdf::Atom> {
+        triple_to_atom(triple, &mut |key| self.resolve_ns(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{DataFormat, StoreLimitsInput};
+    use crate::rdf::{TripleReader, TripleWriter};
+    use crate::state::{triples, Store, NAMESPACE_KEY_INCREMENT, STORE};
+    use crate::storer::StoreEngine;
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{Addr, Order, Uint128};
+    use std::io::BufReader;
+
+    #[test]
+    fn resolution_matches_original_iris() {
+        let data = r#"<http://a.org/s> <http://a.org/p> <http://b.org/o#frag> .
+<http://a.org/s> <http://b.org/q> _:b0 .
+_:b0 <http://a.org/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer> .
+_:b0 <http://b.org/q> "un"@fr .
+<http://c.org/s> <http://a.org/p> "x" .
+"#;
+        let mut storage = MockStorage::new();
+        STORE
+            .save(
+                &mut storage,
+                &Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into()),
+            )
+            .unwrap();
+        NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+        StoreEngine::new(&mut storage)
+            .unwrap()
+            .store_all(
+                &mut TripleReader::new(&DataFormat::NTriples, BufReader::new(data.as_bytes())),
+                Uint128::zero(),
+            )
+            .unwrap();
+
+        let mut resolver = TripleResolver::new(&storage);
+        let mut writer = TripleWriter::new(&DataFormat::NTriples, vec![]);
+        for res in triples().range(&storage, None, None, Order::Ascending) {
+            let atom = resolver.resolve(&res.unwrap().1).unwrap();
+            writer.write(&(&atom).into()).unwrap();
+        }
+        let written = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        let mut lines = written.lines().collect::<Vec<_>>();
+        lines.sort();
+        let mut expected = data.lines().collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(lines, expected);
+
+        assert_eq!(
+            resolver.resolve_node(&Node {
+                namespace: 0,
+                value: "s".to_string()
+            }),
+            Ok("http://a.org/s".to_string())
+        );
+        assert_eq!(
+            resolver.resolve_ns(42),
+            Err(StdError::not_found("Namespace"))
+        );
+    }
+}
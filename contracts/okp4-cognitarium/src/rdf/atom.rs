@@ -1,5 +1,5 @@
 use cosmwasm_std::StdError;
-use rio_api::model::{Literal, NamedNode, Triple};
+use rio_api::model::{BlankNode, Literal, NamedNode, Term, Triple};
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
@@ -55,6 +55,21 @@ pub struct Atom {
     pub value: Value,
 }
 
+impl Atom {
+    /// Returns the IRIs of the atom: its named nodes and the datatype of its literal if any.
+    pub fn iris(&self) -> Vec<&str> {
+        let mut iris = vec![self.property.0.as_str()];
+        if let Subject::NamedNode(iri) = &self.subject {
+            iris.push(iri);
+        }
+        match &self.value {
+            Value::NamedNode(iri) | Value::LiteralDatatype(_, iri) => iris.push(iri),
+            _ => {}
+        }
+        iris
+    }
+}
+
 impl fmt::Display for Atom {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "<{}> <{}> '{}'", self.subject, self.property, self.value)
@@ -65,26 +80,63 @@ impl<'a> From<&'a Atom> for Triple<'a> {
     fn from(atom: &'a Atom) -> Self {
         Triple {
             subject: match &atom.subject {
-                Subject::NamedNode(s) | Subject::BlankNode(s) => NamedNode { iri: s.as_str() },
-            }
-            .into(),
+                Subject::NamedNode(s) => NamedNode { iri: s.as_str() }.into(),
+                Subject::BlankNode(s) => BlankNode { id: s.as_str() }.into(),
+            },
             predicate: NamedNode {
                 iri: &atom.property.0,
             },
-            object: match &atom.value {
-                Value::NamedNode(s) | Value::BlankNode(s) => NamedNode { iri: s.as_str() }.into(),
-                Value::LiteralSimple(s) => Literal::Simple { value: s.as_str() }.into(),
-                Value::LiteralLang(s, l) => Literal::LanguageTaggedString {
-                    value: s,
-                    language: l,
+            object: (&atom.value).into(),
+        }
+    }
+}
+
+impl TryFrom<&Triple<'_>> for Atom {
+    type Error = StdError;
+
+    fn try_from(triple: &Triple<'_>) -> Result<Self, Self::Error> {
+        let unsupported = || StdError::generic_err("RDF-star triples can't be represented");
+        Ok(Atom {
+            subject: match triple.subject {
+                rio_api::model::Subject::NamedNode(node) => {
+                    Subject::NamedNode(node.iri.to_string())
+                }
+                rio_api::model::Subject::BlankNode(node) => Subject::BlankNode(node.id.to_string()),
+                rio_api::model::Subject::Triple(_) => Err(unsupported())?,
+            },
+            property: Property(triple.predicate.iri.to_string()),
+            value: match triple.object {
+                Term::NamedNode(node) => Value::NamedNode(node.iri.to_string()),
+                Term::BlankNode(node) => Value::BlankNode(node.id.to_string()),
+                Term::Literal(Literal::Simple { value }) => Value::LiteralSimple(value.to_string()),
+                Term::Literal(Literal::LanguageTaggedString { value, language }) => {
+                    Value::LiteralLang(value.to_string(), language.to_string())
                 }
-                .into(),
-                Value::LiteralDatatype(s, d) => Literal::Typed {
-                    value: s,
-                    datatype: NamedNode { iri: d },
+                Term::Literal(Literal::Typed { value, datatype }) => {
+                    Value::LiteralDatatype(value.to_string(), datatype.iri.to_string())
                 }
-                .into(),
+                Term::Triple(_) => Err(unsupported())?,
             },
+        })
+    }
+}
+
+impl<'a> From<&'a Value> for Term<'a> {
+    fn from(value: &'a Value) -> Self {
+        match value {
+            Value::NamedNode(s) => NamedNode { iri: s.as_str() }.into(),
+            Value::BlankNode(s) => BlankNode { id: s.as_str() }.into(),
+            Value::LiteralSimple(s) => Literal::Simple { value: s.as_str() }.into(),
+            Value::LiteralLang(s, l) => Literal::LanguageTaggedString {
+                value: s,
+                language: l,
+            }
+            .into(),
+            Value::LiteralDatatype(s, d) => Literal::Typed {
+                value: s,
+                datatype: NamedNode { iri: d },
+            }
+            .into(),
         }
     }
 }
@@ -624,7 +676,7 @@ mod tests {
                 bindings: &bindings,
                 prefixes: &prefixes,
                 expected: Err(StdError::generic_err(
-                    "Unsupported predicate node: BlankNode(\"_2\"). Expected URI",
+                    "Unsupported predicate node: _:_2. Expected URI",
                 )),
             },
         ];
@@ -0,0 +1,103 @@
+use crate::msg::ContainerKind;
+use crate::rdf::{Atom, Property, Subject, Value, RDF_TYPE};
+
+/// The namespace of the RDF vocabulary, the one of the container classes and membership
+/// properties.
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+impl ContainerKind {
+    /// Returns the IRI of the class of the container.
+    pub fn iri(&self) -> String {
+        let class = match self {
+            ContainerKind::Seq => "Seq",
+            ContainerKind::Bag => "Bag",
+            ContainerKind::Alt => "Alt",
+        };
+        format!("{RDF_NS}{class}")
+    }
+}
+
+/// Returns the IRI of the container membership property of the member at the given position,
+/// starting at 1, i.e. `rdf:_1`, `rdf:_2`...
+pub fn container_member(position: usize) -> String {
+    format!("{RDF_NS}_{position}")
+}
+
+/// Builds the triples of a container of the given kind holding the values in order, i.e. the
+/// typing of the container followed by one membership triple per value, ready to be written as
+/// data to insert.
+pub fn container_atoms(container: Subject, kind: ContainerKind, values: Vec<Value>) -> Vec<Atom> {
+    let mut atoms = Vec::with_capacity(values.len() + 1);
+    atoms.push(Atom {
+        subject: container.clone(),
+        property: Property(RDF_TYPE.to_string()),
+        value: Value::NamedNode(kind.iri()),
+    });
+    for (i, value) in values.into_iter().enumerate() {
+        atoms.push(Atom {
+            subject: container.clone(),
+            property: Property(container_member(i + 1)),
+            value,
+        });
+    }
+    atoms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::DataFormat;
+    use crate::rdf::TripleWriter;
+
+    #[test]
+    fn proper_container_atoms() {
+        let values = || {
+            vec![
+                Value::NamedNode("http://a.org/o".to_string()),
+                Value::LiteralSimple("x".to_string()),
+                Value::BlankNode("b".to_string()),
+            ]
+        };
+        let cases = vec![
+            (ContainerKind::Seq, "Seq"),
+            (ContainerKind::Bag, "Bag"),
+            (ContainerKind::Alt, "Alt"),
+        ];
+
+        for (kind, class) in cases {
+            let atoms = container_atoms(Subject::BlankNode("c".to_string()), kind, values());
+
+            let mut writer = TripleWriter::new(&DataFormat::NTriples, vec![]);
+            for atom in &atoms {
+                writer.write(&atom.into()).unwrap();
+            }
+            assert_eq!(
+                String::from_utf8(writer.finish().unwrap()).unwrap(),
+                format!(
+                    r#"_:c <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/1999/02/22-rdf-syntax-ns#{class}> .
+_:c <http://www.w3.org/1999/02/22-rdf-syntax-ns#_1> <http://a.org/o> .
+_:c <http://www.w3.org/1999/02/22-rdf-syntax-ns#_2> "x" .
+_:c <http://www.w3.org/1999/02/22-rdf-syntax-ns#_3> _:b .
+"#
+                )
+            );
+        }
+
+        assert_eq!(
+            container_atoms(
+                Subject::NamedNode("http://a.org/c".to_string()),
+                ContainerKind::Bag,
+                vec![]
+            ),
+            vec![Atom {
+                subject: Subject::NamedNode("http://a.org/c".to_string()),
+                property: Property(RDF_TYPE.to_string()),
+                value: Value::NamedNode(ContainerKind::Bag.iri()),
+            }]
+        );
+        assert_eq!(
+            container_member(12),
+            "http://www.w3.org/1999/02/22-rdf-syntax-ns#_12"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::msg;
-use crate::rdf::{expand_uri, Property, Subject, Value};
-use cosmwasm_std::StdError;
+use crate::rdf::{compact_uri, expand_uri, render_iri, Atom, Property, Subject, Value, XSD_STRING};
+use cosmwasm_std::{StdError, StdResult};
+use oxilangtag::LanguageTag;
 use std::collections::HashMap;
 
 impl TryFrom<(msg::Value, &HashMap<String, String>)> for Subject {
@@ -18,7 +19,8 @@ impl TryFrom<(msg::Value, &HashMap<String, String>)> for Subject {
             } => Ok(Subject::NamedNode(expand_uri(&curie, prefixes)?)),
             msg::Value::BlankNode { value: id } => Ok(Subject::BlankNode(id)),
             _ => Err(StdError::generic_err(format!(
-                "Unsupported subject value: {value:?}. Expected URI or BlankNode",
+                "Unsupported subject value: {}. Expected URI or BlankNode",
+                render_value(&value, prefixes)
             ))),
         }
     }
@@ -38,7 +40,8 @@ impl TryFrom<(msg::Value, &HashMap<String, String>)> for Property {
                 value: msg::IRI::Prefixed(curie),
             } => Ok(Property(expand_uri(&curie, prefixes)?)),
             _ => Err(StdError::generic_err(format!(
-                "Unsupported predicate value: {value:?}. Expected URI"
+                "Unsupported predicate value: {}. Expected URI",
+                render_value(&value, prefixes)
             ))),
         }
     }
@@ -71,15 +74,16 @@ impl TryFrom<(msg::Value, &HashMap<String, String>)> for Value {
                 value,
                 lang: None,
                 datatype: Some(msg::IRI::Full(uri)),
-            } => Ok(Value::LiteralDatatype(value, uri)),
+            } => Ok(Value::typed_literal(value, uri)),
             msg::Value::Literal {
                 value,
                 lang: None,
                 datatype: Some(msg::IRI::Prefixed(curie)),
-            } => Ok(Value::LiteralDatatype(value, expand_uri(&curie, prefixes)?)),
+            } => Ok(Value::typed_literal(value, expand_uri(&curie, prefixes)?)),
             msg::Value::BlankNode { value } => Ok(Value::BlankNode(value)),
             _ => Err(StdError::generic_err(format!(
-                "Unsupported object value: {value:?}. Expected URI, BlankNode or Literal"
+                "Unsupported object value: {}. Expected URI, BlankNode or Literal",
+                render_value(&value, prefixes)
             )))?,
         }
     }
@@ -112,8 +116,8 @@ impl TryFrom<(msg::Node, &HashMap<String, String>)> for Property {
             msg::Node::NamedNode(msg::IRI::Prefixed(curie)) => {
                 Ok(Property(expand_uri(&curie, prefixes)?))
             }
-            _ => Err(StdError::generic_err(format!(
-                "Unsupported predicate node: {node:?}. Expected URI"
+            msg::Node::BlankNode(id) => Err(StdError::generic_err(format!(
+                "Unsupported predicate node: _:{id}. Expected URI"
             ))),
         }
     }
@@ -149,18 +153,145 @@ impl TryFrom<(msg::Literal, &HashMap<String, String>)> for Value {
             msg::Literal::TypedValue {
                 value,
                 datatype: msg::IRI::Full(uri),
-            } => Ok(Value::LiteralDatatype(value, uri)),
+            } => Ok(Value::typed_literal(value, uri)),
             msg::Literal::TypedValue {
                 value,
                 datatype: msg::IRI::Prefixed(prefix),
-            } => Ok(Value::LiteralDatatype(
-                value,
-                expand_uri(&prefix, prefixes)?,
-            )),
+            } => Ok(Value::typed_literal(value, expand_uri(&prefix, prefixes)?)),
+        }
+    }
+}
+
+/// Renders a value for a user-facing message in a Turtle-like syntax, its IRIs being compacted into
+/// CURIEs with the given prefixes where possible, see [render_iri].
+fn render_value(value: &msg::Value, prefixes: &HashMap<String, String>) -> String {
+    let iri = |iri: &msg::IRI| match iri {
+        msg::IRI::Full(full) => render_iri(full, prefixes),
+        msg::IRI::Prefixed(curie) => curie.clone(),
+    };
+    match value {
+        msg::Value::URI { value } => iri(value),
+        msg::Value::BlankNode { value } => format!("_:{value}"),
+        msg::Value::Literal {
+            value,
+            lang,
+            datatype,
+        } => {
+            let mut rendered = format!("{value:?}");
+            if let Some(lang) = lang {
+                rendered.push_str(&format!("@{lang}"));
+            }
+            if let Some(datatype) = datatype {
+                rendered.push_str(&format!("^^{}", iri(datatype)));
+            }
+            rendered
+        }
+    }
+}
+
+impl Value {
+    /// Makes a typed literal value, collapsing the `xsd:string` ones into simple literals.
+    fn typed_literal(value: String, datatype: String) -> Self {
+        if datatype == XSD_STRING {
+            Value::LiteralSimple(value)
+        } else {
+            Value::LiteralDatatype(value, datatype)
+        }
+    }
+}
+
+impl TryFrom<(msg::Triple, &HashMap<String, String>)> for Atom {
+    type Error = StdError;
+
+    fn try_from(
+        (triple, prefixes): (msg::Triple, &HashMap<String, String>),
+    ) -> Result<Self, Self::Error> {
+        let value = Value::try_from((triple.object, prefixes))?;
+        value.check_language_tag()?;
+
+        Ok(Atom {
+            subject: (triple.subject, prefixes).try_into()?,
+            property: (triple.predicate, prefixes).try_into()?,
+            value,
+        })
+    }
+}
+
+impl Atom {
+    /// Maps the atom back to the values of a [msg::Triple], the IRIs being compacted into CURIEs
+    /// with the given prefixes where possible.
+    pub fn to_msg_triple(&self, prefixes: &HashMap<String, String>) -> msg::Triple {
+        let iri = |uri: &str| match compact_uri(uri, prefixes) {
+            Some(curie) => msg::IRI::Prefixed(curie),
+            None => msg::IRI::Full(uri.to_string()),
+        };
+        let literal =
+            |value: &str, lang: Option<&str>, datatype: Option<&str>| msg::Value::Literal {
+                value: value.to_string(),
+                lang: lang.map(ToString::to_string),
+                datatype: datatype.map(iri),
+            };
+
+        msg::Triple {
+            subject: match &self.subject {
+                Subject::NamedNode(uri) => msg::Value::URI { value: iri(uri) },
+                Subject::BlankNode(id) => msg::Value::BlankNode { value: id.clone() },
+            },
+            predicate: msg::Value::URI {
+                value: iri(&self.property.0),
+            },
+            object: match &self.value {
+                Value::NamedNode(uri) => msg::Value::URI { value: iri(uri) },
+                Value::BlankNode(id) => msg::Value::BlankNode { value: id.clone() },
+                Value::LiteralSimple(value) => literal(value, None, None),
+                Value::LiteralLang(value, lang) => literal(value, Some(lang), None),
+                Value::LiteralDatatype(value, datatype) => literal(value, None, Some(datatype)),
+            },
         }
     }
 }
 
+impl Value {
+    /// Checks the language tag of the value, if a language tagged literal, is well-formed as per
+    /// [BCP 47](https://www.rfc-editor.org/rfc/rfc5646#section-2.2.9).
+    pub fn check_language_tag(&self) -> StdResult<()> {
+        match self {
+            Value::LiteralLang(_, lang) => LanguageTag::parse(lang.as_str())
+                .map(|_| ())
+                .map_err(|e| StdError::generic_err(format!("Invalid language tag '{lang}': {e}"))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Serializes the atoms as a JSON array of [msg::Triple], the IRIs being compacted into CURIEs
+/// with the given prefixes where possible.
+///
+/// With `with_prefixes`, the array is wrapped in a [msg::JsonTriples] object along with the
+/// prefixes, for the output to be interpreted without knowing them.
+pub fn write_json_triples(
+    atoms: &[Atom],
+    prefixes: &HashMap<String, String>,
+    with_prefixes: bool,
+) -> StdResult<Vec<u8>> {
+    let triples = atoms
+        .iter()
+        .map(|atom| atom.to_msg_triple(prefixes))
+        .collect::<Vec<_>>();
+
+    if with_prefixes {
+        cosmwasm_std::to_vec(&msg::JsonTriples {
+            prefixes: prefixes
+                .iter()
+                .map(|(prefix, namespace)| (prefix.clone(), namespace.clone()))
+                .collect(),
+            triples,
+        })
+    } else {
+        cosmwasm_std::to_vec(&triples)
+    }
+}
+
 #[derive(Default)]
 pub struct PrefixMap(HashMap<String, String>);
 impl PrefixMap {
@@ -233,10 +364,10 @@ mod tests {
                     lang: None,
                     datatype: None,
                 },
-                 &PrefixMap::default().into_inner(),
+                &PrefixMap::default().into_inner(),
             )),
             Err(StdError::generic_err(
-                "Unsupported subject value: Literal { value: \"rdf\", lang: None, datatype: None }. Expected URI or BlankNode"
+                "Unsupported subject value: \"rdf\". Expected URI or BlankNode"
             ))
         );
     }
@@ -281,7 +412,7 @@ mod tests {
                 &PrefixMap::default().into_inner(),
             )),
             Err(StdError::generic_err(
-                "Unsupported predicate value: BlankNode { value: \"blank\" }. Expected URI"
+                "Unsupported predicate value: _:blank. Expected URI"
             ))
         );
     }
@@ -378,6 +509,22 @@ mod tests {
                 "http://www.w3.org/1999/02/22-rdf-syntax-ns#".to_string()
             ))
         );
+        assert_eq!(
+            (
+                msg::Value::Literal {
+                    value: "foo".to_string(),
+                    lang: None,
+                    datatype: Some(msg::IRI::Prefixed("xsd:string".to_string())),
+                },
+                &<PrefixMap>::from(vec![msg::Prefix {
+                    prefix: "xsd".to_string(),
+                    namespace: "http://www.w3.org/2001/XMLSchema#".to_string(),
+                }])
+                .into_inner(),
+            )
+                .try_into(),
+            Ok(Value::LiteralSimple("foo".to_string()))
+        );
         assert_eq!(
             (
                 msg::Value::BlankNode {
@@ -400,8 +547,233 @@ mod tests {
                 &PrefixMap::default().into_inner(),
             )),
             Err(StdError::generic_err(
-                "Unsupported object value: Literal { value: \"blank\", lang: Some(\"en\"), datatype: Some(Full(\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\")) }. Expected URI, BlankNode or Literal"
+                "Unsupported object value: \"blank\"@en^^<http://www.w3.org/1999/02/22-rdf-syntax-ns#>. Expected URI, BlankNode or Literal"
             ))
         );
     }
+
+    #[test]
+    fn errors_render_curies() {
+        let prefixes = <PrefixMap>::from(vec![msg::Prefix {
+            prefix: "xsd".to_string(),
+            namespace: "http://www.w3.org/2001/XMLSchema#".to_string(),
+        }])
+        .into_inner();
+        let literal = || msg::Value::Literal {
+            value: "1".to_string(),
+            lang: None,
+            datatype: Some(msg::IRI::Full(
+                "http://www.w3.org/2001/XMLSchema#integer".to_string(),
+            )),
+        };
+
+        for (prefixes, rendered) in [
+            (&prefixes, "\"1\"^^xsd:integer"),
+            (
+                &HashMap::new(),
+                "\"1\"^^<http://www.w3.org/2001/XMLSchema#integer>",
+            ),
+        ] {
+            assert_eq!(
+                Property::try_from((literal(), prefixes)),
+                Err(StdError::generic_err(format!(
+                    "Unsupported predicate value: {rendered}. Expected URI"
+                )))
+            );
+            assert_eq!(
+                Subject::try_from((literal(), prefixes)),
+                Err(StdError::generic_err(format!(
+                    "Unsupported subject value: {rendered}. Expected URI or BlankNode"
+                )))
+            );
+        }
+        assert_eq!(
+            render_iri("http://example.org/a", &prefixes),
+            "<http://example.org/a>"
+        );
+    }
+
+    #[test]
+    fn try_from_unknown_prefix() {
+        let prefixes = &PrefixMap::default().into_inner();
+        let err = || StdError::generic_err("Unknown prefix 'foo' in 'foo:bar'");
+        let uri = || msg::Value::URI {
+            value: msg::IRI::Prefixed("foo:bar".to_string()),
+        };
+
+        assert_eq!(Subject::try_from((uri(), prefixes)), Err(err()));
+        assert_eq!(Property::try_from((uri(), prefixes)), Err(err()));
+        assert_eq!(Value::try_from((uri(), prefixes)), Err(err()));
+        assert_eq!(
+            Value::try_from((
+                msg::Value::Literal {
+                    value: "value".to_string(),
+                    lang: None,
+                    datatype: Some(msg::IRI::Prefixed("foo:bar".to_string())),
+                },
+                prefixes
+            )),
+            Err(err())
+        );
+        assert_eq!(
+            Value::try_from((
+                msg::Node::NamedNode(msg::IRI::Prefixed("foo:bar".to_string())),
+                prefixes
+            )),
+            Err(err())
+        );
+        assert_eq!(
+            Value::try_from((
+                msg::Literal::TypedValue {
+                    value: "value".to_string(),
+                    datatype: msg::IRI::Prefixed("foo:bar".to_string()),
+                },
+                prefixes
+            )),
+            Err(err())
+        );
+    }
+
+    #[test]
+    fn json_triples_round_trip() {
+        let prefixes = &<PrefixMap>::from(vec![
+            msg::Prefix {
+                prefix: "ex".to_string(),
+                namespace: "http://example.com/".to_string(),
+            },
+            msg::Prefix {
+                prefix: "xsd".to_string(),
+                namespace: "http://www.w3.org/2001/XMLSchema#".to_string(),
+            },
+        ])
+        .into_inner();
+        let atom = |subject: Subject, value: Value| Atom {
+            subject,
+            property: Property("http://example.com/p".to_string()),
+            value,
+        };
+        let atoms = vec![
+            atom(
+                Subject::NamedNode("http://example.com/s".to_string()),
+                Value::NamedNode("http://example.org/o".to_string()),
+            ),
+            atom(
+                Subject::BlankNode("b0".to_string()),
+                Value::BlankNode("b1".to_string()),
+            ),
+            atom(
+                Subject::NamedNode("http://example.com/s".to_string()),
+                Value::LiteralSimple("foo".to_string()),
+            ),
+            atom(
+                Subject::NamedNode("http://example.com/s".to_string()),
+                Value::LiteralLang("foo".to_string(), "en".to_string()),
+            ),
+            atom(
+                Subject::NamedNode("http://example.com/s".to_string()),
+                Value::LiteralDatatype(
+                    "42".to_string(),
+                    "http://www.w3.org/2001/XMLSchema#integer".to_string(),
+                ),
+            ),
+        ];
+
+        let json = write_json_triples(&atoms, prefixes, false).unwrap();
+        assert!(String::from_utf8(json.clone()).unwrap().starts_with(
+            r#"[{"subject":{"type":"uri","value":{"prefixed":"ex:s"}},"predicate":{"type":"uri","value":{"prefixed":"ex:p"}},"object":{"type":"uri","value":{"full":"http://example.org/o"}}}"#
+        ));
+
+        let triples: Vec<msg::Triple> = cosmwasm_std::from_slice(&json).unwrap();
+        assert_eq!(
+            triples
+                .into_iter()
+                .map(|triple| -> StdResult<Atom> {
+                    Ok(Atom {
+                        subject: (triple.subject, prefixes).try_into()?,
+                        property: (triple.predicate, prefixes).try_into()?,
+                        value: (triple.object, prefixes).try_into()?,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>(),
+            Ok(atoms)
+        );
+    }
+
+    #[test]
+    fn check_language_tag() {
+        for lang in [
+            "en",
+            "en-US",
+            "zh-Hant",
+            "sr-Latn-RS",
+            "es-419",
+            "x-private",
+        ] {
+            assert_eq!(
+                Value::LiteralLang("a".to_string(), lang.to_string()).check_language_tag(),
+                Ok(())
+            );
+        }
+        for lang in ["", "en_US", "123", "abcdefghi", "en-", "en-a"] {
+            assert!(Value::LiteralLang("a".to_string(), lang.to_string())
+                .check_language_tag()
+                .is_err());
+        }
+        assert_eq!(
+            Value::LiteralSimple("en_US".to_string()).check_language_tag(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn json_triples_with_prefixes() {
+        let prefixes = &<PrefixMap>::from(vec![
+            msg::Prefix {
+                prefix: "ex".to_string(),
+                namespace: "http://example.com/".to_string(),
+            },
+            msg::Prefix {
+                prefix: "xsd".to_string(),
+                namespace: "http://www.w3.org/2001/XMLSchema#".to_string(),
+            },
+        ])
+        .into_inner();
+        let atoms = vec![Atom {
+            subject: Subject::NamedNode("http://example.com/s".to_string()),
+            property: Property("http://example.com/p".to_string()),
+            value: Value::LiteralDatatype(
+                "42".to_string(),
+                "http://www.w3.org/2001/XMLSchema#integer".to_string(),
+            ),
+        }];
+
+        let json = write_json_triples(&atoms, prefixes, true).unwrap();
+        assert!(String::from_utf8(json.clone()).unwrap().starts_with(
+            r#"{"prefixes":{"ex":"http://example.com/","xsd":"http://www.w3.org/2001/XMLSchema#"},"triples":[{"subject":{"type":"uri","value":{"prefixed":"ex:s"}}"#
+        ));
+
+        let document: msg::JsonTriples = cosmwasm_std::from_slice(&json).unwrap();
+        let read_prefixes = document.prefixes.into_iter().collect::<HashMap<_, _>>();
+        assert_eq!(&read_prefixes, prefixes);
+        assert_eq!(
+            document
+                .triples
+                .into_iter()
+                .map(|triple| -> StdResult<Atom> {
+                    Ok(Atom {
+                        subject: (triple.subject, &read_prefixes).try_into()?,
+                        property: (triple.predicate, &read_prefixes).try_into()?,
+                        value: (triple.object, &read_prefixes).try_into()?,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>(),
+            Ok(atoms)
+        );
+
+        let json = write_json_triples(&[], &HashMap::new(), true).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            r#"{"prefixes":{},"triples":[]}"#
+        );
+    }
 }
@@ -1,9 +1,11 @@
 mod atom;
+mod container;
 mod mapper;
 mod serde;
 mod uri;
 
 pub use self::atom::*;
+pub use self::container::*;
 pub use self::mapper::*;
 pub use self::serde::*;
 pub use self::uri::*;
@@ -1,86 +1,800 @@
-use crate::msg::DataFormat;
+use crate::error::WriteError;
+use crate::msg::{self, DataFormat, TurtleStyle};
+use crate::rdf::{
+    canonical_boolean, deskolemize_iri, skolem_iri, write_json_triples, AbsoluteIri, Atom,
+    PrefixMap, XSD_BOOLEAN,
+};
+use cosmwasm_std::{StdError, StdResult};
 use rio_api::formatter::TriplesFormatter;
-use rio_api::model::{Quad, Triple};
+use rio_api::model::{BlankNode, GraphName, Literal, NamedNode, Quad, Subject, Term, Triple};
 use rio_api::parser::{QuadsParser, TriplesParser};
 use rio_turtle::{
     NQuadsFormatter, NQuadsParser, NTriplesFormatter, NTriplesParser, TurtleError, TurtleFormatter,
     TurtleParser,
 };
 use rio_xml::{RdfXmlError, RdfXmlFormatter, RdfXmlParser};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
 use std::io::{self, BufRead};
+use std::ops::Range;
+use std::rc::Rc;
 
 pub struct TripleReader<R: BufRead> {
     parser: TriplesParserKind<R>,
+    default_graph: Option<String>,
+    xml_problems: Option<Vec<String>>,
+    /// The unsupported encoding the data has been found in by its byte order mark, if any.
+    unsupported_encoding: Option<&'static str>,
+}
+
+/// The byte order marks of the encodings data may be detected in, only UTF-8 being supported.
+const BYTE_ORDER_MARKS: [(&[u8], &str); 3] = [
+    (&[0xEF, 0xBB, 0xBF], "UTF-8"),
+    (&[0xFE, 0xFF], "UTF-16BE"),
+    (&[0xFF, 0xFE], "UTF-16LE"),
+];
+
+/// Consumes the UTF-8 byte order mark the data may start with, returning the encoding of the
+/// byte order mark it starts with if not UTF-8.
+fn skip_byte_order_mark<R: BufRead>(src: &mut R) -> Option<&'static str> {
+    // A failing read is left to be reported by the parser.
+    let buf = src.fill_buf().ok()?;
+    let (bom, encoding) = BYTE_ORDER_MARKS
+        .iter()
+        .find(|(bom, _)| buf.starts_with(bom))?;
+    if *encoding != "UTF-8" {
+        return Some(encoding);
+    }
+    src.consume(bom.len());
+    None
 }
 
 pub struct TripleWriter<W: std::io::Write> {
     writer: TriplesWriterKind<W>,
+    base: Option<String>,
+    blank_node_labels: Option<BlankNodeLabels>,
+    skolem_base: Option<String>,
+    turtle_prefixes: Option<TurtlePrefixes>,
+    /// The prefixes the IRIs of the JSON triples are compacted with, declared along the triples.
+    json_prefixes: Option<HashMap<String, String>>,
+}
+
+/// The prefixes the IRIs of the triples written in Turtle are compacted with, only the ones
+/// actually used being declared, ahead of the triples which are buffered in the meantime.
+struct TurtlePrefixes {
+    /// The namespaces by prefix, the longest ones first so that the most specific one is used.
+    namespaces: Vec<(String, String)>,
+    used: BTreeMap<String, String>,
+    /// The triples written flat, the grouped ones being buffered by their [GroupedTriples].
+    lines: Vec<String>,
+}
+
+impl TurtlePrefixes {
+    fn new(prefixes: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut namespaces: Vec<_> = prefixes.into_iter().collect();
+        namespaces.sort_by(|(p1, ns1), (p2, ns2)| ns2.len().cmp(&ns1.len()).then(p1.cmp(p2)));
+        Self {
+            namespaces,
+            used: BTreeMap::new(),
+            lines: vec![],
+        }
+    }
+
+    /// Returns the prefixed name of the IRI, if it has a namespace whose remainder is a local
+    /// name not needing to be escaped.
+    fn compact(&mut self, iri: &str) -> Option<String> {
+        let (prefix, namespace, local) =
+            self.namespaces.iter().find_map(|(prefix, namespace)| {
+                iri.strip_prefix(namespace.as_str())
+                    .filter(|local| is_plain_local_name(local))
+                    .map(|local| (prefix, namespace, local))
+            })?;
+        self.used.insert(prefix.clone(), namespace.clone());
+        Some(format!("{prefix}:{local}"))
+    }
+
+    fn named_node(&mut self, node: NamedNode<'_>) -> String {
+        self.compact(node.iri).unwrap_or_else(|| node.to_string())
+    }
+
+    /// Returns the formatted subject, predicate and object of the triple.
+    fn format(&mut self, triple: &Triple<'_>) -> (String, String, String) {
+        let subject = match triple.subject {
+            Subject::NamedNode(node) => self.named_node(node),
+            subject => subject.to_string(),
+        };
+        let predicate = self.named_node(triple.predicate);
+        let object = match triple.object {
+            Term::NamedNode(node) => self.named_node(node),
+            Term::Literal(Literal::Typed { value, datatype }) => format!(
+                "{}^^{}",
+                Literal::Simple { value },
+                self.named_node(datatype)
+            ),
+            object => object.to_string(),
+        };
+        (subject, predicate, object)
+    }
+
+    fn write_declarations<W: io::Write>(&self, dst: &mut W) -> io::Result<()> {
+        for (prefix, namespace) in &self.used {
+            writeln!(dst, "@prefix {prefix}: <{namespace}> .")?;
+        }
+        Ok(())
+    }
+}
+
+/// Tells whether the local part of a prefixed name can be written as is, conservatively only
+/// allowing the characters never needing to be escaped in Turtle.
+fn is_plain_local_name(local: &str) -> bool {
+    !local.starts_with('-')
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// The deterministic labels given to the blank nodes, in the order they are first written.
+#[derive(Default)]
+struct BlankNodeLabels(BTreeMap<String, String>);
+
+impl BlankNodeLabels {
+    fn assign(&mut self, id: &str) {
+        if !self.0.contains_key(id) {
+            let label = format!("b{}", self.0.len());
+            self.0.insert(id.to_string(), label);
+        }
+    }
+
+    fn relabel<'a>(&'a self, triple: &Triple<'a>) -> Triple<'a> {
+        let relabel = |node: BlankNode<'a>| BlankNode {
+            id: self.0.get(node.id).map_or(node.id, String::as_str),
+        };
+        Triple {
+            subject: match triple.subject {
+                Subject::BlankNode(node) => relabel(node).into(),
+                subject => subject,
+            },
+            predicate: triple.predicate,
+            object: match triple.object {
+                Term::BlankNode(node) => relabel(node).into(),
+                object => object,
+            },
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
 pub enum TriplesParserKind<R: BufRead> {
     NTriples(NTriplesParser<R>),
     Turtle(TurtleParser<R>),
-    RdfXml(RdfXmlParser<R>),
+    RdfXml(RdfXmlParser<LastByteReader<R>>, Rc<Cell<Option<u8>>>),
     NQuads(NQuadsParser<R>),
+    /// Parses consecutive segments of data, each in its own format.
+    Multipart(Vec<TriplesParserKind<R>>),
+    Json(JsonTriplesParser<R>),
+}
+
+/// Parses a JSON array of [msg::Triple], or a [msg::JsonTriples] document carrying the prefixes
+/// of its CURIEs, the data being read and mapped to atoms at once on the first parsing.
+pub struct JsonTriplesParser<R: BufRead> {
+    /// The data, until parsed.
+    src: Option<R>,
+    /// The prefixes the CURIEs are expanded with, the ones of the document taking precedence.
+    prefixes: HashMap<String, String>,
+    atoms: Vec<Atom>,
+    /// The index of the next atom to give.
+    position: usize,
+}
+
+impl<R: BufRead> JsonTriplesParser<R> {
+    fn new(src: R) -> Self {
+        Self {
+            src: Some(src),
+            prefixes: HashMap::new(),
+            atoms: vec![],
+            position: 0,
+        }
+    }
+
+    fn parse(&mut self) -> StdResult<()> {
+        let Some(mut src) = self.src.take() else {
+            return Ok(());
+        };
+        let mut data = vec![];
+        src.read_to_end(&mut data)
+            .map_err(|e| StdError::parse_err("JSON triples", e.to_string()))?;
+
+        let triples: Vec<msg::Triple> = match data.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') => {
+                let document: msg::JsonTriples = cosmwasm_std::from_slice(&data)?;
+                self.prefixes.extend(document.prefixes);
+                document.triples
+            }
+            _ => cosmwasm_std::from_slice(&data)?,
+        };
+        self.atoms = triples
+            .into_iter()
+            .map(|triple| Atom::try_from((triple, &self.prefixes)))
+            .collect::<StdResult<_>>()?;
+        Ok(())
+    }
 }
 
 pub enum TriplesWriterKind<W: std::io::Write> {
     NTriples(NTriplesFormatter<W>),
-    Turtle(TurtleFormatter<W>),
-    RdfXml(io::Result<RdfXmlFormatter<W>>),
+    Turtle(io::Result<TurtleFormatter<W>>),
+    TurtleFlat(W),
+    TurtleGrouped(W, GroupedTriples),
+    RdfXml(io::Result<RdfXmlFormatter<XmlBaseWriter<W>>>),
     NQuads(NQuadsFormatter<W>),
+    /// The triples being buffered to be written as JSON triples once all written, see
+    /// [write_json_triples].
+    Json(W, Vec<Atom>),
+}
+
+/// The objects of a subject grouped by predicate, in the order they first appear.
+type PredicateObjects = Vec<(String, Vec<String>)>;
+
+/// The triples buffered to be written grouped by subject, the terms being kept formatted.
+#[derive(Default)]
+pub struct GroupedTriples {
+    index: BTreeMap<String, usize>,
+    subjects: Vec<(String, PredicateObjects)>,
+}
+
+impl GroupedTriples {
+    fn push(&mut self, triple: &Triple<'_>) {
+        self.push_formatted(
+            triple.subject.to_string(),
+            triple.predicate.to_string(),
+            triple.object.to_string(),
+        );
+    }
+
+    fn push_formatted(&mut self, subject: String, predicate: String, object: String) {
+        let i = match self.index.get(&subject) {
+            Some(i) => *i,
+            None => {
+                self.index.insert(subject.clone(), self.subjects.len());
+                self.subjects.push((subject, vec![]));
+                self.subjects.len() - 1
+            }
+        };
+
+        let predicates = &mut self.subjects[i].1;
+        match predicates.iter_mut().find(|(p, _)| *p == predicate) {
+            Some((_, objects)) => objects.push(object),
+            None => predicates.push((predicate, vec![object])),
+        }
+    }
+
+    fn write_to<W: io::Write>(&self, dst: &mut W) -> io::Result<()> {
+        for (subject, predicates) in &self.subjects {
+            write!(dst, "{subject}")?;
+            for (i, (predicate, objects)) in predicates.iter().enumerate() {
+                if i > 0 {
+                    write!(dst, " ;\n\t")?;
+                } else {
+                    write!(dst, " ")?;
+                }
+                write!(dst, "{predicate} {}", objects.join(" , "))?;
+            }
+            writeln!(dst, " .")?;
+        }
+        Ok(())
+    }
 }
 
 impl<R: BufRead> TripleReader<R> {
-    pub fn new(format: &DataFormat, src: R) -> Self {
+    /// Creates a reader of the triples serialized in the given format, the data being expected in
+    /// UTF-8, with or without a byte order mark.
+    pub fn new(format: &DataFormat, mut src: R) -> Self {
+        let unsupported_encoding = skip_byte_order_mark(&mut src);
         TripleReader {
             parser: match format {
-                DataFormat::RDFXml => TriplesParserKind::RdfXml(RdfXmlParser::new(src, None)),
+                DataFormat::RDFXml => {
+                    let last_byte = Rc::new(Cell::new(None));
+                    TriplesParserKind::RdfXml(
+                        RdfXmlParser::new(
+                            LastByteReader {
+                                inner: src,
+                                last_byte: last_byte.clone(),
+                            },
+                            None,
+                        ),
+                        last_byte,
+                    )
+                }
                 DataFormat::Turtle => TriplesParserKind::Turtle(TurtleParser::new(src, None)),
                 DataFormat::NTriples => TriplesParserKind::NTriples(NTriplesParser::new(src)),
                 DataFormat::NQuads => TriplesParserKind::NQuads(NQuadsParser::new(src)),
+                DataFormat::JsonTriples => TriplesParserKind::Json(JsonTriplesParser::new(src)),
             },
+            default_graph: None,
+            xml_problems: None,
+            unsupported_encoding,
         }
     }
 
+    /// Sets the IRI of the graph assigned to the triples read without an explicit graph name,
+    /// i.e. all the triples of the formats not supporting graphs and the N-Quads lacking one.
+    pub fn with_default_graph(mut self, iri: String) -> Self {
+        self.default_graph = Some(iri);
+        self
+    }
+
+    /// Sets the prefixes the CURIEs of the JSON triples are expanded with, the ones declared by
+    /// the data taking precedence. The other formats are left unchanged.
+    pub fn with_prefixes(mut self, prefixes: PrefixMap) -> Self {
+        self.parser.set_prefixes(&prefixes.into_inner());
+        self
+    }
+
+    /// Skips, instead of failing on them, the RDF/XML properties whose text content holds entity
+    /// references that can't be resolved, e.g. undefined entities, the problems being then reported by [TripleReader::problems]. The other
+    /// XML issues remain fatal, see [is_recoverable_xml_error].
+    pub fn with_lenient_xml(mut self) -> Self {
+        self.xml_problems = Some(Vec::new());
+        self
+    }
+
+    /// Returns the recoverable problems met while reading, in the order they have been met.
+    pub fn problems(&self) -> &[String] {
+        self.xml_problems.as_deref().unwrap_or_default()
+    }
+
     pub fn read_all<E, UF>(&mut self, mut use_fn: UF) -> Result<(), E>
     where
         UF: FnMut(Triple<'_>) -> Result<(), E>,
-        E: From<TurtleError> + From<RdfXmlError>,
+        E: From<TurtleError> + From<RdfXmlError> + From<StdError>,
+    {
+        self.read_all_quads(|triple, _| use_fn(triple))
+    }
+
+    /// Reads all the triples along with the name of the graph they belong to, being either the
+    /// one explicitly stated in N-Quads or the default graph if any.
+    pub fn read_all_quads<E, UF>(&mut self, mut use_fn: UF) -> Result<(), E>
+    where
+        UF: FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+        E: From<TurtleError> + From<RdfXmlError> + From<StdError>,
+    {
+        self.read_quads_while(|triple, graph_name| use_fn(triple, graph_name).map(|_| true))
+            .map(|_| ())
+    }
+
+    /// Reads the triples along with the name of the graph they belong to, as long as the given
+    /// function returns `true`. Returns whether all the triples have been read.
+    ///
+    /// The data being parsed statement by statement, the reading stops at the end of the statement
+    /// of the last triple used, the remaining triples of this statement being skipped.
+    pub fn read_quads_while<E, UF>(&mut self, mut use_fn: UF) -> Result<bool, E>
+    where
+        UF: FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<bool, E>,
+        E: From<TurtleError> + From<RdfXmlError> + From<StdError>,
+    {
+        if let Some(encoding) = self.unsupported_encoding {
+            return Err(TurtleError::from(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unsupported {encoding} encoding, only UTF-8 is supported"),
+            ))
+            .into());
+        }
+
+        let default_graph = self
+            .default_graph
+            .as_deref()
+            .map(|iri| GraphName::NamedNode(NamedNode { iri }));
+        let proceed = Cell::new(true);
+        let mut use_quad =
+            |triple: Triple<'_>, graph_name: Option<GraphName<'_>>| -> Result<(), E> {
+                if proceed.get() {
+                    proceed.set(use_fn(triple, graph_name)?);
+                }
+                Ok(())
+            };
+
+        self.parser.read_while(
+            default_graph,
+            &proceed,
+            &mut self.xml_problems,
+            &mut use_quad,
+        )
+    }
+}
+
+impl<'a> TripleReader<&'a [u8]> {
+    /// Creates a reader of data made of consecutive segments in different formats, e.g. a Turtle
+    /// header followed by an N-Triples body, each segment being given by its format and its byte
+    /// range in the data. The segments must be ordered, must not overlap and must lie within the
+    /// data.
+    pub fn multipart(data: &'a [u8], segments: &[(DataFormat, Range<usize>)]) -> StdResult<Self> {
+        check_segments(data, segments)?;
+        let mut unsupported_encoding = None;
+        let mut parsers = Vec::with_capacity(segments.len());
+        for (format, range) in segments {
+            let reader = Self::new(format, &data[range.clone()]);
+            unsupported_encoding = unsupported_encoding.or(reader.unsupported_encoding);
+            parsers.push(reader.parser);
+        }
+
+        Ok(TripleReader {
+            parser: TriplesParserKind::Multipart(parsers),
+            default_graph: None,
+            xml_problems: None,
+            unsupported_encoding,
+        })
+    }
+}
+
+/// Checks the segments of the data are ordered, don't overlap and lie within the data.
+fn check_segments(data: &[u8], segments: &[(DataFormat, Range<usize>)]) -> StdResult<()> {
+    let mut end = 0;
+    for (_, range) in segments {
+        if range.start < end || range.start > range.end || range.end > data.len() {
+            return Err(StdError::generic_err(format!(
+                "Invalid segment range: {range:?}"
+            )));
+        }
+        end = range.end;
+    }
+    Ok(())
+}
+
+impl<R: BufRead> TriplesParserKind<R> {
+    /// Sets the prefixes the CURIEs of the JSON triples are expanded with, including the ones of
+    /// the JSON segments of multipart data.
+    fn set_prefixes(&mut self, prefixes: &HashMap<String, String>) {
+        match self {
+            TriplesParserKind::Json(parser) => parser.prefixes = prefixes.clone(),
+            TriplesParserKind::Multipart(parsers) => {
+                for parser in parsers {
+                    parser.set_prefixes(prefixes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Parses the triples as long as `proceed` holds, returning whether all have been parsed.
+    ///
+    /// The recoverable RDF/XML problems are collected in `xml_problems` if set, the triple of the
+    /// property they affect being skipped, instead of failing.
+    fn read_while<E, UF>(
+        &mut self,
+        default_graph: Option<GraphName<'_>>,
+        proceed: &Cell<bool>,
+        xml_problems: &mut Option<Vec<String>>,
+        use_quad: &mut UF,
+    ) -> Result<bool, E>
+    where
+        UF: FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+        E: From<TurtleError> + From<RdfXmlError> + From<StdError>,
     {
-        match &mut self.parser {
-            TriplesParserKind::NTriples(parser) => parser.parse_all(&mut use_fn),
-            TriplesParserKind::Turtle(parser) => parser.parse_all(&mut use_fn),
-            TriplesParserKind::RdfXml(parser) => parser.parse_all(&mut use_fn),
+        match self {
+            TriplesParserKind::NTriples(parser) => {
+                while proceed.get() && !parser.is_end() {
+                    parser.parse_step(&mut |triple| use_quad(triple, default_graph))?;
+                }
+                Ok(parser.is_end())
+            }
+            TriplesParserKind::Turtle(parser) => {
+                while proceed.get() && !parser.is_end() {
+                    parser.parse_step(&mut |triple| use_quad(triple, default_graph))?;
+                }
+                Ok(parser.is_end())
+            }
+            TriplesParserKind::RdfXml(parser, last_byte) => {
+                let skip_next = Cell::new(false);
+                while proceed.get() && !parser.is_end() {
+                    let step = parser.parse_step(&mut |triple| {
+                        if skip_next.replace(false) {
+                            return Ok(());
+                        }
+                        use_quad(triple, default_graph).map_err(XmlStepError::Use)
+                    });
+                    match step {
+                        Ok(()) => {}
+                        Err(XmlStepError::Use(e)) => return Err(e),
+                        Err(XmlStepError::Parse(e)) => match xml_problems {
+                            Some(problems) if is_recoverable_xml_error(&e, last_byte.get()) => {
+                                problems.push(e.to_string());
+                                skip_next.set(true);
+                            }
+                            _ => return Err(e.into()),
+                        },
+                    }
+                }
+                Ok(parser.is_end())
+            }
             TriplesParserKind::NQuads(parser) => {
-                parser.parse_all(&mut |quad: Quad<'_>| -> Result<(), E> {
-                    use_fn(Triple {
-                        subject: quad.subject,
-                        predicate: quad.predicate,
-                        object: quad.object,
-                    })
-                })
+                while proceed.get() && !parser.is_end() {
+                    parser.parse_step(&mut |quad: Quad<'_>| {
+                        use_quad(
+                            Triple {
+                                subject: quad.subject,
+                                predicate: quad.predicate,
+                                object: quad.object,
+                            },
+                            quad.graph_name.or(default_graph),
+                        )
+                    })?;
+                }
+                Ok(parser.is_end())
+            }
+            TriplesParserKind::Multipart(parsers) => {
+                for parser in parsers {
+                    if !parser.read_while(default_graph, proceed, xml_problems, use_quad)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            TriplesParserKind::Json(parser) => {
+                parser.parse()?;
+                while proceed.get() {
+                    let Some(atom) = parser.atoms.get(parser.position) else {
+                        break;
+                    };
+                    use_quad(atom.into(), default_graph)?;
+                    parser.position += 1;
+                }
+                Ok(parser.position >= parser.atoms.len())
+            }
+        }
+    }
+}
+
+/// The error of an RDF/XML parsing step, telling apart the parsing errors from the ones of the
+/// function the triples are given to.
+enum XmlStepError<E> {
+    Parse(RdfXmlError),
+    Use(E),
+}
+
+impl<E> From<RdfXmlError> for XmlStepError<E> {
+    fn from(value: RdfXmlError) -> Self {
+        XmlStepError::Parse(value)
+    }
+}
+
+/// Tells if an RDF/XML parsing error is recoverable, given the last byte consumed from the data.
+///
+/// The only recoverable errors are the entity references of text content that can't be resolved,
+/// e.g. undefined entities, the text being read up to the `<` of the next tag so that the parser
+/// remains consistent and still ends the property holding the text. The same references in a tag,
+/// read up to its `>`, leave its element unparsed, which the parser can't recover from, as any
+/// other error.
+fn is_recoverable_xml_error(e: &RdfXmlError, last_byte: Option<u8>) -> bool {
+    last_byte == Some(b'<')
+        && matches!(
+            e.source()
+                .and_then(|source| source.downcast_ref::<quick_xml::Error>()),
+            Some(quick_xml::Error::EscapeError(_))
+        )
+}
+
+/// Reader recording the last byte consumed from the underlying one, telling apart the XML text
+/// events from the tags, see [is_recoverable_xml_error].
+pub struct LastByteReader<R: BufRead> {
+    inner: R,
+    last_byte: Rc<Cell<Option<u8>>>,
+}
+
+impl<R: BufRead> io::Read for LastByteReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Some(byte) = n.checked_sub(1).and_then(|i| buf.get(i)) {
+            self.last_byte.set(Some(*byte));
+        }
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for LastByteReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(i) = amt.checked_sub(1) {
+            if let Some(byte) = self.inner.fill_buf().ok().and_then(|buf| buf.get(i)) {
+                self.last_byte.set(Some(*byte));
             }
         }
+        self.inner.consume(amt);
     }
 }
 
 impl<W: io::Write> TripleWriter<W> {
     pub fn new(format: &DataFormat, dst: W) -> Self {
+        Self::with_base(format, dst, None)
+    }
+
+    /// Creates a writer declaring the given base IRI, i.e. `@base` for Turtle and `xml:base` for
+    /// RDF/XML, IRIs under this base being written relative to it.
+    ///
+    /// N-Triples and N-Quads don't support relative IRIs, the base is ignored for those formats.
+    pub fn with_base(format: &DataFormat, mut dst: W, base: Option<String>) -> Self {
+        let base = match format {
+            DataFormat::RDFXml | DataFormat::Turtle => base,
+            DataFormat::NTriples | DataFormat::NQuads | DataFormat::JsonTriples => None,
+        };
+
         TripleWriter {
             writer: match format {
-                DataFormat::RDFXml => TriplesWriterKind::RdfXml(RdfXmlFormatter::new(dst)),
-                DataFormat::Turtle => TriplesWriterKind::Turtle(TurtleFormatter::new(dst)),
+                DataFormat::RDFXml => TriplesWriterKind::RdfXml(RdfXmlFormatter::new(
+                    XmlBaseWriter::new(dst, base.as_deref()),
+                )),
+                DataFormat::Turtle => TriplesWriterKind::Turtle(
+                    base.as_ref()
+                        .map_or(Ok(()), |base| writeln!(dst, "@base <{base}> ."))
+                        .map(|_| TurtleFormatter::new(dst)),
+                ),
                 DataFormat::NTriples => TriplesWriterKind::NTriples(NTriplesFormatter::new(dst)),
                 DataFormat::NQuads => TriplesWriterKind::NQuads(NQuadsFormatter::new(dst)),
+                DataFormat::JsonTriples => TriplesWriterKind::Json(dst, vec![]),
             },
+            base: base.filter(|base| is_relativizable_base(base)),
+            blank_node_labels: None,
+            skolem_base: None,
+            turtle_prefixes: None,
+            json_prefixes: None,
         }
     }
 
-    pub fn write(&mut self, triple: &Triple<'_>) -> io::Result<()> {
-        match &mut self.writer {
-            TriplesWriterKind::Turtle(formatter) => formatter.format(triple),
+    /// Relabels the blank nodes deterministically as `_:b0`, `_:b1`, etc. in the order they are
+    /// first written, regardless of their identifiers in the store, so that writing the same
+    /// triples always gives the same output.
+    ///
+    /// The labels being only consistent within a single output, they shouldn't be relied on to
+    /// relate the blank nodes of distinct outputs, e.g. the pages of a paginated listing.
+    pub fn with_blank_node_relabeling(mut self) -> Self {
+        self.blank_node_labels = Some(BlankNodeLabels::default());
+        self
+    }
+
+    /// Replaces the blank nodes with the skolem IRIs minted from their identifiers under the given
+    /// base, see [skolem_iri], for the consumers not handling blank nodes.
+    ///
+    /// Being minted from the identifiers in the store, the skolem IRIs are consistent across
+    /// outputs, so they take precedence over the relabeling of the blank nodes.
+    pub fn with_skolemization(mut self, base: String) -> Self {
+        self.skolem_base = Some(base);
+        self
+    }
+
+    /// Sets the layout of the triples written in Turtle, the other formats being left unchanged,
+    /// the default one only factorizing the subjects and predicates of consecutive triples.
+    pub fn with_turtle_style(mut self, style: TurtleStyle) -> Self {
+        if let TriplesWriterKind::Turtle(format_result) = self.writer {
+            self.writer = match format_result.and_then(TurtleFormatter::finish) {
+                Ok(dst) => match style {
+                    TurtleStyle::Flat => TriplesWriterKind::TurtleFlat(dst),
+                    TurtleStyle::Grouped => {
+                        TriplesWriterKind::TurtleGrouped(dst, GroupedTriples::default())
+                    }
+                },
+                Err(e) => TriplesWriterKind::Turtle(Err(e)),
+            };
+        }
+        self
+    }
+
+    /// Writes the IRIs as prefixed names using the given prefixes and namespaces: in Turtle,
+    /// declaring with `@prefix` only the prefixes actually used by the triples written, e.g. for
+    /// a subset of the triples of a store, and in JSON triples as CURIEs, the prefixes being
+    /// declared along the triples. The other formats are left unchanged.
+    ///
+    /// The Turtle triples being buffered until the declarations are known, the default layout is
+    /// replaced with the flat one if no other style has been set beforehand.
+    pub fn with_prefixes(mut self, prefixes: impl IntoIterator<Item = (String, String)>) -> Self {
+        if let TriplesWriterKind::Json(..) = self.writer {
+            self.json_prefixes = Some(prefixes.into_iter().collect());
+            return self;
+        }
+
+        self = self.with_turtle_style(TurtleStyle::Flat);
+        if matches!(
+            self.writer,
+            TriplesWriterKind::TurtleFlat(_) | TriplesWriterKind::TurtleGrouped(..)
+        ) {
+            self.turtle_prefixes = Some(TurtlePrefixes::new(prefixes));
+        }
+        self
+    }
+
+    pub fn write(&mut self, triple: &Triple<'_>) -> Result<(), WriteError> {
+        let (subject_iri, object_iri, skolemized);
+        let triple = match &self.skolem_base {
+            Some(base) => {
+                subject_iri = match triple.subject {
+                    Subject::BlankNode(node) => Some(skolem_iri(base, node.id)),
+                    _ => None,
+                };
+                object_iri = match triple.object {
+                    Term::BlankNode(node) => Some(skolem_iri(base, node.id)),
+                    _ => None,
+                };
+                skolemized = Triple {
+                    subject: subject_iri
+                        .as_deref()
+                        .map_or(triple.subject, |iri| NamedNode { iri }.into()),
+                    predicate: triple.predicate,
+                    object: object_iri
+                        .as_deref()
+                        .map_or(triple.object, |iri| NamedNode { iri }.into()),
+                };
+                &skolemized
+            }
+            None => triple,
+        };
+
+        let relabeled;
+        let triple = match &mut self.blank_node_labels {
+            Some(labels) => {
+                if let Subject::BlankNode(node) = triple.subject {
+                    labels.assign(node.id);
+                }
+                if let Term::BlankNode(node) = triple.object {
+                    labels.assign(node.id);
+                }
+                relabeled = labels.relabel(triple);
+                &relabeled
+            }
+            None => triple,
+        };
+
+        let relative;
+        let triple = match &self.base {
+            Some(base) => {
+                relative = relativize_triple(
+                    base,
+                    triple,
+                    matches!(
+                        self.writer,
+                        TriplesWriterKind::Turtle(_)
+                            | TriplesWriterKind::TurtleFlat(_)
+                            | TriplesWriterKind::TurtleGrouped(..)
+                    ),
+                );
+                &relative
+            }
+            None => triple,
+        };
+
+        match (&mut self.writer, &mut self.turtle_prefixes) {
+            (TriplesWriterKind::TurtleFlat(_), Some(prefixes)) => {
+                let (subject, predicate, object) = prefixes.format(triple);
+                prefixes
+                    .lines
+                    .push(format!("{subject} {predicate} {object} ."));
+                Ok(())
+            }
+            (TriplesWriterKind::TurtleGrouped(_, grouped), Some(prefixes)) => {
+                let (subject, predicate, object) = prefixes.format(triple);
+                grouped.push_formatted(subject, predicate, object);
+                Ok(())
+            }
+            (writer, _) => Self::format(writer, triple),
+        }
+    }
+
+    fn format(writer: &mut TriplesWriterKind<W>, triple: &Triple<'_>) -> Result<(), WriteError> {
+        let res = match writer {
+            TriplesWriterKind::Turtle(format_result) => match format_result {
+                Ok(formatter) => formatter.format(triple),
+                Err(e) => Err(WriteError::Formatter(io::Error::new(
+                    e.kind(),
+                    e.to_string(),
+                )))?,
+            },
+            TriplesWriterKind::TurtleFlat(dst) => writeln!(dst, "{triple} ."),
+            TriplesWriterKind::TurtleGrouped(_, grouped) => {
+                grouped.push(triple);
+                Ok(())
+            }
             TriplesWriterKind::NTriples(formatter) => formatter.format(triple),
             TriplesWriterKind::NQuads(formatter) => {
                 use rio_api::formatter::QuadsFormatter;
@@ -94,30 +808,1129 @@ impl<W: io::Write> TripleWriter<W> {
 
                 formatter.format(quad)
             }
+            TriplesWriterKind::Json(_, atoms) => Atom::try_from(triple)
+                .map(|atom| atoms.push(atom))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string())),
             TriplesWriterKind::RdfXml(format_result) => match format_result {
                 Ok(formatter) => formatter.format(triple),
-                Err(e) => Err(io::Error::new(io::ErrorKind::Other, e.to_string())),
+                Err(e) => Err(WriteError::Formatter(io::Error::new(
+                    e.kind(),
+                    e.to_string(),
+                )))?,
             },
-        }
+        };
+
+        // The formatters report the triples they can't represent, e.g. RDF-star ones in RDF/XML,
+        // as invalid input, the other errors coming from the destination.
+        res.map_err(|e| match e.kind() {
+            io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => {
+                WriteError::Format(triple.to_string(), e)
+            }
+            _ => WriteError::Io(e),
+        })
     }
 
-    #[allow(dead_code)]
-    pub fn write_all(&mut self, triples: Vec<&Triple<'_>>) -> io::Result<()> {
-        for triple in triples {
-            self.write(triple)?;
+    pub fn finish(self) -> Result<W, WriteError> {
+        match (self.writer, self.turtle_prefixes) {
+            (TriplesWriterKind::Json(mut dst, atoms), _) => {
+                let with_prefixes = self.json_prefixes.is_some();
+                write_json_triples(
+                    &atoms,
+                    &self.json_prefixes.unwrap_or_default(),
+                    with_prefixes,
+                )
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+                .and_then(|json| dst.write_all(&json))
+                .and_then(|_| dst.flush())
+                .map(|_| dst)
+                .map_err(WriteError::Io)
+            }
+            (TriplesWriterKind::TurtleFlat(mut dst), Some(prefixes)) => prefixes
+                .write_declarations(&mut dst)
+                .and_then(|_| {
+                    prefixes
+                        .lines
+                        .iter()
+                        .try_for_each(|line| writeln!(dst, "{line}"))
+                })
+                .and_then(|_| dst.flush())
+                .map(|_| dst)
+                .map_err(WriteError::Io),
+            (TriplesWriterKind::TurtleGrouped(mut dst, grouped), Some(prefixes)) => prefixes
+                .write_declarations(&mut dst)
+                .and_then(|_| grouped.write_to(&mut dst))
+                .and_then(|_| dst.flush())
+                .map(|_| dst)
+                .map_err(WriteError::Io),
+            (writer, _) => Self::finish_writer(writer),
         }
-        Ok(())
     }
 
-    pub fn finish(self) -> io::Result<W> {
-        match self.writer {
-            TriplesWriterKind::Turtle(formatter) => formatter.finish(),
+    fn finish_writer(writer: TriplesWriterKind<W>) -> Result<W, WriteError> {
+        let res = match writer {
+            TriplesWriterKind::Turtle(format_result) => match format_result {
+                Ok(formatter) => formatter.finish(),
+                Err(e) => Err(WriteError::Formatter(e))?,
+            },
+            TriplesWriterKind::TurtleFlat(mut dst) => dst.flush().map(|_| dst),
+            TriplesWriterKind::TurtleGrouped(mut dst, grouped) => grouped
+                .write_to(&mut dst)
+                .and_then(|_| dst.flush())
+                .map(|_| dst),
             TriplesWriterKind::NTriples(formatter) => formatter.finish(),
             TriplesWriterKind::NQuads(formatter) => formatter.finish(),
+            TriplesWriterKind::Json(mut dst, atoms) => {
+                write_json_triples(&atoms, &HashMap::new(), false)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+                    .and_then(|json| dst.write_all(&json))
+                    .and_then(|_| dst.flush())
+                    .map(|_| dst)
+            }
             TriplesWriterKind::RdfXml(format_result) => match format_result {
-                Ok(formatter) => formatter.finish(),
-                Err(e) => Err(io::Error::new(io::ErrorKind::Other, e.to_string())),
+                Ok(formatter) => formatter.finish().map(XmlBaseWriter::into_inner),
+                Err(e) => Err(WriteError::Formatter(e))?,
             },
+        };
+
+        res.map_err(WriteError::Io)
+    }
+}
+
+/// Only bases denoting a directory-like path allow a plain suffix to be resolved back to the
+/// original IRI, other bases are declared but IRIs are then kept absolute.
+fn is_relativizable_base(base: &str) -> bool {
+    base.ends_with('/') && !base.contains(['?', '#'])
+}
+
+/// Returns the reference of the IRI relative to the base, if it resolves back to the same IRI.
+fn relativize<'a>(base: &str, iri: &'a str) -> Option<&'a str> {
+    let relative = iri.strip_prefix(base)?;
+    let path = relative.split(['?', '#']).next().unwrap_or_default();
+
+    if path.starts_with('/')
+        || path.split('/').next().unwrap_or_default().contains(':')
+        || path
+            .split('/')
+            .any(|segment| segment == "." || segment == "..")
+    {
+        return None;
+    }
+
+    Some(relative)
+}
+
+fn relativize_node<'a>(base: &str, node: NamedNode<'a>) -> NamedNode<'a> {
+    NamedNode {
+        iri: relativize(base, node.iri).unwrap_or(node.iri),
+    }
+}
+
+fn relativize_triple<'a>(base: &str, triple: &Triple<'a>, with_predicate: bool) -> Triple<'a> {
+    Triple {
+        subject: match triple.subject {
+            Subject::NamedNode(node) => relativize_node(base, node).into(),
+            subject => subject,
+        },
+        predicate: if with_predicate {
+            relativize_node(base, triple.predicate)
+        } else {
+            triple.predicate
+        },
+        object: match triple.object {
+            Term::NamedNode(node) => relativize_node(base, node).into(),
+            Term::Literal(Literal::Typed { value, datatype }) => Literal::Typed {
+                value,
+                datatype: relativize_node(base, datatype),
+            }
+            .into(),
+            object => object,
+        },
+    }
+}
+
+/// Returns the absolute IRIs of the prefixes declared by the directives heading the given
+/// [Turtle](https://www.w3.org/TR/turtle/) data, i.e. the ones before its first triple, as hints
+/// of the namespaces its triples use. The scan stops at the first statement not being a directive
+/// or not understood, the hints being then partial.
+pub fn turtle_prefix_hints(data: &[u8]) -> Vec<String> {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(e) => std::str::from_utf8(&data[..e.valid_up_to()]).unwrap_or_default(),
+    };
+
+    let mut hints = Vec::new();
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start();
+        if rest.starts_with('#') {
+            rest = rest.split_once('\n').map_or("", |(_, next)| next);
+            continue;
+        }
+
+        let (keyword, is_prefix, sparql_style) = match rest.split_once(char::is_whitespace) {
+            Some(("@prefix", _)) => ("@prefix", true, false),
+            Some(("@base", _)) => ("@base", false, false),
+            Some((word, _)) if word.eq_ignore_ascii_case("prefix") => (word, true, true),
+            Some((word, _)) if word.eq_ignore_ascii_case("base") => (word, false, true),
+            _ => break,
+        };
+        let Some((declaration, next)) = rest[keyword.len()..].split_once('>') else {
+            break;
+        };
+        let Some((_, iri)) = declaration.split_once('<') else {
+            break;
+        };
+        if is_prefix && AbsoluteIri::try_new(iri).is_ok() {
+            hints.push(iri.to_string());
+        }
+
+        rest = next.trim_start();
+        if !sparql_style {
+            match rest.strip_prefix('.') {
+                Some(next) => rest = next,
+                None => break,
+            }
+        }
+    }
+
+    hints
+}
+
+/// Replaces the invalid UTF-8 sequences of the data with the given replacement, each maximal
+/// invalid subpart counting as one replacement as [String::from_utf8_lossy] does, returning the
+/// resulting data along with the number of replacements made.
+pub fn replace_invalid_utf8<'a>(data: &'a [u8], replacement: &str) -> (Cow<'a, [u8]>, usize) {
+    let mut rest = match std::str::from_utf8(data) {
+        Ok(_) => return (Cow::Borrowed(data), 0),
+        Err(_) => data,
+    };
+
+    let mut replaced = Vec::with_capacity(data.len());
+    let mut count = 0;
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(_) => {
+                replaced.extend_from_slice(rest);
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                replaced.extend_from_slice(valid);
+                replaced.extend_from_slice(replacement.as_bytes());
+                count += 1;
+                rest = &after[e.error_len().unwrap_or(after.len())..];
+            }
         }
     }
+
+    (Cow::Owned(replaced), count)
+}
+
+/// Replaces the invalid UTF-8 sequences of each segment of the data, see [replace_invalid_utf8],
+/// the resulting data being only made of the segments, whose ranges are shifted accordingly.
+/// Returns the resulting data along with the number of replacements made.
+pub fn replace_invalid_utf8_segments<'a>(
+    data: &'a [u8],
+    segments: &mut [(DataFormat, Range<usize>)],
+    replacement: &str,
+) -> StdResult<(Cow<'a, [u8]>, usize)> {
+    check_segments(data, segments)?;
+    if std::str::from_utf8(data).is_ok() {
+        return Ok((Cow::Borrowed(data), 0));
+    }
+
+    let mut replaced = Vec::with_capacity(data.len());
+    let mut count = 0;
+    for (_, range) in segments {
+        let (segment, segment_count) = replace_invalid_utf8(&data[range.clone()], replacement);
+        *range = replaced.len()..replaced.len() + segment.len();
+        replaced.extend_from_slice(&segment);
+        count += segment_count;
+    }
+
+    Ok((Cow::Owned(replaced), count))
+}
+
+/// Replaces the skolem IRIs minted under the given base with the blank nodes they stand for, see
+/// [deskolemize_iri].
+pub fn deskolemize_triple<'a>(base: &str, triple: Triple<'a>) -> Triple<'a> {
+    Triple {
+        subject: match triple.subject {
+            Subject::NamedNode(node) => {
+                deskolemize_iri(node.iri, base).map_or(triple.subject, |id| BlankNode { id }.into())
+            }
+            subject => subject,
+        },
+        predicate: triple.predicate,
+        object: match triple.object {
+            Term::NamedNode(node) => {
+                deskolemize_iri(node.iri, base).map_or(triple.object, |id| BlankNode { id }.into())
+            }
+            object => object,
+        },
+    }
+}
+
+/// Replaces the `xsd:boolean` typed literal object of the triple by its canonical lexical form,
+/// see [canonical_boolean].
+pub fn canonicalize_boolean_triple(triple: Triple<'_>) -> Triple<'_> {
+    Triple {
+        object: canonicalize_boolean_term(triple.object),
+        ..triple
+    }
+}
+
+/// Replaces the `xsd:boolean` typed literal term by its canonical lexical form, see
+/// [canonical_boolean].
+pub fn canonicalize_boolean_term(term: Term<'_>) -> Term<'_> {
+    match term {
+        Term::Literal(Literal::Typed { value, datatype }) if datatype.iri == XSD_BOOLEAN => {
+            Literal::Typed {
+                value: canonical_boolean(value),
+                datatype,
+            }
+            .into()
+        }
+        term => term,
+    }
+}
+
+/// Writer declaring an `xml:base` attribute on the `rdf:RDF` root element written by the
+/// [RdfXmlFormatter], which doesn't allow to customize it.
+pub struct XmlBaseWriter<W: io::Write> {
+    inner: W,
+    attribute: Option<String>,
+    matched: usize,
+}
+
+impl<W: io::Write> XmlBaseWriter<W> {
+    const ROOT_ELEMENT: &'static [u8] = b"<rdf:RDF";
+
+    fn new(inner: W, base: Option<&str>) -> Self {
+        Self {
+            inner,
+            attribute: base.map(|base| {
+                format!(
+                    " xml:base=\"{}\"",
+                    base.replace('&', "&amp;")
+                        .replace('<', "&lt;")
+                        .replace('"', "&quot;")
+                )
+            }),
+            matched: 0,
+        }
+    }
+
+    fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> io::Write for XmlBaseWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(attribute) = &self.attribute else {
+            return self.inner.write(buf);
+        };
+
+        for (i, byte) in buf.iter().enumerate() {
+            self.matched = match (Self::ROOT_ELEMENT.get(self.matched), byte) {
+                (Some(expected), byte) if expected == byte => self.matched + 1,
+                (_, b'<') => 1,
+                _ => 0,
+            };
+
+            if self.matched == Self::ROOT_ELEMENT.len() {
+                self.inner.write_all(&buf[..=i])?;
+                self.inner.write_all(attribute.as_bytes())?;
+                self.attribute = None;
+                self.inner.write_all(&buf[i + 1..])?;
+                return Ok(buf.len());
+            }
+        }
+
+        self.inner.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContractError;
+
+    impl<W: io::Write> TripleWriter<W> {
+        fn write_all(&mut self, triples: Vec<&Triple<'_>>) -> Result<(), WriteError> {
+            for triple in triples {
+                self.write(triple)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn triples() -> Vec<Triple<'static>> {
+        vec![
+            Triple {
+                subject: NamedNode {
+                    iri: "http://example.com/data/s",
+                }
+                .into(),
+                predicate: NamedNode {
+                    iri: "http://example.com/data/p",
+                },
+                object: NamedNode {
+                    iri: "http://example.com/data/o#frag",
+                }
+                .into(),
+            },
+            Triple {
+                subject: NamedNode {
+                    iri: "http://example.com/data/s",
+                }
+                .into(),
+                predicate: NamedNode {
+                    iri: "http://example.com/data/p",
+                },
+                object: NamedNode {
+                    iri: "http://example.com/data/a:b",
+                }
+                .into(),
+            },
+            Triple {
+                subject: NamedNode {
+                    iri: "http://example.com/other",
+                }
+                .into(),
+                predicate: NamedNode {
+                    iri: "http://example.com/data/p",
+                },
+                object: Literal::Typed {
+                    value: "v",
+                    datatype: NamedNode {
+                        iri: "http://example.com/data/type",
+                    },
+                }
+                .into(),
+            },
+        ]
+    }
+
+    fn write(format: &DataFormat, base: Option<&str>) -> String {
+        let mut writer = TripleWriter::with_base(format, vec![], base.map(str::to_string));
+        writer.write_all(triples().iter().collect()).unwrap();
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn write_with_base() {
+        let cases = vec![
+            (
+                DataFormat::Turtle,
+                None,
+                "<http://example.com/data/s> <http://example.com/data/p> <http://example.com/data/o#frag> , <http://example.com/data/a:b> .\n\
+                <http://example.com/other> <http://example.com/data/p> \"v\"^^<http://example.com/data/type> .\n",
+            ),
+            (
+                DataFormat::Turtle,
+                Some("http://example.com/data/"),
+                "@base <http://example.com/data/> .\n\
+                <s> <p> <o#frag> , <http://example.com/data/a:b> .\n\
+                <http://example.com/other> <p> \"v\"^^<type> .\n",
+            ),
+            (
+                DataFormat::Turtle,
+                Some("http://example.com/data"),
+                "@base <http://example.com/data> .\n\
+                <http://example.com/data/s> <http://example.com/data/p> <http://example.com/data/o#frag> , <http://example.com/data/a:b> .\n\
+                <http://example.com/other> <http://example.com/data/p> \"v\"^^<http://example.com/data/type> .\n",
+            ),
+            (
+                DataFormat::NTriples,
+                Some("http://example.com/data/"),
+                "<http://example.com/data/s> <http://example.com/data/p> <http://example.com/data/o#frag> .\n\
+                <http://example.com/data/s> <http://example.com/data/p> <http://example.com/data/a:b> .\n\
+                <http://example.com/other> <http://example.com/data/p> \"v\"^^<http://example.com/data/type> .\n",
+            ),
+            (
+                DataFormat::RDFXml,
+                Some("http://example.com/data/"),
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                <rdf:RDF xml:base=\"http://example.com/data/\" xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+                <rdf:Description rdf:about=\"s\">\
+                <p xmlns=\"http://example.com/data/\" rdf:resource=\"o#frag\"/>\
+                <p xmlns=\"http://example.com/data/\" rdf:resource=\"http://example.com/data/a:b\"/>\
+                </rdf:Description>\
+                <rdf:Description rdf:about=\"http://example.com/other\">\
+                <p xmlns=\"http://example.com/data/\" rdf:datatype=\"type\">v</p>\
+                </rdf:Description>\
+                </rdf:RDF>",
+            ),
+        ];
+
+        for (format, base, expected) in cases {
+            assert_eq!(write(&format, base), expected);
+        }
+    }
+
+    #[test]
+    fn read_with_base() {
+        let expected: Vec<String> = triples().iter().map(Triple::to_string).collect();
+
+        for format in [DataFormat::Turtle, DataFormat::RDFXml] {
+            let data = write(&format, Some("http://example.com/data/"));
+
+            let mut reader = TripleReader::new(&format, data.as_bytes());
+            let mut read = vec![];
+            reader
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    read.push(triple.to_string());
+                    Ok(())
+                })
+                .unwrap();
+
+            assert_eq!(read, expected);
+        }
+    }
+
+    #[test]
+    fn read_write_json_triples() {
+        let read = |data: &str, prefixes: Vec<msg::Prefix>| {
+            let mut read = vec![];
+            TripleReader::new(&DataFormat::JsonTriples, data.as_bytes())
+                .with_prefixes(prefixes.into())
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    read.push(triple.to_string());
+                    Ok(())
+                })
+                .map(|_| read)
+        };
+        let prefix = |prefix: &str, namespace: &str| msg::Prefix {
+            prefix: prefix.to_string(),
+            namespace: namespace.to_string(),
+        };
+
+        let triples = r#"[
+            {"subject": {"type": "uri", "value": {"full": "http://a.org/s"}},
+             "predicate": {"type": "uri", "value": {"prefixed": "a:p"}},
+             "object": {"type": "literal", "value": "un", "xml:lang": "fr"}},
+            {"subject": {"type": "blank_node", "value": "b0"},
+             "predicate": {"type": "uri", "value": {"prefixed": "a:p"}},
+             "object": {"type": "literal", "value": "1", "datatype": {"prefixed": "xsd:integer"}}}
+        ]"#;
+        let expected = vec![
+            "<http://a.org/s> <http://a.org/p> \"un\"@fr".to_string(),
+            "_:b0 <http://a.org/p> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer>".to_string(),
+        ];
+        let prefixes = vec![
+            prefix("a", "http://a.org/"),
+            prefix("xsd", "http://www.w3.org/2001/XMLSchema#"),
+        ];
+        assert_eq!(read(triples, prefixes.clone()), Ok(expected.clone()));
+
+        // the prefixes of the document take precedence over the supplied ones
+        let document = format!(
+            r#"{{"prefixes": {{"a": "http://a.org/", "xsd": "http://www.w3.org/2001/XMLSchema#"}},
+            "triples": {triples}}}"#
+        );
+        assert_eq!(
+            read(&document, vec![prefix("a", "http://other.org/")]),
+            Ok(expected)
+        );
+
+        assert!(read(triples, vec![]).is_err());
+        assert!(read("{\"triples\": 1}", vec![]).is_err());
+        assert_eq!(read("[]", vec![]), Ok(vec![]));
+
+        // written back as JSON triples, the IRIs being in full
+        let mut writer = TripleWriter::new(&DataFormat::JsonTriples, vec![]);
+        TripleReader::new(&DataFormat::JsonTriples, triples.as_bytes())
+            .with_prefixes(prefixes.into())
+            .read_all(|triple| -> Result<(), crate::ContractError> {
+                writer.write(&triple)?;
+                Ok(())
+            })
+            .unwrap();
+        let written = writer.finish().unwrap();
+        let written: Vec<msg::Triple> = cosmwasm_std::from_slice(&written).unwrap();
+        assert_eq!(
+            written.first().map(|triple| &triple.predicate),
+            Some(&msg::Value::URI {
+                value: msg::IRI::Full("http://a.org/p".to_string())
+            })
+        );
+        assert_eq!(
+            read(
+                &String::from_utf8(cosmwasm_std::to_vec(&written).unwrap()).unwrap(),
+                vec![]
+            )
+            .unwrap()
+            .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn read_with_sparql_directives() {
+        let read = |data: &str| {
+            let mut read = vec![];
+            TripleReader::new(&DataFormat::Turtle, data.as_bytes())
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    read.push(triple.to_string());
+                    Ok(())
+                })
+                .map(|_| read)
+        };
+
+        let expected = read(
+            "@base <http://base.org/> .
+            @prefix a: <http://a.org/> .
+            @prefix : <http://b.org/> .
+            <s> a:p :o , \"1\" .",
+        )
+        .unwrap();
+        assert_eq!(
+            expected,
+            vec![
+                "<http://base.org/s> <http://a.org/p> <http://b.org/o>",
+                "<http://base.org/s> <http://a.org/p> \"1\"",
+            ]
+        );
+
+        for data in [
+            "BASE <http://base.org/>
+            PREFIX a: <http://a.org/>
+            PREFIX : <http://b.org/>
+            <s> a:p :o , \"1\" .",
+            "base <http://base.org/>
+            Prefix a: <http://a.org/>
+            @prefix : <http://b.org/> .
+            <s> a:p :o , \"1\" .",
+        ] {
+            assert_eq!(read(data), Ok(expected.clone()), "{data}");
+        }
+    }
+
+    #[test]
+    fn read_with_byte_order_mark() {
+        let expected: Vec<String> = triples().iter().map(Triple::to_string).collect();
+
+        for format in [
+            DataFormat::Turtle,
+            DataFormat::NTriples,
+            DataFormat::NQuads,
+            DataFormat::RDFXml,
+        ] {
+            let data = write(&format, None);
+            for bom in [&[][..], &[0xEF, 0xBB, 0xBF]] {
+                let mut read = vec![];
+                TripleReader::new(&format, [bom, data.as_bytes()].concat().as_slice())
+                    .read_all(|triple| -> Result<(), crate::ContractError> {
+                        read.push(triple.to_string());
+                        Ok(())
+                    })
+                    .unwrap();
+                assert_eq!(read, expected);
+            }
+
+            for (bom, encoding) in [(&[0xFE, 0xFF], "UTF-16BE"), (&[0xFF, 0xFE], "UTF-16LE")] {
+                let res =
+                    TripleReader::new(&format, [&bom[..], data.as_bytes()].concat().as_slice())
+                        .read_all(|_| -> Result<(), crate::ContractError> { Ok(()) });
+                assert!(
+                    matches!(res, Err(ContractError::ParseRDF(ref e)) if e.to_string().contains(&format!("Unsupported {encoding} encoding"))),
+                    "{res:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn read_with_default_graph() {
+        let data = r#"<http://a.org/s> <http://a.org/p> "1" .
+<http://a.org/s> <http://a.org/p> "2" <http://a.org/g> .
+<http://a.org/s> <http://a.org/p> "3" _:g .
+"#;
+        let cases = vec![
+            (
+                DataFormat::NQuads,
+                None,
+                vec![
+                    "<http://a.org/s> <http://a.org/p> \"1\"",
+                    "<http://a.org/s> <http://a.org/p> \"2\" <http://a.org/g>",
+                    "<http://a.org/s> <http://a.org/p> \"3\" _:g",
+                ],
+            ),
+            (
+                DataFormat::NQuads,
+                Some("http://a.org/default"),
+                vec![
+                    "<http://a.org/s> <http://a.org/p> \"1\" <http://a.org/default>",
+                    "<http://a.org/s> <http://a.org/p> \"2\" <http://a.org/g>",
+                    "<http://a.org/s> <http://a.org/p> \"3\" _:g",
+                ],
+            ),
+            (
+                DataFormat::NTriples,
+                Some("http://a.org/default"),
+                vec!["<http://a.org/s> <http://a.org/p> \"1\" <http://a.org/default>"],
+            ),
+        ];
+
+        for (format, default_graph, expected) in cases {
+            let src = match format {
+                DataFormat::NQuads => data,
+                _ => data.lines().next().unwrap(),
+            };
+            let mut reader = TripleReader::new(&format, src.as_bytes());
+            if let Some(iri) = default_graph {
+                reader = reader.with_default_graph(iri.to_string());
+            }
+
+            let mut read = vec![];
+            reader
+                .read_all_quads(|triple, graph_name| -> Result<(), crate::ContractError> {
+                    read.push(
+                        Quad {
+                            subject: triple.subject,
+                            predicate: triple.predicate,
+                            object: triple.object,
+                            graph_name,
+                        }
+                        .to_string(),
+                    );
+                    Ok(())
+                })
+                .unwrap();
+
+            assert_eq!(read, expected);
+        }
+    }
+
+    #[test]
+    fn read_lenient_xml() {
+        let document = |s: &str, p: &str| {
+            format!(
+                r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:a="http://a.org/">
+  <rdf:Description rdf:about="{s}">
+    <a:p>{p}</a:p>
+    <a:q>&amp;</a:q>
+  </rdf:Description>
+  <rdf:Description rdf:about="http://a.org/t">
+    <a:q>2</a:q>
+  </rdf:Description>
+</rdf:RDF>"#
+            )
+        };
+        let read = |data: &str, lenient: bool, capacity: usize| {
+            let mut reader = TripleReader::new(
+                &DataFormat::RDFXml,
+                io::BufReader::with_capacity(capacity, data.as_bytes()),
+            );
+            if lenient {
+                reader = reader.with_lenient_xml();
+            }
+            let mut read = vec![];
+            reader
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    read.push(triple.to_string());
+                    Ok(())
+                })
+                .map(|_| (read, reader.problems().to_vec()))
+        };
+
+        for capacity in [1, 7, 8 * 1024] {
+            assert_eq!(
+                read(&document("http://a.org/s", "1"), false, capacity),
+                Ok((
+                    vec![
+                        "<http://a.org/s> <http://a.org/p> \"1\"".to_string(),
+                        "<http://a.org/s> <http://a.org/q> \"&\"".to_string(),
+                        "<http://a.org/t> <http://a.org/q> \"2\"".to_string(),
+                    ],
+                    vec![]
+                ))
+            );
+
+            // undefined entity in text content
+            let data = document("http://a.org/s", "1 &unknown; 2");
+            assert!(read(&data, false, capacity).is_err());
+            assert_eq!(
+                read(&data, true, capacity),
+                Ok((
+                    vec![
+                        "<http://a.org/s> <http://a.org/q> \"&\"".to_string(),
+                        "<http://a.org/t> <http://a.org/q> \"2\"".to_string(),
+                    ],
+                    vec![
+                        "Error while escaping character at range 3..10: Unrecognized escape symbol: \"unknown\""
+                            .to_string()
+                    ]
+                ))
+            );
+
+            // undefined entity in a tag
+            let data = document("http://a.org/&unknown;", "1");
+            assert!(read(&data, false, capacity).is_err());
+            assert!(read(&data, true, capacity).is_err());
+        }
+    }
+
+    #[test]
+    fn read_multipart() {
+        let header = "@prefix a: <http://a.org/> .\na:s a:p \"1\" , \"2\" .\n";
+        let body = "<http://b.org/s> <http://b.org/p> \"3\" .\n<http://b.org/s> <http://b.org/p> \"4\" .\n";
+        let data = format!("{header}{body}");
+        let segments = [
+            (DataFormat::Turtle, 0..header.len()),
+            (DataFormat::NTriples, header.len()..data.len()),
+        ];
+        let expected = vec![
+            "<http://a.org/s> <http://a.org/p> \"1\"",
+            "<http://a.org/s> <http://a.org/p> \"2\"",
+            "<http://b.org/s> <http://b.org/p> \"3\"",
+            "<http://b.org/s> <http://b.org/p> \"4\"",
+        ];
+
+        let mut read = vec![];
+        TripleReader::multipart(data.as_bytes(), &segments)
+            .unwrap()
+            .read_all(|triple| -> Result<(), crate::ContractError> {
+                read.push(triple.to_string());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(read, expected);
+
+        for limit in 1..=expected.len() {
+            let mut read = vec![];
+            let complete = TripleReader::multipart(data.as_bytes(), &segments)
+                .unwrap()
+                .read_quads_while(|triple, _| -> Result<bool, crate::ContractError> {
+                    read.push(triple.to_string());
+                    Ok(read.len() < limit)
+                })
+                .unwrap();
+            assert_eq!(read, expected[..limit]);
+            assert_eq!(complete, limit == expected.len());
+        }
+
+        // the data as a whole is not valid N-Triples
+        assert!(
+            TripleReader::multipart(data.as_bytes(), &[(DataFormat::NTriples, 0..data.len())])
+                .unwrap()
+                .read_all(|_| -> Result<(), crate::ContractError> { Ok(()) })
+                .is_err()
+        );
+
+        for segments in [
+            vec![(DataFormat::Turtle, 0..data.len() + 1)],
+            vec![
+                (DataFormat::Turtle, 0..header.len()),
+                (DataFormat::NTriples, header.len() - 1..data.len()),
+            ],
+            vec![
+                (DataFormat::NTriples, header.len()..data.len()),
+                (DataFormat::Turtle, 0..header.len()),
+            ],
+        ] {
+            assert!(TripleReader::multipart(data.as_bytes(), &segments).is_err());
+        }
+    }
+
+    struct BrokenWriter;
+
+    impl io::Write for BrokenWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn proper_turtle_prefix_hints() {
+        let cases = vec![
+            ("", vec![]),
+            (
+                "@prefix a: <http://a.org/> .\n@prefix b:<http://b.org/>.\n<http://a.org/s> a:p b:o .",
+                vec!["http://a.org/", "http://b.org/"],
+            ),
+            (
+                "# header\nPREFIX a: <http://a.org/>\nprefix : <http://b.org/>\n\n# data\n:s a:p :o .",
+                vec!["http://a.org/", "http://b.org/"],
+            ),
+            (
+                "@base <http://base.org/> .\n@prefix a: <a/> .\nBASE <http://c.org/>\n@prefix c: <http://c.org/> .",
+                vec!["http://c.org/"],
+            ),
+            (
+                "@prefix a: <http://a.org/> .\n<http://a.org/s> a:p \"x\" .\n@prefix b: <http://b.org/> .",
+                vec!["http://a.org/"],
+            ),
+            (
+                "@prefix a: <http://a.org/>\n@prefix b: <http://b.org/> .",
+                vec!["http://a.org/"],
+            ),
+        ];
+
+        for (data, expected) in cases {
+            assert_eq!(turtle_prefix_hints(data.as_bytes()), expected, "{data}");
+        }
+    }
+
+    #[test]
+    fn write_errors() {
+        let triple = &triples()[0];
+
+        // the formatter can't be created as its header can't be written
+        let mut writer = TripleWriter::new(&DataFormat::RDFXml, BrokenWriter);
+        assert!(matches!(
+            writer.write(triple),
+            Err(WriteError::Formatter(_))
+        ));
+        assert!(matches!(writer.finish(), Err(WriteError::Formatter(_))));
+        let mut writer = TripleWriter::with_base(
+            &DataFormat::Turtle,
+            BrokenWriter,
+            Some("http://example.com/data/".to_string()),
+        );
+        assert!(matches!(
+            writer.write(triple),
+            Err(WriteError::Formatter(_))
+        ));
+
+        // the format can't represent the triple
+        let quoted = Triple {
+            subject: Subject::Triple(triple),
+            predicate: triple.predicate,
+            object: triple.object,
+        };
+        let mut writer = TripleWriter::new(&DataFormat::RDFXml, vec![]);
+        let err = writer.write(&quoted).unwrap_err();
+        assert!(matches!(&err, WriteError::Format(t, _) if *t == quoted.to_string()));
+        assert_eq!(
+            ContractError::from(err),
+            ContractError::FormatRDF(format!(
+                "Error writing triple {quoted}: RDF/XML only supports named or blank subject"
+            ))
+        );
+
+        // the destination fails
+        let mut writer = TripleWriter::new(&DataFormat::NTriples, BrokenWriter);
+        let err = writer.write(triple).unwrap_err();
+        assert!(matches!(&err, WriteError::Io(e) if e.kind() == io::ErrorKind::BrokenPipe));
+        assert_eq!(
+            StdError::from(err),
+            StdError::serialize_err("triple", "Error writing triple: broken")
+        );
+    }
+
+    #[test]
+    fn write_with_blank_node_relabeling() {
+        let write = |format: &DataFormat, ids: [&str; 2]| {
+            let triples = [
+                Triple {
+                    subject: BlankNode { id: ids[0] }.into(),
+                    predicate: NamedNode {
+                        iri: "http://a.org/p",
+                    },
+                    object: BlankNode { id: ids[1] }.into(),
+                },
+                Triple {
+                    subject: BlankNode { id: ids[1] }.into(),
+                    predicate: NamedNode {
+                        iri: "http://a.org/p",
+                    },
+                    object: BlankNode { id: ids[0] }.into(),
+                },
+                Triple {
+                    subject: NamedNode {
+                        iri: "http://a.org/s",
+                    }
+                    .into(),
+                    predicate: NamedNode {
+                        iri: "http://a.org/p",
+                    },
+                    object: BlankNode { id: ids[1] }.into(),
+                },
+            ];
+            let mut writer = TripleWriter::new(format, Vec::new()).with_blank_node_relabeling();
+            for triple in &triples {
+                writer.write(triple).unwrap();
+            }
+            String::from_utf8(writer.finish().unwrap()).unwrap()
+        };
+
+        let cases = vec![
+            (
+                DataFormat::NTriples,
+                "_:b0 <http://a.org/p> _:b1 .\n_:b1 <http://a.org/p> _:b0 .\n<http://a.org/s> <http://a.org/p> _:b1 .\n",
+            ),
+            (
+                DataFormat::Turtle,
+                "_:b0 <http://a.org/p> _:b1 .\n_:b1 <http://a.org/p> _:b0 .\n<http://a.org/s> <http://a.org/p> _:b1 .\n",
+            ),
+        ];
+        for (format, expected) in cases {
+            for ids in [
+                ["riog00000002", "riog00000001"],
+                ["riog00000001", "riog00000002"],
+                ["b1", "b0"],
+                ["x", "y"],
+            ] {
+                assert_eq!(write(&format, ids), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn write_turtle_styles() {
+        let data = r#"<http://a.org/s> <http://a.org/p> "1" .
+<http://a.org/o> <http://a.org/p> "2" .
+<http://a.org/s> <http://a.org/q> _:b .
+<http://a.org/s> <http://a.org/p> "3"@en .
+"#;
+        let parse = |format: &DataFormat, data: &str| {
+            let mut triples = vec![];
+            TripleReader::new(format, data.as_bytes())
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    triples.push(triple.to_string());
+                    Ok(())
+                })
+                .unwrap();
+            triples.sort();
+            triples
+        };
+        let write = |style: Option<TurtleStyle>| {
+            let mut writer = TripleWriter::new(&DataFormat::Turtle, vec![]);
+            if let Some(style) = style {
+                writer = writer.with_turtle_style(style);
+            }
+            TripleReader::new(&DataFormat::NTriples, data.as_bytes())
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    writer.write(&triple).unwrap();
+                    Ok(())
+                })
+                .unwrap();
+            String::from_utf8(writer.finish().unwrap()).unwrap()
+        };
+
+        let cases = vec![
+            (
+                None,
+                "<http://a.org/s> <http://a.org/p> \"1\" .\n\
+                <http://a.org/o> <http://a.org/p> \"2\" .\n\
+                <http://a.org/s> <http://a.org/q> _:b ;\n\
+                \t<http://a.org/p> \"3\"@en .\n",
+            ),
+            (
+                Some(TurtleStyle::Flat),
+                "<http://a.org/s> <http://a.org/p> \"1\" .\n\
+                <http://a.org/o> <http://a.org/p> \"2\" .\n\
+                <http://a.org/s> <http://a.org/q> _:b .\n\
+                <http://a.org/s> <http://a.org/p> \"3\"@en .\n",
+            ),
+            (
+                Some(TurtleStyle::Grouped),
+                "<http://a.org/s> <http://a.org/p> \"1\" , \"3\"@en ;\n\
+                \t<http://a.org/q> _:b .\n\
+                <http://a.org/o> <http://a.org/p> \"2\" .\n",
+            ),
+        ];
+
+        let expected = parse(&DataFormat::NTriples, data);
+        for (style, output) in cases {
+            let written = write(style);
+            assert_eq!(written, output);
+            assert_eq!(parse(&DataFormat::Turtle, &written), expected);
+        }
+    }
+
+    #[test]
+    fn write_turtle_prefixes() {
+        let data = [
+            r#"<http://a.org/s> <http://a.org/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer> ."#,
+            "<http://a.org/s> <http://a.org/ns/q> <http://c.org/o> .",
+            "<http://a.org/o> <http://a.org/p> <http://a.org/x.y> .",
+        ];
+        let prefixes = vec![
+            ("a".to_string(), "http://a.org/".to_string()),
+            ("ns".to_string(), "http://a.org/ns/".to_string()),
+            ("b".to_string(), "http://b.org/".to_string()),
+            (
+                "xsd".to_string(),
+                "http://www.w3.org/2001/XMLSchema#".to_string(),
+            ),
+        ];
+        let parse = |format: &DataFormat, data: &str| {
+            let mut triples = vec![];
+            TripleReader::new(format, data.as_bytes())
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    triples.push(triple.to_string());
+                    Ok(())
+                })
+                .unwrap();
+            triples
+        };
+        let write = |format: &DataFormat, style: Option<TurtleStyle>, data: &str| {
+            let mut writer = TripleWriter::new(format, vec![]);
+            if let Some(style) = style {
+                writer = writer.with_turtle_style(style);
+            }
+            writer = writer.with_prefixes(prefixes.clone());
+            TripleReader::new(&DataFormat::NTriples, data.as_bytes())
+                .read_all(|triple| -> Result<(), crate::ContractError> {
+                    writer.write(&triple).unwrap();
+                    Ok(())
+                })
+                .unwrap();
+            String::from_utf8(writer.finish().unwrap()).unwrap()
+        };
+
+        let cases = vec![
+            (
+                None,
+                &data[2..],
+                "@prefix a: <http://a.org/> .\n\
+                a:o a:p <http://a.org/x.y> .\n",
+            ),
+            (
+                Some(TurtleStyle::Flat),
+                &data[..2],
+                "@prefix a: <http://a.org/> .\n\
+                @prefix ns: <http://a.org/ns/> .\n\
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\
+                a:s a:p \"1\"^^xsd:integer .\n\
+                a:s ns:q <http://c.org/o> .\n",
+            ),
+            (
+                Some(TurtleStyle::Grouped),
+                &data[..2],
+                "@prefix a: <http://a.org/> .\n\
+                @prefix ns: <http://a.org/ns/> .\n\
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\
+                a:s a:p \"1\"^^xsd:integer ;\n\
+                \tns:q <http://c.org/o> .\n",
+            ),
+        ];
+
+        for (style, subset, output) in cases {
+            let subset = subset.join("\n");
+            let written = write(&DataFormat::Turtle, style, &subset);
+            assert_eq!(written, output);
+            assert_eq!(
+                parse(&DataFormat::Turtle, &written),
+                parse(&DataFormat::NTriples, &subset)
+            );
+        }
+
+        let subset = data.join("\n") + "\n";
+        assert_eq!(write(&DataFormat::NTriples, None, &subset), subset);
+    }
 }
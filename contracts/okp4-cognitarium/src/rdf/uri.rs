@@ -1,6 +1,63 @@
-use cosmwasm_std::{StdError, StdResult};
+use crate::error::StoreError;
+use cosmwasm_std::{StdError, StdResult, Uint128};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+/// The IRI of the `xsd:string` datatype, a literal of this type being the same as a simple literal
+/// per RDF 1.1.
+pub const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// The IRI of the `xsd:boolean` datatype, whose values have two lexical forms each.
+pub const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+
+/// The IRI of the `rdf:langString` datatype, the one of the language tagged literals.
+pub const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// The IRI of the `rdf:type` property, linking a resource to one of its classes.
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// The IRI of the `owl:sameAs` property, stating two resources are the same.
+pub const OWL_SAME_AS: &str = "http://www.w3.org/2002/07/owl#sameAs";
+
+/// The IRI of the `owl:equivalentClass` property, stating two classes have the same instances.
+pub const OWL_EQUIVALENT_CLASS: &str = "http://www.w3.org/2002/07/owl#equivalentClass";
+
+/// The IRI of the `rdf:first` property, linking a node of an RDF collection to its item.
+pub const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+
+/// The IRI of the `rdf:rest` property, linking a node of an RDF collection to the next one.
+pub const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+
+/// An IRI known to be absolute, i.e. starting with a scheme as per
+/// [RFC 3987](https://www.rfc-editor.org/rfc/rfc3987#section-2.2), as required for the IRIs
+/// stored by the stores rejecting the relative ones, meaningless without the base they have been
+/// resolved against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbsoluteIri<'a>(&'a str);
+
+impl<'a> AbsoluteIri<'a> {
+    pub fn try_new(iri: &'a str) -> StdResult<Self> {
+        let is_absolute = iri.split_once(':').map_or(false, |(scheme, _)| {
+            let mut chars = scheme.chars();
+            chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+                && chars.all(|c| c.is_ascii_alphanumeric() || ['+', '-', '.'].contains(&c))
+        });
+        if !is_absolute {
+            return Err(StdError::generic_err(format!("Not an absolute IRI: {iri}")));
+        }
+
+        Ok(Self(iri))
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    pub fn scheme(&self) -> &'a str {
+        self.0.split_once(':').map_or(self.0, |(scheme, _)| scheme)
+    }
+}
+
 pub fn explode_iri(iri: &str) -> StdResult<(String, String)> {
     let mut marker_index: Option<usize> = None;
     for delim in ['#', '/', ':'] {
@@ -19,6 +76,52 @@ pub fn explode_iri(iri: &str) -> StdResult<(String, String)> {
     Err(StdError::generic_err("Couldn't extract IRI namespace"))
 }
 
+/// Checks the IRI only contains the characters permitted in IRIs as per
+/// [RFC 3987](https://www.rfc-editor.org/rfc/rfc3987#section-2.2), i.e. the unreserved, reserved
+/// and percent characters along with the non ASCII ones of the `ucschar` and `iprivate` ranges,
+/// spaces and control characters being notably rejected.
+pub fn check_iri_chars(iri: &str) -> StdResult<()> {
+    match iri.char_indices().find(|(_, c)| !is_iri_char(*c)) {
+        Some((index, c)) => Err(StdError::generic_err(format!(
+            "Invalid character {c:?} at byte {index} in IRI: {iri:?}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn is_iri_char(c: char) -> bool {
+    if c.is_ascii() {
+        return c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=%".contains(c);
+    }
+    matches!(c, '\u{A0}'..='\u{D7FF}' | '\u{E000}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFEF}')
+        || (c > '\u{FFFF}'
+            && (c as u32) & 0xFFFE != 0xFFFE
+            && !('\u{E0000}'..='\u{E0FFF}').contains(&c))
+}
+
+/// The path the skolem IRIs standing for blank nodes are minted under, as per
+/// [RDF 1.1](https://www.w3.org/TR/rdf11-concepts/#section-skolemization).
+pub const SKOLEM_PATH: &str = "/.well-known/genid/";
+
+/// Mints the skolem IRI standing for the blank node of the given label under the given base.
+pub fn skolem_iri(base: &str, label: &str) -> String {
+    format!("{}{SKOLEM_PATH}{label}", base.trim_end_matches('/'))
+}
+
+/// Returns the label of the blank node a skolem IRI minted under the given base stands for, if
+/// the IRI is one, see [skolem_iri]. Only the labels made of alphanumeric characters, `_`, `-` and
+/// `.` are recognized, being the ones any format can write as blank node labels.
+pub fn deskolemize_iri<'a>(iri: &'a str, base: &str) -> Option<&'a str> {
+    iri.strip_prefix(base.trim_end_matches('/'))
+        .and_then(|rest| rest.strip_prefix(SKOLEM_PATH))
+        .filter(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || ['_', '-', '.'].contains(&c))
+        })
+}
+
 // Expand a compacted URI (CURIE - URI with prefix) to a full URI.
 pub fn expand_uri(curie: &str, prefixes: &HashMap<String, String>) -> StdResult<String> {
     let idx = curie
@@ -28,18 +131,159 @@ pub fn expand_uri(curie: &str, prefixes: &HashMap<String, String>) -> StdResult<
     let prefix = curie[..idx].to_string();
     let namespace = prefixes
         .get(&prefix)
-        .ok_or_else(|| StdError::generic_err(format!("Prefix not found: {prefix}")))?;
+        .ok_or_else(|| StdError::generic_err(format!("Unknown prefix '{prefix}' in '{curie}'")))?;
     let suffix = curie[idx + 1..].to_string();
 
     Ok(format!("{namespace}{suffix}"))
 }
 
+/// Checks the byte size of an IRI doesn't exceed the given maximum, if any, the IRIs given as
+/// CURIEs having to be checked once expanded since a short CURIE can stand for a huge IRI.
+pub fn check_iri_byte_size(iri: &str, max: Option<Uint128>) -> StdResult<()> {
+    let size = Uint128::from(iri.len() as u128);
+    match max {
+        Some(max) if size > max => Err(StdError::generic_err(
+            StoreError::IriByteSize(size, max).to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the canonical lexical form of an `xsd:boolean` value, i.e. `true` for `1` and `false`
+/// for `0`, any other value being returned as is.
+pub fn canonical_boolean(value: &str) -> &str {
+    match value {
+        "1" => "true",
+        "0" => "false",
+        value => value,
+    }
+}
+
+// Compact a full URI into a CURIE using the longest namespace of the prefixes it starts with, if
+// any allows the CURIE to be expanded back to the same URI.
+pub fn compact_uri(uri: &str, prefixes: &HashMap<String, String>) -> Option<String> {
+    prefixes
+        .iter()
+        .filter_map(|(prefix, namespace)| {
+            uri.strip_prefix(namespace.as_str())
+                .filter(|suffix| !suffix.contains(':'))
+                .map(|suffix| (namespace.len(), prefix, suffix))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(a.1)))
+        .map(|(_, prefix, suffix)| format!("{prefix}:{suffix}"))
+}
+
+/// Normalizes the syntax of an IRI as per
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-6.2.2): the scheme and host are
+/// lowercased, the port is removed when being the default one of the scheme, and the hexadecimal
+/// digits of the percent-encoded octets are uppercased.
+pub fn normalize_iri(iri: &str) -> Cow<'_, str> {
+    let Some((scheme, rest)) = iri.split_once(':') else {
+        return Cow::Borrowed(iri);
+    };
+
+    let scheme = scheme.to_ascii_lowercase();
+    let mut normalized = format!("{scheme}:");
+    match rest.strip_prefix("//") {
+        Some(hierarchy) => {
+            let (authority, path) =
+                hierarchy.split_at(hierarchy.find(['/', '?', '#']).unwrap_or(hierarchy.len()));
+            let (userinfo, host_port) = authority
+                .rfind('@')
+                .map_or(("", authority), |i| authority.split_at(i + 1));
+            let (host, port) = match host_port.rfind(':') {
+                Some(i) if !host_port[i..].contains(']') => (&host_port[..i], &host_port[i + 1..]),
+                _ => (host_port, ""),
+            };
+
+            normalized.push_str("//");
+            normalized.push_str(userinfo);
+            normalized.push_str(&host.to_ascii_lowercase());
+            if !port.is_empty() && Some(port) != default_port(&scheme) {
+                normalized.push(':');
+                normalized.push_str(port);
+            }
+            normalized.push_str(path);
+        }
+        None => normalized.push_str(rest),
+    }
+
+    let mut hex_digits = 0;
+    let normalized: String = normalized
+        .chars()
+        .map(|c| {
+            if hex_digits > 0 && c.is_ascii_hexdigit() {
+                hex_digits -= 1;
+                return c.to_ascii_uppercase();
+            }
+            hex_digits = if c == '%' { 2 } else { 0 };
+            c
+        })
+        .collect();
+
+    if normalized == iri {
+        Cow::Borrowed(iri)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+fn default_port(scheme: &str) -> Option<&'static str> {
+    match scheme {
+        "http" | "ws" => Some("80"),
+        "https" | "wss" => Some("443"),
+        "ftp" => Some("21"),
+        _ => None,
+    }
+}
+
+/// Renders an IRI for a user-facing message: as a CURIE if one of the prefixes allows it, see
+/// [compact_uri], between angle brackets otherwise.
+pub fn render_iri(iri: &str, prefixes: &HashMap<String, String>) -> String {
+    compact_uri(iri, prefixes).unwrap_or_else(|| format!("<{iri}>"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::msg::Prefix;
     use crate::rdf::PrefixMap;
 
+    #[test]
+    fn absolute_iri() {
+        for iri in [
+            "http://www.w3.org/2001/XMLSchema#dateTime",
+            "urn:uuid:6e8bc430-9c3a-11d9-9669-0800200c9a66",
+            "did:key:0x04d1f1b8f8a7a28f9a5a254c326a963a22f5a5b5d5f5e5d5c5b5a",
+            "git+ssh://example.com/repo",
+            "a:",
+        ] {
+            let absolute = AbsoluteIri::try_new(iri);
+            assert_eq!(absolute.map(|iri| iri.as_str()), Ok(iri));
+        }
+        assert_eq!(
+            AbsoluteIri::try_new("urn:uuid:6e8bc430").map(|iri| iri.scheme()),
+            Ok("urn")
+        );
+
+        for iri in [
+            "",
+            "relative/path",
+            "/absolute/path",
+            "#fragment",
+            "../s",
+            "_:b0",
+            "1http://example.com",
+            "ht tp://example.com",
+            ":no-scheme",
+        ] {
+            assert_eq!(
+                AbsoluteIri::try_new(iri),
+                Err(StdError::generic_err(format!("Not an absolute IRI: {iri}")))
+            );
+        }
+    }
+
     #[test]
     fn proper_explode_iri() {
         assert_eq!(
@@ -105,12 +349,16 @@ mod tests {
 
         assert_eq!(
             expand_uri("unknown:resource", prefixes),
-            Err(StdError::generic_err("Prefix not found: unknown"))
+            Err(StdError::generic_err(
+                "Unknown prefix 'unknown' in 'unknown:resource'"
+            ))
         );
 
         assert_eq!(
             expand_uri("malformed_curie:", prefixes),
-            Err(StdError::generic_err("Prefix not found: malformed_curie"))
+            Err(StdError::generic_err(
+                "Unknown prefix 'malformed_curie' in 'malformed_curie:'"
+            ))
         );
 
         assert_eq!(
@@ -118,4 +366,133 @@ mod tests {
             Err(StdError::generic_err("Malformed CURIE: malformed_curie"))
         );
     }
+
+    #[test]
+    fn proper_compact_uri() {
+        let prefixes = &<PrefixMap>::from(vec![
+            Prefix {
+                prefix: "ex".to_string(),
+                namespace: "http://example.com/".to_string(),
+            },
+            Prefix {
+                prefix: "exv".to_string(),
+                namespace: "http://example.com/vocab#".to_string(),
+            },
+        ])
+        .into_inner();
+
+        for (uri, expected) in [
+            ("http://example.com/resource", Some("ex:resource")),
+            ("http://example.com/", Some("ex:")),
+            ("http://example.com/vocab#term", Some("exv:term")),
+            ("http://example.com/a:b", None),
+            ("http://example.org/resource", None),
+        ] {
+            let curie = compact_uri(uri, prefixes);
+            assert_eq!(curie.as_deref(), expected);
+            if let Some(curie) = curie {
+                assert_eq!(expand_uri(&curie, prefixes), Ok(uri.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn proper_skolemization() {
+        for base in ["https://a.org", "https://a.org/"] {
+            let iri = skolem_iri(base, "b0");
+            assert_eq!(iri, "https://a.org/.well-known/genid/b0");
+            assert_eq!(deskolemize_iri(&iri, base), Some("b0"));
+        }
+
+        let cases = vec![
+            ("https://b.org/.well-known/genid/b0", None),
+            ("https://a.org/.well-known/genid/", None),
+            ("https://a.org/b0", None),
+            ("https://a.org/.well-known/genid/b/0", None),
+            ("https://a.org/.well-known/genid/b-0", Some("b-0")),
+        ];
+        for (iri, expected) in cases {
+            assert_eq!(deskolemize_iri(iri, "https://a.org"), expected);
+        }
+    }
+
+    #[test]
+    fn proper_check_iri_chars() {
+        let cases = vec![
+            ("http://example.com/a-b_c.d~e?q=1&r=%2F#f", Ok(())),
+            ("urn:isbn:0-486-27557-4", Ok(())),
+            ("http://example.com/caf\u{E9}/\u{1F600}", Ok(())),
+            (
+                "http://example.com/a b",
+                Err(r#"Invalid character ' ' at byte 20 in IRI: "http://example.com/a b""#),
+            ),
+            (
+                "http://example.com/a\tb",
+                Err(r#"Invalid character '\t' at byte 20 in IRI: "http://example.com/a\tb""#),
+            ),
+            (
+                "http://example.com/\u{1}",
+                Err(r#"Invalid character '\u{1}' at byte 19 in IRI: "http://example.com/\u{1}""#),
+            ),
+            (
+                "http://example.com/\u{7F}",
+                Err(r#"Invalid character '\u{7f}' at byte 19 in IRI: "http://example.com/\u{7f}""#),
+            ),
+            (
+                "http://example.com/<a>",
+                Err(r#"Invalid character '<' at byte 19 in IRI: "http://example.com/<a>""#),
+            ),
+            (
+                "http://example.com/\u{FFFD}",
+                Err(
+                    "Invalid character '\u{FFFD}' at byte 19 in IRI: \"http://example.com/\u{FFFD}\"",
+                ),
+            ),
+        ];
+
+        for (iri, expected) in cases {
+            assert_eq!(
+                check_iri_chars(iri),
+                expected.map_err(StdError::generic_err),
+                "{iri}"
+            );
+        }
+    }
+
+    #[test]
+    fn proper_normalize_iri() {
+        let cases = vec![
+            // scheme case
+            ("HTTP://example.com/x", "http://example.com/x"),
+            ("Urn:ISBN:123", "urn:ISBN:123"),
+            // host case
+            ("http://Example.COM/X", "http://example.com/X"),
+            ("http://User@Example.com/x", "http://User@example.com/x"),
+            ("http://[2001:DB8::1]:8080/x", "http://[2001:db8::1]:8080/x"),
+            // default port
+            ("http://Example.com:80/x", "http://example.com/x"),
+            ("https://example.com:443", "https://example.com"),
+            ("https://example.com:80/x", "https://example.com:80/x"),
+            ("http://example.com:/x", "http://example.com/x"),
+            ("http://[2001:db8::1]:80/x", "http://[2001:db8::1]/x"),
+            ("http://example.com/a:80", "http://example.com/a:80"),
+            // percent-encoding case
+            (
+                "http://example.com/a%c3%a9?q=%2f#%3a",
+                "http://example.com/a%C3%A9?q=%2F#%3A",
+            ),
+            ("http://example.com/100%", "http://example.com/100%"),
+            // already normalized
+            ("http://example.com/x%2F", "http://example.com/x%2F"),
+            ("did:key:abc", "did:key:abc"),
+        ];
+
+        for (iri, expected) in cases {
+            assert_eq!(normalize_iri(iri), expected);
+        }
+        assert!(matches!(
+            normalize_iri("http://example.com/x"),
+            Cow::Borrowed(_)
+        ));
+    }
 }
@@ -0,0 +1,68 @@
+use cosmwasm_std::{StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
+use serde::{Deserialize, Serialize};
+
+/// The number of most recent idempotency keys retained, older ones being pruned as new ones are
+/// recorded.
+pub const IDEMPOTENCY_KEY_RETENTION: u64 = 100;
+
+/// Store the sequence number to assign to the next recorded idempotency key.
+pub const IDEMPOTENCY_KEY_SEQUENCE: Item<'_, u64> = Item::new("idempotency_key_sequence");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    /// The sequence number of the record, giving its age relative to the other records.
+    pub sequence: u64,
+
+    /// The number of triples inserted by the original insertion.
+    pub triple_count: Uint128,
+}
+
+pub struct IdempotencyIndexes<'a> {
+    pub sequence: UniqueIndex<'a, u64, IdempotencyRecord, String>,
+}
+
+impl IndexList<IdempotencyRecord> for IdempotencyIndexes<'_> {
+    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<IdempotencyRecord>> + '_> {
+        let sequence: &dyn Index<IdempotencyRecord> = &self.sequence;
+        Box::new(vec![sequence].into_iter())
+    }
+}
+
+pub fn idempotency_keys<'a>() -> IndexedMap<'a, String, IdempotencyRecord, IdempotencyIndexes<'a>> {
+    IndexedMap::new(
+        "IDEMPOTENCY_KEY",
+        IdempotencyIndexes {
+            sequence: UniqueIndex::new(|record| record.sequence, "IDEMPOTENCY_KEY__SEQUENCE"),
+        },
+    )
+}
+
+/// Records the outcome of the insertion identified by the given key, pruning the oldest record
+/// falling out of the retention window.
+pub fn record_idempotency_key(
+    storage: &mut dyn Storage,
+    key: String,
+    triple_count: Uint128,
+) -> StdResult<()> {
+    let sequence = IDEMPOTENCY_KEY_SEQUENCE
+        .may_load(storage)?
+        .unwrap_or_default();
+
+    if sequence >= IDEMPOTENCY_KEY_RETENTION {
+        let expired = sequence - IDEMPOTENCY_KEY_RETENTION;
+        if let Some((expired_key, _)) = idempotency_keys().idx.sequence.item(storage, expired)? {
+            idempotency_keys().remove(storage, String::from_utf8(expired_key)?)?;
+        }
+    }
+
+    idempotency_keys().save(
+        storage,
+        key,
+        &IdempotencyRecord {
+            sequence,
+            triple_count,
+        },
+    )?;
+    IDEMPOTENCY_KEY_SEQUENCE.save(storage, &(sequence + 1))
+}
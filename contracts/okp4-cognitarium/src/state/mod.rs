@@ -1,7 +1,11 @@
+mod idempotency;
 mod namespaces;
+mod prefixes;
 mod store;
 mod triples;
 
+pub use idempotency::*;
 pub use namespaces::*;
+pub use prefixes::*;
 pub use store::*;
 pub use triples::*;
@@ -1,10 +1,42 @@
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 /// Store a key increment used a unique key for referencing a namespace. Given the size of an `u128`
 /// there is no need to implement a garbage collector mechanism in case some namespaces are removed.
 pub const NAMESPACE_KEY_INCREMENT: Item<'_, u128> = Item::new("namespace_key");
 
+/// Store the progress of the ongoing namespace keys compaction, if any.
+pub const NAMESPACE_COMPACTION: Item<'_, NamespaceCompaction> = Item::new("namespace_compaction");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NamespaceCompaction {
+    /// The key to assign to the next namespace to compact, all the namespaces having a lower key
+    /// being already compacted.
+    pub next_key: u128,
+
+    /// The raw key of the last triple scanned while remapping the namespace being compacted.
+    pub after: Option<Vec<u8>>,
+}
+
+/// Store the progress of the ongoing orphaned namespaces repair, if any.
+pub const NAMESPACE_REPAIR: Item<'_, NamespaceRepair> = Item::new("namespace_repair");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NamespaceRepair {
+    /// The raw key of the last triple scanned while collecting the referenced namespaces.
+    pub after: Option<Vec<u8>>,
+
+    /// The keys of the namespaces referenced by the triples scanned so far.
+    pub referenced: BTreeSet<u128>,
+
+    /// Tells if all the triples have been scanned, the namespaces being then checked.
+    pub scanned: bool,
+
+    /// The last namespace checked against the referenced ones.
+    pub after_namespace: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Namespace {
     /// The namespace value.
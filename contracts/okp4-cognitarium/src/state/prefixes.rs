@@ -0,0 +1,23 @@
+use crate::msg::Prefix;
+use crate::rdf::PrefixMap;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::Map;
+
+/// Store the namespaces of the prefixes registered in the store, by prefix.
+pub const PREFIXES: Map<'_, String, String> = Map::new("prefixes");
+
+/// Loads the registered prefixes, ordered by prefix.
+pub fn load_prefixes(storage: &dyn Storage) -> StdResult<Vec<Prefix>> {
+    PREFIXES
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(prefix, namespace)| Prefix { prefix, namespace }))
+        .collect()
+}
+
+/// Builds the prefix map of an operation from the registered prefixes and the provided ones, the
+/// latter taking precedence over the former.
+pub fn merge_prefixes(storage: &dyn Storage, prefixes: Vec<Prefix>) -> StdResult<PrefixMap> {
+    let mut merged = load_prefixes(storage)?;
+    merged.extend(prefixes);
+    Ok(PrefixMap::from(merged))
+}
@@ -1,8 +1,11 @@
 use crate::msg;
 use crate::msg::StoreResponse;
-use cosmwasm_std::{Addr, Uint128};
+use crate::rdf;
+use cosmwasm_std::{Addr, StdResult, Uint128};
 use cw_storage_plus::Item;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 pub const STORE: Item<'_, Store> = Item::new("store");
 
@@ -11,6 +14,50 @@ pub struct Store {
     pub owner: Addr,
     pub limits: StoreLimits,
     pub stat: StoreStat,
+    #[serde(default)]
+    pub shapes: Vec<PredicateShape>,
+    #[serde(default)]
+    pub retain_empty_namespaces: bool,
+    #[serde(default)]
+    pub functional_properties: Vec<String>,
+    #[serde(default)]
+    pub normalize_iris: bool,
+    #[serde(default)]
+    pub full_iri_keys: bool,
+    #[serde(default)]
+    pub validate_language_tags: bool,
+    #[serde(default)]
+    pub record_triple_metadata: bool,
+    #[serde(default)]
+    pub skolem_base: Option<String>,
+    #[serde(default)]
+    pub lenient_rdf_xml: bool,
+    #[serde(default)]
+    pub byte_accounting: msg::ByteAccounting,
+    #[serde(default)]
+    pub subject_index: bool,
+    #[serde(default)]
+    pub invalid_utf8_replacement: Option<String>,
+    #[serde(default)]
+    pub label_index: bool,
+    #[serde(default)]
+    pub strict_iri_chars: bool,
+    #[serde(default)]
+    pub dedup_imports: bool,
+    #[serde(default)]
+    pub canonical_booleans: bool,
+    #[serde(default)]
+    pub class_hierarchy: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub skip_empty_literals: bool,
+    #[serde(default)]
+    pub reject_literal_types: bool,
+    #[serde(default)]
+    pub require_absolute_iris: bool,
+    /// Whether all the triples are in the predicate index, which is not the case of the triples
+    /// stored before it was maintained until it is built.
+    #[serde(default)]
+    pub predicate_index: bool,
 }
 
 impl Store {
@@ -19,10 +66,48 @@ impl Store {
             owner,
             limits,
             stat: StoreStat::default(),
+            shapes: Vec::new(),
+            retain_empty_namespaces: false,
+            functional_properties: Vec::new(),
+            normalize_iris: false,
+            full_iri_keys: false,
+            validate_language_tags: false,
+            record_triple_metadata: false,
+            skolem_base: None,
+            lenient_rdf_xml: false,
+            byte_accounting: msg::ByteAccounting::Raw,
+            subject_index: false,
+            invalid_utf8_replacement: None,
+            label_index: false,
+            strict_iri_chars: false,
+            dedup_imports: false,
+            canonical_booleans: false,
+            class_hierarchy: BTreeMap::new(),
+            skip_empty_literals: false,
+            reject_literal_types: false,
+            require_absolute_iris: false,
+            predicate_index: true,
         }
     }
 }
 
+impl Store {
+    /// Splits an IRI into the namespace and local value its node is keyed by, being the empty
+    /// namespace and the full IRI if the store keys the nodes by full IRI. The IRI is normalized
+    /// first if the store normalizes them.
+    pub fn split_iri(&self, iri: &str) -> StdResult<(String, String)> {
+        let iri = if self.normalize_iris {
+            rdf::normalize_iri(iri)
+        } else {
+            Cow::Borrowed(iri)
+        };
+        if self.full_iri_keys {
+            return Ok((String::new(), iri.to_string()));
+        }
+        rdf::explode_iri(&iri)
+    }
+}
+
 impl From<Store> for StoreResponse {
     fn from(value: Store) -> Self {
         Self {
@@ -38,10 +123,28 @@ pub struct StoreLimits {
     pub max_triple_count: Uint128,
     pub max_byte_size: Uint128,
     pub max_triple_byte_size: Uint128,
+    #[serde(default)]
+    pub max_literal_byte_size: Option<Uint128>,
     pub max_query_limit: u32,
     pub max_query_variable_count: u32,
     pub max_insert_data_byte_size: Uint128,
     pub max_insert_data_triple_count: Uint128,
+    #[serde(default)]
+    pub allowed_schemes: Option<Vec<String>>,
+    #[serde(default)]
+    pub max_triples_per_subject: Option<Uint128>,
+    #[serde(default)]
+    pub max_triples_per_call: Option<Uint128>,
+    #[serde(default)]
+    pub max_iri_byte_size: Option<Uint128>,
+    #[serde(default)]
+    pub max_collection_triple_count: Option<Uint128>,
+    #[serde(default)]
+    pub max_blank_nodes_per_import: Option<Uint128>,
+    #[serde(default)]
+    pub max_query_output_byte_size: Option<Uint128>,
+    #[serde(default)]
+    pub max_insert_data_byte_size_per_subject: Option<Uint128>,
 }
 
 impl From<msg::StoreLimitsInput> for StoreLimits {
@@ -50,10 +153,19 @@ impl From<msg::StoreLimitsInput> for StoreLimits {
             max_triple_count: value.max_triple_count,
             max_byte_size: value.max_byte_size,
             max_triple_byte_size: value.max_triple_byte_size,
+            max_literal_byte_size: value.max_literal_byte_size,
             max_query_limit: value.max_query_limit,
             max_query_variable_count: value.max_query_variable_count,
             max_insert_data_byte_size: value.max_insert_data_byte_size,
             max_insert_data_triple_count: value.max_insert_data_triple_count,
+            allowed_schemes: value.allowed_schemes,
+            max_triples_per_subject: value.max_triples_per_subject,
+            max_triples_per_call: value.max_triples_per_call,
+            max_iri_byte_size: value.max_iri_byte_size,
+            max_collection_triple_count: value.max_collection_triple_count,
+            max_blank_nodes_per_import: value.max_blank_nodes_per_import,
+            max_query_output_byte_size: value.max_query_output_byte_size,
+            max_insert_data_byte_size_per_subject: value.max_insert_data_byte_size_per_subject,
         }
     }
 }
@@ -64,10 +176,38 @@ impl From<StoreLimits> for msg::StoreLimits {
             max_triple_count: value.max_triple_count,
             max_byte_size: value.max_byte_size,
             max_triple_byte_size: value.max_triple_byte_size,
+            max_literal_byte_size: value.max_literal_byte_size,
             max_query_limit: value.max_query_limit,
             max_query_variable_count: value.max_query_variable_count,
             max_insert_data_byte_size: value.max_insert_data_byte_size,
             max_insert_data_triple_count: value.max_insert_data_triple_count,
+            allowed_schemes: value.allowed_schemes,
+            max_triples_per_subject: value.max_triples_per_subject,
+            max_triples_per_call: value.max_triples_per_call,
+            max_iri_byte_size: value.max_iri_byte_size,
+            max_collection_triple_count: value.max_collection_triple_count,
+            max_blank_nodes_per_import: value.max_blank_nodes_per_import,
+            max_query_output_byte_size: value.max_query_output_byte_size,
+            max_insert_data_byte_size_per_subject: value.max_insert_data_byte_size_per_subject,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PredicateShape {
+    pub predicate: String,
+    pub min_count: Option<u32>,
+    pub max_count: Option<u32>,
+    pub datatype: Option<String>,
+}
+
+impl From<msg::PredicateShape> for PredicateShape {
+    fn from(value: msg::PredicateShape) -> Self {
+        PredicateShape {
+            predicate: value.predicate,
+            min_count: value.min_count,
+            max_count: value.max_count,
+            datatype: value.datatype,
         }
     }
 }
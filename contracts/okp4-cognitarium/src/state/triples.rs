@@ -1,6 +1,6 @@
 use blake3::Hash;
-use cosmwasm_std::StdResult;
-use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use serde::{Deserialize, Serialize};
 
 /// Represents a triple primary key as a tuple of:
@@ -11,12 +11,14 @@ pub type TriplePK<'a> = (&'a [u8], Vec<u8>, Vec<u8>);
 
 pub struct TripleIndexes<'a> {
     pub subject_and_predicate: MultiIndex<'a, (Vec<u8>, Vec<u8>), Triple, TriplePK<'a>>,
+    pub predicate: MultiIndex<'a, Vec<u8>, Triple, TriplePK<'a>>,
 }
 
 impl IndexList<Triple> for TripleIndexes<'_> {
     fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<Triple>> + '_> {
         let subject_and_predicate: &dyn Index<Triple> = &self.subject_and_predicate;
-        Box::new(vec![subject_and_predicate].into_iter())
+        let predicate: &dyn Index<Triple> = &self.predicate;
+        Box::new(vec![subject_and_predicate, predicate].into_iter())
     }
 }
 
@@ -29,15 +31,178 @@ pub fn triples<'a>() -> IndexedMap<'a, TriplePK<'a>, Triple, TripleIndexes<'a>>
                 "TRIPLE",
                 "TRIPLE__SUBJECT_PREDICATE",
             ),
+            predicate: MultiIndex::new(
+                |_pk, triple| triple.predicate.key(),
+                "TRIPLE",
+                "TRIPLE__PREDICATE",
+            ),
         },
     )
 }
 
+/// Store the progress of the ongoing build of the predicate index of the triples stored before it
+/// was maintained, i.e. the raw key of the last triple indexed, if any.
+pub const PREDICATE_INDEXING: Item<'_, Vec<u8>> = Item::new("predicate_indexing");
+
+/// Represents a triple key in the [subject_triples] map as a tuple of:
+/// - Subject in a binary format
+/// - Predicate in a binary format
+/// - Object hash
+pub type SubjectTriplePK = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+/// The copies of the triples keyed by subject, i.e. sorted for the subject range scans to be read
+/// at once instead of resolving each triple through the subject and predicate index. Only
+/// maintained by the stores configured so.
+pub fn subject_triples<'a>() -> Map<'a, SubjectTriplePK, Triple> {
+    Map::new("TRIPLE_BY_SUBJECT")
+}
+
+/// Represents a key in the [labels] map as a tuple of:
+/// - Subject in a binary format
+/// - Predicate in a binary format
+/// - Language tag, lowercased
+pub type LabelPK = (Vec<u8>, Vec<u8>, String);
+
+/// The values of the language-tagged literals keyed by subject, predicate and language, for the
+/// labels of a subject in a given language to be read at once instead of scanning all its objects.
+/// Only maintained by the stores configured so.
+pub fn labels<'a>() -> Map<'a, LabelPK, Vec<String>> {
+    Map::new("LABEL")
+}
+
+/// Adds the value of the triple to the [labels] map, if its object is a language-tagged literal.
+pub fn index_label(storage: &mut dyn Storage, triple: &Triple) -> StdResult<()> {
+    if let Some((key, value)) = triple.label_entry() {
+        let mut values = labels().may_load(storage, key.clone())?.unwrap_or_default();
+        if !values.iter().any(|v| v == value) {
+            values.push(value.to_string());
+            labels().save(storage, key, &values)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the value of the triple from the [labels] map, if its object is a language-tagged
+/// literal.
+pub fn unindex_label(storage: &mut dyn Storage, triple: &Triple) -> StdResult<()> {
+    if let Some((key, value)) = triple.label_entry() {
+        let mut values = labels().may_load(storage, key.clone())?.unwrap_or_default();
+        values.retain(|v| v != value);
+        if values.is_empty() {
+            labels().remove(storage, key);
+        } else {
+            labels().save(storage, key, &values)?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Triple {
     pub subject: Subject,
     pub predicate: Predicate,
     pub object: Object,
+    /// The metadata of the triple, only recorded by the stores configured so.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<TripleMetadata>,
+    /// The named graph the triple belongs to, [None] standing for the default graph. Not serialized
+    /// when [None], the triples stored before its introduction belonging to the default graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph: Option<Node>,
+}
+
+/// Contains the metadata recorded alongside a triple.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TripleMetadata {
+    /// The height of the block the triple has been inserted at.
+    pub inserted_at: u64,
+    /// The height of the block the triple has last been asserted at, either by an insertion or a
+    /// touch.
+    pub last_seen_at: u64,
+}
+
+impl TripleMetadata {
+    /// Returns the metadata of a triple asserted at the given height, given its current ones.
+    pub fn seen_at(current: Option<TripleMetadata>, height: u64) -> Self {
+        Self {
+            inserted_at: current.map_or(height, |metadata| metadata.inserted_at),
+            last_seen_at: height,
+        }
+    }
+}
+
+impl Triple {
+    /// Tells if any node of the triple, including the datatype of a typed literal, is in the
+    /// namespace with the given key.
+    pub fn references_namespace(&self, ns_key: u128) -> bool {
+        self.namespace_keys().contains(&ns_key)
+    }
+
+    /// Returns the key of the triple in the [subject_triples] map.
+    pub fn subject_key(&self) -> SubjectTriplePK {
+        (
+            self.subject.key(),
+            self.predicate.key(),
+            self.object.as_hash().as_bytes().to_vec(),
+        )
+    }
+
+    /// Returns the key of the triple in the [labels] map along with its value, if its object is a
+    /// language-tagged literal.
+    pub fn label_entry(&self) -> Option<(LabelPK, &str)> {
+        match &self.object {
+            Object::Literal(Literal::I18NString { value, language }) => Some((
+                (
+                    self.subject.key(),
+                    self.predicate.key(),
+                    language.to_ascii_lowercase(),
+                ),
+                value,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of the namespaces of the nodes of the triple, including the datatype of a
+    /// typed literal and the named graph, a key being repeated for each node in its namespace.
+    pub fn namespace_keys(&self) -> Vec<u128> {
+        let mut keys = vec![self.predicate.namespace];
+        if let Subject::Named(n) = &self.subject {
+            keys.push(n.namespace);
+        }
+        if let Some(graph) = &self.graph {
+            keys.push(graph.namespace);
+        }
+        match &self.object {
+            Object::Named(n) => keys.push(n.namespace),
+            Object::Literal(Literal::Typed { datatype, .. }) => keys.push(datatype.namespace),
+            Object::Blank(_) | Object::Literal(_) => {}
+        }
+        keys
+    }
+
+    /// Moves all the nodes of the triple in the namespace `from`, including the datatype of a
+    /// typed literal and the named graph, to the namespace `to`.
+    pub fn remap_namespace(&mut self, from: u128, to: u128) {
+        let remap = |node: &mut Node| {
+            if node.namespace == from {
+                node.namespace = to;
+            }
+        };
+
+        if let Subject::Named(n) = &mut self.subject {
+            remap(n);
+        }
+        remap(&mut self.predicate);
+        if let Some(graph) = &mut self.graph {
+            remap(graph);
+        }
+        match &mut self.object {
+            Object::Named(n) => remap(n),
+            Object::Literal(Literal::Typed { datatype, .. }) => remap(datatype),
+            Object::Blank(_) | Object::Literal(_) => {}
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -84,23 +249,23 @@ impl Object {
         match self {
             Object::Named(n) => {
                 hasher
-                    .update(&[b'n'])
+                    .update(b"n")
                     .update(n.namespace.to_be_bytes().as_slice())
                     .update(n.value.as_bytes());
             }
             Object::Blank(n) => {
-                hasher.update(&[b'b']).update(n.as_bytes());
+                hasher.update(b"b").update(n.as_bytes());
             }
             Object::Literal(l) => {
-                hasher.update(&[b'l']);
+                hasher.update(b"l");
                 match l {
-                    Literal::Simple { value } => hasher.update(&[b's']).update(value.as_bytes()),
+                    Literal::Simple { value } => hasher.update(b"s").update(value.as_bytes()),
                     Literal::I18NString { value, language } => hasher
-                        .update(&[b'i'])
+                        .update(b"i")
                         .update(value.as_bytes())
                         .update(language.as_bytes()),
                     Literal::Typed { value, datatype } => hasher
-                        .update(&[b't'])
+                        .update(b"t")
                         .update(value.as_bytes())
                         .update(datatype.namespace.to_be_bytes().as_slice())
                         .update(datatype.value.as_bytes()),
@@ -148,6 +313,7 @@ pub enum Literal {
 #[cfg(test)]
 mod test {
     use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
 
     #[test]
     fn object_hash() {
@@ -264,4 +430,39 @@ mod test {
             assert_ne!(case.0.as_hash(), case.1.as_hash())
         }
     }
+
+    #[test]
+    fn default_graph_serialization() {
+        let legacy = r#"{"subject":{"Named":{"namespace":"0","value":"s"}},"predicate":{"namespace":"0","value":"p"},"object":{"Blank":"o"}}"#;
+        let triple = Triple {
+            subject: Subject::Named(Node {
+                namespace: 0,
+                value: "s".to_string(),
+            }),
+            predicate: Node {
+                namespace: 0,
+                value: "p".to_string(),
+            },
+            object: Object::Blank("o".to_string()),
+            metadata: None,
+            graph: None,
+        };
+
+        assert_eq!(from_slice::<Triple>(legacy.as_bytes()), Ok(triple.clone()));
+        assert_eq!(to_vec(&triple), Ok(legacy.as_bytes().to_vec()));
+        assert_eq!(triple.namespace_keys(), vec![0, 0]);
+
+        let named = Triple {
+            graph: Some(Node {
+                namespace: 1,
+                value: "g".to_string(),
+            }),
+            ..triple
+        };
+        assert_eq!(
+            from_slice::<Triple>(&to_vec(&named).unwrap()),
+            Ok(named.clone())
+        );
+        assert_eq!(named.namespace_keys(), vec![0, 0, 1]);
+    }
 }
@@ -0,0 +1,124 @@
+use crate::state::{
+    index_label, namespaces, subject_triples, triples, unindex_label, NamespaceCompaction, Store,
+    Triple, NAMESPACE_COMPACTION, NAMESPACE_KEY_INCREMENT, STORE,
+};
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+/// Remaps the namespace keys to a dense range, rewriting the triples referencing them, so the
+/// keys freed by removed namespaces are reclaimed.
+///
+/// The namespaces are compacted one at a time in ascending key order: the new key of a namespace
+/// is always lower than the keys yet to compact and never collides with them. Remapping a
+/// namespace requires a full scan of the triples, the `limit` bounds the number of triples and
+/// namespaces processed by a call, the progress being saved so a next call resumes it.
+///
+/// Returns whether the compaction is complete.
+pub fn compact_namespaces(storage: &mut dyn Storage, limit: usize) -> StdResult<bool> {
+    let mut compaction = NAMESPACE_COMPACTION
+        .may_load(storage)?
+        .unwrap_or(NamespaceCompaction {
+            next_key: 0,
+            after: None,
+        });
+    let store = STORE.load(storage)?;
+
+    let mut budget = limit;
+    while budget > 0 {
+        budget -= 1;
+        let next = namespaces()
+            .idx
+            .key
+            .range_raw(
+                storage,
+                Some(Bound::inclusive(compaction.next_key)),
+                None,
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?;
+
+        let mut namespace = match next {
+            Some((_, namespace)) => namespace,
+            None => {
+                NAMESPACE_KEY_INCREMENT.save(storage, &compaction.next_key)?;
+                NAMESPACE_COMPACTION.remove(storage);
+                return Ok(true);
+            }
+        };
+
+        if namespace.key == compaction.next_key {
+            compaction.next_key += 1;
+            continue;
+        }
+
+        let scanned = triples()
+            .range_raw(
+                storage,
+                compaction.after.clone().map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?;
+        let exhausted = scanned.len() < budget;
+        budget -= scanned.len();
+
+        for (key, triple) in scanned {
+            if triple.references_namespace(namespace.key) {
+                remap_triple(storage, triple, namespace.key, compaction.next_key, &store)?;
+            }
+            compaction.after = Some(key);
+        }
+
+        if exhausted {
+            namespace.key = compaction.next_key;
+            namespaces().save(storage, namespace.value.clone(), &namespace)?;
+            compaction.next_key += 1;
+            compaction.after = None;
+        }
+    }
+
+    NAMESPACE_COMPACTION.save(storage, &compaction)?;
+    Ok(false)
+}
+
+fn remap_triple(
+    storage: &mut dyn Storage,
+    mut triple: Triple,
+    from: u128,
+    to: u128,
+    store: &Store,
+) -> StdResult<()> {
+    if store.subject_index {
+        subject_triples().remove(storage, triple.subject_key());
+    }
+    if store.label_index {
+        unindex_label(storage, &triple)?;
+    }
+    triples().remove(
+        storage,
+        (
+            triple.object.as_hash().as_bytes(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        ),
+    )?;
+
+    triple.remap_namespace(from, to);
+    if store.subject_index {
+        subject_triples().save(storage, triple.subject_key(), &triple)?;
+    }
+    if store.label_index {
+        index_label(storage, &triple)?;
+    }
+    triples().save(
+        storage,
+        (
+            triple.object.as_hash().as_bytes(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        ),
+        &triple,
+    )
+}
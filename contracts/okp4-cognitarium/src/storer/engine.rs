@@ -1,24 +1,141 @@
 use crate::error::StoreError;
-use crate::rdf::TripleReader;
+use crate::querier::triple_to_atom;
+use crate::rdf::{AbsoluteIri, TripleReader};
 use crate::state::{
-    namespaces, triples, Literal, Namespace, Node, Object, Store, Subject, Triple,
-    NAMESPACE_KEY_INCREMENT, STORE,
+    idempotency_keys, index_label, namespaces, record_idempotency_key, subject_triples, triples,
+    unindex_label, Literal, Namespace, Node, Object, PredicateShape, Store, Subject, Triple,
+    TripleMetadata, NAMESPACE_KEY_INCREMENT, STORE,
 };
+use crate::storer::explode_cache::IriExplodeCache;
 use crate::{rdf, ContractError};
 use blake3::Hash;
-use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
 use rio_api::model;
 use rio_api::model::Term;
-use std::collections::BTreeMap;
+use std::any::type_name;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
 use std::io::BufRead;
 
+/// The maximum number of namespaces the namespace table can hold to be preloaded in memory when
+/// storing triples, a cap of zero disabling the preload.
+const NS_PRELOAD_CAP: u128 = 64;
+
+/// The maximum number of entries of the namespace table scanned when warming the namespaces from
+/// the hints of an import, a cap of zero disabling the warmup.
+const NS_WARMUP_CAP: usize = 64;
+
+/// The maximum number of distinct triples remembered when deduplicating the triples of an import,
+/// the triples read once it is reached being stored without being deduplicated.
+const IMPORT_DEDUP_CAP: usize = 1024;
+
+/// The maximum number of exploded IRIs kept in cache when storing triples, a capacity of zero
+/// disabling the cache.
+const EXPLODE_CACHE_CAPACITY: usize = 32;
+
+/// Describes why an atom cannot be deleted from the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AtomProblem {
+    /// The atom cannot be converted into a triple.
+    Invalid(String),
+    /// A namespace the atom refers to doesn't exist in the store.
+    MissingNamespace(String),
+    /// The triple doesn't exist in the store.
+    NotFound,
+}
+
+/// The outcome of a delete preview, telling which atoms would be deleted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletePreview {
+    /// The atoms resolving to triples existing in the store, which would be deleted.
+    pub existing: Vec<rdf::Atom>,
+    /// The atoms that wouldn't be deleted, along with the reason why.
+    pub missing: Vec<(rdf::Atom, AtomProblem)>,
+}
+
+/// The outcome of a diff of triples against the store, telling which ones an insertion would add.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportDiff {
+    /// The number of distinct triples not in the store.
+    pub new_count: Uint128,
+    /// The number of distinct triples already in the store.
+    pub existing_count: Uint128,
+    /// The triples not in the store, if requested.
+    pub new: Option<Vec<rdf::Atom>>,
+}
+
+/// The signed variation of the number of triples in the store resulting from an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TripleCountDelta {
+    Added(Uint128),
+    Removed(Uint128),
+}
+
+impl TripleCountDelta {
+    fn between(initial: Uint128, current: Uint128) -> Self {
+        if current >= initial {
+            TripleCountDelta::Added(current - initial)
+        } else {
+            TripleCountDelta::Removed(initial - current)
+        }
+    }
+
+    /// Returns the number of triples added or removed (absolute value).
+    pub fn count(&self) -> Uint128 {
+        match self {
+            TripleCountDelta::Added(count) | TripleCountDelta::Removed(count) => *count,
+        }
+    }
+}
+
+impl fmt::Display for TripleCountDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TripleCountDelta::Added(count) => write!(f, "{count}"),
+            TripleCountDelta::Removed(count) => write!(f, "-{count}"),
+        }
+    }
+}
+
+/// The outcome of a call storing triples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreProgress {
+    /// The number of triples added to the store.
+    pub count: Uint128,
+    /// The number of triples processed.
+    pub processed: Uint128,
+    /// The position of the first triple left to process, if the limit of triples processed per
+    /// call has been reached before the end of the data.
+    pub resume_from: Option<Uint128>,
+    /// The number of triples skipped for having an empty literal as object, see
+    /// [Store::skip_empty_literals].
+    pub empty_literal_count: Uint128,
+}
+
 pub struct StoreEngine<'a> {
     storage: &'a mut dyn Storage,
     store: Store,
     ns_key_inc_offset: u128,
     ns_cache: BTreeMap<String, Namespace>,
+    ns_preload: Option<BTreeMap<String, Namespace>>,
+    ns_preload_cap: u128,
+    ns_hints: Vec<String>,
+    ns_warmup: BTreeMap<String, Option<Namespace>>,
+    ns_warmup_cap: usize,
+    import_dedup_cap: usize,
+    explode_cache_capacity: usize,
+    subject_counts: BTreeMap<Vec<u8>, Uint128>,
+    subject_byte_sizes: BTreeMap<String, Uint128>,
+    shapes: BTreeMap<String, PredicateShape>,
+    shape_counts: BTreeMap<(Vec<u8>, Vec<u8>), u32>,
+    shape_subjects: BTreeMap<Vec<u8>, String>,
+    collection_counts: BTreeMap<String, Uint128>,
+    blank_nodes: BTreeSet<String>,
+    typed_subjects: BTreeSet<(Vec<u8>, Vec<u8>)>,
     initial_triple_count: Uint128,
     initial_byte_size: Uint128,
+    block_height: u64,
 }
 
 impl<'a> StoreEngine<'a> {
@@ -30,33 +147,512 @@ impl<'a> StoreEngine<'a> {
             store: store.clone(),
             ns_key_inc_offset,
             ns_cache: BTreeMap::new(),
+            ns_preload: None,
+            ns_preload_cap: NS_PRELOAD_CAP,
+            ns_hints: Vec::new(),
+            ns_warmup: BTreeMap::new(),
+            ns_warmup_cap: NS_WARMUP_CAP,
+            import_dedup_cap: IMPORT_DEDUP_CAP,
+            explode_cache_capacity: EXPLODE_CACHE_CAPACITY,
+            subject_counts: BTreeMap::new(),
+            subject_byte_sizes: BTreeMap::new(),
+            shapes: store
+                .shapes
+                .iter()
+                .map(|shape| (shape.predicate.clone(), shape.clone()))
+                .collect(),
+            shape_counts: BTreeMap::new(),
+            shape_subjects: BTreeMap::new(),
+            collection_counts: BTreeMap::new(),
+            blank_nodes: BTreeSet::new(),
+            typed_subjects: BTreeSet::new(),
             initial_triple_count: store.stat.triple_count,
             initial_byte_size: store.stat.byte_size,
+            block_height: 0,
         })
     }
 
+    /// Sets the height of the current block, recorded in the triple metadata if the store records
+    /// them.
+    pub fn with_block_height(mut self, block_height: u64) -> Self {
+        self.block_height = block_height;
+        self
+    }
+
+    /// Sets the namespaces the triples to store are expected to use, e.g. the prefixes declared by
+    /// the data, for them to be resolved in a single pass before storing the triples when the
+    /// namespace table is too large to be preloaded.
+    pub fn with_namespace_hints(mut self, hints: Vec<String>) -> Self {
+        self.ns_hints = hints;
+        self
+    }
+
+    /// Stores the triples read from the given position, i.e. skipping the ones before it, within
+    /// the limit of triples processed per call if any. Once the limit reached, the position to
+    /// resume from in a next call is returned along with the number of triples processed.
+    ///
+    /// The minimum counts of the shapes are only checked once all the triples have been processed.
+    ///
+    /// The triples are stored in the named graph they're read in, if any, and in the default graph
+    /// otherwise.
+    ///
+    /// If the store deduplicates the imports, a triple repeated in the data read is only stored
+    /// and accounted for once, as long as the number of distinct triples read stays within a
+    /// bounded capacity.
     pub fn store_all<R: BufRead>(
         &mut self,
         reader: &mut TripleReader<R>,
-    ) -> Result<Uint128, ContractError> {
-        reader.read_all(|t| self.store_triple(t))?;
+        resume_from: Uint128,
+    ) -> Result<StoreProgress, ContractError> {
+        self.preload_namespaces()?;
+        self.warm_namespaces()?;
+        let allowed_schemes = self.store.limits.allowed_schemes.clone();
+        let max_per_call = self.store.limits.max_triples_per_call;
+        let mut explode_cache = Self::explode_cache(&self.store, self.explode_cache_capacity);
+        let mut position = Uint128::zero();
+        let mut processed = Uint128::zero();
+        let mut empty_literal_count = Uint128::zero();
+        let mut seen: BTreeSet<[u8; 32]> = BTreeSet::new();
+        let complete = reader.read_quads_while(|t, graph_name| {
+            if position < resume_from {
+                position += Uint128::one();
+                return Ok(true);
+            }
+            if max_per_call.map_or(false, |max| processed >= max) {
+                return Ok(false);
+            }
+
+            if self.store.skip_empty_literals && is_empty_literal(t.object) {
+                empty_literal_count += Uint128::one();
+                position += Uint128::one();
+                processed += Uint128::one();
+                return Ok(true);
+            }
+
+            // the graphs named by a blank node are not kept, their triples being stored in the
+            // default graph
+            let graph = match graph_name {
+                Some(model::GraphName::NamedNode(node)) => Some(node),
+                _ => None,
+            };
+
+            if self.store.dedup_imports {
+                // keyed by the form to be stored, for the lexical forms of a same value, or a
+                // skolem IRI and its blank node, to be repeats within a same graph
+                let t = Self::prepare_triple(&self.store, t);
+                let quad = model::Quad {
+                    subject: t.subject,
+                    predicate: t.predicate,
+                    object: t.object,
+                    graph_name: graph.map(model::GraphName::NamedNode),
+                };
+                let key = *blake3::hash(quad.to_string().as_bytes()).as_bytes();
+                if seen.contains(&key) {
+                    position += Uint128::one();
+                    processed += Uint128::one();
+                    return Ok(true);
+                }
+                if seen.len() < self.import_dedup_cap {
+                    seen.insert(key);
+                }
+            }
+
+            self.store_triple(t, graph, allowed_schemes.as_deref(), &mut explode_cache)?;
+            position += Uint128::one();
+            processed += Uint128::one();
+            Ok::<bool, ContractError>(true)
+        })?;
+
+        if complete {
+            self.check_min_counts()?;
+        }
+        self.collapse_redundant_types()?;
+        Ok(StoreProgress {
+            count: self.finish()?.count(),
+            processed,
+            resume_from: (!complete).then_some(position),
+            empty_literal_count,
+        })
+    }
+
+    /// Stores the triples like [Self::store_all], unless an insertion identified by the same
+    /// idempotency key has already been performed within the retention window, in which case it
+    /// acts as no-op and returns the triple count of the original insertion.
+    ///
+    /// The idempotency key is only recorded once all the triples have been processed.
+    pub fn store_all_once<R: BufRead>(
+        &mut self,
+        reader: &mut TripleReader<R>,
+        idempotency_key: Option<&str>,
+        resume_from: Uint128,
+    ) -> Result<StoreProgress, ContractError> {
+        let Some(key) = idempotency_key else {
+            return self.store_all(reader, resume_from);
+        };
+
+        if let Some(record) = idempotency_keys().may_load(self.storage, key.to_string())? {
+            return Ok(StoreProgress {
+                count: record.triple_count,
+                processed: Uint128::zero(),
+                resume_from: None,
+                empty_literal_count: Uint128::zero(),
+            });
+        }
+
+        let progress = self.store_all(reader, resume_from)?;
+        if progress.resume_from.is_none() {
+            record_idempotency_key(self.storage, key.to_string(), progress.count)?;
+        }
+        Ok(progress)
+    }
+
+    /// Upserts the read triples, each triple replacing the values its subject has for its
+    /// predicate: the other triples with the same subject and predicate are deleted before the
+    /// triple is stored, an already stored triple being kept as is. The predicates must be
+    /// functional properties unless forced.
+    ///
+    /// Returns the signed variation of the number of triples.
+    pub fn upsert_all<R: BufRead>(
+        &mut self,
+        reader: &mut TripleReader<R>,
+        force: bool,
+    ) -> Result<TripleCountDelta, ContractError> {
+        self.preload_namespaces()?;
+        let allowed_schemes = self.store.limits.allowed_schemes.clone();
+        let mut explode_cache = Self::explode_cache(&self.store, self.explode_cache_capacity);
+        reader.read_all(|t| {
+            self.upsert_triple(t, force, allowed_schemes.as_deref(), &mut explode_cache)
+        })?;
+
+        self.check_min_counts()?;
         self.finish()
     }
 
-    fn store_triple(&mut self, t: model::Triple<'_>) -> Result<(), ContractError> {
+    fn upsert_triple(
+        &mut self,
+        t: model::Triple<'_>,
+        force: bool,
+        allowed_schemes: Option<&[String]>,
+        explode_cache: &mut IriExplodeCache,
+    ) -> Result<(), ContractError> {
+        let t = Self::prepare_triple(&self.store, t);
+        if !force
+            && !self
+                .store
+                .functional_properties
+                .iter()
+                .any(|predicate| predicate == t.predicate.iri)
+        {
+            Err(StoreError::NonFunctionalUpsert(t.predicate.iri.to_string()))?;
+        }
+
+        let (current, object) = self.subject_predicate_triples(t, explode_cache)?;
+        let mut stored = false;
+        for triple in current {
+            if Some(&triple.object) == object.as_ref() {
+                stored = true;
+                continue;
+            }
+
+            let atom = triple_to_atom(&triple, &mut |key| self.namespace_value(key))?;
+            self.delete_triple(&atom)?;
+            // the counts cached for the subject and predicate are now outdated
+            self.subject_counts.remove(&triple.subject.key());
+            self.shape_counts
+                .remove(&(triple.subject.key(), triple.predicate.key()));
+        }
+
+        if stored {
+            return Ok(());
+        }
+        self.store_triple(t, None, allowed_schemes, explode_cache)
+    }
+
+    /// Returns the stored triples having the same subject and predicate as the given one, along
+    /// with the object of the given triple as it would be stored, if its namespaces exist. The
+    /// namespaces are only looked up, not referenced.
+    fn subject_predicate_triples(
+        &self,
+        t: model::Triple<'_>,
+        explode_cache: &mut IriExplodeCache,
+    ) -> Result<(Vec<Triple>, Option<Object>), ContractError> {
+        let storage: &dyn Storage = self.storage;
+        let ns_cache = &self.ns_cache;
+        let mut ns_fn = |ns_str: String| match ns_cache.get(&ns_str) {
+            Some(namespace) => Ok(namespace.key),
+            None => namespaces()
+                .may_load(storage, ns_str)?
+                .map(|namespace| namespace.key)
+                .ok_or_else(|| StdError::not_found(type_name::<Namespace>())),
+        };
+        let normalize_iris = self.store.normalize_iris;
+        let subject =
+            Self::rio_to_subject(t.subject, None, normalize_iris, explode_cache, &mut ns_fn);
+        let predicate =
+            Self::rio_to_node(t.predicate, None, normalize_iris, explode_cache, &mut ns_fn);
+        let object = Self::rio_to_object(t.object, None, normalize_iris, explode_cache, &mut ns_fn);
+
+        let (subject, predicate) = match (subject, predicate) {
+            (Ok(subject), Ok(predicate)) => (subject, predicate),
+            (Err(ContractError::Std(StdError::NotFound { .. })), _)
+            | (_, Err(ContractError::Std(StdError::NotFound { .. }))) => return Ok((vec![], None)),
+            (Err(e), _) | (_, Err(e)) => Err(e)?,
+        };
+        let object = match object {
+            Ok(object) => Some(object),
+            Err(ContractError::Std(StdError::NotFound { .. })) => None,
+            Err(e) => Err(e)?,
+        };
+
+        let current = triples()
+            .idx
+            .subject_and_predicate
+            .prefix((subject.key(), predicate.key()))
+            .range(self.storage, None, None, Order::Ascending)
+            .map(|res| res.map(|(_, triple)| triple))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok((current, object))
+    }
+
+    /// Returns the value of the namespace with the given key, including the namespaces allocated
+    /// by the current operation.
+    fn namespace_value(&self, key: u128) -> StdResult<String> {
+        if let Some(namespace) = self
+            .ns_cache
+            .values()
+            .find(|namespace| namespace.key == key)
+        {
+            return Ok(namespace.value.clone());
+        }
+
+        namespaces()
+            .idx
+            .key
+            .item(self.storage, key)?
+            .map(|(_, namespace)| namespace.value)
+            .ok_or_else(|| StdError::not_found(type_name::<Namespace>()))
+    }
+
+    /// Creates the cache exploding the IRIs of the triples of an operation on the given store,
+    /// keeping them whole if the store keys the nodes by full IRI.
+    fn explode_cache(store: &Store, capacity: usize) -> IriExplodeCache {
+        if store.full_iri_keys {
+            IriExplodeCache::full_iris()
+        } else {
+            IriExplodeCache::new(capacity)
+        }
+        .with_strict_chars(store.strict_iri_chars)
+        .with_absolute_iris(store.require_absolute_iris)
+    }
+
+    /// Re-asserts the read triples existing in the store at the current block height, refreshing
+    /// their metadata without altering the store otherwise, the other ones being ignored. Returns
+    /// the number of triples touched.
+    pub fn touch_all<R: BufRead>(
+        &mut self,
+        reader: &mut TripleReader<R>,
+    ) -> Result<Uint128, ContractError> {
+        if !self.store.record_triple_metadata {
+            Err(StdError::generic_err(
+                "The store doesn't record the triple metadata",
+            ))?;
+        }
+
+        let mut explode_cache = Self::explode_cache(&self.store, self.explode_cache_capacity);
+        let mut count = Uint128::zero();
+        reader.read_all(|t| -> Result<(), ContractError> {
+            let t = Self::prepare_triple(&self.store, t);
+            let mut missing_ns = false;
+            let storage = &*self.storage;
+            let triple = Self::rio_to_triple(
+                t,
+                None,
+                self.store.normalize_iris,
+                &mut explode_cache,
+                &mut |ns_str| match namespaces().may_load(storage, ns_str)? {
+                    Some(namespace) => Ok(namespace.key),
+                    None => {
+                        missing_ns = true;
+                        Err(StdError::not_found(type_name::<Namespace>()))
+                    }
+                },
+            );
+            let triple = match triple {
+                Err(_) if missing_ns => return Ok(()),
+                triple => triple?,
+            };
+
+            let object_hash: Hash = triple.object.as_hash();
+            let pk = (
+                object_hash.as_bytes().as_slice(),
+                triple.predicate.key(),
+                triple.subject.key(),
+            );
+            if let Some(mut stored) = triples().may_load(self.storage, pk.clone())? {
+                stored.metadata = Some(TripleMetadata::seen_at(stored.metadata, self.block_height));
+                triples().save(self.storage, pk, &stored)?;
+                if self.store.subject_index {
+                    subject_triples().save(self.storage, stored.subject_key(), &stored)?;
+                }
+                count += Uint128::one();
+            }
+            Ok(())
+        })?;
+
+        Ok(count)
+    }
+
+    /// Loads the whole namespace table in memory if it doesn't exceed the preload cap, sparing a
+    /// storage read for each namespace first resolved.
+    fn preload_namespaces(&mut self) -> StdResult<()> {
+        if self.ns_preload_cap == 0
+            || self.store.stat.namespace_count > Uint128::from(self.ns_preload_cap)
+        {
+            return Ok(());
+        }
+
+        self.ns_preload = Some(
+            namespaces()
+                .range(self.storage, None, None, Order::Ascending)
+                .collect::<StdResult<_>>()?,
+        );
+        Ok(())
+    }
+
+    /// Resolves the hinted namespaces with a single range scan of the namespace table, from the
+    /// lowest to the highest hint, within the warmup cap of entries scanned. The scanned entries
+    /// are kept along with the hints known to be missing, the hints beyond the scanned range being
+    /// left to be loaded on demand.
+    fn warm_namespaces(&mut self) -> StdResult<()> {
+        let hints: BTreeSet<String> = std::mem::take(&mut self.ns_hints).into_iter().collect();
+        if self.ns_preload.is_some() || self.ns_warmup_cap == 0 {
+            return Ok(());
+        }
+        let (Some(first), Some(last)) = (hints.first(), hints.last()) else {
+            return Ok(());
+        };
+
+        let scanned = namespaces()
+            .range(
+                self.storage,
+                Some(Bound::inclusive(first.clone())),
+                Some(Bound::inclusive(last.clone())),
+                Order::Ascending,
+            )
+            .take(self.ns_warmup_cap)
+            .collect::<StdResult<Vec<_>>>()?;
+        let reached = match scanned.last() {
+            Some((value, _)) if scanned.len() == self.ns_warmup_cap => value.clone(),
+            _ => last.clone(),
+        };
+
+        for hint in hints.into_iter().filter(|hint| *hint <= reached) {
+            self.ns_warmup.insert(hint, None);
+        }
+        for (value, namespace) in scanned {
+            self.ns_warmup.insert(value, Some(namespace));
+        }
+        Ok(())
+    }
+
+    /// Applies the canonicalizations of the literals the store is configured with to the triple.
+    fn canonicalize_with<'t>(store: &Store, t: model::Triple<'t>) -> model::Triple<'t> {
+        if store.canonical_booleans {
+            rdf::canonicalize_boolean_triple(t)
+        } else {
+            t
+        }
+    }
+
+    /// Prepares a read triple to be stored, or looked up, as configured by the store, i.e.
+    /// replacing its skolem IRIs by blank nodes and canonicalizing its literals.
+    fn prepare_triple<'t>(store: &Store, t: model::Triple<'t>) -> model::Triple<'t> {
+        let t = match &store.skolem_base {
+            Some(base) => rdf::deskolemize_triple(base, t),
+            None => t,
+        };
+        Self::canonicalize_with(store, t)
+    }
+
+    /// Stores a triple in the given named graph, [None] standing for the default graph.
+    fn store_triple(
+        &mut self,
+        t: model::Triple<'_>,
+        graph: Option<model::NamedNode<'_>>,
+        allowed_schemes: Option<&[String]>,
+        explode_cache: &mut IriExplodeCache,
+    ) -> Result<(), ContractError> {
+        let t = Self::prepare_triple(&self.store, t);
+
+        if self.store.reject_literal_types && t.predicate.iri == rdf::RDF_TYPE {
+            if let Term::Literal(literal) = t.object {
+                Err(StoreError::LiteralType(
+                    t.subject.to_string(),
+                    literal.to_string(),
+                ))?;
+            }
+        }
+
         self.store.stat.triple_count += Uint128::one();
         if self.store.stat.triple_count > self.store.limits.max_triple_count {
-            Err(StoreError::TripleCount(self.store.limits.max_triple_count))?;
+            Err(StoreError::TripleCount(
+                self.store.stat.triple_count,
+                self.store.limits.max_triple_count,
+            ))?;
         }
-        if self.store.stat.triple_count - self.initial_triple_count
-            > self.store.limits.max_insert_data_triple_count
-        {
+        // saturating as an upsert may have deleted more triples than stored so far
+        let inserted_count = self
+            .store
+            .stat
+            .triple_count
+            .saturating_sub(self.initial_triple_count);
+        if inserted_count > self.store.limits.max_insert_data_triple_count {
             Err(StoreError::InsertDataTripleCount(
+                inserted_count,
                 self.store.limits.max_insert_data_triple_count,
             ))?;
         }
 
-        let t_size = Uint128::from(Self::triple_size(t) as u128);
+        if let Some(max) = self.store.limits.max_blank_nodes_per_import {
+            if let model::Subject::BlankNode(node) = t.subject {
+                self.blank_nodes.insert(node.id.to_string());
+            }
+            if let Term::BlankNode(node) = t.object {
+                self.blank_nodes.insert(node.id.to_string());
+            }
+            let count = Uint128::from(self.blank_nodes.len() as u128);
+            if count > max {
+                Err(StoreError::BlankNodesPerImport(count, max))?;
+            }
+        }
+
+        if let Some(max) = self.store.limits.max_collection_triple_count {
+            self.reference_collection_triple(t, max)?;
+        }
+
+        if let Term::Literal(_) = t.object {
+            let l_size = Uint128::from(Self::object_size(t.object, str::len) as u128);
+            let max_l_size = self
+                .store
+                .limits
+                .max_literal_byte_size
+                .unwrap_or(self.store.limits.max_triple_byte_size);
+            if l_size > max_l_size {
+                Err(StoreError::LiteralByteSize(l_size, max_l_size))?;
+            }
+        }
+
+        if let Some(max_iri_size) = self.store.limits.max_iri_byte_size {
+            for iri in Self::triple_iris(t) {
+                let iri_size = Uint128::from(iri.len() as u128);
+                if iri_size > max_iri_size {
+                    Err(StoreError::IriByteSize(iri_size, max_iri_size))?;
+                }
+            }
+        }
+
+        let t_size = Uint128::from(self.accounted_size(t, graph) as u128);
         if t_size > self.store.limits.max_triple_byte_size {
             Err(StoreError::TripleByteSize(
                 t_size,
@@ -66,66 +662,721 @@ impl<'a> StoreEngine<'a> {
 
         self.store.stat.byte_size += t_size;
         if self.store.stat.byte_size > self.store.limits.max_byte_size {
-            Err(StoreError::ByteSize(self.store.limits.max_byte_size))?;
+            Err(StoreError::ByteSize(
+                self.store.stat.byte_size,
+                self.store.limits.max_byte_size,
+            ))?;
         }
-        if self.store.stat.byte_size - self.initial_byte_size
-            > self.store.limits.max_insert_data_byte_size
-        {
+        let inserted_size = self
+            .store
+            .stat
+            .byte_size
+            .saturating_sub(self.initial_byte_size);
+        if inserted_size > self.store.limits.max_insert_data_byte_size {
             Err(StoreError::InsertDataByteSize(
+                inserted_size,
                 self.store.limits.max_insert_data_byte_size,
             ))?;
         }
+        if let Some(max) = self.store.limits.max_insert_data_byte_size_per_subject {
+            let subject = t.subject.to_string();
+            let size = self.subject_byte_sizes.entry(subject.clone()).or_default();
+            *size += t_size;
+            if *size > max {
+                Err(StoreError::InsertDataByteSizePerSubject(
+                    subject, *size, max,
+                ))?;
+            }
+        }
 
-        let triple = Self::rio_to_triple(t, &mut |ns_str| self.resolve_and_reference_ns(ns_str))?;
+        let normalize_iris = self.store.normalize_iris;
+        let mut triple = Self::rio_to_triple(
+            t,
+            allowed_schemes,
+            normalize_iris,
+            explode_cache,
+            &mut |ns_str| self.resolve_and_reference_ns(ns_str),
+        )?;
+        triple.graph = graph
+            .map(|node| {
+                Self::rio_to_node(
+                    node,
+                    allowed_schemes,
+                    normalize_iris,
+                    explode_cache,
+                    &mut |ns_str| self.resolve_and_reference_ns(ns_str),
+                )
+            })
+            .transpose()?;
         let object_hash: Hash = triple.object.as_hash();
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        );
+        let needs_check = self.store.limits.max_triples_per_subject.is_some()
+            || !self.shapes.is_empty()
+            || !self.store.functional_properties.is_empty();
+        let stored = triples().may_load(self.storage, pk.clone())?;
+        if needs_check && stored.is_none() {
+            if let Some(max) = self.store.limits.max_triples_per_subject {
+                self.reference_subject(&triple.subject, max)?;
+            }
+            self.check_shape(t, &triple)?;
+            self.check_functional_property(t, &triple)?;
+        }
+
+        if !self.store.class_hierarchy.is_empty() && t.predicate.iri == rdf::RDF_TYPE {
+            self.typed_subjects
+                .insert((triple.subject.key(), triple.predicate.key()));
+        }
+
+        // a triple belongs to a single graph, the last one it's stored in, the graph it's moved
+        // from being released
+        if let Some(previous) = stored.as_ref().and_then(|stored| stored.graph.as_ref()) {
+            if triple.graph.as_ref() != Some(previous) {
+                self.release_graph(previous)?;
+            }
+        }
+
+        if self.store.record_triple_metadata {
+            let current = stored.and_then(|stored| stored.metadata);
+            triple.metadata = Some(TripleMetadata::seen_at(current, self.block_height));
+        }
+
+        if self.store.subject_index {
+            subject_triples().save(self.storage, triple.subject_key(), &triple)?;
+        }
+        if self.store.label_index {
+            index_label(self.storage, &triple)?;
+        }
         triples()
-            .save(
-                self.storage,
-                (
-                    object_hash.as_bytes(),
-                    triple.predicate.key(),
-                    triple.subject.key(),
-                ),
-                &triple,
-            )
+            .save(self.storage, pk, &triple)
             .map_err(ContractError::Std)
     }
 
-    pub fn delete_all(&mut self, atoms: &[rdf::Atom]) -> Result<Uint128, ContractError> {
+    /// Releases what a triple has been charged for the named graph it's removed from, i.e. the
+    /// reference to the namespace of the graph and the byte size of its IRI, stored as accounted.
+    fn release_graph(&mut self, graph: &Node) -> Result<(), ContractError> {
+        let namespace = self.namespace_value(graph.namespace)?;
+        self.store.stat.byte_size -= Uint128::from((namespace.len() + graph.value.len()) as u128);
+        self.resolve_and_free_ns(namespace)?;
+        Ok(())
+    }
+
+    /// Accounts for a triple chaining the blank nodes of an RDF collection, i.e. with a `rdf:first`
+    /// or `rdf:rest` predicate, failing if its collection would then expand into more than the
+    /// maximum number of triples. The count is carried along the `rdf:rest` links, so that each
+    /// node of a collection knows the number of triples of the collection up to it.
+    fn reference_collection_triple(
+        &mut self,
+        t: model::Triple<'_>,
+        max: Uint128,
+    ) -> Result<(), ContractError> {
+        let model::Subject::BlankNode(node) = t.subject else {
+            return Ok(());
+        };
+        if t.predicate.iri != rdf::RDF_FIRST && t.predicate.iri != rdf::RDF_REST {
+            return Ok(());
+        }
+
+        let count = self
+            .collection_counts
+            .get(node.id)
+            .map_or(Uint128::one(), |count| *count + Uint128::one());
+        if count > max {
+            Err(StoreError::CollectionTripleCount(count, max))?;
+        }
+        self.collection_counts.insert(node.id.to_string(), count);
+        match t.object {
+            Term::BlankNode(next) if t.predicate.iri == rdf::RDF_REST => {
+                self.collection_counts.insert(next.id.to_string(), count);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Checks a new triple against the shape of its predicate if any, i.e. the datatype of its
+    /// object and the maximum number of triples with this predicate its subject can have. Its
+    /// subject is recorded to check the minimum counts once all the triples are stored.
+    fn check_shape(&mut self, t: model::Triple<'_>, triple: &Triple) -> Result<(), ContractError> {
+        self.shape_subjects
+            .entry(triple.subject.key())
+            .or_insert_with(|| t.subject.to_string());
+
+        let Some(shape) = self.shapes.get(t.predicate.iri) else {
+            return Ok(());
+        };
+        let violation = |reason: String| {
+            StoreError::ShapeViolation(t.subject.to_string(), t.predicate.iri.to_string(), reason)
+        };
+
+        if let Some(datatype) = &shape.datatype {
+            let actual = match t.object {
+                Term::Literal(model::Literal::Simple { .. }) => Some(rdf::XSD_STRING),
+                Term::Literal(model::Literal::LanguageTaggedString { .. }) => {
+                    Some(rdf::RDF_LANG_STRING)
+                }
+                Term::Literal(model::Literal::Typed { datatype, .. }) => Some(datatype.iri),
+                _ => None,
+            };
+            if actual != Some(datatype.as_str()) {
+                Err(violation(format!(
+                    "expected a literal of datatype <{datatype}>, got {}",
+                    t.object
+                )))?;
+            }
+        }
+
+        if let Some(max) = shape.max_count {
+            let key = (triple.subject.key(), triple.predicate.key());
+            let count = match self.shape_counts.get(&key) {
+                Some(count) => *count,
+                None => Self::count_subject_predicate(self.storage, key.clone(), max)?,
+            };
+            if count >= max {
+                Err(violation(format!("more than {max} values")))?;
+            }
+            self.shape_counts.insert(key, count + 1);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a new triple doesn't give a second value to a functional property of its
+    /// subject, the values already stored including the ones of the triples previously stored by
+    /// the same insertion.
+    fn check_functional_property(
+        &self,
+        t: model::Triple<'_>,
+        triple: &Triple,
+    ) -> Result<(), ContractError> {
+        if !self
+            .store
+            .functional_properties
+            .iter()
+            .any(|predicate| predicate == t.predicate.iri)
+        {
+            return Ok(());
+        }
+
+        let key = (triple.subject.key(), triple.predicate.key());
+        if Self::count_subject_predicate(self.storage, key, 1)? > 0 {
+            Err(StoreError::FunctionalPropertyConflict(
+                t.subject.to_string(),
+                t.predicate.iri.to_string(),
+                t.object.to_string(),
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that each subject of the stored triples has at least the minimum number of triples
+    /// required by the shapes.
+    fn check_min_counts(&self) -> Result<(), ContractError> {
+        for shape in self.shapes.values() {
+            let Some(min) = shape.min_count.filter(|min| *min > 0) else {
+                continue;
+            };
+
+            let (ns, value) = self.store.split_iri(&shape.predicate)?;
+            let ns_key = match self
+                .ns_cache
+                .get(&ns)
+                .or_else(|| self.ns_preload.as_ref().and_then(|ns_map| ns_map.get(&ns)))
+            {
+                Some(namespace) => Some(namespace.key),
+                None => namespaces()
+                    .may_load(self.storage, ns)?
+                    .map(|namespace| namespace.key),
+            };
+
+            for (subject_key, subject) in &self.shape_subjects {
+                let count = match ns_key {
+                    Some(namespace) => Self::count_subject_predicate(
+                        self.storage,
+                        (
+                            subject_key.clone(),
+                            Node {
+                                namespace,
+                                value: value.clone(),
+                            }
+                            .key(),
+                        ),
+                        min,
+                    )?,
+                    None => 0,
+                };
+                if count < min {
+                    Err(StoreError::ShapeViolation(
+                        subject.clone(),
+                        shape.predicate.clone(),
+                        format!("fewer than {min} values"),
+                    ))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Counts the stored triples having the given subject and predicate keys, up to `max`.
+    fn count_subject_predicate(
+        storage: &dyn Storage,
+        key: (Vec<u8>, Vec<u8>),
+        max: u32,
+    ) -> StdResult<u32> {
+        let count = triples()
+            .idx
+            .subject_and_predicate
+            .prefix(key)
+            .keys_raw(storage, None, None, Order::Ascending)
+            .take(max as usize)
+            .count();
+
+        Ok(count as u32)
+    }
+
+    /// Accounts for a new triple of the given subject, failing if the subject would then exceed
+    /// the maximum number of triples it can have. The existing triples of a subject are counted
+    /// once using the subject index, up to the maximum.
+    fn reference_subject(&mut self, subject: &Subject, max: Uint128) -> Result<(), ContractError> {
+        let key = subject.key();
+        let count = match self.subject_counts.get(&key) {
+            Some(count) => *count,
+            None => Uint128::from(
+                triples()
+                    .idx
+                    .subject_and_predicate
+                    .sub_prefix(key.clone())
+                    .keys_raw(self.storage, None, None, Order::Ascending)
+                    .take(usize::try_from(max.u128()).unwrap_or(usize::MAX))
+                    .count() as u128,
+            ),
+        };
+
+        if count >= max {
+            Err(StoreError::TriplesPerSubject(count + Uint128::one(), max))?;
+        }
+        self.subject_counts.insert(key, count + Uint128::one());
+
+        Ok(())
+    }
+
+    /// Deletes the `rdf:type` assertions of the subjects typed by the current operation which are
+    /// implied by a more specific type of the subject as per the class hierarchy of the store.
+    ///
+    /// A type is only implied by the types it isn't itself a superclass of, so that the types
+    /// made equivalent by a cycle of the hierarchy are kept.
+    fn collapse_redundant_types(&mut self) -> Result<(), ContractError> {
+        for key in std::mem::take(&mut self.typed_subjects) {
+            let stored = triples()
+                .idx
+                .subject_and_predicate
+                .prefix(key)
+                .range(self.storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(_, triple)| triple))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            let mut types = Vec::with_capacity(stored.len());
+            for triple in stored {
+                if let Object::Named(node) = &triple.object {
+                    let class = node.as_iri(&mut |key| self.namespace_value(key))?;
+                    let superclasses = self.superclasses(&class);
+                    types.push((class, superclasses, triple));
+                }
+            }
+
+            let mut redundant = Vec::new();
+            for (class, superclasses, triple) in &types {
+                let implied = types.iter().any(|(other, other_superclasses, _)| {
+                    other_superclasses.contains(class) && !superclasses.contains(other)
+                });
+                if implied {
+                    redundant.push(triple_to_atom(triple, &mut |key| {
+                        self.namespace_value(key)
+                    })?);
+                }
+            }
+            for atom in &redundant {
+                self.delete_triple(atom)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the transitive superclasses of the class as per the class hierarchy of the store.
+    fn superclasses(&self, class: &str) -> BTreeSet<String> {
+        let mut superclasses = BTreeSet::new();
+        let mut pending = vec![class];
+        while let Some(class) = pending.pop() {
+            for superclass in self.store.class_hierarchy.get(class).into_iter().flatten() {
+                if superclasses.insert(superclass.clone()) {
+                    pending.push(superclass);
+                }
+            }
+        }
+        superclasses
+    }
+
+    /// Converts the triples read the same way they are when stored, i.e. with the same checks and
+    /// normalizations (e.g. `xsd:string` typed literals collapsed into simple ones), without
+    /// writing anything. Returns the converted triples as atoms.
+    pub fn normalize_all<R: BufRead>(
+        reader: &mut TripleReader<R>,
+        store: &Store,
+    ) -> Result<Vec<rdf::Atom>, ContractError> {
+        let mut namespaces: Vec<String> = Vec::new();
+        let mut atoms = Vec::new();
+        reader.read_all(|t| -> Result<(), ContractError> {
+            let triple = Self::rio_to_triple(
+                Self::prepare_triple(store, t),
+                store.limits.allowed_schemes.as_deref(),
+                store.normalize_iris,
+                &mut Self::explode_cache(store, 0),
+                &mut |ns_str| {
+                    let key = match namespaces.iter().position(|ns| *ns == ns_str) {
+                        Some(key) => key,
+                        None => {
+                            namespaces.push(ns_str);
+                            namespaces.len() - 1
+                        }
+                    };
+                    Ok(key as u128)
+                },
+            )?;
+            atoms.push(triple_to_atom(&triple, &mut |key| {
+                namespaces
+                    .get(key as usize)
+                    .cloned()
+                    .ok_or_else(|| StdError::not_found(type_name::<Namespace>()))
+            })?);
+            Ok(())
+        })?;
+
+        Ok(atoms)
+    }
+
+    /// Splits the IRI into the namespace and local value it would be stored as, with the same
+    /// checks and normalizations as the IRIs of the stored triples, without writing anything.
+    pub fn explode(store: &Store, iri: &str) -> Result<(String, String), ContractError> {
+        let mut namespace = String::new();
+        let node = Self::rio_to_node(
+            model::NamedNode { iri },
+            store.limits.allowed_schemes.as_deref(),
+            store.normalize_iris,
+            &mut Self::explode_cache(store, 0),
+            &mut |ns| {
+                namespace = ns;
+                Ok(0)
+            },
+        )?;
+
+        Ok((namespace, node.value))
+    }
+
+    /// Classifies the distinct triples read, converted the same way they are when stored, into the
+    /// ones not in the store and the ones already in it, without writing anything. The triples not
+    /// in the store are returned as atoms if requested.
+    pub fn diff_all<R: BufRead>(
+        storage: &dyn Storage,
+        reader: &mut TripleReader<R>,
+        with_new: bool,
+    ) -> Result<ImportDiff, ContractError> {
+        let store = STORE.load(storage)?;
+        let mut diff = ImportDiff {
+            new: with_new.then(Vec::new),
+            ..ImportDiff::default()
+        };
+        let mut seen = HashSet::new();
+        for atom in Self::normalize_all(reader, &store)? {
+            if seen.contains(&atom) {
+                continue;
+            }
+            if Self::stored_triple_in(storage, &store, &atom)?.is_some() {
+                diff.existing_count += Uint128::one();
+            } else {
+                diff.new_count += Uint128::one();
+                if let Some(new) = &mut diff.new {
+                    new.push(atom.clone());
+                }
+            }
+            seen.insert(atom);
+        }
+
+        Ok(diff)
+    }
+
+    /// Returns the triple an atom is stored as, if present, i.e. with its nodes keyed by their
+    /// namespace, for diagnostics purposes. An atom in a namespace unknown to the store isn't
+    /// present.
+    pub fn stored_triple(storage: &dyn Storage, atom: &rdf::Atom) -> StdResult<Option<Triple>> {
+        Self::stored_triple_in(storage, &STORE.load(storage)?, atom)
+    }
+
+    fn stored_triple_in(
+        storage: &dyn Storage,
+        store: &Store,
+        atom: &rdf::Atom,
+    ) -> StdResult<Option<Triple>> {
+        let mut missing_ns = false;
+        let triple = Self::rio_to_triple(
+            Self::prepare_triple(store, atom.into()),
+            None,
+            store.normalize_iris,
+            &mut Self::explode_cache(store, 0),
+            &mut |ns_str| match namespaces().may_load(storage, ns_str)? {
+                Some(namespace) => Ok(namespace.key),
+                None => {
+                    missing_ns = true;
+                    Err(StdError::not_found(type_name::<Namespace>()))
+                }
+            },
+        );
+        let triple = match triple {
+            Err(_) if missing_ns => return Ok(None),
+            Err(ContractError::Std(e)) => Err(e)?,
+            Err(e) => Err(StdError::generic_err(e.to_string()))?,
+            Ok(triple) => triple,
+        };
+
+        triples().may_load(
+            storage,
+            (
+                triple.object.as_hash().as_bytes(),
+                triple.predicate.key(),
+                triple.subject.key(),
+            ),
+        )
+    }
+
+    /// Checks, without mutating the state, that all the given atoms can be deleted, i.e. that they
+    /// can be converted into triples existing in the store. Returns the problems found for each
+    /// atom that can't.
+    pub fn validate_atoms(
+        storage: &dyn Storage,
+        atoms: &[rdf::Atom],
+    ) -> StdResult<Vec<(rdf::Atom, AtomProblem)>> {
+        Self::preview_delete(storage, atoms).map(|preview| preview.missing)
+    }
+
+    /// Reports, without mutating the state nor the store counters, which of the given atoms
+    /// resolve to existing triples and would be deleted by [StoreEngine::delete_all], and which
+    /// wouldn't along with the reason why.
+    pub fn preview_delete(storage: &dyn Storage, atoms: &[rdf::Atom]) -> StdResult<DeletePreview> {
+        let store = STORE.load(storage)?;
+        let mut preview = DeletePreview::default();
+        for atom in atoms {
+            let mut missing_ns = None;
+            let triple = Self::rio_to_triple(
+                Self::prepare_triple(&store, atom.into()),
+                None,
+                store.normalize_iris,
+                &mut Self::explode_cache(&store, 0),
+                &mut |ns_str| match namespaces().may_load(storage, ns_str.clone())? {
+                    Some(namespace) => Ok(namespace.key),
+                    None => {
+                        missing_ns = Some(ns_str);
+                        Err(StdError::not_found(type_name::<Namespace>()))
+                    }
+                },
+            );
+
+            let problem = match (triple, missing_ns) {
+                (_, Some(ns)) => Some(AtomProblem::MissingNamespace(ns)),
+                (Err(e), None) => Some(AtomProblem::Invalid(e.to_string())),
+                (Ok(triple), None) => {
+                    let object_hash: Hash = triple.object.as_hash();
+                    (!triples().has(
+                        storage,
+                        (
+                            object_hash.as_bytes(),
+                            triple.predicate.key(),
+                            triple.subject.key(),
+                        ),
+                    ))
+                    .then_some(AtomProblem::NotFound)
+                }
+            };
+            match problem {
+                Some(problem) => preview.missing.push((atom.clone(), problem)),
+                None => preview.existing.push(atom.clone()),
+            }
+        }
+
+        Ok(preview)
+    }
+
+    pub fn delete_all(&mut self, atoms: &[rdf::Atom]) -> Result<TripleCountDelta, ContractError> {
         for atom in atoms {
             self.delete_triple(atom)?;
         }
         self.finish()
     }
 
+    /// Deletes at most `limit` of the triples having the given object, found by a scan of the
+    /// triples under its hash, the object being converted the same way it is when stored.
+    /// Returns the signed variation of the number of triples and whether all the triples having
+    /// the object have been deleted.
+    pub fn delete_by_object(
+        &mut self,
+        value: &rdf::Value,
+        limit: usize,
+    ) -> Result<(TripleCountDelta, bool), ContractError> {
+        let Some(object) = self.stored_object(value)? else {
+            return Ok((self.finish()?, true));
+        };
+
+        let found = triples()
+            .sub_prefix(object.as_hash().as_bytes())
+            .range(self.storage, None, None, Order::Ascending)
+            .take(limit + 1)
+            .map(|res| res.map(|(_, triple)| triple))
+            .collect::<StdResult<Vec<_>>>()?;
+        let complete = found.len() <= limit;
+
+        let mut deleted = Vec::with_capacity(found.len());
+        for triple in found.iter().take(limit) {
+            deleted.push(triple_to_atom(triple, &mut |key| {
+                self.namespace_value(key)
+            })?);
+        }
+        for atom in &deleted {
+            self.delete_triple(atom)?;
+        }
+
+        Ok((self.finish()?, complete))
+    }
+
+    /// Deletes the triples of the given graph, [None] standing for the default graph, among at most
+    /// `limit` triples scanned after the given triple key, the graph being named by its IRI.
+    /// Returns the signed variation of the number of triples and, if some triples remain to be
+    /// scanned, the key of the last triple scanned to continue from.
+    pub fn delete_graph(
+        &mut self,
+        graph: Option<&str>,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> Result<(TripleCountDelta, Option<Vec<u8>>), ContractError> {
+        let graph = match graph {
+            Some(iri) => match self.stored_object(&rdf::Value::NamedNode(iri.to_string()))? {
+                Some(Object::Named(node)) => Some(node),
+                // no triple can be in a graph whose namespace is unknown to the store
+                _ => return Ok((self.finish()?, None)),
+            },
+            None => None,
+        };
+
+        let scanned = triples()
+            .range_raw(
+                self.storage,
+                after.clone().map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .take(limit + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+        let cursor = (scanned.len() > limit).then(|| {
+            scanned[..limit]
+                .last()
+                .map(|(key, _)| key.clone())
+                .or(after)
+                .unwrap_or_default()
+        });
+
+        let mut deleted = Vec::new();
+        for (_, triple) in scanned.iter().take(limit) {
+            if triple.graph == graph {
+                deleted.push(triple_to_atom(triple, &mut |key| {
+                    self.namespace_value(key)
+                })?);
+            }
+        }
+        for atom in &deleted {
+            self.delete_triple(atom)?;
+        }
+
+        Ok((self.finish()?, cursor))
+    }
+
+    /// Returns the object a value is stored as, [None] if in a namespace unknown to the store.
+    fn stored_object(&self, value: &rdf::Value) -> Result<Option<Object>, ContractError> {
+        let term = value.into();
+        let mut missing_ns = false;
+        let object = Self::rio_to_object(
+            if self.store.canonical_booleans {
+                rdf::canonicalize_boolean_term(term)
+            } else {
+                term
+            },
+            None,
+            self.store.normalize_iris,
+            &mut Self::explode_cache(&self.store, 0),
+            &mut |ns_str| match namespaces().may_load(self.storage, ns_str)? {
+                Some(namespace) => Ok(namespace.key),
+                None => {
+                    missing_ns = true;
+                    Err(StdError::not_found(type_name::<Namespace>()))
+                }
+            },
+        );
+
+        match object {
+            Err(_) if missing_ns => Ok(None),
+            object => object.map(Some),
+        }
+    }
+
+    /// Deletes a triple from the graph it's stored in, releasing what it has been charged for it.
     fn delete_triple(&mut self, atom: &rdf::Atom) -> Result<(), ContractError> {
-        let triple_model = atom.into();
-        let triple =
-            Self::rio_to_triple(triple_model, &mut |ns_str| self.resolve_and_free_ns(ns_str))?;
+        let triple_model = Self::prepare_triple(&self.store, atom.into());
+        let normalize_iris = self.store.normalize_iris;
+        let mut explode_cache = Self::explode_cache(&self.store, 0);
+        let triple = Self::rio_to_triple(
+            triple_model,
+            None,
+            normalize_iris,
+            &mut explode_cache,
+            &mut |ns_str| self.resolve_and_free_ns(ns_str),
+        )?;
         let object_hash: Hash = triple.object.as_hash();
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        );
 
         self.store.stat.triple_count -= Uint128::one();
-        self.store.stat.byte_size -= Uint128::from(Self::triple_size(triple_model) as u128);
+        self.store.stat.byte_size -= Uint128::from(self.accounted_size(triple_model, None) as u128);
+        if let Some(graph) = triples()
+            .may_load(self.storage, pk.clone())?
+            .and_then(|stored| stored.graph)
+        {
+            self.release_graph(&graph)?;
+        }
 
+        if self.store.subject_index {
+            subject_triples().remove(self.storage, triple.subject_key());
+        }
+        if self.store.label_index {
+            unindex_label(self.storage, &triple)?;
+        }
         triples()
-            .remove(
-                self.storage,
-                (
-                    object_hash.as_bytes(),
-                    triple.predicate.key(),
-                    triple.subject.key(),
-                ),
-            )
+            .remove(self.storage, pk)
             .map_err(ContractError::Std)
     }
 
     /// Flushes the store to the storage.
-    /// Returns the number of triples added or removed (absolute value).
-    fn finish(&mut self) -> Result<Uint128, ContractError> {
+    /// Returns the signed variation of the number of triples since the last flush.
+    fn finish(&mut self) -> Result<TripleCountDelta, ContractError> {
         NAMESPACE_KEY_INCREMENT.save(self.storage, &self.ns_key_inc_offset)?;
 
         for entry in &self.ns_cache {
-            if entry.1.counter > 0 {
+            if entry.1.counter > 0 || self.store.retain_empty_namespaces {
                 namespaces().save(self.storage, entry.0.to_string(), entry.1)?;
             } else {
                 self.store.stat.namespace_count -= Uint128::one();
@@ -135,15 +1386,21 @@ impl<'a> StoreEngine<'a> {
 
         STORE.save(self.storage, &self.store)?;
 
-        let count_diff = self
-            .store
-            .stat
-            .triple_count
-            .abs_diff(self.initial_triple_count);
+        let count_diff =
+            TripleCountDelta::between(self.initial_triple_count, self.store.stat.triple_count);
 
         self.initial_triple_count = self.store.stat.triple_count;
         self.initial_byte_size = self.store.stat.byte_size;
         self.ns_cache.clear();
+        self.subject_counts.clear();
+        self.subject_byte_sizes.clear();
+        self.shape_counts.clear();
+        self.shape_subjects.clear();
+        self.collection_counts.clear();
+        self.blank_nodes.clear();
+        self.typed_subjects.clear();
+        self.ns_preload = None;
+        self.ns_warmup.clear();
 
         Ok(count_diff)
     }
@@ -153,11 +1410,14 @@ impl<'a> StoreEngine<'a> {
             namespace.counter += 1;
             Ok(namespace.key)
         } else {
-            let mut namespace = match namespaces().load(self.storage, ns_str.clone()) {
-                Err(StdError::NotFound { .. }) => Ok(self.allocate_namespace(ns_str.clone())),
-                Ok(n) => Ok(n),
-                Err(e) => Err(e),
-            }?;
+            let loaded = match &mut self.ns_preload {
+                Some(preload) => preload.remove(&ns_str),
+                None => match self.ns_warmup.remove(&ns_str) {
+                    Some(warmed) => warmed,
+                    None => namespaces().may_load(self.storage, ns_str.clone())?,
+                },
+            };
+            let mut namespace = loaded.unwrap_or_else(|| self.allocate_namespace(ns_str.clone()));
 
             namespace.counter += 1;
             self.ns_cache.insert(ns_str, namespace.clone());
@@ -193,52 +1453,139 @@ impl<'a> StoreEngine<'a> {
         ns
     }
 
-    fn rio_to_triple<F>(triple: model::Triple<'_>, ns_fn: &mut F) -> StdResult<Triple>
+    /// Converts a rio triple into a stored one, rejecting any subject, predicate or object IRI
+    /// not using one of the allowed schemes if given.
+    fn rio_to_triple<F>(
+        triple: model::Triple<'_>,
+        allowed_schemes: Option<&[String]>,
+        normalize_iris: bool,
+        explode_cache: &mut IriExplodeCache,
+        ns_fn: &mut F,
+    ) -> Result<Triple, ContractError>
     where
         F: FnMut(String) -> StdResult<u128>,
     {
+        // The model already types the predicate as a named node, but nothing prevents its IRI from
+        // holding a blank node label or a literal, which we don't want to trust parsers about.
+        let predicate = triple.predicate.iri;
+        if predicate.is_empty() || predicate.starts_with("_:") || predicate.starts_with('"') {
+            Err(StoreError::InvalidPredicate(predicate.to_string()))?;
+        }
+
         Ok(Triple {
-            subject: Self::rio_to_subject(triple.subject, ns_fn)?,
-            predicate: Self::rio_to_node(triple.predicate, ns_fn)?,
-            object: Self::rio_to_object(triple.object, ns_fn)?,
+            metadata: None,
+            graph: None,
+            subject: Self::rio_to_subject(
+                triple.subject,
+                allowed_schemes,
+                normalize_iris,
+                explode_cache,
+                ns_fn,
+            )?,
+            predicate: Self::rio_to_node(
+                triple.predicate,
+                allowed_schemes,
+                normalize_iris,
+                explode_cache,
+                ns_fn,
+            )?,
+            object: Self::rio_to_object(
+                triple.object,
+                allowed_schemes,
+                normalize_iris,
+                explode_cache,
+                ns_fn,
+            )?,
         })
     }
 
-    fn rio_to_subject<F>(subject: model::Subject<'_>, ns_fn: &mut F) -> StdResult<Subject>
+    fn rio_to_subject<F>(
+        subject: model::Subject<'_>,
+        allowed_schemes: Option<&[String]>,
+        normalize_iris: bool,
+        explode_cache: &mut IriExplodeCache,
+        ns_fn: &mut F,
+    ) -> Result<Subject, ContractError>
     where
         F: FnMut(String) -> StdResult<u128>,
     {
         match subject {
-            model::Subject::NamedNode(node) => Self::rio_to_node(node, ns_fn).map(Subject::Named),
+            model::Subject::NamedNode(node) => {
+                Self::rio_to_node(node, allowed_schemes, normalize_iris, explode_cache, ns_fn)
+                    .map(Subject::Named)
+            }
             model::Subject::BlankNode(node) => Ok(Subject::Blank(node.id.to_string())),
-            model::Subject::Triple(_) => Err(StdError::generic_err("RDF star syntax unsupported")),
+            model::Subject::Triple(_) => {
+                Err(StdError::generic_err("RDF star syntax unsupported").into())
+            }
         }
     }
 
-    fn rio_to_node<F>(node: model::NamedNode<'_>, ns_fn: &mut F) -> StdResult<Node>
+    fn rio_to_node<F>(
+        node: model::NamedNode<'_>,
+        allowed_schemes: Option<&[String]>,
+        normalize_iris: bool,
+        explode_cache: &mut IriExplodeCache,
+        ns_fn: &mut F,
+    ) -> Result<Node, ContractError>
     where
         F: FnMut(String) -> StdResult<u128>,
     {
-        let (ns, v) = rdf::explode_iri(node.iri)?;
+        let normalized;
+        let iri = if normalize_iris {
+            normalized = rdf::normalize_iri(node.iri);
+            &normalized
+        } else {
+            node.iri
+        };
+        let (ns, v) = explode_cache.explode(iri)?;
+        if let Some(allowed_schemes) = allowed_schemes {
+            let scheme = AbsoluteIri::try_new(iri).ok().map(|iri| iri.scheme());
+            if !scheme.map_or(false, |scheme| {
+                allowed_schemes
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+            }) {
+                Err(StoreError::IriSchemeNotAllowed(iri.to_string()))?;
+            }
+        }
+
         Ok(Node {
             namespace: ns_fn(ns)?,
             value: v,
         })
     }
 
-    fn rio_to_object<F>(object: Term<'_>, ns_fn: &mut F) -> StdResult<Object>
+    fn rio_to_object<F>(
+        object: Term<'_>,
+        allowed_schemes: Option<&[String]>,
+        normalize_iris: bool,
+        explode_cache: &mut IriExplodeCache,
+        ns_fn: &mut F,
+    ) -> Result<Object, ContractError>
     where
         F: FnMut(String) -> StdResult<u128>,
     {
         match object {
             Term::BlankNode(node) => Ok(Object::Blank(node.id.to_string())),
-            Term::NamedNode(node) => Self::rio_to_node(node, ns_fn).map(Object::Named),
-            Term::Literal(literal) => Self::rio_to_literal(literal, ns_fn).map(Object::Literal),
-            Term::Triple(_) => Err(StdError::generic_err("RDF star syntax unsupported")),
+            Term::NamedNode(node) => {
+                Self::rio_to_node(node, allowed_schemes, normalize_iris, explode_cache, ns_fn)
+                    .map(Object::Named)
+            }
+            Term::Literal(literal) => {
+                Self::rio_to_literal(literal, normalize_iris, explode_cache, ns_fn)
+                    .map(Object::Literal)
+            }
+            Term::Triple(_) => Err(StdError::generic_err("RDF star syntax unsupported").into()),
         }
     }
 
-    fn rio_to_literal<F>(literal: model::Literal<'_>, ns_fn: &mut F) -> StdResult<Literal>
+    fn rio_to_literal<F>(
+        literal: model::Literal<'_>,
+        normalize_iris: bool,
+        explode_cache: &mut IriExplodeCache,
+        ns_fn: &mut F,
+    ) -> Result<Literal, ContractError>
     where
         F: FnMut(String) -> StdResult<u128>,
     {
@@ -250,47 +1597,537 @@ impl<'a> StoreEngine<'a> {
                 value: value.to_string(),
                 language: language.to_string(),
             }),
-            model::Literal::Typed { value, datatype } => {
-                Self::rio_to_node(datatype, ns_fn).map(|node| Literal::Typed {
+            model::Literal::Typed { value, datatype } if datatype.iri == rdf::XSD_STRING => {
+                Ok(Literal::Simple {
                     value: value.to_string(),
-                    datatype: node,
                 })
             }
+            // Datatypes are vocabulary IRIs (e.g. XML Schema ones) not subject to the allowed schemes.
+            model::Literal::Typed { value, datatype } => {
+                Self::rio_to_node(datatype, None, normalize_iris, explode_cache, ns_fn).map(
+                    |node| Literal::Typed {
+                        value: value.to_string(),
+                        datatype: node,
+                    },
+                )
+            }
         }
     }
 
-    fn triple_size(triple: model::Triple<'_>) -> usize {
-        Self::subject_size(triple.subject)
-            + Self::node_size(triple.predicate)
-            + Self::object_size(triple.object)
+    /// Returns the byte size of a triple accounted against the limits, its IRIs being sized as
+    /// stored, i.e. normalized if the store normalizes them, for a deleted triple to release what
+    /// it has been charged whatever the form its IRIs are given in.
+    fn accounted_size(
+        &self,
+        triple: model::Triple<'_>,
+        graph: Option<model::NamedNode<'_>>,
+    ) -> usize {
+        if self.store.normalize_iris {
+            Self::triple_size(triple, graph, |iri| rdf::normalize_iri(iri).len())
+        } else {
+            Self::triple_size(triple, graph, str::len)
+        }
+    }
+
+    /// Returns the byte size of a triple in the given named graph, the default graph being free.
+    fn triple_size(
+        triple: model::Triple<'_>,
+        graph: Option<model::NamedNode<'_>>,
+        iri_size: fn(&str) -> usize,
+    ) -> usize {
+        Self::subject_size(triple.subject, iri_size)
+            + iri_size(triple.predicate.iri)
+            + Self::object_size(triple.object, iri_size)
+            + graph.map_or(0, |node| iri_size(node.iri))
+    }
+
+    fn triple_iris(triple: model::Triple<'_>) -> Vec<&str> {
+        let mut iris = vec![triple.predicate.iri];
+        if let model::Subject::NamedNode(n) = triple.subject {
+            iris.push(n.iri);
+        }
+        match triple.object {
+            Term::NamedNode(n) => iris.push(n.iri),
+            Term::Literal(model::Literal::Typed { datatype, .. }) => iris.push(datatype.iri),
+            _ => {}
+        }
+        iris
     }
 
-    fn subject_size(subject: model::Subject<'_>) -> usize {
+    fn subject_size(subject: model::Subject<'_>, iri_size: fn(&str) -> usize) -> usize {
         match subject {
-            model::Subject::NamedNode(n) => Self::node_size(n),
+            model::Subject::NamedNode(n) => iri_size(n.iri),
             model::Subject::BlankNode(n) => n.id.len(),
             model::Subject::Triple(_) => 0,
         }
     }
 
-    fn node_size(node: model::NamedNode<'_>) -> usize {
-        node.iri.len()
-    }
-
-    fn object_size(term: Term<'_>) -> usize {
+    fn object_size(term: Term<'_>, iri_size: fn(&str) -> usize) -> usize {
         match term {
-            Term::NamedNode(n) => Self::node_size(n),
+            Term::NamedNode(n) => iri_size(n.iri),
             Term::BlankNode(n) => n.id.len(),
             Term::Literal(l) => match l {
                 model::Literal::Simple { value } => value.len(),
                 model::Literal::LanguageTaggedString { value, language } => {
                     value.len() + language.len()
                 }
-                model::Literal::Typed { value, datatype } => {
-                    value.len() + Self::node_size(datatype)
+                model::Literal::Typed { value, datatype } if datatype.iri == rdf::XSD_STRING => {
+                    value.len()
                 }
+                model::Literal::Typed { value, datatype } => value.len() + iri_size(datatype.iri),
             },
             Term::Triple(_) => 0,
         }
     }
 }
+
+/// Tells if the term is an empty simple or typed literal.
+fn is_empty_literal(term: Term<'_>) -> bool {
+    matches!(
+        term,
+        Term::Literal(model::Literal::Simple { value } | model::Literal::Typed { value, .. })
+            if value.is_empty()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{DataFormat, StoreLimitsInput};
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Addr;
+    use std::io::BufReader;
+
+    fn import(
+        storage: &mut dyn Storage,
+        data: &[u8],
+        ns_preload_cap: u128,
+        explode_cache_capacity: usize,
+    ) -> Uint128 {
+        let mut reader = TripleReader::new(&DataFormat::Turtle, BufReader::new(data));
+        let mut engine = StoreEngine::new(storage).unwrap();
+        engine.ns_preload_cap = ns_preload_cap;
+        engine.explode_cache_capacity = explode_cache_capacity;
+        engine
+            .store_all(&mut reader, Uint128::zero())
+            .unwrap()
+            .count
+    }
+
+    #[test]
+    fn caches_do_not_change_results() {
+        let datasets: [&[u8]; 2] = [
+            include_bytes!("../../testdata/sample.ttl"),
+            include_bytes!("../../testdata/blank-nodes.ttl"),
+        ];
+
+        let mut storages = vec![];
+        for (cap, capacity) in [(0, 0), (1, 1), (NS_PRELOAD_CAP, EXPLODE_CACHE_CAPACITY)] {
+            let mut storage = MockStorage::new();
+            STORE
+                .save(
+                    &mut storage,
+                    &Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into()),
+                )
+                .unwrap();
+            NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+
+            for data in datasets {
+                import(&mut storage, data, cap, capacity);
+            }
+            storages.push(storage);
+        }
+
+        let dump = |storage: &MockStorage| {
+            (
+                STORE.load(storage).unwrap(),
+                namespaces()
+                    .range(storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()
+                    .unwrap(),
+                triples()
+                    .range(storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()
+                    .unwrap(),
+            )
+        };
+
+        let expected = dump(&storages[0]);
+        assert_eq!(expected.0.stat.triple_count, Uint128::new(65));
+        assert_eq!(expected.0.stat.namespace_count, Uint128::new(19));
+        for storage in &storages[1..] {
+            assert_eq!(dump(storage), expected);
+        }
+    }
+
+    /// A storage counting the reads of single keys, for the storage accesses of an operation to be
+    /// compared.
+    #[derive(Default)]
+    struct ReadCountingStorage {
+        inner: MockStorage,
+        reads: std::cell::Cell<usize>,
+    }
+
+    impl Storage for ReadCountingStorage {
+        fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+            self.reads.set(self.reads.get() + 1);
+            self.inner.get(key)
+        }
+
+        fn range<'a>(
+            &'a self,
+            start: Option<&[u8]>,
+            end: Option<&[u8]>,
+            order: Order,
+        ) -> Box<dyn Iterator<Item = cosmwasm_std::Record> + 'a> {
+            self.inner.range(start, end, order)
+        }
+
+        fn set(&mut self, key: &[u8], value: &[u8]) {
+            self.inner.set(key, value)
+        }
+
+        fn remove(&mut self, key: &[u8]) {
+            self.inner.remove(key)
+        }
+    }
+
+    #[test]
+    fn namespace_warmup() {
+        let existing: &[u8] = include_bytes!("../../testdata/sample.ttl");
+        let data: &[u8] = include_bytes!("../../testdata/blank-nodes.ttl");
+        let hints = rdf::turtle_prefix_hints(data);
+        assert_eq!(hints.len(), 8);
+
+        let mut outcomes = vec![];
+        for (with_hints, warmup_cap) in [(false, NS_WARMUP_CAP), (true, 2), (true, NS_WARMUP_CAP)] {
+            let mut storage = ReadCountingStorage::default();
+            STORE
+                .save(
+                    &mut storage,
+                    &Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into()),
+                )
+                .unwrap();
+            NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+            import(&mut storage, existing, 0, EXPLODE_CACHE_CAPACITY);
+
+            storage.reads.set(0);
+            let mut reader = TripleReader::new(&DataFormat::Turtle, BufReader::new(data));
+            let mut engine = StoreEngine::new(&mut storage).unwrap();
+            engine.ns_preload_cap = 0;
+            engine.ns_warmup_cap = warmup_cap;
+            if with_hints {
+                engine = engine.with_namespace_hints(hints.clone());
+            }
+            let count = engine
+                .store_all(&mut reader, Uint128::zero())
+                .unwrap()
+                .count;
+            let reads = storage.reads.get();
+
+            outcomes.push((
+                reads,
+                count,
+                STORE.load(&storage).unwrap(),
+                namespaces()
+                    .range(&storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()
+                    .unwrap(),
+                triples()
+                    .range(&storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()
+                    .unwrap(),
+            ));
+        }
+
+        for outcome in &outcomes[1..] {
+            assert_eq!(outcome.1, outcomes[0].1);
+            assert_eq!(outcome.2, outcomes[0].2);
+            assert_eq!(outcome.3, outcomes[0].3);
+            assert_eq!(outcome.4, outcomes[0].4);
+        }
+        // the capped warmup only resolves the lowest hints
+        assert!(outcomes[1].0 < outcomes[0].0);
+        assert!(outcomes[2].0 < outcomes[1].0);
+    }
+
+    #[test]
+    fn full_iri_keys() {
+        let data: &[u8] = include_bytes!("../../testdata/sample.nt");
+
+        let mut outcomes = vec![];
+        for full_iri_keys in [false, true] {
+            let mut storage = MockStorage::new();
+            STORE
+                .save(
+                    &mut storage,
+                    &Store {
+                        full_iri_keys,
+                        ..Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into())
+                    },
+                )
+                .unwrap();
+            NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+
+            let mut reader = TripleReader::new(&DataFormat::NTriples, BufReader::new(data));
+            StoreEngine::new(&mut storage)
+                .unwrap()
+                .store_all(&mut reader, Uint128::zero())
+                .unwrap();
+
+            let ns_by_key: BTreeMap<u128, String> = namespaces()
+                .range(&storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(ns, namespace)| (namespace.key, ns)))
+                .collect::<StdResult<_>>()
+                .unwrap();
+            let mut atoms = triples()
+                .range(&storage, None, None, Order::Ascending)
+                .map(|res| {
+                    triple_to_atom(&res.unwrap().1, &mut |key| Ok(ns_by_key[&key].clone()))
+                        .map(|atom| atom.to_string())
+                })
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            atoms.sort();
+
+            outcomes.push((STORE.load(&storage).unwrap().stat, atoms));
+        }
+
+        let (exploded, full) = (&outcomes[0], &outcomes[1]);
+        assert_eq!(exploded.0.namespace_count, Uint128::new(17));
+        assert_eq!(full.0.namespace_count, Uint128::one());
+        assert_eq!(full.0.triple_count, exploded.0.triple_count);
+        assert_eq!(full.1, exploded.1);
+    }
+
+    #[test]
+    fn validate_atoms() {
+        let mut storage = MockStorage::new();
+        STORE
+            .save(
+                &mut storage,
+                &Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into()),
+            )
+            .unwrap();
+        NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+        import(
+            &mut storage,
+            b"<http://a.org/s> <http://a.org/p> \"x\" .",
+            NS_PRELOAD_CAP,
+            EXPLODE_CACHE_CAPACITY,
+        );
+
+        let atom = |subject: &str, value: &str| rdf::Atom {
+            subject: rdf::Subject::NamedNode(subject.to_string()),
+            property: rdf::Property("http://a.org/p".to_string()),
+            value: rdf::Value::LiteralSimple(value.to_string()),
+        };
+        let atoms = vec![
+            atom("http://a.org/s", "x"),
+            atom("s", "x"),
+            atom("http://b.org/s", "x"),
+            atom("http://a.org/s", "y"),
+        ];
+
+        for (require_absolute_iris, invalid) in [
+            (false, "Generic error: Couldn't extract IRI namespace"),
+            (true, "Generic error: Not an absolute IRI: s"),
+        ] {
+            let mut store = STORE.load(&storage).unwrap();
+            store.require_absolute_iris = require_absolute_iris;
+            STORE.save(&mut storage, &store).unwrap();
+
+            assert_eq!(
+                StoreEngine::validate_atoms(&storage, &atoms),
+                Ok(vec![
+                    (atoms[1].clone(), AtomProblem::Invalid(invalid.to_string())),
+                    (
+                        atoms[2].clone(),
+                        AtomProblem::MissingNamespace("http://b.org/".to_string())
+                    ),
+                    (atoms[3].clone(), AtomProblem::NotFound),
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn rio_to_triple_rejects_invalid_predicate() {
+        for predicate in ["", "_:b0", "\"foo\""] {
+            let triple = model::Triple {
+                subject: model::NamedNode {
+                    iri: "http://example.com/s",
+                }
+                .into(),
+                predicate: model::NamedNode { iri: predicate },
+                object: model::NamedNode {
+                    iri: "http://example.com/o",
+                }
+                .into(),
+            };
+
+            assert_eq!(
+                StoreEngine::rio_to_triple(
+                    triple,
+                    None,
+                    false,
+                    &mut IriExplodeCache::new(0),
+                    &mut |_| Ok(0u128)
+                ),
+                Err(ContractError::Store(StoreError::InvalidPredicate(
+                    predicate.to_string()
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn triple_count_delta() {
+        let cases = vec![
+            (10u128, 12u128, TripleCountDelta::Added(2u128.into()), "2"),
+            (
+                12u128,
+                10u128,
+                TripleCountDelta::Removed(2u128.into()),
+                "-2",
+            ),
+            (10u128, 10u128, TripleCountDelta::Added(0u128.into()), "0"),
+        ];
+
+        for (initial, current, expected, display) in cases {
+            let delta = TripleCountDelta::between(initial.into(), current.into());
+            assert_eq!(delta, expected);
+            assert_eq!(delta.count(), Uint128::from(initial.abs_diff(current)));
+            assert_eq!(delta.to_string(), display);
+        }
+    }
+
+    #[test]
+    fn preview_delete() {
+        let mut storage = MockStorage::new();
+        STORE
+            .save(
+                &mut storage,
+                &Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into()),
+            )
+            .unwrap();
+        NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+        import(
+            &mut storage,
+            b"<http://a.org/s> <http://a.org/p> \"x\" , \"y\" .",
+            NS_PRELOAD_CAP,
+            EXPLODE_CACHE_CAPACITY,
+        );
+        let store = STORE.load(&storage).unwrap();
+        let namespaces_before = namespaces()
+            .range(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+
+        let atom = |subject: &str, value: &str| rdf::Atom {
+            subject: rdf::Subject::NamedNode(subject.to_string()),
+            property: rdf::Property("http://a.org/p".to_string()),
+            value: rdf::Value::LiteralSimple(value.to_string()),
+        };
+        let atoms = vec![
+            atom("http://a.org/s", "x"),
+            atom("http://a.org/s", "z"),
+            atom("http://b.org/s", "x"),
+            atom("http://a.org/s", "y"),
+        ];
+
+        assert_eq!(
+            StoreEngine::preview_delete(&storage, &atoms),
+            Ok(DeletePreview {
+                existing: vec![atoms[0].clone(), atoms[3].clone()],
+                missing: vec![
+                    (atoms[1].clone(), AtomProblem::NotFound),
+                    (
+                        atoms[2].clone(),
+                        AtomProblem::MissingNamespace("http://b.org/".to_string())
+                    ),
+                ],
+            })
+        );
+        assert_eq!(STORE.load(&storage).unwrap(), store);
+        assert_eq!(
+            namespaces()
+                .range(&storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            namespaces_before
+        );
+        assert_eq!(
+            triples()
+                .range_raw(&storage, None, None, Order::Ascending)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn preview_delete_canonical_booleans() {
+        let mut storage = MockStorage::new();
+        let mut store = Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into());
+        store.canonical_booleans = true;
+        STORE.save(&mut storage, &store).unwrap();
+        NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+        import(
+            &mut storage,
+            b"<http://a.org/s> <http://a.org/p> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .",
+            NS_PRELOAD_CAP,
+            EXPLODE_CACHE_CAPACITY,
+        );
+
+        let atom = |value: &str| rdf::Atom {
+            subject: rdf::Subject::NamedNode("http://a.org/s".to_string()),
+            property: rdf::Property("http://a.org/p".to_string()),
+            value: rdf::Value::LiteralDatatype(value.to_string(), rdf::XSD_BOOLEAN.to_string()),
+        };
+        let atoms = vec![atom("1"), atom("true"), atom("0")];
+
+        assert_eq!(
+            StoreEngine::preview_delete(&storage, &atoms),
+            Ok(DeletePreview {
+                existing: vec![atoms[0].clone(), atoms[1].clone()],
+                missing: vec![(atoms[2].clone(), AtomProblem::NotFound)],
+            })
+        );
+    }
+
+    #[test]
+    fn delete_all_reports_removed_triples() {
+        let mut storage = MockStorage::new();
+        STORE
+            .save(
+                &mut storage,
+                &Store::new(Addr::unchecked("owner"), StoreLimitsInput::default().into()),
+            )
+            .unwrap();
+        NAMESPACE_KEY_INCREMENT.save(&mut storage, &0u128).unwrap();
+        assert_eq!(
+            import(
+                &mut storage,
+                b"<http://a.org/s> <http://a.org/p> \"x\" , \"y\" , \"z\" .",
+                NS_PRELOAD_CAP,
+                EXPLODE_CACHE_CAPACITY,
+            ),
+            Uint128::new(3)
+        );
+
+        let atom = |value: &str| rdf::Atom {
+            subject: rdf::Subject::NamedNode("http://a.org/s".to_string()),
+            property: rdf::Property("http://a.org/p".to_string()),
+            value: rdf::Value::LiteralSimple(value.to_string()),
+        };
+        let mut engine = StoreEngine::new(&mut storage).unwrap();
+        assert_eq!(
+            engine.delete_all(&[atom("x"), atom("y")]),
+            Ok(TripleCountDelta::Removed(Uint128::new(2)))
+        );
+        assert_eq!(
+            STORE.load(&storage).unwrap().stat.triple_count,
+            Uint128::new(1)
+        );
+    }
+}
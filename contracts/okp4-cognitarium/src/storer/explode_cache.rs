@@ -0,0 +1,180 @@
+use crate::rdf::{self, AbsoluteIri};
+use cosmwasm_std::StdResult;
+use std::collections::BTreeMap;
+
+/// A bounded least recently used cache of IRIs exploded into their namespace and local name,
+/// sparing the lookup of the delimiters for the IRIs repeated across the triples of an import.
+///
+/// A capacity of zero disables the cache, every IRI being then exploded.
+///
+/// For the stores keying the nodes by full IRI, IRIs are not exploded but kept whole under the
+/// empty namespace, see [IriExplodeCache::full_iris].
+///
+/// The characters of the IRIs can be checked before being exploded, see
+/// [IriExplodeCache::with_strict_chars], as well as their absoluteness, see
+/// [IriExplodeCache::with_absolute_iris].
+pub struct IriExplodeCache {
+    capacity: usize,
+    full_iris: bool,
+    strict_chars: bool,
+    absolute_iris: bool,
+    tick: u64,
+    entries: BTreeMap<String, (u64, (String, String))>,
+    recency: BTreeMap<u64, String>,
+}
+
+impl IriExplodeCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            full_iris: false,
+            strict_chars: false,
+            absolute_iris: false,
+            tick: 0,
+            entries: BTreeMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a cache-less instance keeping the IRIs whole, as the empty namespace and the full
+    /// IRI as local value.
+    pub fn full_iris() -> Self {
+        Self {
+            full_iris: true,
+            ..Self::new(0)
+        }
+    }
+
+    /// Tells if the IRIs are checked to only contain permitted IRI characters, see
+    /// [rdf::check_iri_chars], before being exploded.
+    pub fn with_strict_chars(mut self, strict_chars: bool) -> Self {
+        self.strict_chars = strict_chars;
+        self
+    }
+
+    /// Tells if the IRIs are required to be absolute, see [AbsoluteIri], before being exploded.
+    pub fn with_absolute_iris(mut self, absolute_iris: bool) -> Self {
+        self.absolute_iris = absolute_iris;
+        self
+    }
+
+    pub fn explode(&mut self, iri: &str) -> StdResult<(String, String)> {
+        let iri = if self.absolute_iris {
+            AbsoluteIri::try_new(iri)?.as_str()
+        } else {
+            iri
+        };
+        if self.strict_chars {
+            rdf::check_iri_chars(iri)?;
+        }
+        if self.full_iris {
+            return Ok((String::new(), iri.to_string()));
+        }
+        if self.capacity == 0 {
+            return rdf::explode_iri(iri);
+        }
+
+        self.tick += 1;
+        if let Some((used, exploded)) = self.entries.get_mut(iri) {
+            self.recency.remove(used);
+            self.recency.insert(self.tick, iri.to_string());
+            *used = self.tick;
+            return Ok(exploded.clone());
+        }
+
+        let exploded = rdf::explode_iri(iri)?;
+        if self.entries.len() >= self.capacity {
+            if let Some((_, evicted)) = self.recency.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.recency.insert(self.tick, iri.to_string());
+        self.entries
+            .insert(iri.to_string(), (self.tick, exploded.clone()));
+
+        Ok(exploded)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explode() {
+        let iris = [
+            "http://example.com/a",
+            "http://example.com/b",
+            "http://example.com#a",
+            "http://example.com/a",
+            "urn:isbn:123",
+            "http://example.com/b",
+            "http://example.com/c",
+            "http://example.com/a",
+        ];
+
+        for capacity in [0, 1, 2, 16] {
+            let mut cache = IriExplodeCache::new(capacity);
+            for iri in iris {
+                assert_eq!(cache.explode(iri), rdf::explode_iri(iri));
+                assert!(cache.len() <= capacity);
+            }
+        }
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = IriExplodeCache::new(2);
+        for iri in [
+            "http://example.com/a",
+            "http://example.com/b",
+            "http://example.com/a",
+            "http://example.com/c",
+        ] {
+            cache.explode(iri).unwrap();
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.entries.contains_key("http://example.com/a"));
+        assert!(cache.entries.contains_key("http://example.com/c"));
+        assert!(!cache.entries.contains_key("http://example.com/b"));
+    }
+
+    #[test]
+    fn full_iris() {
+        let mut cache = IriExplodeCache::full_iris();
+        for iri in ["http://example.com/a", "urn:isbn:123", "a:"] {
+            assert_eq!(cache.explode(iri), Ok((String::new(), iri.to_string())));
+        }
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn strict_chars() {
+        for iri in ["http://example.com/a b", "http://example.com/a\u{0}b"] {
+            for strict_chars in [false, true] {
+                for mut cache in [IriExplodeCache::new(2), IriExplodeCache::full_iris()] {
+                    cache = cache.with_strict_chars(strict_chars);
+                    assert_eq!(cache.explode(iri).is_err(), strict_chars);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn absolute_iris() {
+        for (iri, absolute) in [("http://example.com/a", true), ("example.com/a", false)] {
+            for absolute_iris in [false, true] {
+                for mut cache in [IriExplodeCache::new(2), IriExplodeCache::full_iris()] {
+                    cache = cache.with_absolute_iris(absolute_iris);
+                    assert_eq!(cache.explode(iri).is_err(), absolute_iris && !absolute);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,12 @@
+mod compaction;
 mod engine;
+mod explode_cache;
+mod overlay;
+mod predicate_index;
+mod repair;
 
+pub use compaction::*;
 pub use engine::*;
+pub use overlay::*;
+pub use predicate_index::*;
+pub use repair::*;
@@ -0,0 +1,172 @@
+use cosmwasm_std::{Order, Record, Storage};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::ops::Bound;
+
+/// A storage buffering its writes in memory on top of a read-only storage, the reads seeing the
+/// buffered writes, e.g. to run an operation altering the store from a query without writing
+/// anything.
+pub struct OverlayStorage<'a> {
+    inner: &'a dyn Storage,
+    /// The values written by key, [None] standing for a removed key.
+    writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+/// A buffered write, [None] standing for a removed key.
+type Write = (Vec<u8>, Option<Vec<u8>>);
+
+impl<'a> OverlayStorage<'a> {
+    pub fn new(inner: &'a dyn Storage) -> Self {
+        Self {
+            inner,
+            writes: BTreeMap::new(),
+        }
+    }
+}
+
+impl Storage for OverlayStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.writes.get(key) {
+            Some(value) => value.clone(),
+            None => self.inner.get(key),
+        }
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        let bounds = (
+            start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec())),
+            end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec())),
+        );
+        let mut writes: Vec<Write> = self
+            .writes
+            .range(bounds)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        if order == Order::Descending {
+            writes.reverse();
+        }
+
+        Box::new(MergedRange {
+            inner: self.inner.range(start, end, order).peekable(),
+            writes: writes.into_iter().peekable(),
+            order,
+        })
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.writes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.writes.insert(key.to_vec(), None);
+    }
+}
+
+/// Merges the records of a range of the underlying storage with the writes buffered within the
+/// same range, the latter taking precedence.
+struct MergedRange<'a> {
+    inner: Peekable<Box<dyn Iterator<Item = Record> + 'a>>,
+    writes: Peekable<std::vec::IntoIter<Write>>,
+    order: Order,
+}
+
+impl Iterator for MergedRange<'_> {
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = match (self.inner.peek(), self.writes.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((inner_key, _)), Some((write_key, _))) => match self.order {
+                    Order::Ascending => inner_key.cmp(write_key),
+                    Order::Descending => write_key.cmp(inner_key),
+                },
+            };
+            match next {
+                Ordering::Less => return self.inner.next(),
+                // the record is overridden by the write
+                Ordering::Equal => {
+                    self.inner.next();
+                }
+                Ordering::Greater => {}
+            }
+            if let Some((key, Some(value))) = self.writes.next() {
+                return Some((key, value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn overlay_reads_its_writes() {
+        let mut inner = MockStorage::new();
+        for key in ["a", "b", "c", "d"] {
+            inner.set(key.as_bytes(), key.to_uppercase().as_bytes());
+        }
+
+        let mut overlay = OverlayStorage::new(&inner);
+        overlay.set(b"b", b"B2");
+        overlay.remove(b"c");
+        overlay.set(b"e", b"E");
+        overlay.remove(b"f");
+
+        assert_eq!(overlay.get(b"a"), Some(b"A".to_vec()));
+        assert_eq!(overlay.get(b"b"), Some(b"B2".to_vec()));
+        assert_eq!(overlay.get(b"c"), None);
+        assert_eq!(overlay.get(b"e"), Some(b"E".to_vec()));
+        assert_eq!(overlay.get(b"f"), None);
+
+        let range = |start: Option<&[u8]>, end: Option<&[u8]>, order| {
+            overlay
+                .range(start, end, order)
+                .map(|(key, value)| {
+                    (
+                        String::from_utf8(key).unwrap(),
+                        String::from_utf8(value).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let records = |records: &[(&str, &str)]| {
+            records
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            range(None, None, Order::Ascending),
+            records(&[("a", "A"), ("b", "B2"), ("d", "D"), ("e", "E")])
+        );
+        assert_eq!(
+            range(None, None, Order::Descending),
+            records(&[("e", "E"), ("d", "D"), ("b", "B2"), ("a", "A")])
+        );
+        assert_eq!(
+            range(Some(b"b"), Some(b"e"), Order::Ascending),
+            records(&[("b", "B2"), ("d", "D")])
+        );
+        assert_eq!(
+            range(Some(b"c"), None, Order::Descending),
+            records(&[("e", "E"), ("d", "D")])
+        );
+
+        // nothing is written to the underlying storage
+        assert_eq!(inner.get(b"b"), Some(b"B".to_vec()));
+        assert_eq!(inner.get(b"c"), Some(b"C".to_vec()));
+        assert_eq!(inner.get(b"e"), None);
+    }
+}
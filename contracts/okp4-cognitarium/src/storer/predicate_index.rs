@@ -0,0 +1,57 @@
+use crate::state::{triples, PREDICATE_INDEXING, STORE};
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+/// Builds the predicate index of the triples stored before it was maintained, the triples stored
+/// since being indexed on write.
+///
+/// The `limit` bounds the number of triples processed by a call, the progress being saved so a
+/// next call resumes it. Once complete, the store is flagged as having all its triples indexed.
+///
+/// Returns whether the index is complete.
+pub fn index_predicates(storage: &mut dyn Storage, limit: usize) -> StdResult<bool> {
+    let mut store = STORE.load(storage)?;
+    if store.predicate_index {
+        return Ok(true);
+    }
+
+    let scanned = triples()
+        .range_raw(
+            storage,
+            PREDICATE_INDEXING
+                .may_load(storage)?
+                .map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let complete = scanned.len() < limit;
+
+    let mut after = None;
+    for (key, triple) in scanned {
+        let object_hash = triple.object.as_hash();
+        // Saving the triple with no previous value only adds its index entries, the ones already
+        // present being overwritten as is.
+        triples().replace(
+            storage,
+            (
+                object_hash.as_bytes(),
+                triple.predicate.key(),
+                triple.subject.key(),
+            ),
+            Some(&triple),
+            None,
+        )?;
+        after = Some(key);
+    }
+
+    if complete {
+        store.predicate_index = true;
+        STORE.save(storage, &store)?;
+        PREDICATE_INDEXING.remove(storage);
+    } else if let Some(after) = after {
+        PREDICATE_INDEXING.save(storage, &after)?;
+    }
+    Ok(complete)
+}
@@ -0,0 +1,75 @@
+use crate::state::{namespaces, triples, NAMESPACE_REPAIR, STORE};
+use cosmwasm_std::{Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+
+/// Removes the orphaned namespaces, i.e. the ones accounted as referenced while no triple
+/// references them anymore, fixing the namespace count of the store. When the store retains the
+/// empty namespaces, their reference counter is reset instead.
+///
+/// The triples are first scanned to collect the referenced namespaces, then the namespaces are
+/// checked against them. The `limit` bounds the number of triples and namespaces processed by a
+/// call, the progress being saved so a next call resumes it.
+///
+/// Returns whether the repair is complete, along with the number of namespaces repaired by this
+/// call.
+pub fn repair_namespaces(storage: &mut dyn Storage, limit: usize) -> StdResult<(bool, usize)> {
+    let mut repair = NAMESPACE_REPAIR.may_load(storage)?.unwrap_or_default();
+
+    let mut budget = limit;
+    if !repair.scanned {
+        let scanned = triples()
+            .range_raw(
+                storage,
+                repair.after.clone().map(Bound::ExclusiveRaw),
+                None,
+                Order::Ascending,
+            )
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?;
+        repair.scanned = scanned.len() < budget;
+        budget -= scanned.len();
+
+        for (key, triple) in scanned {
+            repair.referenced.extend(triple.namespace_keys());
+            repair.after = Some(key);
+        }
+    }
+
+    let mut repaired = 0;
+    if repair.scanned {
+        let checked = namespaces()
+            .range(
+                storage,
+                repair.after_namespace.clone().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?;
+        let complete = checked.len() < budget;
+
+        let mut store = STORE.load(storage)?;
+        for (value, mut namespace) in checked {
+            if namespace.counter > 0 && !repair.referenced.contains(&namespace.key) {
+                if store.retain_empty_namespaces {
+                    namespace.counter = 0;
+                    namespaces().save(storage, value.clone(), &namespace)?;
+                } else {
+                    namespaces().remove(storage, value.clone())?;
+                    store.stat.namespace_count -= Uint128::one();
+                }
+                repaired += 1;
+            }
+            repair.after_namespace = Some(value);
+        }
+        STORE.save(storage, &store)?;
+
+        if complete {
+            NAMESPACE_REPAIR.remove(storage);
+            return Ok((true, repaired));
+        }
+    }
+
+    NAMESPACE_REPAIR.save(storage, &repair)?;
+    Ok((false, repaired))
+}
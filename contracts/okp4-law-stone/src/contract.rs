@@ -34,6 +34,7 @@ pub fn instantiate(
         data: msg.program.clone(),
         pin: true,
         compression_algorithm: None,
+        alias: None,
     };
 
     let store_program_msg = WasmMsg::Execute {
@@ -338,10 +339,12 @@ mod tests {
                             data,
                             pin,
                             compression_algorithm,
+                            alias,
                         } => {
                             assert_eq!(data, program);
                             assert!(pin, "the main program should be pinned");
                             assert_eq!(compression_algorithm, None);
+                            assert_eq!(alias, None);
                         }
                         _ => panic!("storage message should be a StoreObject message"),
                     }
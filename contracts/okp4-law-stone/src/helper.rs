@@ -15,12 +15,12 @@ pub fn object_ref_to_uri(object: ObjectRef) -> StdResult<CosmwasmUri> {
 }
 
 pub fn get_reply_event_attribute(events: Vec<Event>, key: String) -> Option<String> {
-    return events
+    events
         .iter()
         .flat_map(|e| e.attributes.clone())
         .filter(|a| a.key == key)
         .map(|a| a.value)
-        .next();
+        .next()
 }
 
 fn term_as_vec(term: TermValue) -> Result<Vec<String>, ContractError> {
@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use okp4_objectarium::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use okp4_objectarium::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
 }
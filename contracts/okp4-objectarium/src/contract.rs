@@ -5,15 +5,22 @@ use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
 use cw2::set_contract_version;
 
 use crate::crypto;
+use crate::encoding;
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, ObjectId, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, ObjectId, QueryMsg};
 use crate::state;
-use crate::state::{objects, pins, Bucket, Object, Pin, BUCKET, DATA};
+use crate::state::{objects, pins, Bucket, Object, Pin, BUCKET};
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// size of the chunks fed to the hasher when verifying the integrity of an object
+const VERIFY_CHUNK_SIZE: usize = 64 * 1024;
+
+// maximum length of an object alias
+const MAX_ALIAS_LENGTH: usize = 64;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<'_>,
@@ -35,6 +42,20 @@ pub fn instantiate(
     Ok(Response::default())
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut<'_>, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let bucket = BUCKET.load(deps.storage)?;
+    let migrated = state::migrate_legacy_data(deps.storage, bucket.config.chunk_size)?;
+    let reindexed = state::reindex_objects(deps.storage)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated_data_count", migrated.to_string())
+        .add_attribute("reindexed_object_count", reindexed.to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<'_>,
@@ -47,10 +68,32 @@ pub fn execute(
             data,
             pin,
             compression_algorithm,
-        } => execute::store_object(deps, info, data, pin, compression_algorithm),
+            alias,
+        } => execute::store_object(
+            deps,
+            info,
+            execute::ObjectData::Decoded(data),
+            pin,
+            compression_algorithm,
+            alias,
+        ),
+        ExecuteMsg::StoreBase64Object {
+            data,
+            pin,
+            compression_algorithm,
+            alias,
+        } => execute::store_object(
+            deps,
+            info,
+            execute::ObjectData::Base64(data),
+            pin,
+            compression_algorithm,
+            alias,
+        ),
         ExecuteMsg::PinObject { id } => execute::pin_object(deps, info, id),
         ExecuteMsg::UnpinObject { id } => execute::unpin_object(deps, info, id),
         ExecuteMsg::ForgetObject { id } => execute::forget_object(deps, info, id),
+        ExecuteMsg::TagObject { id, tags } => execute::tag_object(deps, info, id, tags),
     }
 }
 
@@ -59,19 +102,31 @@ pub mod execute {
     use crate::compress::CompressionAlgorithm;
     use crate::crypto::Hash;
     use crate::msg;
-    use crate::state::BucketLimits;
-    use crate::ContractError::ObjectPinned;
-    use cosmwasm_std::{Order, StdError, Uint128};
+    use crate::state::Tag;
+    use crate::state::{BucketLimits, EvictionPolicy};
+    use crate::ContractError::{
+        AliasAlreadyUsed, AliasMismatch, InvalidAlias, ObjectNotOwned, ObjectNotPinned,
+        ObjectPinned,
+    };
+    use cosmwasm_std::{Addr, Order, StdError, Storage, Uint128};
     use std::any::type_name;
+    use std::collections::BTreeSet;
+
+    /// The content of an object to store, either decoded or still encoded in base64 to be decoded
+    /// block by block.
+    pub enum ObjectData {
+        Decoded(Binary),
+        Base64(String),
+    }
 
     pub fn store_object(
         deps: DepsMut<'_>,
         info: MessageInfo,
-        data: Binary,
+        data: ObjectData,
         pin: bool,
         compression_algorithm: Option<msg::CompressionAlgorithm>,
+        alias: Option<String>,
     ) -> Result<Response, ContractError> {
-        let size = (data.len() as u128).into();
         let bucket = BUCKET.load(deps.storage)?;
         let compressions = &bucket.config.accepted_compression_algorithms;
         let compression: CompressionAlgorithm = compression_algorithm
@@ -79,6 +134,31 @@ pub mod execute {
             .or_else(|| compressions.first().cloned())
             .unwrap_or(CompressionAlgorithm::Passthrough);
 
+        // the object id being the hash of its content, identical objects are stored once and
+        // storing it again only pins it if requested
+        let hash_algorithm = bucket.config.hash_algorithm.into();
+        let (id, size) = match &data {
+            ObjectData::Decoded(data) => (crypto::hash(&hash_algorithm, &data.0), data.len()),
+            ObjectData::Base64(data) => (
+                encoding::hash_base64(&hash_algorithm, data.as_bytes())?,
+                encoding::decoded_len(data.as_bytes()),
+            ),
+        };
+        let size = (size as u128).into();
+        if state::has_data(deps.storage, &id) {
+            if let Some(alias) = &alias {
+                if objects().load(deps.storage, id.clone())?.alias.as_ref() != Some(alias) {
+                    return Err(AliasMismatch {});
+                }
+            }
+            if pin {
+                add_pin(deps.storage, &id, info.sender)?;
+            }
+            return Ok(Response::new()
+                .add_attribute("action", "store_object")
+                .add_attribute("id", id));
+        }
+
         // pre-conditions
         if let Some(limit) = bucket.limits.max_object_size {
             if size > limit {
@@ -98,10 +178,16 @@ pub mod execute {
         }
         if let Some(limit) = bucket.limits.max_total_size {
             let value = bucket.stat.size + size;
-            if value > limit {
+            if value > limit && bucket.config.eviction_policy.is_none() {
                 return Err(BucketError::MaxTotalSizeLimitExceeded(value, limit).into());
             }
         }
+        if let Some(alias) = &alias {
+            validate_alias(alias)?;
+            if state::ALIASES.has(deps.storage, alias.clone()) {
+                return Err(AliasAlreadyUsed(alias.clone()));
+            }
+        }
         if !compressions.contains(&compression) {
             return Err(BucketError::CompressionAlgorithmNotAccepted(
                 compression.into(),
@@ -115,18 +201,42 @@ pub mod execute {
             .into());
         }
 
-        // store object data
-        let id = crypto::hash(&bucket.config.hash_algorithm.into(), &data.0);
-        let data_path = DATA.key(id.clone());
-        if data_path.has(deps.storage) {
-            return Err(ContractError::Bucket(BucketError::ObjectAlreadyStored));
-        }
-        let compressed_data = compression.compress(&data.0)?;
+        // evict objects to make room for the new one
+        let evicted = match (bucket.limits.max_total_size, bucket.config.eviction_policy) {
+            (Some(limit), Some(policy)) if bucket.stat.size + size > limit => {
+                evict_objects(deps.storage, policy, bucket.stat.size + size, limit)?
+            }
+            _ => vec![],
+        };
 
-        data_path.save(deps.storage, &compressed_data)?;
+        let compressed_size = match (data, &compression) {
+            (ObjectData::Base64(data), CompressionAlgorithm::Passthrough) => {
+                encoding::save_base64_data(
+                    deps.storage,
+                    &id,
+                    data.as_bytes(),
+                    bucket.config.chunk_size,
+                )?
+            }
+            (data, _) => {
+                let compressed_data = match data {
+                    ObjectData::Decoded(data) => compression.compress(&data.0)?,
+                    ObjectData::Base64(data) => {
+                        compression.compress(&encoding::decode_base64(data.as_bytes())?)?
+                    }
+                };
+                state::save_data(
+                    deps.storage,
+                    &id,
+                    &compressed_data,
+                    bucket.config.chunk_size,
+                )?;
+                (compressed_data.len() as u128).into()
+            }
+        };
 
         // store object
-        let compressed_size = (compressed_data.len() as u128).into();
+        let seq = state::next_sequence(deps.storage)?;
         let object = &Object {
             id,
             owner: info.sender.clone(),
@@ -134,9 +244,15 @@ pub mod execute {
             pin_count: if pin { Uint128::one() } else { Uint128::zero() },
             compression,
             compressed_size,
+            stored_seq: seq,
+            pinned_seq: if pin { seq } else { 0 },
+            alias,
         };
 
         objects().save(deps.storage, object.id.clone(), object)?;
+        if let Some(alias) = &object.alias {
+            state::ALIASES.save(deps.storage, alias.clone(), &object.id)?;
+        }
 
         // save bucket stats
         BUCKET.update(deps.storage, |mut bucket| -> Result<_, ContractError> {
@@ -161,7 +277,30 @@ pub mod execute {
 
         Ok(Response::new()
             .add_attribute("action", "store_object")
-            .add_attribute("id", object.id.clone()))
+            .add_attribute("id", object.id.clone())
+            .add_attributes(
+                evicted
+                    .into_iter()
+                    .map(|id| ("evicted", String::from(id)))
+                    .collect::<Vec<_>>(),
+            ))
+    }
+
+    /// validate_alias checks the given object alias is not empty, not too long, and only made of
+    /// ASCII alphanumeric characters, '-', '_', '.' or '/'.
+    fn validate_alias(alias: &str) -> Result<(), ContractError> {
+        if alias.is_empty() || alias.len() > MAX_ALIAS_LENGTH {
+            return Err(InvalidAlias(format!(
+                "length must be between 1 and {MAX_ALIAS_LENGTH}"
+            )));
+        }
+        if let Some(c) = alias
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '-' | '_' | '.' | '/'))
+        {
+            return Err(InvalidAlias(format!("unexpected character '{c}'")));
+        }
+        Ok(())
     }
 
     pub fn pin_object(
@@ -173,20 +312,29 @@ pub mod execute {
             .add_attribute("action", "pin_object")
             .add_attribute("id", object_id.clone());
 
-        let id: Hash = object_id.try_into()?;
-        if pins().has(deps.storage, (id.clone(), info.sender.clone())) {
-            return Ok(res);
+        let id = state::object_key(deps.storage, object_id)?;
+        add_pin(deps.storage, &id, info.sender)?;
+
+        Ok(res)
+    }
+
+    /// add_pin pins the given object for the given address, if not already pinned by it.
+    fn add_pin(storage: &mut dyn Storage, id: &Hash, address: Addr) -> Result<(), ContractError> {
+        if pins().has(storage, (id.clone(), address.clone())) {
+            return Ok(());
         }
 
-        let o = objects().update(deps.storage, id.clone(), |o| -> Result<Object, StdError> {
+        let seq = state::next_sequence(storage)?;
+        let o = objects().update(storage, id.clone(), |o| -> Result<Object, StdError> {
             o.map(|mut e: Object| -> Object {
                 e.pin_count += Uint128::one();
+                e.pinned_seq = seq;
                 e
             })
             .ok_or_else(|| StdError::not_found(type_name::<Object>()))
         })?;
 
-        let bucket = BUCKET.load(deps.storage)?;
+        let bucket = BUCKET.load(storage)?;
 
         match bucket.limits {
             BucketLimits {
@@ -197,14 +345,14 @@ pub mod execute {
             }
             _ => {
                 pins().save(
-                    deps.storage,
-                    (id.clone(), info.sender.clone()),
+                    storage,
+                    (id.clone(), address.clone()),
                     &Pin {
-                        id,
-                        address: info.sender,
+                        id: id.clone(),
+                        address,
                     },
                 )?;
-                Ok(res)
+                Ok(())
             }
         }
     }
@@ -214,59 +362,166 @@ pub mod execute {
         info: MessageInfo,
         object_id: ObjectId,
     ) -> Result<Response, ContractError> {
-        let id: Hash = object_id.clone().try_into()?;
-        let object_path = objects().key(id.clone());
-        let mut object = object_path.load(deps.storage)?;
+        let id = state::object_key(deps.storage, object_id.clone())?;
+        let old = objects().load(deps.storage, id.clone())?;
+        let mut object = old.clone();
 
-        let res = Response::new()
+        remove_pin(deps.storage, &mut object, info.sender)?;
+        // saved through the indexed map for the object to leave the pinned eviction keys
+        objects().replace(deps.storage, id, Some(&object), Some(&old))?;
+
+        Ok(Response::new()
             .add_attribute("action", "unpin_object")
-            .add_attribute("id", object_id);
+            .add_attribute("id", object_id))
+    }
 
-        if !pins().has(deps.storage, (id.clone(), info.sender.clone())) {
-            return Ok(res);
+    pub fn forget_object(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        object_id: ObjectId,
+    ) -> Result<Response, ContractError> {
+        let id = state::object_key(deps.storage, object_id.clone())?;
+        let mut object = objects().load(deps.storage, id.clone())?;
+        if pins().has(deps.storage, (id.clone(), info.sender.clone())) {
+            remove_pin(deps.storage, &mut object, info.sender)?;
         }
 
-        object.pin_count -= Uint128::one();
-        object_path.save(deps.storage, &object)?;
-
-        pins().remove(deps.storage, (id, info.sender))?;
+        if !object.pin_count.is_zero() {
+            return Err(ObjectPinned {});
+        }
+        remove_object(deps.storage, &object)?;
 
-        Ok(res)
+        Ok(Response::new()
+            .add_attribute("action", "forget_object")
+            .add_attribute("id", object_id))
     }
 
-    pub fn forget_object(
+    pub fn tag_object(
         deps: DepsMut<'_>,
         info: MessageInfo,
         object_id: ObjectId,
+        tags: Vec<String>,
     ) -> Result<Response, ContractError> {
-        let id: Hash = object_id.clone().try_into()?;
-        if pins().has(deps.storage, (id.clone(), info.sender.clone())) {
-            pins().remove(deps.storage, (id.clone(), info.sender))?;
+        let id = state::object_key(deps.storage, object_id.clone())?;
+        let object = objects().load(deps.storage, id.clone())?;
+        if object.owner != info.sender {
+            return Err(ObjectNotOwned {});
         }
 
-        if pins()
-            .idx
-            .object
-            .prefix(id.clone())
-            .keys_raw(deps.storage, None, None, Order::Ascending)
-            .next()
-            .is_some()
-        {
-            return Err(ObjectPinned {});
+        let tags: BTreeSet<String> = tags.into_iter().collect();
+        let bucket = BUCKET.load(deps.storage)?;
+        if let Some(limit) = bucket.limits.max_object_tags {
+            let value = Uint128::from(tags.len() as u128);
+            if value > limit {
+                return Err(BucketError::MaxObjectTagsLimitExceeded(value, limit).into());
+            }
         }
-        let object = query::object(deps.as_ref(), object_id.clone())?;
-        BUCKET.update(deps.storage, |mut b| -> Result<_, ContractError> {
+        if let Some(limit) = bucket.limits.max_tag_length {
+            if let Some(value) = tags
+                .iter()
+                .map(|tag| Uint128::from(tag.len() as u128))
+                .find(|len| *len > limit)
+            {
+                return Err(BucketError::MaxTagLengthLimitExceeded(value, limit).into());
+            }
+        }
+
+        state::remove_tags(deps.storage, &id)?;
+        for tag in tags {
+            state::tags().save(
+                deps.storage,
+                (id.clone(), tag.clone()),
+                &Tag {
+                    id: id.clone(),
+                    tag,
+                },
+            )?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "tag_object")
+            .add_attribute("id", object_id))
+    }
+
+    /// remove_object removes the given object and its content from the bucket, updating the
+    /// bucket stats.
+    fn remove_object(storage: &mut dyn Storage, object: &Object) -> Result<(), ContractError> {
+        BUCKET.update(storage, |mut b| -> Result<_, ContractError> {
             b.stat.object_count -= Uint128::one();
             b.stat.size -= object.size;
+            b.stat.compressed_size -= object.compressed_size;
             Ok(b)
         })?;
 
-        objects().remove(deps.storage, id.clone())?;
-        DATA.remove(deps.storage, id);
+        objects().remove(storage, object.id.clone())?;
+        state::remove_data(storage, &object.id)?;
+        state::remove_tags(storage, &object.id)?;
+        if let Some(alias) = &object.alias {
+            state::ALIASES.remove(storage, alias.clone());
+        }
 
-        Ok(Response::new()
-            .add_attribute("action", "forget_object")
-            .add_attribute("id", object_id))
+        Ok(())
+    }
+
+    /// evict_objects evicts unpinned objects, in the order defined by the given policy, until the
+    /// total size of the bucket fits the given limit. The ids of the evicted objects are returned.
+    ///
+    /// An error is returned if evicting all the unpinned objects is not enough.
+    fn evict_objects(
+        storage: &mut dyn Storage,
+        policy: EvictionPolicy,
+        total_size: Uint128,
+        limit: Uint128,
+    ) -> Result<Vec<Hash>, ContractError> {
+        // the unpinned objects are read lazily in the eviction order, only the ones to be evicted
+        // being loaded
+        let objects = objects();
+        let candidates =
+            objects
+                .idx
+                .eviction(policy)
+                .sub_prefix(0)
+                .range(storage, None, None, Order::Ascending);
+
+        let mut value = total_size;
+        let mut evicted = vec![];
+        for res in candidates {
+            if value <= limit {
+                break;
+            }
+            let (_, object) = res?;
+            value -= object.size;
+            evicted.push(object);
+        }
+        if value > limit {
+            return Err(BucketError::MaxTotalSizeLimitExceeded(value, limit).into());
+        }
+
+        for object in &evicted {
+            remove_object(storage, object)?;
+        }
+        Ok(evicted.into_iter().map(|object| object.id).collect())
+    }
+
+    /// remove_pin removes the pin of the given address on the object, decrementing its pin count.
+    /// An error is returned if the object is not pinned by the address.
+    fn remove_pin(
+        storage: &mut dyn Storage,
+        object: &mut Object,
+        address: Addr,
+    ) -> Result<(), ContractError> {
+        let key = (object.id.clone(), address);
+        if !pins().has(storage, key.clone()) {
+            return Err(ObjectNotPinned {});
+        }
+
+        object.pin_count = object
+            .pin_count
+            .checked_sub(Uint128::one())
+            .map_err(StdError::from)?;
+        pins().remove(storage, key)?;
+
+        Ok(())
     }
 }
 
@@ -275,27 +530,40 @@ pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> Result<Binary, Contrac
     Ok(match msg {
         QueryMsg::Bucket {} => to_binary(&query::bucket(deps)?),
         QueryMsg::Object { id } => to_binary(&query::object(deps, id)?),
+        QueryMsg::ObjectByAlias { alias } => to_binary(&query::fetch_by_alias(deps, alias)?),
         QueryMsg::ObjectData { id } => to_binary(&query::data(deps, id)?),
+        QueryMsg::ObjectDataWithChecksum { id } => to_binary(&query::data_with_checksum(deps, id)?),
+        QueryMsg::ObjectDataRange { id, offset, length } => {
+            to_binary(&query::data_range(deps, id, offset, length)?)
+        }
         QueryMsg::Objects {
             address,
             after,
             first,
         } => to_binary(&query::fetch_objects(deps, address, after, first)?),
+        QueryMsg::ObjectSize { id } => to_binary(&query::object_size(deps, id)?),
         QueryMsg::ObjectPins { id, after, first } => {
             to_binary(&query::object_pins(deps, id, after, first)?)
         }
+        QueryMsg::ObjectsByTag { tag, after, first } => {
+            to_binary(&query::objects_by_tag(deps, tag, after, first)?)
+        }
+        QueryMsg::VerifyObject { id } => to_binary(&query::verify_object(deps, id)?),
     }?)
 }
 
 pub mod query {
     use super::*;
+    use crate::compress::CompressionAlgorithm;
     use crate::crypto::Hash;
-    use crate::cursor;
+    use crate::cursor::{self, AsCursor};
     use crate::msg::{
-        BucketResponse, Cursor, ObjectPinsResponse, ObjectResponse, ObjectsResponse, PageInfo,
+        BucketResponse, Cursor, ObjectDataResponse, ObjectPinsResponse, ObjectResponse,
+        ObjectSizeResponse, ObjectsResponse, PageInfo,
     };
     use crate::pagination::{PaginationHandler, QueryPage};
-    use cosmwasm_std::{Addr, Order};
+    use crate::state::Tag;
+    use cosmwasm_std::{Addr, Order, Uint128};
 
     pub fn bucket(deps: Deps<'_>) -> Result<BucketResponse, ContractError> {
         let bucket = BUCKET.load(deps.storage)?;
@@ -309,19 +577,105 @@ pub mod query {
     }
 
     pub fn object(deps: Deps<'_>, object_id: ObjectId) -> Result<ObjectResponse, ContractError> {
-        let id: Hash = object_id.try_into()?;
+        let id = state::object_key(deps.storage, object_id)?;
+        let object = objects().load(deps.storage, id)?;
+        Ok((&object).into())
+    }
+
+    pub fn fetch_by_alias(deps: Deps<'_>, alias: String) -> Result<ObjectResponse, ContractError> {
+        let id = state::ALIASES.load(deps.storage, alias)?;
         let object = objects().load(deps.storage, id)?;
         Ok((&object).into())
     }
 
+    pub fn object_size(
+        deps: Deps<'_>,
+        object_id: ObjectId,
+    ) -> Result<ObjectSizeResponse, ContractError> {
+        let id = state::object_key(deps.storage, object_id)?;
+        let object = objects().load(deps.storage, id.clone())?;
+        Ok(ObjectSizeResponse {
+            size: object.size,
+            compressed_size: object.compressed_size,
+            chunk_count: state::chunk_count(deps.storage, &id)?,
+        })
+    }
+
     pub fn data(deps: Deps<'_>, object_id: ObjectId) -> Result<Binary, ContractError> {
-        let id: Hash = object_id.try_into()?;
+        let id = state::object_key(deps.storage, object_id)?;
         let compression = objects().load(deps.storage, id.clone())?.compression;
-        let data = DATA.load(deps.storage, id)?;
+        let data = state::load_data(deps.storage, &id)?;
         let decompressed_data = compression.decompress(&data)?;
         Ok(Binary::from(decompressed_data))
     }
 
+    pub fn data_with_checksum(
+        deps: Deps<'_>,
+        object_id: ObjectId,
+    ) -> Result<ObjectDataResponse, ContractError> {
+        let id = state::object_key(deps.storage, object_id)?;
+        let compression = objects().load(deps.storage, id.clone())?.compression;
+        let algorithm: crypto::HashAlgorithm =
+            BUCKET.load(deps.storage)?.config.hash_algorithm.into();
+
+        let (data, checksum) = match compression {
+            CompressionAlgorithm::Passthrough => {
+                state::load_hashed_data(deps.storage, &id, &algorithm)?
+            }
+            compression => {
+                let data = compression.decompress(&state::load_data(deps.storage, &id)?)?;
+                let checksum = content_hash(&algorithm, &data);
+                (data, checksum)
+            }
+        };
+
+        Ok(ObjectDataResponse {
+            checksum: checksum.into(),
+            data: Binary::from(data),
+        })
+    }
+
+    pub fn data_range(
+        deps: Deps<'_>,
+        object_id: ObjectId,
+        offset: Uint128,
+        length: Uint128,
+    ) -> Result<Binary, ContractError> {
+        let id = state::object_key(deps.storage, object_id)?;
+        let object = objects().load(deps.storage, id.clone())?;
+
+        let out_of_bounds = || ContractError::RangeOutOfBounds {
+            offset,
+            length,
+            size: object.size,
+        };
+        let end = offset.checked_add(length).map_err(|_| out_of_bounds())?;
+        if end > object.size {
+            return Err(out_of_bounds());
+        }
+        let (offset, length) = (
+            usize::try_from(offset.u128()).map_err(|_| out_of_bounds())?,
+            usize::try_from(length.u128()).map_err(|_| out_of_bounds())?,
+        );
+
+        if length == 0 {
+            return Ok(Binary::default());
+        }
+
+        match object.compression {
+            CompressionAlgorithm::Passthrough => Ok(Binary::from(state::load_data_range(
+                deps.storage,
+                &id,
+                offset,
+                length,
+            )?)),
+            compression => {
+                let data = compression.decompress(&state::load_data(deps.storage, &id)?)?;
+                Ok(Binary::from(&data[offset..offset + length]))
+            }
+        }
+    }
+
     pub fn fetch_objects(
         deps: Deps<'_>,
         address: Option<String>,
@@ -334,7 +688,10 @@ pub mod query {
         };
 
         let handler: PaginationHandler<'_, Object, Hash> =
-            PaginationHandler::from(BUCKET.load(deps.storage)?.pagination);
+            PaginationHandler::from(BUCKET.load(deps.storage)?.pagination).with_filter(format!(
+                "objects/{}",
+                address.as_ref().map(Addr::as_str).unwrap_or_default()
+            ));
 
         let page: (Vec<Object>, PageInfo) = handler.query_page(
             |min_bound| match address {
@@ -362,11 +719,12 @@ pub mod query {
         after: Option<Cursor>,
         first: Option<u32>,
     ) -> StdResult<ObjectPinsResponse> {
-        let id: Hash = object_id.try_into()?;
+        let id = state::object_key(deps.storage, object_id)?;
         objects().load(deps.storage, id.clone())?;
 
         let handler: PaginationHandler<'_, Pin, (Hash, Addr)> =
-            PaginationHandler::from(BUCKET.load(deps.storage)?.pagination);
+            PaginationHandler::from(BUCKET.load(deps.storage)?.pagination)
+                .with_filter(format!("object_pins/{}", String::from(id.clone())));
 
         let page: (Vec<Pin>, PageInfo) = handler.query_page_cursor_fn(
             |min_bound| {
@@ -377,12 +735,8 @@ pub mod query {
                     Order::Ascending,
                 )
             },
-            |c| {
-                cursor::decode(c)
-                    .and_then(|raw| deps.api.addr_validate(raw.as_str()))
-                    .map(|addr| (id.clone(), addr))
-            },
-            |pin: &Pin| cursor::encode(pin.clone().address.into_string()),
+            |c| cursor::decode_address(deps.api, c).map(|addr| (id.clone(), addr)),
+            |pin: &Pin| cursor::encode_address(&pin.address),
             after,
             first,
         )?;
@@ -396,6 +750,71 @@ pub mod query {
             page_info: page.1,
         })
     }
+
+    pub fn objects_by_tag(
+        deps: Deps<'_>,
+        tag: String,
+        after: Option<Cursor>,
+        first: Option<u32>,
+    ) -> StdResult<ObjectsResponse> {
+        let handler: PaginationHandler<'_, Tag, (Hash, String)> =
+            PaginationHandler::from(BUCKET.load(deps.storage)?.pagination)
+                .with_filter(format!("objects_by_tag/{tag}"));
+
+        let page: (Vec<Tag>, PageInfo) = handler.query_page_cursor_fn(
+            |min_bound| {
+                state::tags().idx.tag.prefix(tag.clone()).range(
+                    deps.storage,
+                    min_bound,
+                    None,
+                    Order::Ascending,
+                )
+            },
+            |c| Object::decode_cursor(c).map(|id| (id, tag.clone())),
+            |tag: &Tag| cursor::encode(&tag.id),
+            after,
+            first,
+        )?;
+
+        Ok(ObjectsResponse {
+            data: page
+                .0
+                .iter()
+                .map(|tag| {
+                    objects()
+                        .load(deps.storage, tag.id.clone())
+                        .map(|object| (&object).into())
+                })
+                .collect::<StdResult<_>>()?,
+            page_info: page.1,
+        })
+    }
+
+    pub fn verify_object(deps: Deps<'_>, object_id: ObjectId) -> Result<bool, ContractError> {
+        let id = state::object_key(deps.storage, object_id)?;
+        let compression = objects().load(deps.storage, id.clone())?.compression;
+        let algorithm: crypto::HashAlgorithm =
+            BUCKET.load(deps.storage)?.config.hash_algorithm.into();
+
+        let hash = match compression {
+            CompressionAlgorithm::Passthrough => state::hash_data(deps.storage, &id, &algorithm)?,
+            compression => {
+                let data = state::load_data(deps.storage, &id)?;
+                content_hash(&algorithm, &compression.decompress(&data)?)
+            }
+        };
+
+        Ok(hash == id)
+    }
+
+    /// Hashes the content of an object, feeding it to the hasher chunk by chunk.
+    fn content_hash(algorithm: &crypto::HashAlgorithm, data: &[u8]) -> Hash {
+        let mut hasher = algorithm.hasher();
+        for chunk in data.chunks(VERIFY_CHUNK_SIZE) {
+            hasher.update(chunk);
+        }
+        hasher.finalize()
+    }
 }
 
 impl From<state::HashAlgorithm> for crypto::HashAlgorithm {
@@ -414,10 +833,12 @@ impl From<state::HashAlgorithm> for crypto::HashAlgorithm {
 mod tests {
     use super::*;
     use crate::crypto::Hash;
+    use crate::cursor;
     use crate::error::BucketError;
     use crate::msg::{
-        BucketConfig, BucketLimitsBuilder, BucketResponse, CompressionAlgorithm, HashAlgorithm,
-        ObjectPinsResponse, ObjectResponse, ObjectsResponse, PageInfo, PaginationConfigBuilder,
+        BucketConfig, BucketLimitsBuilder, BucketResponse, CompressionAlgorithm, EvictionPolicy,
+        HashAlgorithm, ObjectDataResponse, ObjectPinsResponse, ObjectResponse, ObjectSizeResponse,
+        ObjectsResponse, PageInfo, PaginationConfigBuilder,
     };
     use base64::{engine::general_purpose, Engine as _};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
@@ -749,6 +1170,7 @@ mod tests {
                     data: Binary::from_base64(content).unwrap(),
                     pin: *pin,
                     compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                    alias: None,
                 };
                 let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
                 assert_eq!(
@@ -762,7 +1184,7 @@ mod tests {
                 assert_eq!(
                     Binary::from_base64(content).unwrap(),
                     Binary::from(
-                        DATA.load(&deps.storage, decode_hash(expected_hash.clone()))
+                        state::load_data(&deps.storage, &decode_hash(expected_hash.clone()))
                             .unwrap()
                     ),
                 );
@@ -816,28 +1238,61 @@ mod tests {
     }
 
     #[test]
-    fn store_object_already_stored() {
+    fn store_object_deduplicated() {
         let mut deps = mock_dependencies();
-        let info = mock_info("creator", &[]);
         let msg = InstantiateMsg {
             bucket: String::from("test"),
             config: Default::default(),
             limits: Default::default(),
             pagination: Default::default(),
         };
-        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
 
-        let object = general_purpose::STANDARD.encode("already existing object");
-        let msg = ExecuteMsg::StoreObject {
-            data: Binary::from_base64(object.as_str()).unwrap(),
-            pin: true,
-            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+        let store = |deps: DepsMut<'_>, sender: &str, data: &str, pin: bool| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::StoreObject {
+                    data: Binary::from(data.as_bytes()),
+                    pin,
+                    compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                    alias: None,
+                },
+            )
+            .unwrap()
+            .attributes
         };
-        execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+
+        let first = store(deps.as_mut(), "creator", "identical object", true);
+        let id = first[1].value.clone();
+        for (sender, pin) in [("creator", true), ("alice", false), ("bob", true)] {
+            assert_eq!(
+                store(deps.as_mut(), sender, "identical object", pin),
+                vec![
+                    Attribute::new("action", "store_object"),
+                    Attribute::new("id", id.clone()),
+                ]
+            );
+        }
+        let near = store(deps.as_mut(), "alice", "identical objecT", false);
+        assert_ne!(near[1].value, id);
+
+        let object = objects()
+            .load(&deps.storage, id.clone().try_into().unwrap())
+            .unwrap();
+        assert_eq!(object.owner, "creator");
+        assert_eq!(object.pin_count, Uint128::new(2));
         assert_eq!(
-            execute(deps.as_mut(), mock_env(), info, msg).err(),
-            Some(ContractError::Bucket(BucketError::ObjectAlreadyStored)),
+            pins()
+                .keys_raw(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            2
         );
+
+        let bucket = BUCKET.load(&deps.storage).unwrap();
+        assert_eq!(bucket.stat.object_count, Uint128::new(2));
+        assert_eq!(bucket.stat.size, Uint128::new(32));
     }
 
     #[test]
@@ -928,12 +1383,14 @@ mod tests {
                 data: Binary::from_base64(obj1.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
             let msg = ExecuteMsg::StoreObject {
                 data: Binary::from_base64(obj2.as_str()).unwrap(),
                 pin: true,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
 
@@ -1056,6 +1513,7 @@ mod tests {
                     data: Binary::from_base64(obj.as_str()).unwrap(),
                     pin: false,
                     compression_algorithm: case.compression_algorithm,
+                    alias: None,
                 },
             );
 
@@ -1076,6 +1534,7 @@ mod tests {
                             size: Uint128::from(data.len() as u128),
                             compressed_size: expected.compressed_size.into(),
                             compression_algorithm: expected.compression_algorithm,
+                            alias: None,
                         }
                     );
                     assert_eq!(res_object_data, data.as_bytes().to_vec());
@@ -1085,43 +1544,237 @@ mod tests {
     }
 
     #[test]
-    fn object() {
-        let mut deps = mock_dependencies();
-        let info = mock_info("creator", &[]);
+    fn store_object_eviction() {
+        const OKP4: &str = "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6";
+        const DATA: &str = "3a6eb0790f39ac87c94f3856b2dd2c5d110e6811602261a9a923d3bb23adc8b7";
 
-        let msg = InstantiateMsg {
-            bucket: String::from("test"),
-            config: Default::default(),
-            limits: Default::default(),
-            pagination: Default::default(),
-        };
-        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        struct TC {
+            policy: EvictionPolicy,
+            pins: Vec<(ObjectId, &'static str)>,
+            unpins: Vec<(ObjectId, &'static str)>,
+            expected: Result<Vec<ObjectId>, ContractError>,
+        }
 
-        match query(
-            deps.as_ref(),
-            mock_env(),
-            QueryMsg::Object {
-                id: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".to_string(),
+        let cases = vec![
+            TC {
+                policy: EvictionPolicy::OldestFirst,
+                pins: vec![],
+                unpins: vec![],
+                expected: Ok(vec![OKP4.to_string()]),
             },
-        )
-        .err()
-        .unwrap()
-        {
-            ContractError::Std(NotFound { .. }) => (),
-            _ => panic!("assertion failed"),
-        }
+            TC {
+                policy: EvictionPolicy::OldestFirst,
+                pins: vec![(OKP4.to_string(), "bob")],
+                unpins: vec![],
+                expected: Ok(vec![DATA.to_string()]),
+            },
+            TC {
+                policy: EvictionPolicy::OldestFirst,
+                pins: vec![(OKP4.to_string(), "bob")],
+                unpins: vec![(OKP4.to_string(), "bob")],
+                expected: Ok(vec![OKP4.to_string()]),
+            },
+            TC {
+                policy: EvictionPolicy::LeastRecentlyPinned,
+                pins: vec![],
+                unpins: vec![],
+                expected: Ok(vec![OKP4.to_string()]),
+            },
+            TC {
+                policy: EvictionPolicy::LeastRecentlyPinned,
+                pins: vec![(OKP4.to_string(), "bob")],
+                unpins: vec![(OKP4.to_string(), "bob")],
+                expected: Ok(vec![DATA.to_string()]),
+            },
+            TC {
+                policy: EvictionPolicy::LeastRecentlyPinned,
+                pins: vec![(OKP4.to_string(), "bob"), (DATA.to_string(), "alice")],
+                unpins: vec![],
+                expected: Err(ContractError::Bucket(
+                    BucketError::MaxTotalSizeLimitExceeded(13u8.into(), 10u8.into()),
+                )),
+            },
+        ];
 
-        let data = general_purpose::STANDARD.encode("hello");
-        let msg = ExecuteMsg::StoreObject {
-            data: Binary::from_base64(data.as_str()).unwrap(),
-            pin: true,
-            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
-        };
-        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        for case in cases {
+            let mut deps = mock_dependencies();
+            let info = mock_info("creator", &[]);
 
-        let msg = QueryMsg::Object {
-            id: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".to_string(),
-        };
+            let msg = InstantiateMsg {
+                bucket: String::from("test"),
+                config: BucketConfig {
+                    eviction_policy: Some(case.policy),
+                    ..Default::default()
+                },
+                limits: BucketLimitsBuilder::default()
+                    .max_total_size(10u128)
+                    .build()
+                    .unwrap(),
+                pagination: Default::default(),
+            };
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+            for content in ["okp4", "data"] {
+                let msg = ExecuteMsg::StoreObject {
+                    data: Binary::from(content.as_bytes()),
+                    pin: false,
+                    compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                    alias: None,
+                };
+                execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+            }
+            for (id, sender) in case.pins {
+                let msg = ExecuteMsg::PinObject { id };
+                execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg).unwrap();
+            }
+            for (id, sender) in case.unpins {
+                let msg = ExecuteMsg::UnpinObject { id };
+                execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg).unwrap();
+            }
+
+            let msg = ExecuteMsg::StoreObject {
+                data: Binary::from("hello".as_bytes()),
+                pin: false,
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
+            };
+            let result = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+
+            match case.expected {
+                Ok(expected_evicted) => {
+                    let res = result.unwrap();
+                    let evicted: Vec<ObjectId> = res
+                        .attributes
+                        .iter()
+                        .filter(|attr| attr.key == "evicted")
+                        .map(|attr| attr.value.clone())
+                        .collect();
+                    assert_eq!(evicted, expected_evicted);
+
+                    for id in evicted {
+                        assert!(!objects().has(&deps.storage, decode_hash(id.clone())));
+                        assert!(!state::has_data(&deps.storage, &decode_hash(id)));
+                    }
+                    let bucket = BUCKET.load(&deps.storage).unwrap();
+                    assert_eq!(bucket.stat.size, Uint128::new(9));
+                    assert_eq!(bucket.stat.compressed_size, Uint128::new(9));
+                    assert_eq!(bucket.stat.object_count, Uint128::new(2));
+                }
+                Err(err) => assert_eq!(result.unwrap_err(), err),
+            }
+        }
+    }
+
+    #[test]
+    fn migrate_eviction_indexes() {
+        const OKP4: &str = "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6";
+
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: BucketConfig {
+                eviction_policy: Some(EvictionPolicy::OldestFirst),
+                ..Default::default()
+            },
+            limits: BucketLimitsBuilder::default()
+                .max_total_size(10u128)
+                .build()
+                .unwrap(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        for content in ["okp4", "data"] {
+            let msg = ExecuteMsg::StoreObject {
+                data: Binary::from(content.as_bytes()),
+                pin: false,
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        // simulate objects stored before the eviction indexes
+        let stored = objects()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        for (id, object) in stored {
+            objects().remove(&mut deps.storage, id.clone()).unwrap();
+            cw_storage_plus::Map::<Hash, Object>::new("OBJECT")
+                .save(&mut deps.storage, id, &object)
+                .unwrap();
+        }
+
+        let store_hello = |deps: DepsMut<'_>| {
+            let msg = ExecuteMsg::StoreObject {
+                data: Binary::from("hello".as_bytes()),
+                pin: false,
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
+            };
+            execute(deps, mock_env(), info.clone(), msg)
+        };
+        assert_eq!(
+            store_hello(deps.as_mut()).unwrap_err(),
+            ContractError::Bucket(BucketError::MaxTotalSizeLimitExceeded(
+                13u8.into(),
+                10u8.into()
+            ))
+        );
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(
+            res.attributes[2],
+            Attribute::new("reindexed_object_count", "2")
+        );
+
+        let res = store_hello(deps.as_mut()).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("evicted", OKP4.to_string())));
+    }
+
+    #[test]
+    fn object() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: Default::default(),
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        match query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Object {
+                id: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".to_string(),
+            },
+        )
+        .err()
+        .unwrap()
+        {
+            ContractError::Std(NotFound { .. }) => (),
+            _ => panic!("assertion failed"),
+        }
+
+        let data = general_purpose::STANDARD.encode("hello");
+        let msg = ExecuteMsg::StoreObject {
+            data: Binary::from_base64(data.as_str()).unwrap(),
+            pin: true,
+            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = QueryMsg::Object {
+            id: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".to_string(),
+        };
         let result = query(deps.as_ref(), mock_env(), msg).unwrap();
         let response: ObjectResponse = from_binary(&result).unwrap();
         assert_eq!(
@@ -1132,88 +1785,626 @@ mod tests {
         assert!(response.is_pinned);
         assert_eq!(response.size.u128(), 5u128);
 
-        let data = general_purpose::STANDARD.encode("okp4");
+        let data = general_purpose::STANDARD.encode("okp4");
+        let msg = ExecuteMsg::StoreObject {
+            data: Binary::from_base64(data.as_str()).unwrap(),
+            pin: false,
+            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = QueryMsg::Object {
+            id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string(),
+        };
+        let result = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let response: ObjectResponse = from_binary(&result).unwrap();
+        assert_eq!(
+            response.id,
+            ObjectId::from("315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6")
+        );
+        assert_eq!(response.owner, info.sender);
+        assert!(!response.is_pinned);
+        assert_eq!(response.size.u128(), 4u128);
+    }
+
+    #[test]
+    fn object_size() {
+        let id = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string();
+        for (chunk_size, expected_chunk_count) in [(None, 1), (Some(4u128), 3)] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("creator", &[]);
+            let msg = InstantiateMsg {
+                bucket: String::from("test"),
+                config: BucketConfig {
+                    chunk_size: chunk_size.map(Uint128::new),
+                    ..Default::default()
+                },
+                limits: Default::default(),
+                pagination: Default::default(),
+            };
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+            assert!(matches!(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::ObjectSize { id: id.clone() },
+                ),
+                Err(ContractError::Std(NotFound { .. }))
+            ));
+
+            let msg = ExecuteMsg::StoreObject {
+                data: Binary::from(b"hello world".to_vec()),
+                pin: false,
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+            let result = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ObjectSize { id: id.clone() },
+            )
+            .unwrap();
+            assert_eq!(
+                from_binary::<ObjectSizeResponse>(&result).unwrap(),
+                ObjectSizeResponse {
+                    size: Uint128::new(11),
+                    compressed_size: Uint128::new(11),
+                    chunk_count: expected_chunk_count,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn store_object_chunked() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: BucketConfig {
+                chunk_size: Some(Uint128::zero()),
+                ..Default::default()
+            },
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        assert_eq!(
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg).err(),
+            Some(ContractError::Bucket(BucketError::ZeroChunkSize))
+        );
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: BucketConfig {
+                chunk_size: Some(4u128.into()),
+                ..Default::default()
+            },
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let data = Binary::from(b"hello world".to_vec());
+        let msg = ExecuteMsg::StoreObject {
+            data: data.clone(),
+            pin: false,
+            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let id = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string();
+        let chunks = state::DATA
+            .prefix(decode_hash(id.clone()))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                (0, b"hell".to_vec()),
+                (1, b"o wo".to_vec()),
+                (2, b"rld".to_vec()),
+            ]
+        );
+
+        let result = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ObjectData { id: id.clone() },
+        )
+        .unwrap();
+        assert_eq!(result, to_binary(&data).unwrap());
+
+        let result = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyObject { id: id.clone() },
+        )
+        .unwrap();
+        assert!(from_binary::<bool>(&result).unwrap());
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ForgetObject { id: id.clone() },
+        )
+        .unwrap();
+        assert_eq!(
+            state::DATA
+                .prefix(decode_hash(id))
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn store_base64_object() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let encoded = general_purpose::STANDARD.encode(&data);
+
+        let store = |chunk_size: Option<u128>, msg: ExecuteMsg| {
+            let mut deps = mock_dependencies();
+            let info = mock_info("creator", &[]);
+            let msg_instantiate = InstantiateMsg {
+                bucket: String::from("test"),
+                config: BucketConfig {
+                    chunk_size: chunk_size.map(Uint128::from),
+                    ..Default::default()
+                },
+                limits: Default::default(),
+                pagination: Default::default(),
+            };
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg_instantiate).unwrap();
+            let res = execute(deps.as_mut(), mock_env(), info, msg);
+            let records = state::DATA
+                .range(&deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            let objects = objects()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            let bucket = BUCKET.load(&deps.storage).unwrap();
+            (res, records, objects, bucket)
+        };
+
+        for compression in [
+            CompressionAlgorithm::Passthrough,
+            CompressionAlgorithm::Snappy,
+        ] {
+            for chunk_size in [None, Some(1), Some(10), Some(999), Some(1000), Some(5000)] {
+                let whole = store(
+                    chunk_size,
+                    ExecuteMsg::StoreObject {
+                        data: Binary::from(data.clone()),
+                        pin: true,
+                        compression_algorithm: Some(compression),
+                        alias: None,
+                    },
+                );
+                let streamed = store(
+                    chunk_size,
+                    ExecuteMsg::StoreBase64Object {
+                        data: encoded.clone(),
+                        pin: true,
+                        compression_algorithm: Some(compression),
+                        alias: None,
+                    },
+                );
+                assert!(whole.0.is_ok());
+                assert_eq!(streamed, whole, "{compression:?}, {chunk_size:?}");
+                assert_eq!(streamed.2[0].1.size, Uint128::from(1000u128));
+            }
+        }
+
+        let (res, records, ..) = store(
+            None,
+            ExecuteMsg::StoreBase64Object {
+                data: "b2t*NA==".to_string(),
+                pin: false,
+                compression_algorithm: None,
+                alias: None,
+            },
+        );
+        assert_eq!(
+            res.err(),
+            Some(ContractError::Std(StdError::parse_err(
+                "base64",
+                "Invalid base64 byte 0x2a at position 3"
+            )))
+        );
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn object_data_range() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: BucketConfig {
+                chunk_size: Some(4u128.into()),
+                ..Default::default()
+            },
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        for (data, compression_algorithm) in [
+            ("hello world", CompressionAlgorithm::Passthrough),
+            ("foo bar", CompressionAlgorithm::Snappy),
+        ] {
+            let msg = ExecuteMsg::StoreObject {
+                data: Binary::from(data.as_bytes()),
+                pin: false,
+                compression_algorithm: Some(compression_algorithm),
+                alias: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        let hello_id =
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string();
+        let foo_id = "fbc1a9f858ea9e177916964bd88c3d37b91a1e84412765e29950777f265c4b75".to_string();
+        let cases = vec![
+            (hello_id.clone(), 0u128, 4u128, Ok("hell")),
+            (hello_id.clone(), 5u128, 2u128, Ok(" w")),
+            (hello_id.clone(), 2u128, 7u128, Ok("llo wor")),
+            (hello_id.clone(), 0u128, 11u128, Ok("hello world")),
+            (hello_id.clone(), 9u128, 2u128, Ok("ld")),
+            (hello_id.clone(), 11u128, 0u128, Ok("")),
+            (foo_id.clone(), 4u128, 3u128, Ok("bar")),
+            (
+                hello_id.clone(),
+                10u128,
+                2u128,
+                Err(ContractError::RangeOutOfBounds {
+                    offset: 10u128.into(),
+                    length: 2u128.into(),
+                    size: 11u128.into(),
+                }),
+            ),
+            (
+                foo_id,
+                8u128,
+                0u128,
+                Err(ContractError::RangeOutOfBounds {
+                    offset: 8u128.into(),
+                    length: 0u128.into(),
+                    size: 7u128.into(),
+                }),
+            ),
+            (
+                hello_id,
+                u128::MAX,
+                1u128,
+                Err(ContractError::RangeOutOfBounds {
+                    offset: u128::MAX.into(),
+                    length: 1u128.into(),
+                    size: 11u128.into(),
+                }),
+            ),
+        ];
+
+        for (id, offset, length, expected) in cases {
+            let result = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ObjectDataRange {
+                    id,
+                    offset: offset.into(),
+                    length: length.into(),
+                },
+            );
+            assert_eq!(
+                result,
+                expected.map(|data| to_binary(&Binary::from(data.as_bytes())).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn object_data() {
+        struct TC {
+            compression_algorithm: Option<CompressionAlgorithm>,
+        }
+
+        let cases = vec![
+            TC {
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            },
+            TC {
+                compression_algorithm: Some(CompressionAlgorithm::Snappy),
+            },
+            TC {
+                compression_algorithm: Some(CompressionAlgorithm::Lzma),
+            },
+        ];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+            let info = mock_info("creator", &[]);
+            let data =
+                Binary::from_base64(general_purpose::STANDARD.encode("okp4").as_str()).unwrap();
+
+            let msg = InstantiateMsg {
+                bucket: String::from("test"),
+                config: Default::default(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            };
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+            match query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ObjectData {
+                    id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6"
+                        .to_string(),
+                },
+            )
+            .err()
+            .unwrap()
+            {
+                ContractError::Std(NotFound { .. }) => (),
+                _ => panic!("assertion failed"),
+            }
+
+            let msg = ExecuteMsg::StoreObject {
+                data: data.clone(),
+                pin: false,
+                compression_algorithm: case.compression_algorithm,
+                alias: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+            let msg = QueryMsg::ObjectData {
+                id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string(),
+            };
+            let result = query(deps.as_ref(), mock_env(), msg).unwrap();
+            assert_eq!(result, to_binary(&data).unwrap());
+        }
+    }
+
+    #[test]
+    fn object_data_with_checksum() {
+        let cases = vec![
+            (HashAlgorithm::MD5, CompressionAlgorithm::Passthrough),
+            (HashAlgorithm::Sha256, CompressionAlgorithm::Snappy),
+            (HashAlgorithm::Sha512, CompressionAlgorithm::Lzma),
+        ];
+
+        for (hash_algorithm, compression_algorithm) in cases {
+            let mut deps = mock_dependencies();
+            let info = mock_info("creator", &[]);
+            let msg = InstantiateMsg {
+                bucket: String::from("test"),
+                config: BucketConfig {
+                    hash_algorithm,
+                    chunk_size: Some(3u128.into()),
+                    ..Default::default()
+                },
+                limits: Default::default(),
+                pagination: Default::default(),
+            };
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+            let data = Binary::from("hello okp4".as_bytes());
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                ExecuteMsg::StoreObject {
+                    data: data.clone(),
+                    pin: false,
+                    compression_algorithm: Some(compression_algorithm),
+                    alias: None,
+                },
+            )
+            .unwrap();
+            let id = res
+                .attributes
+                .iter()
+                .find(|a| a.key == "id")
+                .unwrap()
+                .value
+                .clone();
+
+            let result = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ObjectDataWithChecksum { id: id.clone() },
+            )
+            .and_then(|res| from_binary::<ObjectDataResponse>(&res).map_err(Into::into));
+            assert_eq!(result, Ok(ObjectDataResponse { data, checksum: id }));
+        }
+    }
+
+    #[test]
+    fn verify_object() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        let id = "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string();
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: Default::default(),
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        match query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyObject { id: id.clone() },
+        )
+        .err()
+        .unwrap()
+        {
+            ContractError::Std(NotFound { .. }) => (),
+            _ => panic!("assertion failed"),
+        }
+
+        let msg = ExecuteMsg::StoreObject {
+            data: Binary::from_base64(general_purpose::STANDARD.encode("okp4").as_str()).unwrap(),
+            pin: false,
+            compression_algorithm: Some(CompressionAlgorithm::Snappy),
+            alias: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let result = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyObject { id: id.clone() },
+        )
+        .unwrap();
+        assert!(from_binary::<bool>(&result).unwrap());
+
+        // simulate a corruption of the stored content
+        let hash: Hash = id.clone().try_into().unwrap();
+        let corrupted = crate::compress::CompressionAlgorithm::Snappy
+            .compress(b"okp5")
+            .unwrap();
+        state::save_data(&mut deps.storage, &hash, &corrupted, None).unwrap();
+
+        let result = query(deps.as_ref(), mock_env(), QueryMsg::VerifyObject { id }).unwrap();
+        assert!(!from_binary::<bool>(&result).unwrap());
+    }
+
+    #[test]
+    fn migrate_legacy_data() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        let id = "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string();
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: BucketConfig {
+                chunk_size: Some(Uint128::new(3)),
+                ..Default::default()
+            },
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
         let msg = ExecuteMsg::StoreObject {
-            data: Binary::from_base64(data.as_str()).unwrap(),
+            data: Binary::from("okp4".as_bytes()),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
         };
-        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        let msg = QueryMsg::Object {
-            id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string(),
-        };
-        let result = query(deps.as_ref(), mock_env(), msg).unwrap();
-        let response: ObjectResponse = from_binary(&result).unwrap();
+        // simulate an object stored before the chunking
+        let hash: Hash = id.clone().try_into().unwrap();
+        state::remove_data(&mut deps.storage, &hash).unwrap();
+        state::LEGACY_DATA
+            .save(&mut deps.storage, hash.clone(), &b"okp4".to_vec())
+            .unwrap();
+        assert!(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ObjectData { id: id.clone() }
+        )
+        .is_err());
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
         assert_eq!(
-            response.id,
-            ObjectId::from("315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6")
+            res.attributes,
+            vec![
+                Attribute::new("action", "migrate"),
+                Attribute::new("migrated_data_count", "1"),
+                Attribute::new("reindexed_object_count", "1"),
+            ]
+        );
+        assert!(!state::LEGACY_DATA.has(&deps.storage, hash.clone()));
+        assert_eq!(
+            state::DATA
+                .prefix(hash)
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            2
+        );
+
+        let result = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ObjectData { id: id.clone() },
+        )
+        .unwrap();
+        assert_eq!(result, to_binary(&Binary::from("okp4".as_bytes())).unwrap());
+        let result = query(deps.as_ref(), mock_env(), QueryMsg::VerifyObject { id }).unwrap();
+        assert!(from_binary::<bool>(&result).unwrap());
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(
+            res.attributes[1],
+            Attribute::new("migrated_data_count", "0")
         );
-        assert_eq!(response.owner, info.sender);
-        assert!(!response.is_pinned);
-        assert_eq!(response.size.u128(), 4u128);
     }
 
     #[test]
-    fn object_data() {
-        struct TC {
-            compression_algorithm: Option<CompressionAlgorithm>,
-        }
+    fn verify_chunked_object() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        let id = "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string();
 
-        let cases = vec![
-            TC {
-                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
-            },
-            TC {
-                compression_algorithm: Some(CompressionAlgorithm::Snappy),
-            },
-            TC {
-                compression_algorithm: Some(CompressionAlgorithm::Lzma),
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: BucketConfig {
+                chunk_size: Some(Uint128::new(3)),
+                ..Default::default()
             },
-        ];
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        for case in cases {
-            let mut deps = mock_dependencies();
-            let info = mock_info("creator", &[]);
-            let data =
-                Binary::from_base64(general_purpose::STANDARD.encode("okp4").as_str()).unwrap();
+        let msg = ExecuteMsg::StoreObject {
+            data: Binary::from_base64(general_purpose::STANDARD.encode("okp4").as_str()).unwrap(),
+            pin: false,
+            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-            let msg = InstantiateMsg {
-                bucket: String::from("test"),
-                config: Default::default(),
-                limits: Default::default(),
-                pagination: Default::default(),
-            };
-            instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let hash: Hash = id.clone().try_into().unwrap();
+        assert_eq!(
+            state::DATA
+                .prefix(hash.clone())
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            2
+        );
 
-            match query(
-                deps.as_ref(),
-                mock_env(),
-                QueryMsg::ObjectData {
-                    id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6"
-                        .to_string(),
-                },
-            )
-            .err()
-            .unwrap()
-            {
-                ContractError::Std(NotFound { .. }) => (),
-                _ => panic!("assertion failed"),
-            }
+        let result = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyObject { id: id.clone() },
+        )
+        .unwrap();
+        assert!(from_binary::<bool>(&result).unwrap());
 
-            let msg = ExecuteMsg::StoreObject {
-                data: data.clone(),
-                pin: false,
-                compression_algorithm: case.compression_algorithm,
-            };
-            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        // simulate a corruption of the last chunk of the stored content
+        state::DATA
+            .save(&mut deps.storage, (hash, 1), &b"5".to_vec())
+            .unwrap();
 
-            let msg = QueryMsg::ObjectData {
-                id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string(),
-            };
-            let result = query(deps.as_ref(), mock_env(), msg).unwrap();
-            assert_eq!(result, to_binary(&data).unwrap());
-        }
+        let result = query(deps.as_ref(), mock_env(), QueryMsg::VerifyObject { id }).unwrap();
+        assert!(!from_binary::<bool>(&result).unwrap());
     }
 
     #[test]
@@ -1474,6 +2665,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1482,6 +2674,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1490,6 +2683,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1572,7 +2766,7 @@ mod tests {
                 )],
                 unpin_senders: vec![mock_info("bob", &[])],
                 expected_count: 1,
-                expected_error: None,
+                expected_error: Some(ContractError::ObjectNotPinned {}),
                 expected_object_pin_count: vec![
                     (
                         ObjectId::from(
@@ -1627,7 +2821,7 @@ mod tests {
                 )],
                 unpin_senders: vec![mock_info("bob", &[])],
                 expected_count: 0,
-                expected_error: None,
+                expected_error: Some(ContractError::ObjectNotPinned {}),
                 expected_object_pin_count: vec![(
                     ObjectId::from(
                         "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6",
@@ -1677,6 +2871,30 @@ mod tests {
                     ),
                 ],
             },
+            TC {
+                // Double unpin
+                pin: vec![ObjectId::from(
+                    "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6",
+                )],
+                pin_senders: vec![mock_info("bob", &[])],
+                unpin: vec![
+                    ObjectId::from(
+                        "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6",
+                    ),
+                    ObjectId::from(
+                        "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6",
+                    ),
+                ],
+                unpin_senders: vec![mock_info("bob", &[]), mock_info("bob", &[])],
+                expected_count: 0,
+                expected_error: Some(ContractError::ObjectNotPinned {}),
+                expected_object_pin_count: vec![(
+                    ObjectId::from(
+                        "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6",
+                    ),
+                    Uint128::zero(),
+                )],
+            },
             TC {
                 // Object not exists
                 pin: vec![ObjectId::from(
@@ -1742,6 +2960,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1750,6 +2969,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1758,6 +2978,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1847,6 +3068,7 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
         };
         execute(deps.as_mut(), mock_env(), info1.clone(), msg).unwrap();
         let data = general_purpose::STANDARD.encode("object2");
@@ -1854,6 +3076,7 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
         };
         execute(deps.as_mut(), mock_env(), info1, msg).unwrap();
         let data = general_purpose::STANDARD.encode("object3");
@@ -1861,9 +3084,13 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
         };
         execute(deps.as_mut(), mock_env(), info2, msg).unwrap();
 
+        let state_cursor = |filter: &str, key: &str| {
+            cursor::encode_state(key.to_string(), Order::Ascending, filter.as_bytes()).unwrap()
+        };
         let cases = vec![
             (
                 QueryMsg::Objects {
@@ -1874,7 +3101,10 @@ mod tests {
                 3,
                 PageInfo {
                     has_next_page: false,
-                    cursor: "CZC4Avd5xNeJaBkK6MYrA1ZSQPNr76GU1k2JJSjmaDyF".to_string(),
+                    cursor: state_cursor(
+                        "objects/",
+                        "CZC4Avd5xNeJaBkK6MYrA1ZSQPNr76GU1k2JJSjmaDyF",
+                    ),
                 },
             ),
             (
@@ -1898,7 +3128,10 @@ mod tests {
                 2,
                 PageInfo {
                     has_next_page: false,
-                    cursor: "CZC4Avd5xNeJaBkK6MYrA1ZSQPNr76GU1k2JJSjmaDyF".to_string(),
+                    cursor: state_cursor(
+                        "objects/creator1",
+                        "CZC4Avd5xNeJaBkK6MYrA1ZSQPNr76GU1k2JJSjmaDyF",
+                    ),
                 },
             ),
             (
@@ -1910,19 +3143,28 @@ mod tests {
                 1,
                 PageInfo {
                     has_next_page: true,
-                    cursor: "5bfWM6UF5MowkQVp16q5pnXvwc9SVkS4xZkFeVLdswjU".to_string(),
+                    cursor: state_cursor(
+                        "objects/creator1",
+                        "5bfWM6UF5MowkQVp16q5pnXvwc9SVkS4xZkFeVLdswjU",
+                    ),
                 },
             ),
             (
                 QueryMsg::Objects {
                     address: Some("creator1".to_string()),
                     first: Some(1),
-                    after: Some("5bfWM6UF5MowkQVp16q5pnXvwc9SVkS4xZkFeVLdswjU".to_string()),
+                    after: Some(state_cursor(
+                        "objects/creator1",
+                        "5bfWM6UF5MowkQVp16q5pnXvwc9SVkS4xZkFeVLdswjU",
+                    )),
                 },
                 1,
                 PageInfo {
                     has_next_page: false,
-                    cursor: "CZC4Avd5xNeJaBkK6MYrA1ZSQPNr76GU1k2JJSjmaDyF".to_string(),
+                    cursor: state_cursor(
+                        "objects/creator1",
+                        "CZC4Avd5xNeJaBkK6MYrA1ZSQPNr76GU1k2JJSjmaDyF",
+                    ),
                 },
             ),
         ];
@@ -1935,6 +3177,21 @@ mod tests {
             assert_eq!(response.page_info, case.2);
         }
 
+        let msg = QueryMsg::Objects {
+            address: None,
+            first: Some(1),
+            after: Some(state_cursor(
+                "objects/creator1",
+                "5bfWM6UF5MowkQVp16q5pnXvwc9SVkS4xZkFeVLdswjU",
+            )),
+        };
+        assert_eq!(
+            query(deps.as_ref(), mock_env(), msg),
+            Err(ContractError::Std(StdError::generic_err(
+                "Cursor doesn't match the order or filter of the listing"
+            )))
+        );
+
         let msg = QueryMsg::Objects {
             address: Some("creator2".to_string()),
             first: None,
@@ -1951,6 +3208,7 @@ mod tests {
                 size: 7u128.into(),
                 compressed_size: 7u128.into(),
                 compression_algorithm: CompressionAlgorithm::Passthrough,
+                alias: None,
             }
         );
     }
@@ -1974,6 +3232,7 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
         };
         execute(deps.as_mut(), mock_env(), info1.clone(), msg).unwrap();
         // 1: 445008b7f2932922bdb184771d9978516a4f89d77000c2d6eab18b0894aac3a7
@@ -1982,6 +3241,7 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: true,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            alias: None,
         };
         execute(deps.as_mut(), mock_env(), info2, msg).unwrap();
         // 2: abafa4428bdc8c34dae28bbc17303a62175f274edf59757b3e9898215a428a56
@@ -1990,6 +3250,14 @@ mod tests {
         };
         execute(deps.as_mut(), mock_env(), info1, msg).unwrap();
 
+        let state_cursor = |key: &str| {
+            cursor::encode_state(
+                key.to_string(),
+                Order::Ascending,
+                b"object_pins/abafa4428bdc8c34dae28bbc17303a62175f274edf59757b3e9898215a428a56",
+            )
+            .unwrap()
+        };
         let cases = vec![
             (
                 QueryMsg::ObjectPins {
@@ -2014,7 +3282,7 @@ mod tests {
                 vec!["creator1".to_string(), "creator2".to_string()],
                 PageInfo {
                     has_next_page: false,
-                    cursor: "Hdm2eF21ryF".to_string(),
+                    cursor: state_cursor("Hdm2eF21ryF"),
                 },
             ),
             (
@@ -2027,7 +3295,7 @@ mod tests {
                 vec!["creator1".to_string()],
                 PageInfo {
                     has_next_page: true,
-                    cursor: "Hdm2eF21ryE".to_string(),
+                    cursor: state_cursor("Hdm2eF21ryE"),
                 },
             ),
             (
@@ -2035,12 +3303,12 @@ mod tests {
                     id: "abafa4428bdc8c34dae28bbc17303a62175f274edf59757b3e9898215a428a56"
                         .to_string(),
                     first: Some(1),
-                    after: Some("Hdm2eF21ryE".to_string()),
+                    after: Some(state_cursor("Hdm2eF21ryE")),
                 },
                 vec!["creator2".to_string()],
                 PageInfo {
                     has_next_page: false,
-                    cursor: "Hdm2eF21ryF".to_string(),
+                    cursor: state_cursor("Hdm2eF21ryF"),
                 },
             ),
         ];
@@ -2241,6 +3509,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2249,6 +3518,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2257,6 +3527,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2315,6 +3586,8 @@ mod tests {
                 Uint128::from(case.expected_count as u128)
             );
             assert_eq!(bucket.stat.size, case.expected_total_size);
+            // the objects being stored uncompressed, their compressed size is their size
+            assert_eq!(bucket.stat.compressed_size, case.expected_total_size);
         }
     }
 
@@ -2345,6 +3618,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             },
         )
         .unwrap();
@@ -2367,6 +3641,7 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
             },
         );
 
@@ -2376,4 +3651,236 @@ mod tests {
             "Object should successfully restored after a forgot"
         );
     }
+
+    #[test]
+    fn tag_object() {
+        let mut deps = mock_dependencies();
+        let info1 = mock_info("creator1", &[]);
+        let info2 = mock_info("creator2", &[]);
+
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: Default::default(),
+            limits: BucketLimitsBuilder::default()
+                .max_object_tags(2u128)
+                .max_tag_length(5u128)
+                .build()
+                .unwrap(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info1.clone(), msg).unwrap();
+
+        let id1 = "445008b7f2932922bdb184771d9978516a4f89d77000c2d6eab18b0894aac3a7".to_string();
+        let id2 = "abafa4428bdc8c34dae28bbc17303a62175f274edf59757b3e9898215a428a56".to_string();
+        let id3 = "0a6d95579ba3dd2f79c870906fd894007ce449020d111d358894cfbbcd9a03a4".to_string();
+        for (data, info) in [
+            ("object1", &info1),
+            ("object2", &info2),
+            ("object3", &info1),
+        ] {
+            let msg = ExecuteMsg::StoreObject {
+                data: Binary::from(data.as_bytes()),
+                pin: false,
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                alias: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        let tag = |deps: DepsMut<'_>, info: &MessageInfo, id: &str, tags: Vec<&str>| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::TagObject {
+                    id: id.to_string(),
+                    tags: tags.into_iter().map(ToString::to_string).collect(),
+                },
+            )
+        };
+        let list = |deps: Deps<'_>, tag: &str, first: Option<u32>, after: Option<String>| {
+            let result = query(
+                deps,
+                mock_env(),
+                QueryMsg::ObjectsByTag {
+                    tag: tag.to_string(),
+                    first,
+                    after,
+                },
+            )
+            .unwrap();
+            let response: ObjectsResponse = from_binary(&result).unwrap();
+            (
+                response
+                    .data
+                    .into_iter()
+                    .map(|object| object.id)
+                    .collect::<Vec<_>>(),
+                response.page_info,
+            )
+        };
+
+        let res = tag(deps.as_mut(), &info1, &id1, vec!["a", "b", "a"]).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "tag_object"),
+                Attribute::new("id", id1.clone()),
+            ]
+        );
+        tag(deps.as_mut(), &info2, &id2, vec!["b"]).unwrap();
+        tag(deps.as_mut(), &info1, &id3, vec!["b", "c"]).unwrap();
+
+        assert_eq!(
+            tag(deps.as_mut(), &info2, &id1, vec!["d"]).err(),
+            Some(ContractError::ObjectNotOwned {})
+        );
+        assert_eq!(
+            tag(deps.as_mut(), &info1, &id1, vec!["a", "b", "c"]).err(),
+            Some(ContractError::Bucket(
+                BucketError::MaxObjectTagsLimitExceeded(3u128.into(), 2u128.into())
+            ))
+        );
+        assert_eq!(
+            tag(deps.as_mut(), &info1, &id1, vec!["a", "toolong"]).err(),
+            Some(ContractError::Bucket(
+                BucketError::MaxTagLengthLimitExceeded(7u128.into(), 5u128.into())
+            ))
+        );
+
+        let (ids, page_info) = list(deps.as_ref(), "b", None, None);
+        assert_eq!(ids, vec![id3.clone(), id1.clone(), id2.clone()]);
+        assert!(!page_info.has_next_page);
+
+        let (ids, page_info) = list(deps.as_ref(), "b", Some(2), None);
+        assert_eq!(ids, vec![id3.clone(), id1.clone()]);
+        assert!(page_info.has_next_page);
+        let (ids, page_info) = list(deps.as_ref(), "b", Some(2), Some(page_info.cursor));
+        assert_eq!(ids, vec![id2.clone()]);
+        assert!(!page_info.has_next_page);
+
+        assert_eq!(list(deps.as_ref(), "a", None, None).0, vec![id1.clone()]);
+        assert_eq!(list(deps.as_ref(), "c", None, None).0, vec![id3.clone()]);
+        assert_eq!(list(deps.as_ref(), "d", None, None).0, Vec::<String>::new());
+
+        // retagging replaces the tags
+        tag(deps.as_mut(), &info1, &id1, vec!["c"]).unwrap();
+        assert_eq!(list(deps.as_ref(), "a", None, None).0, Vec::<String>::new());
+        assert_eq!(
+            list(deps.as_ref(), "b", None, None).0,
+            vec![id3.clone(), id2.clone()]
+        );
+        assert_eq!(
+            list(deps.as_ref(), "c", None, None).0,
+            vec![id3.clone(), id1.clone()]
+        );
+
+        // forgetting an object removes its tags
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info1,
+            ExecuteMsg::ForgetObject { id: id3.clone() },
+        )
+        .unwrap();
+        assert_eq!(list(deps.as_ref(), "b", None, None).0, vec![id2]);
+        assert_eq!(list(deps.as_ref(), "c", None, None).0, vec![id1]);
+    }
+
+    #[test]
+    fn store_object_alias() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                bucket: String::from("test"),
+                config: Default::default(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let store = |deps: DepsMut<'_>, data: &str, alias: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::StoreObject {
+                    data: Binary::from(data.as_bytes()),
+                    pin: false,
+                    compression_algorithm: None,
+                    alias: Some(alias.to_string()),
+                },
+            )
+        };
+        let by_alias = |deps: Deps<'_>, alias: &str| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::ObjectByAlias {
+                    alias: alias.to_string(),
+                },
+            )
+            .map(|res| from_binary::<ObjectResponse>(&res).unwrap())
+        };
+
+        store(deps.as_mut(), "okp4", "docs/readme.md").unwrap();
+        let object = by_alias(deps.as_ref(), "docs/readme.md").unwrap();
+        assert_eq!(
+            object.id,
+            "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6"
+        );
+        assert_eq!(object.alias, Some("docs/readme.md".to_string()));
+
+        // storing the same object again keeps its alias
+        store(deps.as_mut(), "okp4", "docs/readme.md").unwrap();
+        assert_eq!(
+            store(deps.as_mut(), "okp4", "other").err(),
+            Some(ContractError::AliasMismatch {})
+        );
+
+        assert_eq!(
+            store(deps.as_mut(), "cosmos", "docs/readme.md").err(),
+            Some(ContractError::AliasAlreadyUsed(
+                "docs/readme.md".to_string()
+            ))
+        );
+        assert_eq!(
+            store(deps.as_mut(), "cosmos", "").err(),
+            Some(ContractError::InvalidAlias(
+                "length must be between 1 and 64".to_string()
+            ))
+        );
+        assert_eq!(
+            store(deps.as_mut(), "cosmos", "read me").err(),
+            Some(ContractError::InvalidAlias(
+                "unexpected character ' '".to_string()
+            ))
+        );
+        assert!(matches!(
+            by_alias(deps.as_ref(), "unknown").err(),
+            Some(ContractError::Std(NotFound { .. }))
+        ));
+
+        // forgetting the object frees its alias
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::ForgetObject {
+                id: object.id.clone(),
+            },
+        )
+        .unwrap();
+        assert!(by_alias(deps.as_ref(), "docs/readme.md").is_err());
+        store(deps.as_mut(), "cosmos", "docs/readme.md").unwrap();
+        assert_ne!(
+            by_alias(deps.as_ref(), "docs/readme.md").unwrap().id,
+            object.id
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::msg::ObjectId;
 use cosmwasm_std::{StdError, StdResult};
 use cw_storage_plus::{Key, KeyDeserialize, Prefixer, PrimaryKey};
 use md5;
@@ -32,6 +33,63 @@ impl HashAlgorithm {
             HashAlgorithm::Sha512 => sha512_hash,
         }
     }
+
+    /// output_size returns the size in bytes of the hashes computed with the given algorithm.
+    pub fn output_size(&self) -> usize {
+        match self {
+            HashAlgorithm::MD5 => 16,
+            HashAlgorithm::Sha224 => 28,
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+
+    /// hasher returns a new incremental hasher using the given algorithm.
+    pub fn hasher(&self) -> Hasher {
+        match self {
+            HashAlgorithm::MD5 => Hasher::MD5(md5::Md5::new()),
+            HashAlgorithm::Sha224 => Hasher::Sha224(sha2::Sha224::new()),
+            HashAlgorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha384 => Hasher::Sha384(sha2::Sha384::new()),
+            HashAlgorithm::Sha512 => Hasher::Sha512(sha2::Sha512::new()),
+        }
+    }
+}
+
+/// Hasher allows to compute a hash incrementally, by feeding the data chunk by chunk, so large
+/// contents don't have to be hashed at once.
+pub enum Hasher {
+    MD5(md5::Md5),
+    Sha224(sha2::Sha224),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+}
+
+impl Hasher {
+    /// update feeds the hasher with the given chunk of data.
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::MD5(h) => h.update(data),
+            Hasher::Sha224(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha384(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+        }
+    }
+
+    /// finalize consumes the hasher and returns the hash of all the data it has been fed with.
+    pub fn finalize(self) -> Hash {
+        match self {
+            Hasher::MD5(h) => h.finalize().to_vec(),
+            Hasher::Sha224(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Sha384(h) => h.finalize().to_vec(),
+            Hasher::Sha512(h) => h.finalize().to_vec(),
+        }
+        .into()
+    }
 }
 
 /// Hash represent a Object hash as binary value.  
@@ -83,6 +141,24 @@ impl TryFrom<String> for Hash {
     }
 }
 
+/// validate_object_id decodes the given client-supplied object id into the hash it stands for,
+/// checking its size matches the one of the hashes computed with the given algorithm, so that
+/// malformed ids are rejected before reaching the storage lookups.
+pub fn validate_object_id(id: ObjectId, algorithm: &HashAlgorithm) -> StdResult<Hash> {
+    let hash: Hash = id.try_into()?;
+    let expected = algorithm.output_size();
+    if hash.0.len() != expected {
+        return Err(StdError::parse_err(
+            type_name::<Hash>(),
+            format!(
+                "invalid hash length: {} bytes, expected {expected}",
+                hash.0.len()
+            ),
+        ));
+    }
+    Ok(hash)
+}
+
 impl From<Hash> for String {
     fn from(hash: Hash) -> Self {
         base16ct::lower::encode_string(hash.0.as_slice())
@@ -151,7 +227,8 @@ impl AsRef<[u8]> for Hash {
 
 #[cfg(test)]
 mod tests {
-    use crate::crypto::Hash;
+    use crate::crypto::{hash, validate_object_id, Hash, HashAlgorithm};
+    use cosmwasm_std::StdError;
 
     #[test]
     fn vec_from_hash() {
@@ -163,4 +240,68 @@ mod tests {
         let result: Vec<u8> = h.into();
         assert_eq!(result, vec![3, 2, 1])
     }
+
+    #[test]
+    fn proper_validate_object_id() {
+        let data = b"hello".to_vec();
+        for algorithm in [
+            HashAlgorithm::MD5,
+            HashAlgorithm::Sha224,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha384,
+            HashAlgorithm::Sha512,
+        ] {
+            let expected = hash(&algorithm, &data);
+            assert_eq!(
+                validate_object_id(expected.clone().into(), &algorithm),
+                Ok(expected)
+            );
+        }
+
+        let md5_id: String = hash(&HashAlgorithm::MD5, &data).into();
+        let cases = vec![
+            (
+                md5_id,
+                StdError::parse_err(
+                    "okp4_objectarium::crypto::Hash",
+                    "invalid hash length: 16 bytes, expected 32",
+                ),
+            ),
+            (
+                "".to_string(),
+                StdError::parse_err(
+                    "okp4_objectarium::crypto::Hash",
+                    "invalid hash length: 0 bytes, expected 32",
+                ),
+            ),
+            (
+                "not a hash".to_string(),
+                StdError::parse_err("alloc::vec::Vec<u8>", "invalid Base16 encoding"),
+            ),
+        ];
+        for (id, expected) in cases {
+            assert_eq!(
+                validate_object_id(id, &HashAlgorithm::Sha256),
+                Err(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn incremental_hash() {
+        let data = b"the quick brown fox jumps over the lazy dog".to_vec();
+        for algorithm in [
+            HashAlgorithm::MD5,
+            HashAlgorithm::Sha224,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha384,
+            HashAlgorithm::Sha512,
+        ] {
+            let mut hasher = algorithm.hasher();
+            for chunk in data.chunks(7) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), hash(&algorithm, &data));
+        }
+    }
 }
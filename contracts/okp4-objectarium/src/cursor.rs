@@ -1,20 +1,102 @@
-use crate::crypto::Hash;
+use crate::crypto::{self, Hash, HashAlgorithm};
 use crate::msg::Cursor;
 use crate::state::Object;
-use cosmwasm_std::{StdError, StdResult};
+use cosmwasm_std::{from_slice, to_vec, Addr, Api, Order, StdError, StdResult};
+use okp4_cursor::check_cursor_length;
+use serde::{Deserialize, Serialize};
 
 pub fn encode<I: AsRef<[u8]>>(id: I) -> Cursor {
     bs58::encode(id).into_string()
 }
 
 pub fn decode<I: AsRef<[u8]>>(cursor: I) -> StdResult<Cursor> {
-    let raw = bs58::decode(cursor)
-        .into_vec()
-        .map_err(|err| StdError::parse_err("Cursor", err))?;
+    let raw = decode_raw(cursor)?;
 
     String::from_utf8(raw).map_err(|err| StdError::parse_err("Cursor", err))
 }
 
+/// decode_batch decodes each of the given cursors, e.g. the ones of a listing paginated across
+/// several shards, the error of an invalid cursor telling its index in the batch.
+pub fn decode_batch<I: AsRef<[u8]>>(cursors: &[I]) -> Vec<StdResult<Cursor>> {
+    cursors
+        .iter()
+        .enumerate()
+        .map(|(index, cursor)| {
+            decode(cursor).map_err(|err| match err {
+                StdError::ParseErr {
+                    target_type, msg, ..
+                } => StdError::parse_err(target_type, format!("cursor #{index}: {msg}")),
+                err => err,
+            })
+        })
+        .collect()
+}
+
+/// decode_raw decodes the bytes of a cursor, rejecting the cursors longer than
+/// [okp4_cursor::MAX_CURSOR_LENGTH] before decoding them, a base58 string not decoding into more
+/// bytes than it has characters. All the cursor decodings go through it.
+fn decode_raw<I: AsRef<[u8]>>(cursor: I) -> StdResult<Vec<u8>> {
+    check_cursor_length(cursor.as_ref())?;
+
+    bs58::decode(cursor)
+        .into_vec()
+        .map_err(|err| StdError::parse_err("Cursor", err))
+}
+
+/// encode_address encodes an address in its normalized, i.e. lowercased, form, so that all the
+/// textual forms of an address yield the same cursor.
+pub fn encode_address(address: &Addr) -> Cursor {
+    encode(address.as_str().to_lowercase())
+}
+
+/// decode_address decodes a cursor encoded by [encode_address], the address being normalized
+/// through its canonical form, which fails if it isn't a valid address, e.g. with a wrong prefix.
+pub fn decode_address(api: &dyn Api, cursor: Cursor) -> StdResult<Addr> {
+    let raw = decode(cursor)?;
+    api.addr_canonicalize(&raw)
+        .and_then(|canonical| api.addr_humanize(&canonical))
+}
+
+/// PaginationState is the state of a listing a cursor can carry along with the key to continue
+/// after: the order of the listing and the hash of the filter it is restricted by, so the cursor
+/// is only accepted to continue the very same listing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct PaginationState {
+    key: Cursor,
+    descending: bool,
+    filter_hash: String,
+}
+
+/// encode_state encodes the given key cursor along with the order and filter of the listing.
+pub fn encode_state(key: Cursor, order: Order, filter: &[u8]) -> StdResult<Cursor> {
+    to_vec(&PaginationState {
+        key,
+        descending: order == Order::Descending,
+        filter_hash: filter_hash(filter),
+    })
+    .map(encode)
+}
+
+/// decode_state decodes a cursor encoded by [encode_state] and returns its key cursor, failing
+/// if the cursor comes from a listing of a different order or filter.
+pub fn decode_state(cursor: Cursor, order: Order, filter: &[u8]) -> StdResult<Cursor> {
+    let raw = decode_raw(cursor)?;
+    let state: PaginationState =
+        from_slice(&raw).map_err(|err| StdError::parse_err("Cursor", err))?;
+
+    if state.descending != (order == Order::Descending) || state.filter_hash != filter_hash(filter)
+    {
+        return Err(StdError::generic_err(
+            "Cursor doesn't match the order or filter of the listing",
+        ));
+    }
+    Ok(state.key)
+}
+
+fn filter_hash(filter: &[u8]) -> String {
+    encode(crypto::hash(&HashAlgorithm::Sha256, &filter.to_vec()))
+}
+
 pub trait AsCursor<PK> {
     fn encode_cursor(&self) -> Cursor;
     fn decode_cursor(_: Cursor) -> StdResult<PK>;
@@ -26,16 +108,15 @@ impl AsCursor<Hash> for Object {
     }
 
     fn decode_cursor(cursor: Cursor) -> StdResult<Hash> {
-        bs58::decode(cursor)
-            .into_vec()
-            .map(Into::into)
-            .map_err(|err| StdError::parse_err("Cursor", err))
+        decode_raw(cursor).map(Into::into)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cosmwasm_std::testing::MockApi;
+    use okp4_cursor::MAX_CURSOR_LENGTH;
 
     #[test]
     fn proper_encode() {
@@ -49,6 +130,122 @@ mod tests {
         assert_eq!(decode("BzZCCcK"), Ok("an_id".to_string()));
     }
 
+    #[test]
+    fn address_cursor() {
+        let api = MockApi::default();
+
+        assert_eq!(
+            encode_address(&Addr::unchecked("Creator")),
+            encode_address(&Addr::unchecked("creator"))
+        );
+        for address in ["creator", "Creator", "CREATOR"] {
+            assert_eq!(
+                decode_address(&api, encode(address)),
+                Ok(Addr::unchecked("creator"))
+            );
+        }
+        assert_eq!(
+            decode_address(&api, encode_address(&Addr::unchecked("CreAtor"))),
+            Ok(Addr::unchecked("creator"))
+        );
+
+        assert!(matches!(
+            decode_address(&api, encode("a")),
+            Err(StdError::GenericErr { msg, .. }) if msg.starts_with("Invalid input: human address too short")
+        ));
+        assert!(decode_address(&api, "?".to_string()).is_err());
+    }
+
+    #[test]
+    fn pagination_state() {
+        let cursor = encode_state("BzZCCcK".to_string(), Order::Ascending, b"owner").unwrap();
+        assert_eq!(
+            decode_state(cursor.clone(), Order::Ascending, b"owner"),
+            Ok("BzZCCcK".to_string())
+        );
+
+        let mismatch = Err(StdError::generic_err(
+            "Cursor doesn't match the order or filter of the listing",
+        ));
+        assert_eq!(
+            decode_state(cursor.clone(), Order::Ascending, b"other"),
+            mismatch
+        );
+        assert_eq!(
+            decode_state(cursor.clone(), Order::Ascending, b""),
+            mismatch
+        );
+        assert_eq!(decode_state(cursor, Order::Descending, b"owner"), mismatch);
+
+        assert!(decode_state("BzZCCcK".to_string(), Order::Ascending, b"owner").is_err());
+    }
+
+    #[test]
+    fn cursor_length_cap() {
+        let too_long = "1".repeat(MAX_CURSOR_LENGTH + 1);
+        let expected = || {
+            StdError::parse_err(
+                "Cursor",
+                format!(
+                    "cursor too long: {} bytes, maximum is {MAX_CURSOR_LENGTH}",
+                    MAX_CURSOR_LENGTH + 1
+                ),
+            )
+        };
+
+        assert_eq!(decode(&too_long), Err(expected()));
+        assert_eq!(
+            decode_address(&MockApi::default(), too_long.clone()),
+            Err(expected())
+        );
+        assert_eq!(
+            decode_state(too_long.clone(), Order::Ascending, b""),
+            Err(expected())
+        );
+        assert_eq!(Object::decode_cursor(too_long), Err(expected()));
+
+        let longest = "1".repeat(MAX_CURSOR_LENGTH);
+        assert_eq!(decode(&longest), Ok("\0".repeat(MAX_CURSOR_LENGTH)));
+
+        let state = encode_state(encode([0xff; 64]), Order::Descending, b"owner").unwrap();
+        assert!(state.len() <= MAX_CURSOR_LENGTH);
+    }
+
+    #[test]
+    fn batch_decode() {
+        assert_eq!(decode_batch::<&str>(&[]), vec![]);
+        assert_eq!(
+            decode_batch(&["BzZCCcK", "?", "", "VtB5VXc"]),
+            vec![
+                Ok("an_id".to_string()),
+                Err(StdError::parse_err(
+                    "Cursor",
+                    "cursor #1: provided string contained invalid character '?' at byte 0"
+                )),
+                Ok("".to_string()),
+                Err(StdError::parse_err(
+                    "Cursor",
+                    "cursor #3: invalid utf-8 sequence of 1 bytes from index 0"
+                )),
+            ]
+        );
+
+        let too_long = "1".repeat(MAX_CURSOR_LENGTH + 1);
+        assert_eq!(
+            decode_batch(&["BzZCCcK", too_long.as_str()]),
+            vec![
+                Ok("an_id".to_string()),
+                Err(StdError::parse_err(
+                    "Cursor",
+                    format!(
+                        "cursor #1: cursor too long: {} bytes, maximum is {MAX_CURSOR_LENGTH}",
+                        MAX_CURSOR_LENGTH + 1
+                    )
+                )),
+            ]
+        );
+    }
+
     #[test]
     fn invalid_decode() {
         assert_eq!(
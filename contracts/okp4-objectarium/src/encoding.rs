@@ -0,0 +1,276 @@
+use crate::crypto::{Hash, HashAlgorithm};
+use crate::state::DATA;
+use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
+use thiserror::Error;
+
+/// The padding character of the base64 encoding.
+const PADDING: u8 = b'=';
+
+/// The default number of bytes decoded at once when no block size is imposed.
+const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    #[error("Invalid base64 byte {byte:#04x} at position {position}")]
+    InvalidByte { position: usize, byte: u8 },
+
+    #[error("Invalid base64 padding at position {0}")]
+    InvalidPadding(usize),
+
+    #[error("Invalid base64 length: {0}")]
+    InvalidLength(usize),
+}
+
+impl From<DecodeError> for StdError {
+    fn from(err: DecodeError) -> Self {
+        StdError::parse_err("base64", err.to_string())
+    }
+}
+
+/// Base64Decoder decodes a standard, padded, base64 encoded content block by block, so that a
+/// large content never has to be decoded at once, the decoded blocks being for instance fed to a
+/// [crate::crypto::Hasher] or split into storage chunks.
+///
+/// The decoding stops at the first malformed input, the error locating the offending byte.
+pub struct Base64Decoder<'a> {
+    input: &'a [u8],
+    position: usize,
+    block_size: usize,
+}
+
+impl<'a> Base64Decoder<'a> {
+    /// Creates a decoder of the given input, yielding blocks of at most `block_size` decoded
+    /// bytes, rounded down to a multiple of 3 (at least 3).
+    pub fn new(input: &'a [u8], block_size: usize) -> Self {
+        Self {
+            input,
+            position: 0,
+            block_size: (block_size / 3).max(1) * 3,
+        }
+    }
+
+    /// Decodes the group of 4 bytes starting at the given position, appending the decoded bytes to
+    /// the output.
+    fn decode_quad(&self, position: usize, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        let quad = &self.input[position..position + 4];
+        let is_last = position + 4 == self.input.len();
+        let padding = quad.iter().rev().take_while(|b| **b == PADDING).count();
+        if padding > 2 || (padding > 0 && !is_last) {
+            let offset = quad.iter().position(|b| *b == PADDING).unwrap_or(0);
+            return Err(DecodeError::InvalidPadding(position + offset));
+        }
+
+        let mut values = [0u8; 4];
+        for (i, byte) in quad[..4 - padding].iter().enumerate() {
+            values[i] = decode_byte(*byte).ok_or(DecodeError::InvalidByte {
+                position: position + i,
+                byte: *byte,
+            })?;
+        }
+
+        // the bits of the last symbol not making up a decoded byte must be zero for the encoding
+        // to be canonical
+        let last = 3 - padding;
+        let unused_bits = match padding {
+            1 => values[last] & 0b0000_0011,
+            2 => values[last] & 0b0000_1111,
+            _ => 0,
+        };
+        if unused_bits != 0 {
+            return Err(DecodeError::InvalidByte {
+                position: position + last,
+                byte: quad[last],
+            });
+        }
+
+        let bytes = [
+            (values[0] << 2) | (values[1] >> 4),
+            (values[1] << 4) | (values[2] >> 2),
+            (values[2] << 6) | values[3],
+        ];
+        out.extend_from_slice(&bytes[..3 - padding]);
+        Ok(())
+    }
+}
+
+impl Iterator for Base64Decoder<'_> {
+    type Item = Result<Vec<u8>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.input.len() {
+            return None;
+        }
+        if self.input.len() % 4 != 0 {
+            self.position = self.input.len();
+            return Some(Err(DecodeError::InvalidLength(self.input.len())));
+        }
+
+        let end = self
+            .input
+            .len()
+            .min(self.position + self.block_size / 3 * 4);
+        let mut block = Vec::with_capacity(self.block_size);
+        for position in (self.position..end).step_by(4) {
+            if let Err(e) = self.decode_quad(position, &mut block) {
+                self.position = self.input.len();
+                return Some(Err(e));
+            }
+        }
+        self.position = end;
+
+        Some(Ok(block))
+    }
+}
+
+/// decode_byte returns the 6 bits value of the given symbol of the standard base64 alphabet.
+fn decode_byte(byte: u8) -> Option<u8> {
+    match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// decoded_len returns the length of the content encoded in base64, the encoding being assumed
+/// valid.
+pub fn decoded_len(encoded: &[u8]) -> usize {
+    let padding = encoded
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|b| **b == PADDING)
+        .count();
+    (encoded.len() / 4 * 3).saturating_sub(padding)
+}
+
+/// decode_base64 returns the whole content encoded in base64, for the cases it can't be processed
+/// block by block.
+pub fn decode_base64(encoded: &[u8]) -> StdResult<Vec<u8>> {
+    Ok(Base64Decoder::new(encoded, DEFAULT_BLOCK_SIZE)
+        .collect::<Result<Vec<_>, _>>()?
+        .concat())
+}
+
+/// hash_base64 returns the hash of the content encoded in base64, decoding it block by block.
+pub fn hash_base64(algorithm: &HashAlgorithm, encoded: &[u8]) -> StdResult<Hash> {
+    let mut hasher = algorithm.hasher();
+    for block in Base64Decoder::new(encoded, DEFAULT_BLOCK_SIZE) {
+        hasher.update(&block?);
+    }
+    Ok(hasher.finalize())
+}
+
+/// save_base64_data stores the content of an object encoded in base64, decoding it block by block
+/// into chunks of at most `chunk_size` bytes, as [crate::state::save_data] would do with the
+/// decoded content. The size of the decoded content is returned.
+pub fn save_base64_data(
+    storage: &mut dyn Storage,
+    id: &Hash,
+    encoded: &[u8],
+    chunk_size: Option<Uint128>,
+) -> StdResult<Uint128> {
+    let chunk_size = chunk_size
+        .and_then(|size| usize::try_from(size.u128()).ok())
+        .filter(|size| *size > 0);
+
+    let mut size = 0u128;
+    let mut index = 0u32;
+    let mut buffer = Vec::new();
+    for block in Base64Decoder::new(encoded, chunk_size.unwrap_or(DEFAULT_BLOCK_SIZE)) {
+        let block = block?;
+        size += block.len() as u128;
+        buffer.extend(block);
+
+        // the last chunk is kept until the end so that a content fitting a single chunk is not
+        // split
+        while let Some(chunk_size) = chunk_size.filter(|chunk_size| buffer.len() > *chunk_size) {
+            let rest = buffer.split_off(chunk_size);
+            DATA.save(storage, (id.clone(), index), &buffer)?;
+            buffer = rest;
+            index = index.checked_add(1).ok_or_else(|| {
+                StdError::generic_err("Object content exceeds the maximum number of chunks")
+            })?;
+        }
+    }
+    DATA.save(storage, (id.clone(), index), &buffer)?;
+
+    Ok(size.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto;
+    use base64::{engine::general_purpose, Engine};
+
+    #[test]
+    fn decode_base64_blocks() {
+        for len in 0..20 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37 % 256) as u8).collect();
+            let encoded = general_purpose::STANDARD.encode(&data);
+            for block_size in [1, 3, 4, 6, 1024] {
+                let blocks = Base64Decoder::new(encoded.as_bytes(), block_size)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                assert!(blocks.iter().all(|block| block.len() <= block_size.max(3)));
+                assert_eq!(blocks.concat(), data, "len {len}, block size {block_size}");
+            }
+        }
+
+        let cases = vec![
+            ("b2twNA", DecodeError::InvalidLength(6)),
+            (
+                "b2t*NA==",
+                DecodeError::InvalidByte {
+                    position: 3,
+                    byte: b'*',
+                },
+            ),
+            ("b2twNA==b2t\n", DecodeError::InvalidPadding(6)),
+            ("b===", DecodeError::InvalidPadding(1)),
+            (
+                "b2twNB==",
+                DecodeError::InvalidByte {
+                    position: 5,
+                    byte: b'B',
+                },
+            ),
+        ];
+        for (encoded, expected) in cases {
+            let result = Base64Decoder::new(encoded.as_bytes(), 3)
+                .collect::<Result<Vec<_>, _>>()
+                .err();
+            assert_eq!(result, Some(expected), "{encoded}");
+        }
+    }
+
+    #[test]
+    fn streamed_data_matches_whole_data() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let encoded = general_purpose::STANDARD.encode(&data);
+
+        for algorithm in [HashAlgorithm::MD5, HashAlgorithm::Sha256] {
+            assert_eq!(
+                hash_base64(&algorithm, encoded.as_bytes()).unwrap(),
+                crypto::hash(&algorithm, &data)
+            );
+        }
+
+        assert_eq!(decode_base64(encoded.as_bytes()).unwrap(), data);
+        for len in 0..10 {
+            let encoded = general_purpose::STANDARD.encode(&data[..len]);
+            assert_eq!(decoded_len(encoded.as_bytes()), len);
+        }
+
+        assert_eq!(
+            decode_base64(b"b2t*NA=="),
+            Err(StdError::parse_err(
+                "base64",
+                "Invalid base64 byte 0x2a at position 3"
+            ))
+        );
+    }
+}
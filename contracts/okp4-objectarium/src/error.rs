@@ -14,8 +14,30 @@ pub enum ContractError {
     #[error("Object is pinned and cannot be forgotten")]
     ObjectPinned {},
 
+    #[error("Object is not pinned by the sender")]
+    ObjectNotPinned {},
+
+    #[error("Only the owner of the object can tag it")]
+    ObjectNotOwned {},
+
+    #[error("Invalid object alias: {0}")]
+    InvalidAlias(String),
+
+    #[error("Object alias already in use: {0}")]
+    AliasAlreadyUsed(String),
+
+    #[error("Object is already stored with another alias")]
+    AliasMismatch {},
+
     #[error("Compression error: {0}")]
     CompressionError(String),
+
+    #[error("Range out of object bounds: {offset} + {length} / {size}")]
+    RangeOutOfBounds {
+        offset: Uint128,
+        length: Uint128,
+        size: Uint128,
+    },
 }
 
 #[derive(Error, Debug, Eq, PartialEq)]
@@ -23,6 +45,9 @@ pub enum BucketError {
     #[error("Name of bucket could not be empty")]
     EmptyName,
 
+    #[error("Chunk size could not be zero")]
+    ZeroChunkSize,
+
     #[error("Maximum total size exceeded: {0} / {1}")]
     MaxTotalSizeLimitExceeded(Uint128, Uint128),
 
@@ -35,8 +60,11 @@ pub enum BucketError {
     #[error("Maximum object pins number exceeded: {0} / {1}")]
     MaxObjectPinsLimitExceeded(Uint128, Uint128),
 
-    #[error("Object is already stored")]
-    ObjectAlreadyStored,
+    #[error("Maximum object tags number exceeded: {0} / {1}")]
+    MaxObjectTagsLimitExceeded(Uint128, Uint128),
+
+    #[error("Maximum tag length exceeded: {0} / {1}")]
+    MaxTagLengthLimitExceeded(Uint128, Uint128),
 
     #[error("Compression algorithm is not accepted: {0:?} (accepted: \"{1:?}\")")]
     CompressionAlgorithmNotAccepted(CompressionAlgorithm, Vec<CompressionAlgorithm>),
@@ -60,6 +88,10 @@ fn test_bucket_error_messages() {
             ContractError::Bucket(BucketError::EmptyName),
             "Name of bucket could not be empty",
         ),
+        (
+            ContractError::Bucket(BucketError::ZeroChunkSize),
+            "Chunk size could not be zero",
+        ),
         (
             ContractError::Bucket(BucketError::MaxTotalSizeLimitExceeded(
                 200u8.into(),
@@ -82,10 +114,6 @@ fn test_bucket_error_messages() {
             ContractError::Bucket(BucketError::MaxObjectPinsLimitExceeded(5u8.into(), 2u8.into())),
             "Maximum object pins number exceeded: 5 / 2",
         ),
-        (
-            ContractError::Bucket(BucketError::ObjectAlreadyStored),
-            "Object is already stored",
-        ),
         (
             ContractError::Bucket(BucketError::CompressionAlgorithmNotAccepted(
                 CompressionAlgorithm::Snappy,
@@ -94,6 +122,19 @@ fn test_bucket_error_messages() {
             "Compression algorithm is not accepted: Snappy (accepted: \"[Passthrough]\")",
         ),
         (ContractError::ObjectPinned {}, "Object is pinned and cannot be forgotten"),
+        (ContractError::ObjectNotPinned {}, "Object is not pinned by the sender"),
+        (
+            ContractError::ObjectNotOwned {},
+            "Only the owner of the object can tag it",
+        ),
+        (
+            ContractError::Bucket(BucketError::MaxObjectTagsLimitExceeded(3u8.into(), 2u8.into())),
+            "Maximum object tags number exceeded: 3 / 2",
+        ),
+        (
+            ContractError::Bucket(BucketError::MaxTagLengthLimitExceeded(12u8.into(), 8u8.into())),
+            "Maximum tag length exceeded: 12 / 8",
+        ),
         (
             ContractError::CompressionError("Insufficient ch'i to compress file".to_string()),
             "Compression error: Insufficient ch'i to compress file",
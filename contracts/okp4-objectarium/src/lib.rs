@@ -1,7 +1,8 @@
 pub mod compress;
 pub mod contract;
 pub mod crypto;
-mod cursor;
+pub mod cursor;
+pub mod encoding;
 mod error;
 pub mod msg;
 mod pagination;
@@ -27,13 +27,22 @@ pub struct InstantiateMsg {
     pub pagination: PaginationConfig,
 }
 
+/// Migrate messages
+///
+/// Upgrades the state of a bucket stored by a former version of the contract, i.e. moves the
+/// content of the objects stored before the chunking into chunks, following the chunk size of the
+/// bucket, and indexes the objects stored before the eviction for them to be evicted.
+#[cw_serde]
+pub struct MigrateMsg {}
+
 /// Execute messages
 #[cw_serde]
 pub enum ExecuteMsg {
     /// # StoreObject
     /// StoreObject store an object to the bucket and make the sender the owner of the object.
     /// The object is referenced by the hash of its content and this value is returned.
-    /// If the object is already stored, an error is returned.
+    /// If the object is already stored, it is not stored again and its existing owner is kept, the
+    /// object being only pinned for the sender if requested.
     ///
     /// The "pin" parameter specifies if the object should be pinned for the sender. In such case,
     /// the object cannot be removed (forget) from the storage.
@@ -43,6 +52,11 @@ pub enum ExecuteMsg {
     /// is the first algorithm of the bucket configuration limits. Note that the chosen algorithm can
     /// save storage space, but it will increase CPU usage. Depending on the chosen compression algorithm
     /// and the achieved compression ratio, the gas cost of the operation will vary, either increasing or decreasing.
+    ///
+    /// The "alias" parameter gives the object a human-friendly name, unique within the bucket, to
+    /// retrieve it by (see [QueryMsg::ObjectByAlias]). It is made of at most 64 ASCII alphanumeric
+    /// characters, '-', '_', '.' or '/', and is freed when the object is removed. If the object is
+    /// already stored, the alias must be the one it has been stored with, if any.
     StoreObject {
         /// The content of the object to store.
         data: Binary,
@@ -52,6 +66,28 @@ pub enum ExecuteMsg {
         /// If None, the first algorithm specified in the list of accepted compression algorithms of the bucket
         /// is used (see [BucketLimits::accepted_compression_algorithms]).
         compression_algorithm: Option<CompressionAlgorithm>,
+        /// The alias of the object, if any.
+        #[serde(default)]
+        alias: Option<String>,
+    },
+
+    /// # StoreBase64Object
+    /// StoreBase64Object stores an object as [ExecuteMsg::StoreObject] does, its content being
+    /// given encoded in base64 and decoded block by block as it is hashed and stored, so that a
+    /// large content is never decoded at once. The content is nonetheless decoded at once to be
+    /// compressed by an algorithm other than passthrough.
+    StoreBase64Object {
+        /// The content of the object to store, encoded in standard, padded, base64.
+        data: String,
+        /// Specifies if the object should be pinned for the sender.
+        pin: bool,
+        /// Specifies the compression algorithm to use when storing the object.
+        /// If None, the first algorithm specified in the list of accepted compression algorithms of the bucket
+        /// is used (see [BucketLimits::accepted_compression_algorithms]).
+        compression_algorithm: Option<CompressionAlgorithm>,
+        /// The alias of the object, if any.
+        #[serde(default)]
+        alias: Option<String>,
     },
 
     /// # ForgetObject
@@ -69,9 +105,22 @@ pub enum ExecuteMsg {
 
     /// # UnpinObject
     /// UnpinObject unpins the object in the bucket for the considered sender. If the object is not pinned
-    /// for the sender, this is a no-op.
+    /// for the sender, an error is returned.
     /// The object can be removed from the storage if it is not pinned anymore.
     UnpinObject { id: ObjectId },
+
+    /// # TagObject
+    /// TagObject replaces the set of tags of the object, allowing to retrieve it by any of its
+    /// tags. Only the owner of the object can tag it, an empty set removing all its tags.
+    ///
+    /// The number of tags of an object and their length are bounded by the bucket limits
+    /// (see [BucketLimits::max_object_tags] and [BucketLimits::max_tag_length]).
+    TagObject {
+        /// The id of the object to tag.
+        id: ObjectId,
+        /// The tags of the object, duplicates being ignored.
+        tags: Vec<String>,
+    },
 }
 
 /// Query messages
@@ -91,6 +140,14 @@ pub enum QueryMsg {
         id: ObjectId,
     },
 
+    /// # ObjectByAlias
+    /// ObjectByAlias returns the object information of the object stored with the given alias.
+    #[returns(ObjectResponse)]
+    ObjectByAlias {
+        /// The alias of the object to get.
+        alias: String,
+    },
+
     /// # Objects
     /// Objects returns the list of objects in the bucket with support for pagination.
     #[returns(ObjectsResponse)]
@@ -111,6 +168,40 @@ pub enum QueryMsg {
         id: ObjectId,
     },
 
+    /// # ObjectDataWithChecksum
+    /// ObjectDataWithChecksum returns the content of the object with the given id along with the
+    /// hash of the content, recomputed while reading it, so that its integrity can be checked
+    /// against the object id without trusting the transport.
+    #[returns(ObjectDataResponse)]
+    ObjectDataWithChecksum {
+        /// The id of the object to get.
+        id: ObjectId,
+    },
+
+    /// # ObjectDataRange
+    /// ObjectDataRange returns the `length` bytes of the content of the object with the given id
+    /// starting at `offset`, the range having to be within the object content.
+    ///
+    /// When the object is not compressed, only the chunks covering the range are read.
+    #[returns(Binary)]
+    ObjectDataRange {
+        /// The id of the object to get.
+        id: ObjectId,
+        /// The position of the first byte to return.
+        offset: Uint128,
+        /// The number of bytes to return.
+        length: Uint128,
+    },
+
+    /// # ObjectSize
+    /// ObjectSize returns the size of the object with the given id, along with the number of
+    /// chunks its content is stored in, without reading its content.
+    #[returns(ObjectSizeResponse)]
+    ObjectSize {
+        /// The id of the object to get the size of.
+        id: ObjectId,
+    },
+
     /// # ObjectPins
     /// ObjectPins returns the list of addresses that pinned the object with the given id with
     /// support for pagination.
@@ -123,6 +214,27 @@ pub enum QueryMsg {
         /// The point in the sequence to start returning pins.
         after: Option<Cursor>,
     },
+
+    /// # ObjectsByTag
+    /// ObjectsByTag returns the list of objects having the given tag with support for pagination.
+    #[returns(ObjectsResponse)]
+    ObjectsByTag {
+        /// The tag of the objects to get.
+        tag: String,
+        /// The number of objects to return.
+        first: Option<u32>,
+        /// The point in the sequence to start returning objects.
+        after: Option<Cursor>,
+    },
+
+    /// # VerifyObject
+    /// VerifyObject recomputes the hash of the content of the object with the given id and tells
+    /// if it still matches the object id, guarding against storage corruption.
+    #[returns(bool)]
+    VerifyObject {
+        /// The id of the object to verify.
+        id: ObjectId,
+    },
 }
 
 /// # PageInfo
@@ -179,10 +291,24 @@ pub enum CompressionAlgorithm {
     Lzma,
 }
 
+/// EvictionPolicy is an enumeration that defines the different orders in which unpinned objects
+/// are evicted from a bucket to make room for a new object.
+#[cw_serde]
+#[derive(Copy)]
+pub enum EvictionPolicy {
+    /// # OldestFirst
+    /// Evicts the objects in the order they have been stored, the oldest first.
+    OldestFirst,
+    /// # LeastRecentlyPinned
+    /// Evicts the objects in the order they have been last pinned, the objects never pinned first,
+    /// then the oldest first.
+    LeastRecentlyPinned,
+}
+
 /// HashAlgorithm is an enumeration that defines the different hash algorithms
 /// supported for hashing the content of objects.
 #[cw_serde]
-#[derive(Copy)]
+#[derive(Copy, Default)]
 pub enum HashAlgorithm {
     /// # MD5
     /// Represents the MD5 algorithm.
@@ -218,6 +344,7 @@ pub enum HashAlgorithm {
     /// SHA-256 hashes are stored on-chain as 64 hexadecimal characters.
     ///
     /// See [the SHA-2 Wikipedia page](https://en.wikipedia.org/wiki/SHA-2) for more information.
+    #[default]
     Sha256,
 
     /// # SHA384
@@ -245,12 +372,6 @@ pub enum HashAlgorithm {
     Sha512,
 }
 
-impl Default for HashAlgorithm {
-    fn default() -> Self {
-        Self::Sha256
-    }
-}
-
 /// BucketConfig is the type of the configuration of a bucket.
 ///
 /// The configuration is set at the instantiation of the bucket, and is immutable and cannot be changed.
@@ -278,6 +399,21 @@ pub struct BucketConfig {
     /// here will fail.
     #[serde(default = "CompressionAlgorithm::values")]
     pub accepted_compression_algorithms: Vec<CompressionAlgorithm>,
+    /// The maximum size, in bytes, of the chunks the (compressed) content of the objects is split
+    /// into when stored, allowing to store objects exceeding the size limits of a single storage value.
+    /// The content is reassembled when fetched, and the id of the object is always computed over
+    /// the whole content.
+    ///
+    /// If not set, the content of an object is stored as a single chunk. It cannot be zero.
+    #[serde(default)]
+    pub chunk_size: Option<Uint128>,
+    /// The policy used to evict unpinned objects when storing a new object would exceed the maximum
+    /// total size of the bucket (see [BucketLimits::max_total_size]). Objects are evicted according
+    /// to the policy until the new object fits, pinned objects being never evicted.
+    ///
+    /// If not set, no object is evicted and storing an object exceeding the limit fails.
+    #[serde(default)]
+    pub eviction_policy: Option<EvictionPolicy>,
 }
 
 impl Default for BucketConfig {
@@ -285,6 +421,8 @@ impl Default for BucketConfig {
         Self {
             hash_algorithm: Default::default(),
             accepted_compression_algorithms: CompressionAlgorithm::values(),
+            chunk_size: None,
+            eviction_policy: None,
         }
     }
 }
@@ -310,6 +448,12 @@ pub struct BucketLimits {
     pub max_object_size: Option<Uint128>,
     /// The maximum number of pins in the bucket for an object.
     pub max_object_pins: Option<Uint128>,
+    /// The maximum number of tags of an object.
+    #[serde(default)]
+    pub max_object_tags: Option<Uint128>,
+    /// The maximum length, in bytes, of a tag.
+    #[serde(default)]
+    pub max_tag_length: Option<Uint128>,
 }
 
 /// PaginationConfig is the type carrying configuration for paginated queries.
@@ -368,6 +512,33 @@ pub struct ObjectResponse {
     pub compressed_size: Uint128,
     /// The compression algorithm used to compress the content of the object.
     pub compression_algorithm: CompressionAlgorithm,
+    /// The alias of the object, if any.
+    pub alias: Option<String>,
+}
+
+/// # ObjectDataResponse
+/// ObjectDataResponse is the response of the ObjectDataWithChecksum query.
+#[cw_serde]
+pub struct ObjectDataResponse {
+    /// The content of the object.
+    pub data: Binary,
+    /// The hash of the content, hex encoded as the object ids, computed with the hash algorithm
+    /// of the bucket.
+    pub checksum: String,
+}
+
+/// # ObjectSizeResponse
+/// ObjectSizeResponse is the response of the ObjectSize query.
+#[cw_serde]
+pub struct ObjectSizeResponse {
+    /// The size of the object.
+    pub size: Uint128,
+    /// The size of the object as stored, i.e. when compressed. If the object is not compressed,
+    /// the value is the same as `size`.
+    pub compressed_size: Uint128,
+    /// The number of chunks the content of the object is stored in, one if the bucket doesn't
+    /// split the objects into chunks.
+    pub chunk_count: u32,
 }
 
 /// # ObjectsResponse
@@ -433,6 +604,8 @@ mod tests {
         assert_eq!(limits.max_objects, None);
         assert_eq!(limits.max_object_size, None);
         assert_eq!(limits.max_total_size, None);
+        assert_eq!(limits.max_object_tags, None);
+        assert_eq!(limits.max_tag_length, None);
     }
 
     #[test]
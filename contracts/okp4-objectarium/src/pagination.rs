@@ -1,7 +1,7 @@
-use crate::cursor::AsCursor;
+use crate::cursor::{self, AsCursor};
 use crate::msg::{Cursor, PageInfo};
 use crate::state::Pagination;
-use cosmwasm_std::{StdError, StdResult};
+use cosmwasm_std::{Order, StdError, StdResult};
 use cw_storage_plus::{Bound, PrimaryKey};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -15,6 +15,7 @@ where
 {
     max_page_size: u32,
     default_page_size: u32,
+    filter: Option<Vec<u8>>,
 
     _data_type: PhantomData<T>,
     _pk_type: PhantomData<PK>,
@@ -75,12 +76,21 @@ where
         PaginationHandler {
             max_page_size,
             default_page_size,
+            filter: None,
             _data_type: PhantomData,
             _pk_type: PhantomData,
             _lifetime: PhantomData,
         }
     }
 
+    /// with_filter makes the cursors carry the state of the listing, i.e. its order and the given
+    /// filter, a cursor being then rejected when used to continue a listing having a different
+    /// filter.
+    pub fn with_filter<F: AsRef<[u8]>>(mut self, filter: F) -> Self {
+        self.filter = Some(filter.as_ref().to_vec());
+        self
+    }
+
     pub fn query_page_cursor_fn<I, CD, CE>(
         self,
         iter_fn: I,
@@ -94,8 +104,12 @@ where
         CD: FnOnce(Cursor) -> StdResult<PK>,
         CE: FnOnce(&T) -> Cursor,
     {
-        let min_bound = match after {
-            Some(cursor) => Some(Bound::exclusive(cursor_dec_fn(cursor)?)),
+        let filter = self.filter.clone();
+        let min_bound = match (after, &filter) {
+            (Some(cursor), Some(filter)) => Some(Bound::exclusive(cursor_dec_fn(
+                cursor::decode_state(cursor, Order::Ascending, filter)?,
+            )?)),
+            (Some(cursor), None) => Some(Bound::exclusive(cursor_dec_fn(cursor)?)),
             _ => None,
         };
         let page_size = self.compute_page_size(first)?;
@@ -109,7 +123,11 @@ where
             items.pop();
         }
 
-        let cursor = items.last().map_or_else(String::new, cursor_enc_fn);
+        let cursor = match (items.last().map(cursor_enc_fn), filter) {
+            (Some(key), Some(filter)) => cursor::encode_state(key, Order::Ascending, &filter)?,
+            (Some(key), None) => key,
+            (None, _) => String::new(),
+        };
 
         Ok((
             items,
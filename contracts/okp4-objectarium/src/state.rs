@@ -1,15 +1,200 @@
 use crate::compress::CompressionAlgorithm;
-use crate::crypto::Hash;
+use crate::crypto::{self, Hash};
 use crate::error::BucketError;
 use crate::error::BucketError::EmptyName;
 use crate::msg;
-use crate::msg::{ObjectResponse, PaginationConfig};
-use cosmwasm_std::{Addr, StdError, StdResult, Uint128};
+use crate::msg::{ObjectId, ObjectResponse, PaginationConfig};
+use cosmwasm_std::{Addr, Order, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::any::type_name;
+
+/// DATA holds the (compressed) content of the objects, split into chunks keyed by the object id
+/// and the index of the chunk.
+pub const DATA: Map<'_, (Hash, u32), Vec<u8>> = Map::new("DATA_CHUNK");
+
+/// LEGACY_DATA holds the (compressed) content of the objects stored before the chunking, as a
+/// single value keyed by the object id, until moved into [DATA] by [migrate_legacy_data].
+pub const LEGACY_DATA: Map<'_, Hash, Vec<u8>> = Map::new("DATA");
+
+/// migrate_legacy_data moves the content of the objects stored before the chunking into chunks of
+/// at most `chunk_size` bytes, as [save_data] would have stored it, returning the number of
+/// objects moved.
+pub fn migrate_legacy_data(
+    storage: &mut dyn Storage,
+    chunk_size: Option<Uint128>,
+) -> StdResult<usize> {
+    let ids = LEGACY_DATA
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Hash>>>()?;
+    for id in &ids {
+        let data = LEGACY_DATA.load(storage, id.clone())?;
+        save_data(storage, id, &data, chunk_size)?;
+        LEGACY_DATA.remove(storage, id.clone());
+    }
+    Ok(ids.len())
+}
+
+/// save_data stores the given content of an object, split into chunks of at most `chunk_size`
+/// bytes. If no chunk size is given, the content is stored as a single chunk.
+pub fn save_data(
+    storage: &mut dyn Storage,
+    id: &Hash,
+    data: &[u8],
+    chunk_size: Option<Uint128>,
+) -> StdResult<()> {
+    let chunk_size = chunk_size
+        .and_then(|size| usize::try_from(size.u128()).ok())
+        .filter(|size| *size > 0 && *size < data.len());
+
+    match chunk_size {
+        Some(size) => {
+            for (index, chunk) in data.chunks(size).enumerate() {
+                let index = u32::try_from(index).map_err(|_| {
+                    StdError::generic_err("Object content exceeds the maximum number of chunks")
+                })?;
+                DATA.save(storage, (id.clone(), index), &chunk.to_vec())?;
+            }
+            Ok(())
+        }
+        None => DATA.save(storage, (id.clone(), 0), &data.to_vec()),
+    }
+}
 
-pub const DATA: Map<'_, Hash, Vec<u8>> = Map::new("DATA");
+/// has_data tells if some content is stored for the given object.
+pub fn has_data(storage: &dyn Storage, id: &Hash) -> bool {
+    DATA.has(storage, (id.clone(), 0))
+}
+
+/// object_key returns the key of the object identified by the given client-supplied id, validated
+/// against the hash algorithm of the bucket, see [crypto::validate_object_id].
+pub fn object_key(storage: &dyn Storage, id: ObjectId) -> StdResult<Hash> {
+    crypto::validate_object_id(id, &BUCKET.load(storage)?.config.hash_algorithm.into())
+}
+
+/// load_data reassembles the content of the given object from its chunks.
+pub fn load_data(storage: &dyn Storage, id: &Hash) -> StdResult<Vec<u8>> {
+    if !has_data(storage, id) {
+        return Err(StdError::not_found(type_name::<Vec<u8>>()));
+    }
+
+    DATA.prefix(id.clone())
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Vec::new(), |mut data, chunk| {
+            data.extend(chunk?.1);
+            Ok(data)
+        })
+}
+
+/// hash_data returns the hash of the stored content of the given object, feeding the hasher chunk
+/// by chunk so that the content is never loaded as a whole.
+pub fn hash_data(
+    storage: &dyn Storage,
+    id: &Hash,
+    algorithm: &crypto::HashAlgorithm,
+) -> StdResult<Hash> {
+    if !has_data(storage, id) {
+        return Err(StdError::not_found(type_name::<Vec<u8>>()));
+    }
+
+    let mut hasher = algorithm.hasher();
+    for chunk in DATA
+        .prefix(id.clone())
+        .range(storage, None, None, Order::Ascending)
+    {
+        hasher.update(&chunk?.1);
+    }
+    Ok(hasher.finalize())
+}
+
+/// load_hashed_data returns the stored content of the given object along with its hash, the hasher
+/// being fed with each chunk as it is read.
+pub fn load_hashed_data(
+    storage: &dyn Storage,
+    id: &Hash,
+    algorithm: &crypto::HashAlgorithm,
+) -> StdResult<(Vec<u8>, Hash)> {
+    if !has_data(storage, id) {
+        return Err(StdError::not_found(type_name::<Vec<u8>>()));
+    }
+
+    let mut hasher = algorithm.hasher();
+    let mut data = Vec::new();
+    for chunk in DATA
+        .prefix(id.clone())
+        .range(storage, None, None, Order::Ascending)
+    {
+        let chunk = chunk?.1;
+        hasher.update(&chunk);
+        data.extend(chunk);
+    }
+    Ok((data, hasher.finalize()))
+}
+
+/// load_data_range returns `length` bytes of the stored content of the given object starting at
+/// `offset`, reading only the chunks covering the range. The range is expected to be within the
+/// stored content.
+pub fn load_data_range(
+    storage: &dyn Storage,
+    id: &Hash,
+    offset: usize,
+    length: usize,
+) -> StdResult<Vec<u8>> {
+    let first = DATA
+        .may_load(storage, (id.clone(), 0))?
+        .ok_or_else(|| StdError::not_found(type_name::<Vec<u8>>()))?;
+
+    // All the chunks but the last one have the same size, the one of the first chunk.
+    let chunk_size = first.len().max(1);
+    let to_index = |position: usize| {
+        u32::try_from(position / chunk_size)
+            .map_err(|_| StdError::generic_err("Range exceeds the maximum number of chunks"))
+    };
+    let (first_index, last_index) = (
+        to_index(offset)?,
+        to_index(offset + length.saturating_sub(1))?,
+    );
+
+    let mut data = Vec::with_capacity(length);
+    for index in first_index..=last_index {
+        let chunk = if index == 0 {
+            first.clone()
+        } else {
+            DATA.load(storage, (id.clone(), index))?
+        };
+        data.extend(chunk);
+    }
+
+    let start = offset - first_index as usize * chunk_size;
+    Ok(data
+        .get(start..start + length)
+        .ok_or_else(|| StdError::generic_err("Range exceeds the object content"))?
+        .to_vec())
+}
+
+/// chunk_count returns the number of chunks the content of the given object is stored in, without
+/// reading the chunks.
+pub fn chunk_count(storage: &dyn Storage, id: &Hash) -> StdResult<u32> {
+    let count = DATA
+        .prefix(id.clone())
+        .keys_raw(storage, None, None, Order::Ascending)
+        .count();
+    u32::try_from(count)
+        .map_err(|_| StdError::generic_err("Object content exceeds the maximum number of chunks"))
+}
+
+/// remove_data removes all the chunks of content of the given object.
+pub fn remove_data(storage: &mut dyn Storage, id: &Hash) -> StdResult<()> {
+    let indexes = DATA
+        .prefix(id.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u32>>>()?;
+    for index in indexes {
+        DATA.remove(storage, (id.clone(), index));
+    }
+    Ok(())
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Bucket {
@@ -49,6 +234,9 @@ impl Bucket {
         if n.is_empty() {
             return Err(EmptyName);
         }
+        if config.chunk_size.map_or(false, |size| size.is_zero()) {
+            return Err(BucketError::ZeroChunkSize);
+        }
 
         Ok(Self {
             owner,
@@ -106,6 +294,44 @@ impl From<HashAlgorithm> for msg::HashAlgorithm {
     }
 }
 
+/// EvictionPolicy is an enumeration that defines the different orders in which unpinned objects
+/// are evicted from a bucket.
+#[derive(Serialize, Copy, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub enum EvictionPolicy {
+    /// Evicts the objects in the order they have been stored.
+    OldestFirst,
+    /// Evicts the objects in the order they have been last pinned.
+    LeastRecentlyPinned,
+}
+
+impl EvictionPolicy {
+    /// rank returns the rank of the given object in the eviction order, the lowest being evicted first.
+    pub fn rank(&self, object: &Object) -> (u64, u64) {
+        match self {
+            EvictionPolicy::OldestFirst => (object.stored_seq, 0),
+            EvictionPolicy::LeastRecentlyPinned => (object.pinned_seq, object.stored_seq),
+        }
+    }
+}
+
+impl From<msg::EvictionPolicy> for EvictionPolicy {
+    fn from(policy: msg::EvictionPolicy) -> Self {
+        match policy {
+            msg::EvictionPolicy::OldestFirst => EvictionPolicy::OldestFirst,
+            msg::EvictionPolicy::LeastRecentlyPinned => EvictionPolicy::LeastRecentlyPinned,
+        }
+    }
+}
+
+impl From<EvictionPolicy> for msg::EvictionPolicy {
+    fn from(policy: EvictionPolicy) -> Self {
+        match policy {
+            EvictionPolicy::OldestFirst => msg::EvictionPolicy::OldestFirst,
+            EvictionPolicy::LeastRecentlyPinned => msg::EvictionPolicy::LeastRecentlyPinned,
+        }
+    }
+}
+
 impl From<msg::CompressionAlgorithm> for CompressionAlgorithm {
     fn from(algorithm: msg::CompressionAlgorithm) -> Self {
         match algorithm {
@@ -140,6 +366,16 @@ pub struct BucketConfig {
     ///
     /// The default is all compression algorithms.
     pub accepted_compression_algorithms: Vec<CompressionAlgorithm>,
+    /// The maximum size of the chunks the content of the objects is split into when stored.
+    ///
+    /// The default is to store the content as a single chunk.
+    #[serde(default)]
+    pub chunk_size: Option<Uint128>,
+    /// The policy used to evict unpinned objects when the maximum total size is exceeded.
+    ///
+    /// The default is to never evict objects.
+    #[serde(default)]
+    pub eviction_policy: Option<EvictionPolicy>,
 }
 
 impl From<msg::BucketConfig> for BucketConfig {
@@ -151,6 +387,8 @@ impl From<msg::BucketConfig> for BucketConfig {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
+            chunk_size: config.chunk_size,
+            eviction_policy: config.eviction_policy.map(Into::into),
         }
     }
 }
@@ -164,6 +402,8 @@ impl From<BucketConfig> for msg::BucketConfig {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
+            chunk_size: config.chunk_size,
+            eviction_policy: config.eviction_policy.map(Into::into),
         }
     }
 }
@@ -181,6 +421,12 @@ pub struct BucketLimits {
     pub max_object_size: Option<Uint128>,
     /// The maximum number of pins in the bucket for an object.
     pub max_object_pins: Option<Uint128>,
+    /// The maximum number of tags of an object.
+    #[serde(default)]
+    pub max_object_tags: Option<Uint128>,
+    /// The maximum length of a tag.
+    #[serde(default)]
+    pub max_tag_length: Option<Uint128>,
 }
 
 impl From<msg::BucketLimits> for BucketLimits {
@@ -190,6 +436,8 @@ impl From<msg::BucketLimits> for BucketLimits {
             max_objects: limits.max_objects,
             max_object_size: limits.max_object_size,
             max_object_pins: limits.max_object_pins,
+            max_object_tags: limits.max_object_tags,
+            max_tag_length: limits.max_tag_length,
         }
     }
 }
@@ -201,6 +449,8 @@ impl From<BucketLimits> for msg::BucketLimits {
             max_objects: limits.max_objects,
             max_object_size: limits.max_object_size,
             max_object_pins: limits.max_object_pins,
+            max_object_tags: limits.max_object_tags,
+            max_tag_length: limits.max_tag_length,
         }
     }
 }
@@ -256,6 +506,19 @@ impl TryFrom<PaginationConfig> for Pagination {
 
 pub const BUCKET: Item<'_, Bucket> = Item::new("bucket");
 
+/// The ids of the objects by alias.
+pub const ALIASES: Map<'_, String, Hash> = Map::new("ALIAS");
+
+/// SEQUENCE is a monotonic counter ordering the storage and the pinning of the objects.
+pub const SEQUENCE: Item<'_, u64> = Item::new("sequence");
+
+/// next_sequence increments the sequence and returns its new value.
+pub fn next_sequence(storage: &mut dyn Storage) -> StdResult<u64> {
+    let seq = SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+    SEQUENCE.save(storage, &seq)?;
+    Ok(seq)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Object {
     /// The id of the object.
@@ -270,6 +533,15 @@ pub struct Object {
     pub compression: CompressionAlgorithm,
     /// The size of the object after compression.
     pub compressed_size: Uint128,
+    /// The sequence number at which the object has been stored.
+    #[serde(default)]
+    pub stored_seq: u64,
+    /// The sequence number at which the object has been last pinned, zero if never pinned.
+    #[serde(default)]
+    pub pinned_seq: u64,
+    /// The alias of the object, if any.
+    #[serde(default)]
+    pub alias: Option<String>,
 }
 
 impl From<&Object> for ObjectResponse {
@@ -281,26 +553,75 @@ impl From<&Object> for ObjectResponse {
             is_pinned: object.pin_count > Uint128::zero(),
             compressed_size: object.compressed_size,
             compression_algorithm: object.compression.into(),
+            alias: object.alias.clone(),
         }
     }
 }
 
+/// EvictionKey is the key of an object in the eviction indexes: whether it is pinned (1) or not
+/// (0), followed by its rank for the eviction policy of the index, see [EvictionPolicy::rank].
+pub type EvictionKey = (u8, (u64, u64));
+
+/// eviction_key returns the key of the given object in the index of the given eviction policy.
+fn eviction_key(policy: EvictionPolicy, object: &Object) -> EvictionKey {
+    (u8::from(!object.pin_count.is_zero()), policy.rank(object))
+}
+
 pub struct ObjectIndexes<'a> {
     pub owner: MultiIndex<'a, Addr, Object, Hash>,
+    /// The objects in the [EvictionPolicy::OldestFirst] order, the unpinned ones first.
+    pub oldest_first: MultiIndex<'a, EvictionKey, Object, Hash>,
+    /// The objects in the [EvictionPolicy::LeastRecentlyPinned] order, the unpinned ones first.
+    pub least_recently_pinned: MultiIndex<'a, EvictionKey, Object, Hash>,
+}
+
+impl ObjectIndexes<'_> {
+    /// eviction returns the index of the objects in the order of the given eviction policy.
+    pub fn eviction(&self, policy: EvictionPolicy) -> &MultiIndex<'_, EvictionKey, Object, Hash> {
+        match policy {
+            EvictionPolicy::OldestFirst => &self.oldest_first,
+            EvictionPolicy::LeastRecentlyPinned => &self.least_recently_pinned,
+        }
+    }
 }
 
 impl IndexList<Object> for ObjectIndexes<'_> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Object>> + '_> {
         let owner: &dyn Index<Object> = &self.owner;
-        Box::new(vec![owner].into_iter())
+        let oldest_first: &dyn Index<Object> = &self.oldest_first;
+        let least_recently_pinned: &dyn Index<Object> = &self.least_recently_pinned;
+        Box::new(vec![owner, oldest_first, least_recently_pinned].into_iter())
     }
 }
 
+/// reindex_objects saves anew all the objects through [objects], filling the indexes added since
+/// they have been stored, e.g. the eviction ones, returning the number of objects reindexed.
+pub fn reindex_objects(storage: &mut dyn Storage) -> StdResult<usize> {
+    let ids = objects()
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Hash>>>()?;
+    for id in &ids {
+        let object = objects().load(storage, id.clone())?;
+        objects().save(storage, id.clone(), &object)?;
+    }
+    Ok(ids.len())
+}
+
 pub fn objects<'a>() -> IndexedMap<'a, Hash, Object, ObjectIndexes<'a>> {
     IndexedMap::new(
         "OBJECT",
         ObjectIndexes {
             owner: MultiIndex::new(|_, object| object.owner.clone(), "OBJECT", "OBJECT__OWNER"),
+            oldest_first: MultiIndex::new(
+                |_, object| eviction_key(EvictionPolicy::OldestFirst, object),
+                "OBJECT",
+                "OBJECT__OLDEST_FIRST",
+            ),
+            least_recently_pinned: MultiIndex::new(
+                |_, object| eviction_key(EvictionPolicy::LeastRecentlyPinned, object),
+                "OBJECT",
+                "OBJECT__LEAST_RECENTLY_PINNED",
+            ),
         },
     )
 }
@@ -332,3 +653,43 @@ pub fn pins<'a>() -> IndexedMap<'a, (Hash, Addr), Pin, PinIndexes<'a>> {
         },
     )
 }
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Tag {
+    /// The id of the tagged object.
+    pub id: Hash,
+    /// The tag.
+    pub tag: String,
+}
+
+pub struct TagIndexes<'a> {
+    pub tag: MultiIndex<'a, String, Tag, (Hash, String)>,
+}
+
+impl IndexList<Tag> for TagIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Tag>> + '_> {
+        let tag: &dyn Index<Tag> = &self.tag;
+        Box::new(vec![tag].into_iter())
+    }
+}
+
+pub fn tags<'a>() -> IndexedMap<'a, (Hash, String), Tag, TagIndexes<'a>> {
+    IndexedMap::new(
+        "TAG",
+        TagIndexes {
+            tag: MultiIndex::new(|_, tag| tag.tag.clone(), "TAG", "TAG__TAG"),
+        },
+    )
+}
+
+/// remove_tags removes all the tags of the given object.
+pub fn remove_tags(storage: &mut dyn Storage, id: &Hash) -> StdResult<()> {
+    let keys = tags()
+        .prefix(id.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for tag in keys {
+        tags().remove(storage, (id.clone(), tag))?;
+    }
+    Ok(())
+}
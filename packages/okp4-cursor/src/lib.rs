@@ -0,0 +1,40 @@
+use cosmwasm_std::{StdError, StdResult};
+
+/// The maximum length of the cursors accepted by the contracts, bounding what is decoded from
+/// an untrusted cursor. It leaves room for the largest cursors issued, e.g. the keys of the
+/// triples made of the subject and predicate of a cognitarium triple.
+pub const MAX_CURSOR_LENGTH: usize = 4096;
+
+/// Ensures the cursor, as provided to a contract, is at most [MAX_CURSOR_LENGTH] bytes long. It
+/// has to be called before decoding it.
+pub fn check_cursor_length(cursor: &[u8]) -> StdResult<()> {
+    let length = cursor.len();
+    if length > MAX_CURSOR_LENGTH {
+        return Err(StdError::parse_err(
+            "Cursor",
+            format!("cursor too long: {length} bytes, maximum is {MAX_CURSOR_LENGTH}"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_length() {
+        assert_eq!(check_cursor_length(&[]), Ok(()));
+        assert_eq!(check_cursor_length(&[0; MAX_CURSOR_LENGTH]), Ok(()));
+        assert_eq!(
+            check_cursor_length(&[0; MAX_CURSOR_LENGTH + 1]),
+            Err(StdError::parse_err(
+                "Cursor",
+                format!(
+                    "cursor too long: {} bytes, maximum is {MAX_CURSOR_LENGTH}",
+                    MAX_CURSOR_LENGTH + 1
+                )
+            ))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::string::FromUtf8Error;
 use thiserror::Error;
 use url::ParseError;
@@ -19,8 +20,8 @@ pub enum CosmwasmUriError {
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum TermParseError {
-    #[error("Value is not UTF-8 encoded: {0}")]
-    NotUtf8Value(FromUtf8Error),
+    #[error("Value at bytes {0:?} is not UTF-8 encoded: {1}")]
+    NotUtf8Value(Range<usize>, FromUtf8Error),
 
     #[error("Reach unexpected EOF")]
     Eof,
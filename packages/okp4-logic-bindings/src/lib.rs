@@ -4,7 +4,7 @@ mod term_parser;
 pub mod uri;
 
 pub use query::{Answer, AskResponse, LogicCustomQuery, Result, Substitution, Term};
-pub use term_parser::TermValue;
+pub use term_parser::{PartialTermValue, TermValue};
 
 // Exposed for testing only
 // Both unit tests and integration tests are compiled to native code, so everything in here does not need to compile to Wasm.
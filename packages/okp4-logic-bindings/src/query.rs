@@ -1,5 +1,5 @@
 use crate::error::TermParseError;
-use crate::term_parser::{from_str, TermValue};
+use crate::term_parser::{from_str, from_str_partial, PartialTermValue, TermValue};
 use cosmwasm_std::CustomQuery;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -53,6 +53,11 @@ impl Term {
     pub fn parse(self) -> std::result::Result<TermValue, TermParseError> {
         from_str(self.name.as_str())
     }
+
+    /// Parses the complete top-level terms of a possibly truncated term, see [PartialTermValue].
+    pub fn parse_partial(self) -> std::result::Result<PartialTermValue, TermParseError> {
+        from_str_partial(self.name.as_str())
+    }
 }
 
 #[cfg(test)]
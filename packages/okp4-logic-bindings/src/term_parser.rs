@@ -1,20 +1,34 @@
 use crate::error::TermParseError;
+use std::ops::Range;
 
-/// Represents a Prolog response term element which can be a tuple, an array or a string value.
+/// Represents a Prolog response term element which can be a tuple, an array, a compound term or a
+/// string value.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum TermValue {
     Tuple(Vec<TermValue>),
     Array(Vec<TermValue>),
+    /// A compound term, e.g. `foo(a,b)`, made of its functor name and its arguments, which may be
+    /// empty for a zero-arg functor like `foo()`.
+    Compound(String, Vec<TermValue>),
     Value(String),
 }
 
+/// Represents the complete top-level terms parsed from a possibly truncated Prolog response term.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PartialTermValue {
+    /// The top-level terms completely parsed.
+    pub values: Vec<TermValue>,
+    /// Tells if the input ended in the middle of a term, which has then been discarded.
+    pub truncated: bool,
+}
+
 struct Parser<'a> {
     slice: &'a [u8],
     index: usize,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(slice: &'a [u8]) -> Parser<'_> {
+    pub fn new(slice: &'a [u8]) -> Parser<'a> {
         Parser { slice, index: 0 }
     }
 
@@ -83,10 +97,27 @@ impl<'a> Parser<'a> {
             .map(TermValue::Tuple)
     }
 
+    /// Decodes the bytes of a value as UTF-8, the error locating the value by its byte range in
+    /// the input.
+    fn decode_value(bytes: Vec<u8>, range: Range<usize>) -> Result<TermValue, TermParseError> {
+        String::from_utf8(bytes)
+            .map_err(|e| TermParseError::NotUtf8Value(range, e))
+            .map(TermValue::Value)
+    }
+
     fn parse_value(&mut self) -> Result<TermValue, TermParseError> {
+        let start = self.index;
         let mut bytes: Vec<u8> = Vec::new();
         loop {
             match self.peek() {
+                Some(b'(') if !bytes.is_empty() => {
+                    let functor = String::from_utf8(bytes)
+                        .map_err(|e| TermParseError::NotUtf8Value(start..self.index, e))?;
+                    self.eat_char();
+                    return self
+                        .parse_seq(b')')
+                        .map(|args| TermValue::Compound(functor, args));
+                }
                 Some(t) if [b'[', b'(', b'\'', b'"', b' '].contains(&t) => {
                     Err(TermParseError::UnexpectedValueToken(char::from(t)))?;
                 }
@@ -102,18 +133,18 @@ impl<'a> Parser<'a> {
             return Err(TermParseError::EmptyValue);
         }
 
-        String::from_utf8(bytes)
-            .map_err(TermParseError::NotUtf8Value)
-            .map(TermValue::Value)
+        Self::decode_value(bytes, start..self.index)
     }
 
     fn parse_escaped_value(&mut self) -> Result<TermValue, TermParseError> {
+        let start = self.index;
         let mut bytes: Vec<u8> = Vec::new();
-        loop {
+        let end = loop {
             match self.peek() {
                 Some(b'\'') => {
+                    let end = self.index;
                     self.eat_char();
-                    break;
+                    break end;
                 }
                 Some(b'\\') => {
                     self.eat_char();
@@ -133,11 +164,9 @@ impl<'a> Parser<'a> {
                 }
                 None => Err(TermParseError::Eof)?,
             }
-        }
+        };
 
-        String::from_utf8(bytes)
-            .map_err(TermParseError::NotUtf8Value)
-            .map(TermValue::Value)
+        Self::decode_value(bytes, start..end)
     }
 
     fn parse(&mut self) -> Result<TermValue, TermParseError> {
@@ -183,6 +212,54 @@ impl<'a> Parser<'a> {
 
         Ok(TermValue::Tuple(values))
     }
+
+    fn parse_partial(&mut self) -> Result<PartialTermValue, TermParseError> {
+        let mut values: Vec<TermValue> = Vec::new();
+        loop {
+            let value = match self.peek() {
+                Some(b'[') => {
+                    self.eat_char();
+                    self.parse_array()
+                }
+                Some(b'(') => {
+                    self.eat_char();
+                    self.parse_tuple()
+                }
+                Some(b'\'') => {
+                    self.eat_char();
+                    self.parse_escaped_value()
+                }
+                Some(_) => self.parse_value(),
+                None => break,
+            };
+
+            match value {
+                Ok(value) => values.push(value),
+                Err(TermParseError::Eof) => {
+                    return Ok(PartialTermValue {
+                        values,
+                        truncated: true,
+                    })
+                }
+                Err(e) => Err(e)?,
+            }
+
+            match self.peek() {
+                Some(b',') => {
+                    self.eat_char();
+                }
+                None => {
+                    break;
+                }
+                Some(t) => Err(TermParseError::UnexpectedRootToken(char::from(t)))?,
+            }
+        }
+
+        Ok(PartialTermValue {
+            values,
+            truncated: false,
+        })
+    }
 }
 
 /// Parses a Prolog response term from bytes
@@ -198,6 +275,19 @@ pub fn from_str(s: &str) -> Result<TermValue, TermParseError> {
     from_slice(s.as_bytes())
 }
 
+/// Parses the complete top-level terms of a possibly truncated Prolog response term from bytes,
+/// the input ending in the middle of a term being reported instead of failing.
+///
+/// As nothing delimits a non quoted value, a trailing one is always considered complete.
+pub fn from_slice_partial(v: &[u8]) -> Result<PartialTermValue, TermParseError> {
+    Parser::new(v).parse_partial()
+}
+
+/// Parses the complete top-level terms of a possibly truncated Prolog response term from a string.
+pub fn from_str_partial(s: &str) -> Result<PartialTermValue, TermParseError> {
+    from_slice_partial(s.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,7 +304,7 @@ mod tests {
             (")", Err(TermParseError::EmptyValue)),
             (",", Err(TermParseError::EmptyValue)),
             ("foo[", Err(TermParseError::UnexpectedValueToken('['))),
-            ("foo(", Err(TermParseError::UnexpectedValueToken('('))),
+            ("foo(", Err(TermParseError::Eof)),
             ("foo'", Err(TermParseError::UnexpectedValueToken('\''))),
             ("foo\"", Err(TermParseError::UnexpectedValueToken('"'))),
             ("foo ", Err(TermParseError::UnexpectedValueToken(' '))),
@@ -234,8 +324,10 @@ mod tests {
             index: 0,
         }
         .parse_value();
-        assert!(res.is_err());
-        matches!(res.err().unwrap(), TermParseError::NotUtf8Value(_));
+        assert!(matches!(
+            res.err().unwrap(),
+            TermParseError::NotUtf8Value(range, _) if range == (0..1)
+        ));
     }
 
     #[test]
@@ -275,8 +367,10 @@ mod tests {
             index: 0,
         }
         .parse_escaped_value();
-        assert!(res.is_err());
-        matches!(res.err().unwrap(), TermParseError::NotUtf8Value(_));
+        assert!(matches!(
+            res.err().unwrap(),
+            TermParseError::NotUtf8Value(range, _) if range == (0..1)
+        ));
     }
 
     #[test]
@@ -366,6 +460,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_compound() {
+        let cases = vec![
+            (
+                "foo(a,b)",
+                Ok(TermValue::Compound(
+                    "foo".to_string(),
+                    vec![
+                        TermValue::Value("a".to_string()),
+                        TermValue::Value("b".to_string()),
+                    ],
+                )),
+            ),
+            ("foo()", Ok(TermValue::Compound("foo".to_string(), vec![]))),
+            (
+                "f(g(a),[b],'c d',(1,2))",
+                Ok(TermValue::Compound(
+                    "f".to_string(),
+                    vec![
+                        TermValue::Compound(
+                            "g".to_string(),
+                            vec![TermValue::Value("a".to_string())],
+                        ),
+                        TermValue::Array(vec![TermValue::Value("b".to_string())]),
+                        TermValue::Value("c d".to_string()),
+                        TermValue::Tuple(vec![
+                            TermValue::Value("1".to_string()),
+                            TermValue::Value("2".to_string()),
+                        ]),
+                    ],
+                )),
+            ),
+            (
+                "[foo(bar()),baz]",
+                Ok(TermValue::Array(vec![
+                    TermValue::Compound(
+                        "foo".to_string(),
+                        vec![TermValue::Compound("bar".to_string(), vec![])],
+                    ),
+                    TermValue::Value("baz".to_string()),
+                ])),
+            ),
+            (
+                "foo(a),(b)",
+                Ok(TermValue::Tuple(vec![
+                    TermValue::Compound("foo".to_string(), vec![TermValue::Value("a".to_string())]),
+                    TermValue::Tuple(vec![TermValue::Value("b".to_string())]),
+                ])),
+            ),
+            ("foo(a,)", Err(TermParseError::UnexpectedEndOfSeq)),
+            ("foo(a", Err(TermParseError::Eof)),
+            ("foo(a)b", Err(TermParseError::UnexpectedRootToken('b'))),
+        ];
+
+        for case in cases {
+            let res = from_str(case.0);
+            assert_eq!(res, case.1);
+        }
+    }
+
     #[test]
     fn parse() {
         let cases = vec![
@@ -395,4 +549,99 @@ mod tests {
             assert_eq!(res, case.1);
         }
     }
+
+    #[test]
+    fn parse_partial() {
+        let cases = vec![
+            ("", Ok((vec![], false))),
+            (
+                "[hello],('a',b)",
+                Ok((
+                    vec![
+                        TermValue::Array(vec![TermValue::Value("hello".to_string())]),
+                        TermValue::Tuple(vec![
+                            TermValue::Value("a".to_string()),
+                            TermValue::Value("b".to_string()),
+                        ]),
+                    ],
+                    false,
+                )),
+            ),
+            (
+                "[hello],('a',",
+                Ok((
+                    vec![TermValue::Array(vec![TermValue::Value(
+                        "hello".to_string(),
+                    )])],
+                    true,
+                )),
+            ),
+            (
+                "[hello],('a",
+                Ok((
+                    vec![TermValue::Array(vec![TermValue::Value(
+                        "hello".to_string(),
+                    )])],
+                    true,
+                )),
+            ),
+            (
+                "[hello],(",
+                Ok((
+                    vec![TermValue::Array(vec![TermValue::Value(
+                        "hello".to_string(),
+                    )])],
+                    true,
+                )),
+            ),
+            (
+                "[hello],",
+                Ok((
+                    vec![TermValue::Array(vec![TermValue::Value(
+                        "hello".to_string(),
+                    )])],
+                    false,
+                )),
+            ),
+            ("[hel", Ok((vec![], true))),
+            ("'hel", Ok((vec![], true))),
+            (
+                "hello,'wor",
+                Ok((vec![TermValue::Value("hello".to_string())], true)),
+            ),
+            ("[hello]],(", Err(TermParseError::UnexpectedRootToken(']'))),
+            ("[hello, (", Err(TermParseError::UnexpectedValueToken(' '))),
+        ];
+
+        for case in cases {
+            let res = from_str_partial(case.0);
+            assert_eq!(
+                res,
+                case.1
+                    .map(|(values, truncated)| PartialTermValue { values, truncated })
+            );
+        }
+    }
+
+    #[test]
+    fn parse_not_utf8_value() {
+        let cases: Vec<(&[u8], Range<usize>)> = vec![
+            (b"[foo,'b\xffr',baz]", 6..9),
+            (b"(a,b\xffc)", 3..6),
+            (b"[foo,[bar,\xc3],baz]", 10..11),
+            (b"ok,'\xe2\x82',\xff", 4..6),
+        ];
+
+        for (input, expected) in cases {
+            match from_slice(input) {
+                Err(TermParseError::NotUtf8Value(range, _)) => assert_eq!(range, expected),
+                res => panic!("unexpected result: {res:?}"),
+            }
+        }
+
+        assert_eq!(
+            from_slice(b"[a,\xff]").err().unwrap().to_string(),
+            "Value at bytes 3..4 is not UTF-8 encoded: invalid utf-8 sequence of 1 bytes from index 0"
+        );
+    }
 }
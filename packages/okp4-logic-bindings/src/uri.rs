@@ -1,6 +1,7 @@
 use crate::error::CosmwasmUriError;
 use serde::{de, ser};
 use std::collections::HashMap;
+use std::fmt;
 use url::Url;
 
 const COSMWASM_SCHEME: &str = "cosmwasm";
@@ -49,9 +50,9 @@ impl CosmwasmUri {
     }
 
     fn encode_query(self) -> String {
-        return form_urlencoded::Serializer::new(String::new())
+        form_urlencoded::Serializer::new(String::new())
             .append_pair(COSMWASM_QUERY_PARAM, self.raw_query.as_str())
-            .finish();
+            .finish()
     }
 }
 
@@ -96,10 +97,10 @@ impl TryFrom<String> for CosmwasmUri {
     }
 }
 
-impl ToString for CosmwasmUri {
-    fn to_string(&self) -> String {
+impl fmt::Display for CosmwasmUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let encoded_query = self.clone().encode_query();
-        match self.contract_name.clone() {
+        let uri = match self.contract_name.clone() {
             Some(name) => [
                 COSMWASM_SCHEME,
                 ":",
@@ -118,7 +119,8 @@ impl ToString for CosmwasmUri {
                 encoded_query.as_str(),
             ]
             .join(""),
-        }
+        };
+        write!(f, "{uri}")
     }
 }
 